@@ -0,0 +1,108 @@
+use crate::AgentBoardError;
+use crate::db::Database;
+use crate::models::{BenchResult, CardFilter, Status};
+use std::time::{Duration, Instant};
+
+/// Time create/get/list/comment operations against the configured database.
+/// Works on a scratch board that is purged afterwards; its cards and comments
+/// don't start hooks or notifiers.
+pub async fn run(db: &Database, iterations: usize) -> Result<Vec<BenchResult>, AgentBoardError> {
+    db.mute_hooks(true);
+    let result = run_muted(db, iterations).await;
+    db.mute_hooks(false);
+    result
+}
+
+async fn run_muted(db: &Database, iterations: usize) -> Result<Vec<BenchResult>, AgentBoardError> {
+    let board = db
+        .create_board(
+            format!("bench-{}", chrono::Utc::now().format("%Y%m%d%H%M%S")),
+            Some("Temporary board created by agent-board bench".into()),
+        )
+        .await?;
+
+    let result = run_on_board(db, &board.id, iterations).await;
+    db.purge_board(&board.id, false, None).await?;
+    result
+}
+
+async fn run_on_board(
+    db: &Database,
+    board_id: &str,
+    iterations: usize,
+) -> Result<Vec<BenchResult>, AgentBoardError> {
+    let mut card_ids = Vec::with_capacity(iterations);
+    let mut create = Vec::with_capacity(iterations);
+    for i in 0..iterations {
+        let start = Instant::now();
+        let card = db
+            .create_card(
+                board_id,
+                format!("Bench card {}", i),
+                None,
+                Status::Todo,
+                None,
+                None,
+                &[],
+            )
+            .await?;
+        create.push(start.elapsed());
+        card_ids.push(card.id);
+    }
+
+    let mut get = Vec::with_capacity(iterations);
+    for id in &card_ids {
+        let start = Instant::now();
+        db.get_card(id).await?;
+        get.push(start.elapsed());
+    }
+
+    let mut comment = Vec::with_capacity(iterations);
+    for id in &card_ids {
+        let start = Instant::now();
+        db.add_comment(id, "bench".into(), None, None, None).await?;
+        comment.push(start.elapsed());
+    }
+
+    // Listing a full board is much slower than point operations, so sample less
+    let list_iterations = iterations.div_ceil(10);
+    let mut list = Vec::with_capacity(list_iterations);
+    for _ in 0..list_iterations {
+        let start = Instant::now();
+        db.list_cards(board_id, &CardFilter::default()).await?;
+        list.push(start.elapsed());
+    }
+
+    Ok(vec![
+        summarize("create card", create),
+        summarize("get card", get),
+        summarize("add comment", comment),
+        summarize(&format!("list cards ({} cards)", iterations), list),
+    ])
+}
+
+fn summarize(operation: &str, mut samples: Vec<Duration>) -> BenchResult {
+    samples.sort();
+    let total: Duration = samples.iter().sum();
+    let percentile = |p: f64| -> f64 {
+        if samples.is_empty() {
+            return 0.0;
+        }
+        let idx = ((samples.len() as f64 * p).ceil() as usize).clamp(1, samples.len()) - 1;
+        samples[idx].as_secs_f64() * 1000.0
+    };
+
+    BenchResult {
+        operation: operation.to_string(),
+        iterations: samples.len(),
+        p50_ms: percentile(0.50),
+        p90_ms: percentile(0.90),
+        p99_ms: percentile(0.99),
+        max_ms: percentile(1.0),
+        ops_per_sec: if total.is_zero() {
+            0.0
+        } else {
+            samples.len() as f64 / total.as_secs_f64()
+        },
+    }
+}
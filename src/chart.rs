@@ -0,0 +1,69 @@
+//! Renderers for `chart` subcommands, drawn from recorded `board_stats`.
+
+use crate::models::{Board, BoardStats};
+use colored::Colorize;
+use std::fmt::Write;
+
+/// Cumulative flow diagram: one stacked bar per recorded day, done at the
+/// base and todo on top, scaled so the busiest day fills `width` cells.
+/// Widening todo means work arrives faster than it leaves; a flat done
+/// band means nothing is finishing.
+pub fn render_cfd(board: &Board, stats: &[BoardStats], width: usize) -> String {
+    let mut out = String::new();
+    if stats.is_empty() {
+        let _ = writeln!(
+            out,
+            "No stats recorded for {} yet; run `agent-board stats snapshot` (or `sweep`) daily to collect them.",
+            board.name
+        );
+        return out;
+    }
+
+    let _ = writeln!(out, "Cumulative flow: {} - {}", board.name, board.id);
+    let _ = writeln!(
+        out,
+        "{} done  {} pending review  {} in progress  {} todo",
+        "█".green(),
+        "▓".cyan(),
+        "▒".yellow(),
+        "░".normal()
+    );
+    let max = stats
+        .iter()
+        .map(|s| s.counts.total)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    for entry in stats {
+        let c = &entry.counts;
+        let bands = [c.done, c.pending_review, c.in_progress, c.todo];
+        // Round cumulative edges, not each band, so bars of equal totals match
+        let mut bar = String::new();
+        let (mut sum, mut drawn) = (0, 0);
+        for (i, n) in bands.iter().enumerate() {
+            sum += n;
+            let edge = (sum * width + max / 2) / max;
+            let cells = edge - drawn;
+            drawn = edge;
+            let band = match i {
+                0 => "█".repeat(cells).green(),
+                1 => "▓".repeat(cells).cyan(),
+                2 => "▒".repeat(cells).yellow(),
+                _ => "░".repeat(cells).normal(),
+            };
+            let _ = write!(bar, "{}", band);
+        }
+        let _ = writeln!(
+            out,
+            "{} {}{} {:>4} done {:>4} review {:>4} doing {:>4} todo",
+            entry.day.format("%Y-%m-%d"),
+            bar,
+            " ".repeat(width - drawn),
+            c.done,
+            c.pending_review,
+            c.in_progress,
+            c.todo
+        );
+    }
+    out
+}
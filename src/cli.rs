@@ -1,6 +1,10 @@
 use crate::AgentBoardError;
-use crate::models::{OutputFormat, Status};
+use crate::models::{
+    AgentAccess, BoardSort, CheckRule, CommentKind, EventKind, GateRequirement, MutationBudget,
+    NotifierKind, OutputFormat, Status, SweepStep,
+};
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
 #[command(name = "agent-board")]
@@ -23,16 +27,53 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub quiet: bool,
 
+    /// Show table cells in full instead of cutting long values with "..."
+    #[arg(long, global = true)]
+    pub no_truncate: bool,
+
+    /// Fit tables within this many characters, trimming the widest columns first
+    #[arg(long, global = true, value_name = "N")]
+    pub max_width: Option<usize>,
+
     /// Show detailed debug output
     #[arg(long, global = true)]
     pub verbose: bool,
 
+    /// Write the formatted result to this file (atomically) instead of stdout
+    #[arg(long, global = true)]
+    pub output: Option<PathBuf>,
+
+    /// Namespace for boards and agents (default: AGENT_BOARD_NAMESPACE or "default")
+    #[arg(long, global = true)]
+    pub namespace: Option<String>,
+
+    /// Work on a temporary local copy of the database and write it back when
+    /// the command succeeds (for network or container-mounted paths; also
+    /// AGENT_BOARD_LOCAL_CACHE=1). Single writer only: concurrent syncs overwrite
+    /// each other.
+    #[arg(long, global = true)]
+    pub local_cache: bool,
+
+    /// Act as this agent for assignments, comments, and events (humans only:
+    /// refused when AGENT_BOARD_AGENT_ID names another agent); events record
+    /// who ran the command
+    #[arg(long = "as", global = true, value_name = "AGENT_ID")]
+    pub act_as: Option<String>,
+
+    /// Board context for this invocation, by name or ID; commands that take a
+    /// board use it when none is given (put it before the command)
+    #[arg(long)]
+    pub board: Option<String>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
 
 impl Cli {
     pub fn get_agent_id(&self) -> Result<String, AgentBoardError> {
+        if let Some(agent_id) = &self.act_as {
+            return Ok(agent_id.clone());
+        }
         std::env::var("AGENT_BOARD_AGENT_ID").map_err(|_| {
             AgentBoardError::InvalidArgs(
                 "No agent identity configured.\n\n\
@@ -43,6 +84,59 @@ impl Cli {
             )
         })
     }
+
+    /// Who is running a command `--as` another agent: the agent in
+    /// AGENT_BOARD_AGENT_ID, else the login user. None without `--as`, or when
+    /// it names the same agent.
+    pub fn get_principal(&self) -> Option<String> {
+        let act_as = self.act_as.as_deref()?;
+        let agent = std::env::var("AGENT_BOARD_AGENT_ID").ok();
+        if agent.as_deref() == Some(act_as) {
+            return None;
+        }
+        Some(
+            agent
+                .or_else(|| std::env::var("USER").ok())
+                .unwrap_or_else(|| "human".to_string()),
+        )
+    }
+
+    /// Parse `AGENT_BOARD_MUTATION_BUDGET`: a count, optionally per window
+    /// (`100` or `100/30m`; the window defaults to an hour)
+    pub fn get_mutation_budget(&self) -> Result<Option<MutationBudget>, AgentBoardError> {
+        let Ok(value) = std::env::var("AGENT_BOARD_MUTATION_BUDGET") else {
+            return Ok(None);
+        };
+        let invalid = || {
+            AgentBoardError::InvalidArgs(format!(
+                "AGENT_BOARD_MUTATION_BUDGET must look like 100 or 100/1h, got '{}'",
+                value
+            ))
+        };
+        let (limit, window) = match value.split_once('/') {
+            Some((limit, window)) => (
+                limit,
+                crate::parse_duration(window)
+                    .filter(|w| *w > chrono::Duration::zero())
+                    .ok_or_else(invalid)?,
+            ),
+            None => (value.as_str(), chrono::Duration::hours(1)),
+        };
+        let limit = limit.trim().parse().map_err(|_| invalid())?;
+        Ok(Some(MutationBudget { limit, window }))
+    }
+
+    pub fn get_local_cache(&self) -> bool {
+        self.local_cache
+            || std::env::var("AGENT_BOARD_LOCAL_CACHE").is_ok_and(|v| v == "1" || v == "true")
+    }
+
+    pub fn get_namespace(&self) -> String {
+        self.namespace
+            .clone()
+            .or_else(|| std::env::var("AGENT_BOARD_NAMESPACE").ok())
+            .unwrap_or_else(|| "default".to_string())
+    }
 }
 
 #[derive(Subcommand, Debug)]
@@ -50,10 +144,50 @@ pub enum Commands {
     /// Show version information
     Version,
 
-    /// Get any entity by ID (auto-detects type from prefix: agent_, board_, card_)
+    /// Get entities by ID (auto-detects type from prefix: agent_, board_, card_, comment_, item_)
     Get {
-        /// Entity ID (e.g., board_xxx, card_xxx, agent_xxx)
-        id: String,
+        /// Entity IDs (e.g., board_xxx, card_xxx, comment_xxx); several IDs give a JSON array
+        #[arg(required = true, num_args = 1..)]
+        ids: Vec<String>,
+
+        /// Include assignment history for cards
+        #[arg(long)]
+        history: bool,
+
+        /// Include time spent in each status for cards
+        #[arg(long)]
+        timings: bool,
+
+        /// Board view: show this status column as just its card count (repeatable)
+        #[arg(long)]
+        collapse: Vec<Status>,
+
+        /// Board view: cards per column before an "…and N more" footer
+        #[arg(long)]
+        max_cards_per_column: Option<usize>,
+
+        /// Board view: emphasize cards whose name, ID, description, tags or labels contain TERM and dim the rest
+        #[arg(long, value_name = "TERM")]
+        highlight: Option<String>,
+
+        /// Print only each card's or board's ETag, a hash that changes with its
+        /// content, so pollers can skip unchanged entities
+        #[arg(long)]
+        etag_only: bool,
+
+        /// Output format
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+
+    /// Show the assignment history of a card, or earlier versions of a field
+    History {
+        /// Card ID
+        card_id: String,
+
+        /// Which history to show
+        #[arg(long, value_enum, default_value = "assignee")]
+        field: crate::models::HistoryField,
 
         /// Output format
         #[arg(long)]
@@ -84,8 +218,16 @@ pub enum Commands {
         command: DeleteCommands,
     },
 
-    /// Get all cards assigned to current agent
+    /// Get all cards assigned to current agent (or another agent, or everyone)
     Mine {
+        /// Show this agent's cards instead of your own
+        #[arg(long, conflicts_with = "all")]
+        agent: Option<String>,
+
+        /// Show every assigned card, grouped by agent
+        #[arg(long)]
+        all: bool,
+
         /// Filter by board
         #[arg(long)]
         board: Option<String>,
@@ -99,270 +241,1546 @@ pub enum Commands {
         format: Option<OutputFormat>,
     },
 
-    /// Show current agent identity (from AGENT_BOARD_AGENT_ID)
-    Whoami,
-}
-
-// ============================================================================
-// LIST subcommands
-// ============================================================================
-
-#[derive(Subcommand, Debug)]
-pub enum ListCommands {
-    /// List all boards
-    Boards {
-        /// Include soft-deleted boards
+    /// Check board hygiene rules; exits 7 when any is violated (for CI gates)
+    Check {
+        /// Board ID (default: the --board context)
         #[arg(long)]
-        include_deleted: bool,
+        board: Option<String>,
+
+        /// Rules to enforce, comma-separated (default: all)
+        #[arg(long, value_delimiter = ',')]
+        fail_on: Vec<CheckRule>,
 
         /// Output format
         #[arg(long)]
         format: Option<OutputFormat>,
     },
 
-    /// List cards on a board
-    Cards {
-        /// Board ID
-        board_id: String,
-
-        /// Filter by status
-        #[arg(long)]
-        status: Option<Status>,
-
-        /// Filter by assignee
-        #[arg(long)]
-        assigned_to: Option<String>,
+    /// Report board hygiene problems (missing descriptions, orphaned work, stale reviews, ...)
+    Audit {
+        /// Board ID (default: the --board context)
+        board_id: Option<String>,
 
-        /// Filter by tag (repeatable, cards must have ALL specified tags)
-        #[arg(long)]
-        tag: Vec<String>,
+        /// Flag reviews waiting longer than this (e.g., 3d)
+        #[arg(long, default_value = "3d")]
+        stale: String,
 
-        /// Include soft-deleted cards
-        #[arg(long)]
-        include_deleted: bool,
+        /// Flag active agents with no activity for this long (e.g., 7d)
+        #[arg(long, default_value = "7d")]
+        idle: String,
 
         /// Output format
         #[arg(long)]
         format: Option<OutputFormat>,
     },
 
-    /// List all registered agents
-    Agents {
-        /// Include deactivated agents
+    /// Finish a card with a closing comment (atomically)
+    Done {
+        /// Card ID
+        card_id: String,
+
+        /// What was done, for the reviewer (e.g., "Implemented in PR #123")
         #[arg(long)]
-        include_inactive: bool,
+        comment: String,
 
-        /// Output format
+        /// Move to pending_review instead of done
         #[arg(long)]
-        format: Option<OutputFormat>,
+        review: bool,
     },
 
-    /// List comments on a card
-    Comments {
+    /// Move a done card back to work with a reason (recorded as a comment and a card_reopened event)
+    Reopen {
         /// Card ID
         card_id: String,
 
-        /// Output format
+        /// Why the work didn't stick (e.g., "tests failing on main")
         #[arg(long)]
-        format: Option<OutputFormat>,
+        reason: String,
+
+        /// Status to reopen into (todo or in-progress)
+        #[arg(long, default_value = "todo")]
+        to: Status,
     },
-}
 
-// ============================================================================
-// CREATE subcommands
-// ============================================================================
+    /// Run JSON operations read from stdin and print the results as JSON
+    #[command(
+        after_help = "Operations: create_board, create_card, update_card, comment, done, reopen, add_dependency, delete_card.\n\nExample:\n  echo '{\"op\":\"create_card\",\"board\":\"backend\",\"name\":\"Add rate limits\"}' | agent-board apply"
+    )]
+    Apply {
+        /// Run the remaining operations after one fails (default: stop at the first failure)
+        #[arg(long)]
+        keep_going: bool,
+    },
 
-#[derive(Subcommand, Debug)]
-pub enum CreateCommands {
-    /// Create a new board
-    Board {
-        /// Board name
-        name: String,
+    /// Acknowledge a comment with an emoji instead of replying
+    React {
+        /// Comment ID
+        comment_id: String,
 
-        /// Board description
+        /// Reaction (e.g., +1, 👀, 🎉)
         #[arg(long)]
-        description: Option<String>,
+        emoji: String,
+
+        /// Take back your reaction
+        #[arg(long)]
+        remove: bool,
     },
 
-    /// Create a new card on a board
-    Card {
-        /// Board ID
-        board_id: String,
+    /// Apply periodic board policies (run from cron or CI): tag cards idle
+    /// past stale.<status>, remind about cards due within due.remind_before,
+    /// prune events older than retention.events, and record board stats
+    Sweep {
+        /// Only sweep this board (default: every board with a matching setting)
+        #[arg(long)]
+        board: Option<String>,
 
-        /// Card name
-        name: String,
+        /// Run only these steps (repeatable)
+        #[arg(long, value_enum, conflicts_with = "skip")]
+        only: Vec<SweepStep>,
 
-        /// Card description
+        /// Leave out these steps (repeatable)
+        #[arg(long, value_enum)]
+        skip: Vec<SweepStep>,
+
+        /// Show what would be done without changing anything
         #[arg(long)]
-        description: Option<String>,
+        dry_run: bool,
 
-        /// Initial status
-        #[arg(long, default_value = "todo")]
-        status: Status,
+        /// Output format
+        #[arg(long)]
+        format: Option<OutputFormat>,
     },
 
-    /// Register a new agent identity
-    Agent {
-        /// Agent name (auto-generated if not provided)
-        name: Option<String>,
+    /// What changed for you since your last brief: assigned cards, new comments
+    /// and mentions, newly unblocked cards (uses AGENT_BOARD_AGENT_ID)
+    Brief {
+        /// Start from this time instead of your last brief (e.g., 1d, 2026-01-31)
+        #[arg(long)]
+        since: Option<String>,
 
-        /// Command to invoke this agent (e.g., stakpak, claude, aider)
-        #[arg(long, default_value = "stakpak")]
-        command: String,
+        /// Don't move your last-brief cursor
+        #[arg(long)]
+        peek: bool,
 
-        /// Agent description
+        /// Output format (table prints a compact text digest)
         #[arg(long)]
-        description: Option<String>,
+        format: Option<OutputFormat>,
     },
 
-    /// Add checklist items to a card
-    Checklist {
+    /// One document with a card, its board summary, assignee, dependency
+    /// neighborhood and recent comments, for an agent's context
+    Describe {
         /// Card ID
         card_id: String,
 
-        /// Checklist item text (repeatable)
-        #[arg(long, required = true)]
-        item: Vec<String>,
+        /// How many dependency links to follow each way
+        #[arg(long, default_value_t = 1)]
+        depth: usize,
+
+        /// How many recent comments to include
+        #[arg(long, default_value_t = 5)]
+        comments: usize,
+
+        /// Output format (json, or prompt for an LLM-ready document; table falls back to prompt)
+        #[arg(long)]
+        format: Option<OutputFormat>,
     },
 
-    /// Add a comment to a card
-    Comment {
+    /// Write a card's `describe` document to a markdown file in the working
+    /// directory, so any agent framework can pick up its task from a file
+    Context {
         /// Card ID
         card_id: String,
 
-        /// Comment text
-        text: Option<String>,
+        /// File to write
+        #[arg(long, default_value = ".agent-card.md")]
+        out: PathBuf,
 
-        /// Read comment text from file
+        /// How many dependency links to follow each way
+        #[arg(long, default_value_t = 1)]
+        depth: usize,
+
+        /// How many recent comments to include
+        #[arg(long, default_value_t = 5)]
+        comments: usize,
+
+        /// Keep running and rewrite the file whenever the card changes
         #[arg(long)]
-        file: Option<String>,
+        watch: bool,
+
+        /// Seconds between checks with --watch
+        #[arg(long, default_value_t = 5, requires = "watch")]
+        interval: u64,
     },
-}
 
-// ============================================================================
-// UPDATE subcommands
-// ============================================================================
+    /// Star a board so `list boards` shows it first
+    Star {
+        /// Board ID or name
+        board: String,
 
-#[derive(Subcommand, Debug)]
-pub enum UpdateCommands {
-    /// Update card fields
-    Card {
-        /// Card ID
-        card_id: String,
+        /// Unstar instead
+        #[arg(long)]
+        remove: bool,
+    },
 
-        /// Update card name
+    /// Changelog of a board: cards added, completed, reopened, moved, and reassigned
+    Diff {
+        /// Board ID (default: the --board context)
+        board_id: Option<String>,
+
+        /// Start of the window (e.g., 24h, 7d, 2026-01-31)
+        #[arg(long, default_value = "24h")]
+        since: String,
+
+        /// End of the window (default: now)
         #[arg(long)]
-        name: Option<String>,
+        until: Option<String>,
 
-        /// Update description
+        /// Output format (table, markdown, json, simple)
         #[arg(long)]
-        description: Option<String>,
+        format: Option<OutputFormat>,
+    },
 
-        /// Update status
+    /// Summary for stakeholders: cards completed and started, blocked cards,
+    /// and upcoming due dates (--format email for an HTML message)
+    Digest {
+        /// Board ID (default: the --board context)
         #[arg(long)]
-        status: Option<Status>,
+        board: Option<String>,
 
-        /// Assign card to agent ID (use 'null' to unassign)
-        #[arg(long, conflicts_with = "assign_to_me")]
-        assign: Option<String>,
+        /// Start of the window (e.g., 24h, 7d, 2026-01-31)
+        #[arg(long, default_value = "24h")]
+        since: String,
 
-        /// Assign card to current agent (uses AGENT_BOARD_AGENT_ID)
-        #[arg(long, conflicts_with = "assign")]
-        assign_to_me: bool,
+        /// Include open cards due within this long (e.g., 3d, 1w)
+        #[arg(long, default_value = "7d")]
+        due_within: String,
 
-        /// Add tag (repeatable)
+        /// Mail the digest through the digest.command database setting instead of printing it
         #[arg(long)]
-        add_tag: Vec<String>,
+        send: bool,
 
-        /// Remove tag (repeatable)
+        /// Recipient address for the To header (repeatable)
+        #[arg(long, requires = "send")]
+        to: Vec<String>,
+
+        /// Output format (table, markdown, json, simple, email)
         #[arg(long)]
-        remove_tag: Vec<String>,
+        format: Option<OutputFormat>,
     },
 
-    /// Update board details
-    Board {
-        /// Board ID
-        board_id: String,
+    /// Most recently active cards across boards, with what changed last
+    Recent {
+        /// Only cards on this board
+        #[arg(long)]
+        board: Option<String>,
 
-        /// Update board name
+        /// Number of cards to show
+        #[arg(long, default_value = "20")]
+        limit: usize,
+
+        /// Output format
         #[arg(long)]
-        name: Option<String>,
+        format: Option<OutputFormat>,
+    },
 
-        /// Update description
+    /// Rank cards by how closely their names match some text, to check
+    /// whether work has been filed already
+    Similar {
+        /// Text to match, e.g. the name you are about to give a new card
+        text: String,
+
+        /// Only cards on this board, by name or ID (default: the --board context, else every board)
         #[arg(long)]
-        description: Option<String>,
+        board: Option<String>,
+
+        /// Skip done cards
+        #[arg(long)]
+        open: bool,
+
+        /// Lowest similarity to show, from 0 to 1
+        #[arg(long, default_value = "0.3")]
+        min_score: f64,
+
+        /// Number of cards to show
+        #[arg(long, default_value = "10")]
+        limit: usize,
+
+        /// Output format (table, markdown, json, simple)
+        #[arg(long)]
+        format: Option<OutputFormat>,
     },
 
-    /// Update agent details
-    Agent {
-        /// Agent ID
-        agent_id: String,
+    /// Search cards: by name similarity, or with --semantic by meaning
+    /// (embeddings stored with `embed`)
+    Search {
+        /// What to look for
+        query: String,
 
-        /// Update agent name
+        /// Rank by embedding similarity (needs a build with the `semantic` feature)
         #[arg(long)]
-        name: Option<String>,
+        semantic: bool,
 
-        /// Update command
+        /// Embedding of the query as a JSON array, instead of running embedding.command
+        #[arg(long, requires = "semantic")]
+        embedding_file: Option<PathBuf>,
+
+        /// Only cards on this board, by name or ID (default: the --board context, else every board)
         #[arg(long)]
-        command: Option<String>,
+        board: Option<String>,
 
-        /// Update description
+        /// Skip done cards
         #[arg(long)]
-        description: Option<String>,
+        open: bool,
 
-        /// Update working directory (use "." for current directory)
+        /// Number of cards to show
+        #[arg(long, default_value = "10")]
+        limit: usize,
+
+        /// Output format (table, markdown, json, simple)
         #[arg(long)]
-        workdir: Option<String>,
+        format: Option<OutputFormat>,
     },
 
-    /// Check or uncheck a checklist item
-    ChecklistItem {
-        /// Item ID
-        item_id: String,
+    /// Store card embeddings for `search --semantic`: one card from a file
+    /// or embedding.command, or every new or changed card on a board
+    #[cfg(feature = "semantic")]
+    Embed {
+        /// Card ID (default: every card on --board whose embedding is missing or stale)
+        card_id: Option<String>,
 
-        /// Check the item (mark as complete)
-        #[arg(long, conflicts_with = "uncheck")]
-        check: bool,
+        /// Board to embed, by name or ID (default: the --board context)
+        #[arg(long, conflicts_with = "card_id")]
+        board: Option<String>,
 
-        /// Uncheck the item (mark as incomplete)
-        #[arg(long, conflicts_with = "check")]
-        uncheck: bool,
+        /// Read the card's embedding from this file (a JSON array of numbers)
+        #[arg(long, requires = "card_id")]
+        embedding_file: Option<PathBuf>,
     },
-}
 
-// ============================================================================
-// DELETE subcommands
-// ============================================================================
+    /// Show current agent identity (from AGENT_BOARD_AGENT_ID)
+    Whoami,
 
-#[derive(Subcommand, Debug)]
-pub enum DeleteCommands {
-    /// Delete a board (soft delete)
-    Board {
-        /// Board ID
-        board_id: String,
+    /// Print a board's card dependency graph (Graphviz DOT or Mermaid)
+    Graph {
+        /// Board ID (default: the --board context)
+        board_id: Option<String>,
+
+        /// Output format: mermaid (default), dot, or json
+        #[arg(long)]
+        format: Option<OutputFormat>,
     },
 
-    /// Delete a card (soft delete)
-    Card {
+    /// Reports for coordinators (blocked work, ...)
+    Report {
+        #[command(subcommand)]
+        command: ReportCommands,
+    },
+
+    /// Record daily per-status card counts for trend charts
+    Stats {
+        #[command(subcommand)]
+        command: StatsCommands,
+    },
+
+    /// Charts drawn from recorded board stats
+    Chart {
+        #[command(subcommand)]
+        command: ChartCommands,
+    },
+
+    /// Export board data in other formats
+    Export {
+        #[command(subcommand)]
+        command: ExportCommands,
+    },
+
+    /// Import boards and cards from other tools
+    Import {
+        #[command(subcommand)]
+        command: ImportCommands,
+    },
+
+    /// Create or update cards from a declared plan file (YAML or JSON)
+    Plan {
+        #[command(subcommand)]
+        command: PlanCommands,
+    },
+
+    /// Hold cards back from done until a human signs off
+    Gate {
+        #[command(subcommand)]
+        command: GateCommands,
+    },
+
+    /// Look up tags already in use, or change tags on many cards at once
+    #[command(alias = "tag")]
+    Tags {
+        #[command(subcommand)]
+        command: TagsCommands,
+    },
+
+    /// Manage a board's colored labels (applied to cards with --label)
+    Label {
+        #[command(subcommand)]
+        command: LabelCommands,
+    },
+
+    /// Permanently remove entities (cannot be undone)
+    Purge {
+        #[command(subcommand)]
+        command: PurgeCommands,
+    },
+
+    /// Read and change settings (per-board key/value)
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+
+    /// Create, list, and restore database snapshots
+    Snapshot {
+        #[command(subcommand)]
+        command: SnapshotCommands,
+    },
+
+    /// Database housekeeping
+    Db {
+        #[command(subcommand)]
+        command: DbCommands,
+    },
+
+    /// Synchronize with other agent-board databases
+    Sync {
+        #[command(subcommand)]
+        command: SyncCommands,
+    },
+
+    /// Measure database operation latency (uses a temporary board)
+    Bench {
+        /// Number of cards to create, get, and comment on
+        #[arg(long, default_value = "100")]
+        iterations: usize,
+
+        /// Output format
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+}
+
+// ============================================================================
+// LIST subcommands
+// ============================================================================
+
+#[derive(Subcommand, Debug)]
+pub enum ListCommands {
+    /// List all boards
+    Boards {
+        /// Include soft-deleted boards
+        #[arg(long)]
+        include_deleted: bool,
+
+        /// Only boards created after this (e.g., 2d, 1w, 2026-01-31, or an RFC 3339 timestamp)
+        #[arg(long)]
+        created_after: Option<String>,
+
+        /// Only boards created before this (same formats as --created-after)
+        #[arg(long)]
+        created_before: Option<String>,
+
+        /// Only boards updated since this (same formats as --created-after)
+        #[arg(long)]
+        updated_since: Option<String>,
+
+        /// Order after starred boards
+        #[arg(long, value_enum, default_value = "recent")]
+        sort: BoardSort,
+
+        /// Only boards in this group (set with `update board --group`)
+        #[arg(long)]
+        group: Option<String>,
+
+        /// Skip the card totals footer under the table
+        #[arg(long)]
+        no_summary: bool,
+
+        /// Output format
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+
+    /// List cards on a board
+    Cards {
+        /// Board ID (default: the --board context)
+        board_id: Option<String>,
+
+        /// Filter by status
+        #[arg(long)]
+        status: Option<Status>,
+
+        /// Filter by assignee
+        #[arg(long)]
+        assigned_to: Option<String>,
+
+        /// Filter by tag (repeatable, cards must have ALL specified tags)
+        #[arg(long)]
+        tag: Vec<String>,
+
+        /// Include soft-deleted cards
+        #[arg(long)]
+        include_deleted: bool,
+
+        /// Only cards created after this (e.g., 2d, 1w, 2026-01-31, or an RFC 3339 timestamp)
+        #[arg(long)]
+        created_after: Option<String>,
+
+        /// Only cards created before this (same formats as --created-after)
+        #[arg(long)]
+        created_before: Option<String>,
+
+        /// Only cards updated since this (same formats as --created-after)
+        #[arg(long)]
+        updated_since: Option<String>,
+
+        /// Fetch core card fields only (tags and checklist are left empty)
+        #[arg(long)]
+        brief: bool,
+
+        /// Skip the status totals footer under the table
+        #[arg(long)]
+        no_summary: bool,
+
+        /// Output format
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+
+    /// List all registered agents
+    Agents {
+        /// Include deactivated agents
+        #[arg(long)]
+        include_inactive: bool,
+
+        /// Only agents with this capability (repeatable, AND logic)
+        #[arg(long)]
+        capability: Vec<String>,
+
+        /// Output format
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+
+    /// List comments on a card
+    Comments {
         /// Card ID
         card_id: String,
+
+        /// Only comments by this author (agent ID)
+        #[arg(long)]
+        author: Option<String>,
+
+        /// Only comments newer than this (e.g., 30m, 2h, 1d, 2026-01-31, or an RFC 3339 timestamp)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Show at most this many of the most recent comments
+        #[arg(long)]
+        limit: Option<usize>,
+
+        /// Newest first
+        #[arg(long)]
+        reverse: bool,
+
+        /// Only comments of this kind
+        #[arg(long)]
+        kind: Option<CommentKind>,
+
+        /// Show the full text of comments over the board's size limit (default: preview)
+        #[arg(long)]
+        full: bool,
+
+        /// Output format
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+
+    /// List configured event hooks
+    Hooks {
+        /// Output format
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+
+    /// List Slack/Discord notifiers
+    Notifiers {
+        /// Filter by board
+        #[arg(long)]
+        board: Option<String>,
+
+        /// Output format
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+
+    /// List recorded events (card created/moved/assigned, comment added)
+    Events {
+        /// Filter by board
+        #[arg(long)]
+        board: Option<String>,
+
+        /// Filter by card
+        #[arg(long)]
+        card: Option<String>,
+
+        /// Filter by event type
+        #[arg(long)]
+        event: Option<EventKind>,
+
+        /// Only events newer than this (e.g., 30m, 2h, 1d, 2026-01-31, or an RFC 3339 timestamp)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Show at most this many of the most recent events
+        #[arg(long, default_value = "50")]
+        limit: usize,
+
+        /// Output format
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+}
+
+// ============================================================================
+// CREATE subcommands
+// ============================================================================
+
+#[derive(Subcommand, Debug)]
+pub enum CreateCommands {
+    /// Create a new board
+    Board {
+        /// Board name
+        name: String,
+
+        /// Board description
+        #[arg(long)]
+        description: Option<String>,
+
+        /// Print only the new ID (for CARD_ID=$(agent-board create ...))
+        #[arg(long)]
+        id_only: bool,
+    },
+
+    /// Create a new card on a board
+    Card {
+        /// Board ID (with a --board context, the only argument is the card name)
+        board_id: String,
+
+        /// Card name
+        name: Option<String>,
+
+        /// Card description
+        #[arg(long)]
+        description: Option<String>,
+
+        /// Initial status
+        #[arg(long, default_value = "todo")]
+        status: Status,
+
+        /// Due date (e.g., 3d from now, 2026-01-31 end of day, or an RFC 3339 timestamp)
+        #[arg(long)]
+        due: Option<String>,
+
+        /// Estimated effort (e.g., 30m, 4h, 2d)
+        #[arg(long)]
+        estimate: Option<String>,
+
+        /// Apply a board label by name (repeatable; see `label create`)
+        #[arg(long)]
+        label: Vec<String>,
+
+        /// If an open card on the board has a very similar name, return it
+        /// instead of creating a duplicate (board policy: cards.dedupe)
+        #[arg(long)]
+        dedupe: bool,
+
+        /// Print only the new ID (for CARD_ID=$(agent-board create ...))
+        #[arg(long)]
+        id_only: bool,
     },
 
-    /// Unregister an agent (soft delete)
+    /// Register a new agent identity
     Agent {
-        /// Agent ID
-        agent_id: String,
+        /// Agent name (auto-generated if not provided)
+        name: Option<String>,
+
+        /// Command to invoke this agent (e.g., stakpak, claude, aider)
+        #[arg(long, default_value = "stakpak")]
+        command: String,
+
+        /// Agent description
+        #[arg(long)]
+        description: Option<String>,
+
+        /// Print only the new ID (for CARD_ID=$(agent-board create ...))
+        #[arg(long)]
+        id_only: bool,
     },
 
-    /// Delete a comment from a card
-    Comment {
-        /// Comment ID
-        comment_id: String,
+    /// Add checklist items to a card
+    Checklist {
+        /// Card ID
+        card_id: String,
+
+        /// Checklist item text (repeatable)
+        #[arg(long, required = true)]
+        item: Vec<String>,
     },
 
-    /// Delete a checklist item
-    ChecklistItem {
-        /// Item ID
-        item_id: String,
+    /// Make a card depend on another card
+    Dependency {
+        /// Card that is blocked
+        card_id: String,
+
+        /// Card that must be done first
+        depends_on: String,
+    },
+
+    /// Run a shell command when an event fires (event JSON on stdin)
+    Hook {
+        /// Event to react to
+        event: EventKind,
+
+        /// Shell command to run (via sh -c)
+        command: String,
+
+        /// Only fire for cards on this board
+        #[arg(long)]
+        board: Option<String>,
+
+        /// card-moved only: fire when the card enters this status
+        #[arg(long)]
+        status: Option<Status>,
+
+        /// Print only the new ID (for CARD_ID=$(agent-board create ...))
+        #[arg(long)]
+        id_only: bool,
+    },
+
+    /// Post to a Slack or Discord webhook when a board's cards reach a status
+    Notifier {
+        /// Chat service the webhook belongs to
+        kind: NotifierKind,
+
+        /// Incoming webhook URL
+        url: String,
+
+        /// Board whose cards to announce
+        #[arg(long)]
+        board: Option<String>,
+
+        /// Announce cards entering this status (repeatable; default: pending-review and done)
+        #[arg(long = "on", value_name = "STATUS")]
+        on: Vec<Status>,
+
+        /// Print only the new ID (for CARD_ID=$(agent-board create ...))
+        #[arg(long)]
+        id_only: bool,
+    },
+
+    /// Add a comment to a card
+    Comment {
+        /// Card ID
+        card_id: String,
+
+        /// Comment text
+        text: Option<String>,
+
+        /// Read comment text from file
+        #[arg(long)]
+        file: Option<String>,
+
+        /// Kind of comment (status-update, question, review, blocker)
+        #[arg(long)]
+        kind: Option<CommentKind>,
+
+        /// Machine-readable JSON to attach (e.g., '{"tests": {"passed": 12}}')
+        #[arg(long)]
+        meta: Option<String>,
+
+        /// Print only the new ID (for CARD_ID=$(agent-board create ...))
+        #[arg(long)]
+        id_only: bool,
+    },
+}
+
+// ============================================================================
+// UPDATE subcommands
+// ============================================================================
+
+#[derive(Subcommand, Debug)]
+pub enum UpdateCommands {
+    /// Update card fields
+    Card {
+        /// Card ID
+        card_id: String,
+
+        /// Update card name
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Update description
+        #[arg(long)]
+        description: Option<String>,
+
+        /// Append a paragraph to the description (the previous version is kept)
+        #[arg(long, conflicts_with = "description")]
+        append_description: Option<String>,
+
+        /// Update status
+        #[arg(long)]
+        status: Option<Status>,
+
+        /// Assign card to agent ID (use 'null' to unassign)
+        #[arg(long, conflicts_with = "assign_to_me")]
+        assign: Option<String>,
+
+        /// Assign card to current agent (uses AGENT_BOARD_AGENT_ID)
+        #[arg(long, conflicts_with = "assign")]
+        assign_to_me: bool,
+
+        /// Allow --assign to an ID that is not an active agent (external identities)
+        #[arg(long, requires = "assign")]
+        force: bool,
+
+        /// Set due date (same formats as create card; use 'null' to clear)
+        #[arg(long)]
+        due: Option<String>,
+
+        /// Set estimated effort (e.g., 4h; use 'null' to clear)
+        #[arg(long)]
+        estimate: Option<String>,
+
+        /// Set the pull request URL (use 'null' to clear)
+        #[arg(long)]
+        pr_url: Option<String>,
+
+        /// Add tag (repeatable)
+        #[arg(long)]
+        add_tag: Vec<String>,
+
+        /// Remove tag (repeatable)
+        #[arg(long)]
+        remove_tag: Vec<String>,
+
+        /// Add tags not yet used on the board without a warning
+        #[arg(long, requires = "add_tag")]
+        create_tag: bool,
+
+        /// Apply a board label by name (repeatable)
+        #[arg(long)]
+        label: Vec<String>,
+
+        /// Remove a label by name (repeatable)
+        #[arg(long)]
+        remove_label: Vec<String>,
+    },
+
+    /// Update board details
+    Board {
+        /// Board ID
+        board_id: String,
+
+        /// Update board name
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Update description
+        #[arg(long)]
+        description: Option<String>,
+
+        /// Put the board in a group (folder) for `list boards`; use 'null' to ungroup
+        #[arg(long)]
+        group: Option<String>,
+
+        /// Pin a note every agent reading the board sees (kanban, listings,
+        /// brief); use 'null' to unpin
+        #[arg(long)]
+        pin: Option<String>,
+
+        /// When a card's last dependency is done, comment on it and move it
+        /// from `blocked` back to todo
+        #[arg(long)]
+        auto_unblock: Option<bool>,
+    },
+
+    /// Update agent details
+    Agent {
+        /// Agent ID
+        agent_id: String,
+
+        /// Update agent name
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Update command
+        #[arg(long)]
+        command: Option<String>,
+
+        /// Update description
+        #[arg(long)]
+        description: Option<String>,
+
+        /// Update working directory (use "." for current directory)
+        #[arg(long)]
+        workdir: Option<String>,
+
+        /// Register another working directory, e.g. a worktree (repeatable, "." for current)
+        #[arg(long)]
+        add_workdir: Vec<String>,
+
+        /// Unregister an extra working directory (repeatable, "." for current)
+        #[arg(long)]
+        remove_workdir: Vec<String>,
+
+        /// Declare a skill, e.g. rust or terraform (repeatable)
+        #[arg(long)]
+        add_capability: Vec<String>,
+
+        /// Drop a skill (repeatable)
+        #[arg(long)]
+        remove_capability: Vec<String>,
+
+        /// What the agent may change: full, comment-only, or read-only
+        #[arg(long)]
+        access: Option<AgentAccess>,
+
+        /// Limit the agent's changes to one board (ID or name)
+        #[arg(long, conflicts_with = "any_board")]
+        only_board: Option<String>,
+
+        /// Lift the --only-board restriction
+        #[arg(long)]
+        any_board: bool,
+    },
+
+    /// Check or uncheck a checklist item
+    ChecklistItem {
+        /// Item ID
+        item_id: String,
+
+        /// Check the item (mark as complete)
+        #[arg(long, conflicts_with = "uncheck")]
+        check: bool,
+
+        /// Uncheck the item (mark as incomplete)
+        #[arg(long, conflicts_with = "check")]
+        uncheck: bool,
+    },
+}
+
+// ============================================================================
+// DELETE subcommands
+// ============================================================================
+
+#[derive(Subcommand, Debug)]
+pub enum DeleteCommands {
+    /// Delete a board (soft delete)
+    Board {
+        /// Board ID
+        board_id: String,
+
+        /// Show what would be deleted without deleting anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Get a confirmation token (agents, when the board sets delete.require_confirmation)
+        #[arg(long, conflicts_with_all = ["dry_run", "confirm", "yes"])]
+        request: bool,
+
+        /// Token from --request (valid for 10 minutes, single use)
+        #[arg(long, value_name = "TOKEN")]
+        confirm: Option<String>,
+
+        /// Confirm without a token (humans only; ignored with an agent identity)
+        #[arg(long)]
+        yes: bool,
+
+        /// Output format for the impact report
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+
+    /// Delete a card (soft delete)
+    Card {
+        /// Card ID
+        card_id: String,
+    },
+
+    /// Unregister an agent (soft delete); its open cards are unassigned by default
+    Agent {
+        /// Agent ID
+        agent_id: String,
+
+        /// Hand the agent's open cards to this agent instead of unassigning them
+        #[arg(long, conflicts_with = "unassign")]
+        reassign_to: Option<String>,
+
+        /// Unassign the agent's open cards (default)
+        #[arg(long)]
+        unassign: bool,
+    },
+
+    /// Delete a comment from a card (soft delete; only its author or a human)
+    Comment {
+        /// Comment ID
+        comment_id: String,
+    },
+
+    /// Delete a checklist item (soft delete)
+    ChecklistItem {
+        /// Item ID
+        item_id: String,
+    },
+
+    /// Remove an event hook
+    Hook {
+        /// Hook ID
+        hook_id: String,
+    },
+
+    /// Remove a Slack/Discord notifier
+    Notifier {
+        /// Notifier ID
+        notifier_id: String,
+    },
+
+    /// Remove a dependency between two cards
+    Dependency {
+        /// Card that was blocked
+        card_id: String,
+
+        /// Card it depended on
+        depends_on: String,
+    },
+}
+
+// ============================================================================
+// REPORT subcommands
+// ============================================================================
+
+#[derive(Subcommand, Debug)]
+pub enum ReportCommands {
+    /// Per-agent leaderboard: cards completed, average cycle time, comments, cards held
+    Agents {
+        /// Reporting window (e.g., 7d, 24h, 2026-01-01)
+        #[arg(long, default_value = "7d")]
+        since: String,
+
+        /// Output format (table, markdown, json)
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+
+    /// Open cards by time in their current status, flagging those over a threshold
+    Aging {
+        /// Board ID (default: the --board context)
+        #[arg(long)]
+        board: Option<String>,
+
+        /// Flag cards that have been in their status longer than this (e.g., 3d, 12h)
+        #[arg(long, default_value = "3d")]
+        threshold: String,
+
+        /// Output format
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+
+    /// Done cards moved back to work by `reopen`, newest first
+    Reopened {
+        /// Board ID (default: the --board context)
+        #[arg(long)]
+        board: Option<String>,
+
+        /// Reporting window (e.g., 30d, 2026-01-01)
+        #[arg(long, default_value = "30d")]
+        since: String,
+
+        /// Output format
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+
+    /// Open cards waiting on unfinished dependencies, tagged `blocked`, or idle too long
+    Blocked {
+        /// Board ID (default: the --board context)
+        #[arg(long)]
+        board: Option<String>,
+
+        /// Also flag cards not updated for this long (e.g., 3d, 12h)
+        #[arg(long)]
+        stale: Option<String>,
+
+        /// Output format
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+
+    /// Dated cards whose assignee can't plausibly finish them on time, with
+    /// an agent who could (projected from estimates and daily capacity)
+    Schedule {
+        /// Board ID (default: the --board context)
+        #[arg(long)]
+        board: Option<String>,
+
+        /// Hours of work each agent gets through per day (e.g., 6h, 90m)
+        #[arg(long, default_value = "6h")]
+        capacity: String,
+
+        /// Also list cards that are on track
+        #[arg(long)]
+        all: bool,
+
+        /// Output format (table, json, simple)
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+
+    /// The dependency chain with the most estimated work, with owners: the
+    /// cards to focus on to shorten delivery
+    CriticalPath {
+        /// Board ID (default: the --board context)
+        board_id: Option<String>,
+
+        /// Output format (table, json, simple)
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+
+    /// Cards waiting in pending_review across boards, longest in review first
+    ReviewQueue {
+        /// Only this board (default: the --board context, else every board)
+        #[arg(long)]
+        board: Option<String>,
+
+        /// Output format (table, markdown, json)
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+}
+
+// ============================================================================
+// PLAN subcommands
+// ============================================================================
+
+#[derive(Subcommand, Debug)]
+pub enum PlanCommands {
+    /// Preview what apply would create, update, or leave orphaned (changes nothing)
+    Diff {
+        /// Plan file (.yaml/.yml or .json)
+        file: String,
+
+        /// Board ID (default: the --board context)
+        #[arg(long)]
+        board: Option<String>,
+
+        /// Output format
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+
+    /// Create the plan's cards, or update the ones created by an earlier apply
+    Apply {
+        /// Plan file (.yaml/.yml or .json)
+        file: String,
+
+        /// Board ID (default: the --board context)
+        #[arg(long)]
+        board: Option<String>,
+
+        /// Output format
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+}
+
+// ============================================================================
+// EXPORT subcommands
+// ============================================================================
+
+#[derive(Subcommand, Debug)]
+pub enum ExportCommands {
+    /// Mermaid gantt chart from due dates and estimates
+    Gantt {
+        /// Board ID (default: the --board context)
+        board_id: Option<String>,
+    },
+}
+
+// ============================================================================
+// STATS / CHART subcommands
+// ============================================================================
+
+#[derive(Subcommand, Debug)]
+pub enum StatsCommands {
+    /// Record today's card counts by status (run daily; `sweep` records them too)
+    Snapshot {
+        /// Only this board (default: the --board context, else every board)
+        #[arg(long)]
+        board: Option<String>,
+
+        /// Output format
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ChartCommands {
+    /// Cumulative flow diagram: stacked daily counts by status
+    Cfd {
+        /// Board ID (default: the --board context)
+        board_id: Option<String>,
+
+        /// Days of history to show
+        #[arg(long, default_value = "30")]
+        days: u32,
+
+        /// Bar width in characters
+        #[arg(long, default_value = "50")]
+        width: usize,
+
+        /// Output format (json and simple print the recorded counts)
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+}
+
+// ============================================================================
+// IMPORT subcommands
+// ============================================================================
+
+#[derive(Subcommand, Debug)]
+pub enum ImportCommands {
+    /// Cards from a `task export` file: projects become boards, annotations comments
+    Taskwarrior {
+        /// Path to the JSON written by `task export`
+        file: String,
+
+        /// Board for tasks without a project, ID or name (default: the --board context, else "Inbox")
+        #[arg(long)]
+        board: Option<String>,
+
+        /// Report what would be created without writing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Output format
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+}
+
+// ============================================================================
+// GATE subcommands
+// ============================================================================
+
+#[derive(Subcommand, Debug)]
+pub enum GateCommands {
+    /// Block moving a card past pending_review until the gate is approved
+    Create {
+        /// Card ID
+        card_id: String,
+
+        /// Who must approve
+        #[arg(long, default_value = "human")]
+        require: GateRequirement,
+    },
+
+    /// Approve a card's open gates (run without AGENT_BOARD_AGENT_ID)
+    Approve {
+        /// Card ID
+        card_id: String,
+
+        /// Name recorded as the approver (default: $USER)
+        #[arg(long)]
+        by: Option<String>,
+    },
+
+    /// List a card's gates and who approved them
+    List {
+        /// Card ID
+        card_id: String,
+
+        /// Output format
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+}
+
+// ============================================================================
+// TAGS subcommands
+// ============================================================================
+
+#[derive(Subcommand, Debug)]
+pub enum TagsCommands {
+    /// Existing tags on a board, most used first
+    Suggest {
+        /// Board ID (default: the --board context)
+        #[arg(long)]
+        board: Option<String>,
+
+        /// Only tags starting with this text
+        #[arg(long)]
+        prefix: Option<String>,
+
+        /// Output format (simple prints one tag per line)
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+
+    /// Add or remove tags on every card matching a filter, in one transaction
+    Apply {
+        /// Board ID (default: the --board context)
+        #[arg(long)]
+        board: Option<String>,
+
+        /// Only cards with this status
+        #[arg(long)]
+        status: Option<Status>,
+
+        /// Only cards assigned to this agent
+        #[arg(long)]
+        assigned_to: Option<String>,
+
+        /// Only cards that already have this tag (can be repeated, AND logic)
+        #[arg(long)]
+        tag: Vec<String>,
+
+        /// Tag to add (can be repeated)
+        #[arg(long)]
+        add_tag: Vec<String>,
+
+        /// Tag to remove (can be repeated)
+        #[arg(long)]
+        remove_tag: Vec<String>,
+
+        /// Add tags new to the board without a warning
+        #[arg(long)]
+        create_tag: bool,
+
+        /// Only count and list the matching cards
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+// ============================================================================
+// LABEL subcommands
+// ============================================================================
+
+#[derive(Subcommand, Debug)]
+pub enum LabelCommands {
+    /// Create a label on a board
+    Create {
+        /// Label name
+        name: String,
+
+        /// Board ID (default: the --board context)
+        #[arg(long)]
+        board: Option<String>,
+
+        /// Display color (black, red, green, yellow, blue, magenta, cyan,
+        /// white, or "bright <color>")
+        #[arg(long, default_value = "blue")]
+        color: String,
+    },
+
+    /// Rename a label (cards keep it)
+    Rename {
+        /// Current label name
+        name: String,
+
+        /// New label name
+        new_name: String,
+
+        /// Board ID (default: the --board context)
+        #[arg(long)]
+        board: Option<String>,
+    },
+
+    /// Delete a label and remove it from every card
+    Delete {
+        /// Label name
+        name: String,
+
+        /// Board ID (default: the --board context)
+        #[arg(long)]
+        board: Option<String>,
+    },
+
+    /// List a board's labels
+    List {
+        /// Board ID (default: the --board context)
+        #[arg(long)]
+        board: Option<String>,
+
+        /// Output format
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+}
+
+// ============================================================================
+// PURGE subcommands
+// ============================================================================
+
+#[derive(Subcommand, Debug)]
+pub enum PurgeCommands {
+    /// Permanently remove a board (deleted or not) with all its cards, comments, and checklists
+    Board {
+        /// Board ID
+        board_id: String,
+
+        /// Show what would be removed without removing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Get a confirmation token (agents, when the board sets delete.require_confirmation)
+        #[arg(long, conflicts_with_all = ["dry_run", "confirm", "yes"])]
+        request: bool,
+
+        /// Token from --request (valid for 10 minutes, single use)
+        #[arg(long, value_name = "TOKEN")]
+        confirm: Option<String>,
+
+        /// Confirm without a token (humans only; ignored with an agent identity)
+        #[arg(long)]
+        yes: bool,
+
+        /// Output format for the impact report
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+}
+
+// ============================================================================
+// CONFIG subcommands
+// ============================================================================
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommands {
+    /// Settings of one board (e.g., auto_unblock)
+    Board {
+        /// Board ID
+        board_id: String,
+
+        #[command(subcommand)]
+        command: BoardConfigCommands,
+    },
+
+    /// Database-wide settings: journal_mode (wal, delete, ...), synchronous (off, normal, full, extra), single_writer, and width.<column> table widths
+    Db {
+        #[command(subcommand)]
+        command: BoardConfigCommands,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum BoardConfigCommands {
+    /// Print a setting's value
+    Get {
+        /// Setting key
+        key: String,
+    },
+
+    /// Set a setting
+    Set {
+        /// Setting key (lowercase letters, digits, '_' and '.')
+        key: String,
+
+        /// Value
+        value: String,
+    },
+
+    /// Remove a setting
+    Unset {
+        /// Setting key
+        key: String,
+    },
+
+    /// List all settings
+    List {
+        /// Output format
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+}
+
+// ============================================================================
+// SNAPSHOT subcommands
+// ============================================================================
+
+#[derive(Subcommand, Debug)]
+pub enum SnapshotCommands {
+    /// Save a copy of the current database
+    Create {
+        /// Snapshot name (e.g., before-refactor)
+        name: String,
+    },
+
+    /// List available snapshots
+    List {
+        /// Output format
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+
+    /// Replace the database with a snapshot (current state is kept as pre-restore-*)
+    Restore {
+        /// Snapshot name
+        name: String,
+    },
+}
+
+// ============================================================================
+// DB subcommands
+// ============================================================================
+
+#[derive(Subcommand, Debug)]
+pub enum DbCommands {
+    /// Show the database path, size, schema version, and active journal and durability settings
+    Status {
+        /// Output format
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+
+    /// Run maintenance steps on the database
+    Maintain {
+        /// Remove stored comment bodies no comment refers to anymore
+        #[arg(long)]
+        gc_blobs: bool,
+
+        /// Output format
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+}
+
+// ============================================================================
+// SYNC subcommands
+// ============================================================================
+
+#[derive(Subcommand, Debug)]
+pub enum SyncCommands {
+    /// Merge boards, cards, agents, and comments from another database (newest wins)
+    Merge {
+        /// Path to the other database file
+        path: String,
+
+        /// Output format
+        #[arg(long)]
+        format: Option<OutputFormat>,
     },
 }
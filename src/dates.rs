@@ -0,0 +1,401 @@
+//! Points in time typed on the command line (`--due`, `--since`,
+//! `--created-after`, ...).
+//!
+//! Besides RFC 3339 timestamps, values can be written the way people say
+//! them: `2026-03-01 17:00`, `tomorrow 5pm`, `next monday`, `mar 4 9:30am`,
+//! `friday noon`. Times without an offset are read in the `timezone`
+//! database setting: `utc` (the default), `local`, a fixed offset like
+//! `+05:30`, or an IANA name like `Europe/Berlin`, which must exist in the
+//! system's zoneinfo and is applied through `TZ`. A trailing `utc`, `local`,
+//! or offset overrides it for one value.
+
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime,
+    TimeZone, Utc, Weekday,
+};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// Where a time without an offset is read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Zone {
+    Utc,
+    Local,
+    Fixed(FixedOffset),
+}
+
+/// Which way a value leans when it leaves something out. `Past` (`--since`,
+/// `--created-after`): a bare date is its start and a bare weekday the most
+/// recent one. `Future` (`--due`): the end of the day and the coming one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bias {
+    Past,
+    Future,
+}
+
+static DEFAULT_ZONE: OnceLock<Zone> = OnceLock::new();
+
+pub fn default_zone() -> Zone {
+    DEFAULT_ZONE.get().copied().unwrap_or(Zone::Utc)
+}
+
+/// Apply the `timezone` setting for this run. Call once at startup, before
+/// anything reads local time.
+pub fn set_default_zone(setting: Option<&str>) {
+    let zone = match setting {
+        None => Zone::Utc,
+        Some(value) => match zone_from_str(value) {
+            Some(zone) => zone,
+            None if is_named_zone(value) => {
+                // SAFETY: runs on the only thread, before any child process
+                // or local-time lookup
+                unsafe { std::env::set_var("TZ", value) };
+                Zone::Local
+            }
+            None => {
+                eprintln!(
+                    "Warning: timezone '{}' is not in this system's zoneinfo; reading times as UTC",
+                    value
+                );
+                Zone::Utc
+            }
+        },
+    };
+    let _ = DEFAULT_ZONE.set(zone);
+}
+
+/// Whether the `timezone` setting can be `value`
+pub fn is_valid_zone(value: &str) -> bool {
+    zone_from_str(value).is_some() || is_named_zone(value)
+}
+
+/// `utc`, `local`, or a fixed offset (`+05:30`, `-0800`, `+02`)
+fn zone_from_str(value: &str) -> Option<Zone> {
+    match value.to_lowercase().as_str() {
+        "utc" | "z" => Some(Zone::Utc),
+        "local" => Some(Zone::Local),
+        _ => parse_offset(value).map(Zone::Fixed),
+    }
+}
+
+fn parse_offset(value: &str) -> Option<FixedOffset> {
+    let (sign, digits) = match value.as_bytes().first()? {
+        b'+' => (1, &value[1..]),
+        b'-' => (-1, &value[1..]),
+        _ => return None,
+    };
+    let digits = digits.replace(':', "");
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let (hours, minutes) = match digits.len() {
+        2 => (digits.parse::<i32>().ok()?, 0),
+        4 => (digits[..2].parse().ok()?, digits[2..].parse::<i32>().ok()?),
+        _ => return None,
+    };
+    if hours > 14 || minutes > 59 {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+/// An IANA name with a file in the system's zoneinfo (`$TZDIR` or
+/// /usr/share/zoneinfo)
+fn is_named_zone(name: &str) -> bool {
+    if name.is_empty() || name.starts_with('/') || name.split('/').any(|part| part == "..") {
+        return false;
+    }
+    let dir = std::env::var_os("TZDIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("/usr/share/zoneinfo"));
+    dir.join(name).is_file()
+}
+
+/// Parse an absolute or natural point in time; relative durations ("3d") are
+/// left to the caller, since their direction depends on the flag
+pub fn parse(value: &str, now: DateTime<Utc>, zone: Zone, bias: Bias) -> Option<DateTime<Utc>> {
+    if let Ok(ts) = DateTime::parse_from_rfc3339(value) {
+        return Some(ts.with_timezone(&Utc));
+    }
+    let lower = value.trim().to_lowercase();
+    let mut words: Vec<&str> = lower.split_whitespace().filter(|w| *w != "at").collect();
+    if words == ["now"] {
+        return Some(now);
+    }
+    let zone = match words.last().and_then(|w| zone_from_str(w)) {
+        Some(zone) if words.len() > 1 => {
+            words.pop();
+            zone
+        }
+        _ => zone,
+    };
+
+    // `2026-03-01T17:00` reads as one word (lowercased above)
+    if let [word] = words.as_slice()
+        && let Some(naive) = ["%Y-%m-%dt%H:%M:%S", "%Y-%m-%dt%H:%M"]
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(word, format).ok())
+    {
+        return resolve(naive, zone);
+    }
+
+    let today = local_date(now, zone);
+    let (date, used) = match parse_date(&words, today, bias) {
+        Some(found) => found,
+        // A time alone is today's, or tomorrow's once it has passed for a due date
+        None => (today, 0),
+    };
+    let time = match &words[used..] {
+        [] if used > 0 => None,
+        [word] => Some(parse_clock(word)?),
+        [number, suffix @ ("am" | "pm")] => Some(parse_clock(&format!("{}{}", number, suffix))?),
+        _ => return None,
+    };
+    match time {
+        Some(time) => {
+            let at = resolve(date.and_time(time), zone)?;
+            if used == 0 && bias == Bias::Future && at < now {
+                return resolve((date + Duration::days(1)).and_time(time), zone);
+            }
+            Some(at)
+        }
+        None => {
+            let time = match bias {
+                Bias::Past => NaiveTime::MIN,
+                Bias::Future => NaiveTime::from_hms_opt(23, 59, 59).expect("valid time of day"),
+            };
+            resolve(date.and_time(time), zone)
+        }
+    }
+}
+
+fn local_date(now: DateTime<Utc>, zone: Zone) -> NaiveDate {
+    match zone {
+        Zone::Utc => now.date_naive(),
+        Zone::Local => now.with_timezone(&Local).date_naive(),
+        Zone::Fixed(offset) => now.with_timezone(&offset).date_naive(),
+    }
+}
+
+/// The instant `naive` names in `zone`; in a daylight-saving gap, an hour later
+fn resolve(naive: NaiveDateTime, zone: Zone) -> Option<DateTime<Utc>> {
+    let pick = |naive: NaiveDateTime| match zone {
+        Zone::Utc => Some(naive.and_utc()),
+        Zone::Local => Local
+            .from_local_datetime(&naive)
+            .earliest()
+            .map(|t| t.with_timezone(&Utc)),
+        Zone::Fixed(offset) => offset
+            .from_local_datetime(&naive)
+            .single()
+            .map(|t| t.with_timezone(&Utc)),
+    };
+    pick(naive).or_else(|| pick(naive + Duration::hours(1)))
+}
+
+/// A date from the leading words, and how many words it used
+fn parse_date(words: &[&str], today: NaiveDate, bias: Bias) -> Option<(NaiveDate, usize)> {
+    let first = *words.first()?;
+    match first {
+        "today" => return Some((today, 1)),
+        "tomorrow" => return Some((today + Duration::days(1), 1)),
+        "yesterday" => return Some((today - Duration::days(1), 1)),
+        "next" | "last" => {
+            let weekday = parse_weekday(words.get(1)?)?;
+            let date = if first == "next" {
+                following(today, weekday, 1)
+            } else {
+                preceding(today, weekday, 1)
+            };
+            return Some((date, 2));
+        }
+        _ => {}
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(first, "%Y-%m-%d") {
+        return Some((date, 1));
+    }
+    if let Some(weekday) = parse_weekday(first) {
+        let date = match bias {
+            Bias::Past => preceding(today, weekday, 0),
+            Bias::Future => following(today, weekday, 0),
+        };
+        return Some((date, 1));
+    }
+
+    // `mar 4`, `march 4 2026`, `4 mar`, `4 march 2026`
+    let (month, day) = match (parse_month(first), words.get(1)) {
+        (Some(month), Some(day)) => (month, day.parse::<u32>().ok()?),
+        (None, Some(second)) => (parse_month(second)?, first.parse::<u32>().ok()?),
+        _ => return None,
+    };
+    if let Some(year) = words
+        .get(2)
+        .filter(|w| w.len() == 4)
+        .and_then(|w| w.parse::<i32>().ok())
+    {
+        return Some((NaiveDate::from_ymd_opt(year, month, day)?, 3));
+    }
+    let date = NaiveDate::from_ymd_opt(today.year(), month, day)?;
+    let date = match bias {
+        Bias::Past if date > today => date.with_year(today.year() - 1)?,
+        Bias::Future if date < today => date.with_year(today.year() + 1)?,
+        _ => date,
+    };
+    Some((date, 2))
+}
+
+/// The first `weekday` at least `skip` days after `today`
+fn following(today: NaiveDate, weekday: Weekday, skip: i64) -> NaiveDate {
+    (skip..skip + 7)
+        .map(|days| today + Duration::days(days))
+        .find(|d| d.weekday() == weekday)
+        .expect("every weekday within a week")
+}
+
+/// The last `weekday` at least `skip` days before `today`
+fn preceding(today: NaiveDate, weekday: Weekday, skip: i64) -> NaiveDate {
+    (skip..skip + 7)
+        .map(|days| today - Duration::days(days))
+        .find(|d| d.weekday() == weekday)
+        .expect("every weekday within a week")
+}
+
+fn parse_weekday(word: &str) -> Option<Weekday> {
+    const WEEKDAYS: [(Weekday, &str); 7] = [
+        (Weekday::Mon, "monday"),
+        (Weekday::Tue, "tuesday"),
+        (Weekday::Wed, "wednesday"),
+        (Weekday::Thu, "thursday"),
+        (Weekday::Fri, "friday"),
+        (Weekday::Sat, "saturday"),
+        (Weekday::Sun, "sunday"),
+    ];
+    WEEKDAYS
+        .iter()
+        .find(|(_, name)| word == *name || word == &name[..3])
+        .map(|(weekday, _)| *weekday)
+}
+
+fn parse_month(word: &str) -> Option<u32> {
+    const MONTHS: [&str; 12] = [
+        "january",
+        "february",
+        "march",
+        "april",
+        "may",
+        "june",
+        "july",
+        "august",
+        "september",
+        "october",
+        "november",
+        "december",
+    ];
+    let word = word.trim_end_matches('.');
+    MONTHS
+        .iter()
+        .position(|name| word == *name || word == &name[..3])
+        .map(|i| i as u32 + 1)
+}
+
+/// `17:00`, `17:00:30`, `5pm`, `5:30pm`, `noon`, `midnight`
+fn parse_clock(word: &str) -> Option<NaiveTime> {
+    match word {
+        "noon" => return NaiveTime::from_hms_opt(12, 0, 0),
+        "midnight" => return Some(NaiveTime::MIN),
+        _ => {}
+    }
+    let (clock, pm) = if let Some(clock) = word.strip_suffix("pm") {
+        (clock, Some(true))
+    } else if let Some(clock) = word.strip_suffix("am") {
+        (clock, Some(false))
+    } else {
+        (word, None)
+    };
+    let mut parts = clock.split(':');
+    let hour: u32 = parts.next()?.parse().ok()?;
+    let minute: u32 = parts.next().map_or(Some(0), |m| m.parse().ok())?;
+    let second: u32 = parts.next().map_or(Some(0), |s| s.parse().ok())?;
+    if parts.next().is_some() {
+        return None;
+    }
+    let hour = match pm {
+        Some(_) if !(1..=12).contains(&hour) => return None,
+        Some(true) => hour % 12 + 12,
+        Some(false) => hour % 12,
+        // A bare number is too ambiguous to be a time
+        None if !clock.contains(':') => return None,
+        None => hour,
+    };
+    NaiveTime::from_hms_opt(hour, minute, second)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A Tuesday morning
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-03-10T09:00:00Z")
+            .unwrap()
+            .into()
+    }
+
+    fn at(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value).unwrap().into()
+    }
+
+    #[test]
+    fn reads_natural_forms_in_the_configured_zone() {
+        let utc = |value, bias| parse(value, now(), Zone::Utc, bias);
+        assert_eq!(
+            utc("tomorrow 5pm", Bias::Future),
+            Some(at("2026-03-11T17:00:00Z"))
+        );
+        assert_eq!(
+            utc("next monday", Bias::Future),
+            Some(at("2026-03-16T23:59:59Z"))
+        );
+        assert_eq!(utc("monday", Bias::Past), Some(at("2026-03-09T00:00:00Z")));
+        assert_eq!(
+            utc("last tue", Bias::Past),
+            Some(at("2026-03-03T00:00:00Z"))
+        );
+        assert_eq!(
+            utc("mar 4 9:30am", Bias::Future),
+            Some(at("2027-03-04T09:30:00Z"))
+        );
+        assert_eq!(utc("8am", Bias::Future), Some(at("2026-03-11T08:00:00Z")));
+        assert_eq!(
+            utc("2026-01-31", Bias::Future),
+            Some(at("2026-01-31T23:59:59Z"))
+        );
+        assert_eq!(
+            utc("2026-03-01 17:00 +02:00", Bias::Past),
+            Some(at("2026-03-01T15:00:00Z"))
+        );
+
+        let berlin = Zone::Fixed(FixedOffset::east_opt(3600).unwrap());
+        assert_eq!(
+            parse("friday noon", now(), berlin, Bias::Future),
+            Some(at("2026-03-13T11:00:00Z"))
+        );
+        assert_eq!(
+            parse("2026-03-01T17:00 utc", now(), berlin, Bias::Past),
+            Some(at("2026-03-01T17:00:00Z"))
+        );
+        for bad in ["5", "tomorrow 25:00", "next week", "mar 40", "13pm"] {
+            assert_eq!(parse(bad, now(), Zone::Utc, Bias::Future), None, "{}", bad);
+        }
+    }
+
+    #[test]
+    fn accepts_offsets_and_names_for_the_timezone_setting() {
+        assert!(is_valid_zone("local"));
+        assert!(is_valid_zone("+05:30"));
+        assert!(is_valid_zone("-0800"));
+        assert!(!is_valid_zone("+25:00"));
+        assert!(!is_valid_zone("../etc/passwd"));
+        assert!(!is_valid_zone("Mars/Olympus_Mons"));
+    }
+}
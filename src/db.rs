@@ -1,27 +1,571 @@
 use crate::AgentBoardError;
 use crate::cli::Cli;
 use crate::models::*;
-use chrono::{DateTime, Utc};
+use crate::schedule;
+use crate::similarity;
+use crate::storage::{self, LocalCache};
+use chrono::{DateTime, NaiveDate, Utc};
 use libsql::{Builder, Connection};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
+/// Board setting: release dependents when their last blocker is done ("true"/"false")
+pub const SETTING_AUTO_UNBLOCK: &str = "auto_unblock";
+/// Board settings checked before a card may enter done ("true"/"false")
+pub const SETTING_DONE_REQUIRE_CHECKLIST: &str = "done.require_checklist";
+pub const SETTING_DONE_REQUIRE_COMMENT: &str = "done.require_comment";
+pub const SETTING_DONE_REQUIRE_PR_URL: &str = "done.require_pr_url";
+/// Board setting: deleting or purging the board needs `--yes` (humans) or a
+/// token from `--request` (agents) ("true"/"false")
+pub const SETTING_DELETE_REQUIRE_CONFIRMATION: &str = "delete.require_confirmation";
+
+/// Tags agent-board itself reads or sets: `blocked` holds a card until
+/// `auto_unblock` releases it, `stale` marks cards `sweep` flagged
+pub const TAG_BLOCKED: &str = "blocked";
+pub const TAG_STALE: &str = "stale";
+pub const BUILT_IN_TAGS: &[&str] = &[TAG_BLOCKED, TAG_STALE];
+
+/// How long a removal token from `--request` stays valid
+const REMOVAL_TOKEN_MINUTES: i64 = 10;
+
+/// Board setting: comments larger than this many bytes are shown as a
+/// preview, with the full text kept for `get <comment_id>`
+pub const SETTING_COMMENT_MAX_BYTES: &str = "comments.max_bytes";
+const DEFAULT_COMMENT_MAX_BYTES: usize = 16 * 1024;
+
+/// Board setting: a card entering pending_review gets a linked review card
+/// on this board (stored as the board ID), closed when the card is done
+pub const SETTING_REVIEW_BOARD: &str = "review.board";
+
+/// Board setting: what `create card` does when an open card on the board
+/// already has a very similar name: "return" it instead, or "error"
+pub const SETTING_CARDS_DEDUPE: &str = "cards.dedupe";
+const DEDUPE_MODES: &[&str] = &["return", "error"];
+
+/// Board setting: `sweep` reminds about open cards due within this long
+/// (comment plus `card_due_soon`), once per due date
+pub const SETTING_DUE_REMIND_BEFORE: &str = "due.remind_before";
+/// Board setting: `sweep` deletes the board's events older than this
+pub const SETTING_RETENTION_EVENTS: &str = "retention.events";
+
+/// Board setting prefix for WIP limits, e.g. "wip.in_progress" = "3"
+pub const SETTING_WIP_PREFIX: &str = "wip.";
+/// Board setting prefix for staleness policies, e.g. "stale.in_progress" = "24h"
+pub const SETTING_STALE_PREFIX: &str = "stale.";
+
+/// Settings whose value must be "true" or "false"
+const BOOLEAN_SETTINGS: &[&str] = &[
+    SETTING_AUTO_UNBLOCK,
+    SETTING_DONE_REQUIRE_CHECKLIST,
+    SETTING_DONE_REQUIRE_COMMENT,
+    SETTING_DONE_REQUIRE_PR_URL,
+    SETTING_DELETE_REQUIRE_CONFIRMATION,
+];
+
+/// Settings that hold agents back, so only a human may set or unset them
+const HUMAN_ONLY_SETTINGS: &[&str] = &[
+    SETTING_DONE_REQUIRE_CHECKLIST,
+    SETTING_DONE_REQUIRE_COMMENT,
+    SETTING_DONE_REQUIRE_PR_URL,
+    SETTING_DELETE_REQUIRE_CONFIRMATION,
+];
+
+/// Database setting (`config db`): SQLite journal mode, applied on open
+pub const DB_SETTING_JOURNAL_MODE: &str = "journal_mode";
+/// Database setting: SQLite `synchronous` level, applied on open
+pub const DB_SETTING_SYNCHRONOUS: &str = "synchronous";
+/// Database setting: writers take `<db>.lock` and wait their turn ("true"/"false")
+pub const DB_SETTING_SINGLE_WRITER: &str = "single_writer";
+/// Database setting prefix: `width.<column>` overrides how many characters
+/// a table column shows before it is cut with "..."
+pub const DB_SETTING_WIDTH_PREFIX: &str = "width.";
+/// Table columns whose width `width.<column>` can set
+pub const TABLE_COLUMNS: &[&str] = &[
+    "board",
+    "command",
+    "detail",
+    "last_change",
+    "name",
+    "reason",
+    "working_directory",
+];
+/// Narrowest column width; anything less leaves no room beside the "..."
+const MIN_COLUMN_WIDTH: usize = 8;
+/// Database setting: shell command that prints an embedding for the text on
+/// its stdin (`embed`, `search --semantic`)
+pub const DB_SETTING_EMBEDDING_COMMAND: &str = "embedding.command";
+/// Database setting: zone for times typed without an offset (`utc`, `local`,
+/// `+05:30`, or an IANA name); see `dates`
+pub const DB_SETTING_TIMEZONE: &str = "timezone";
+/// Database setting: shell command that mails the message on its stdin
+/// (`digest --send`), e.g. `sendmail -t`
+pub const DB_SETTING_DIGEST_COMMAND: &str = "digest.command";
+/// How long a writer waits for the `single_writer` lock before giving up
+const WRITER_LOCK_TIMEOUT_SECS: u64 = 30;
+const JOURNAL_MODES: &[&str] = &["wal", "delete", "truncate", "persist", "memory"];
+const SYNCHRONOUS_LEVELS: &[&str] = &["off", "normal", "full", "extra"];
+// WAL lets agents read while another writes; full is SQLite's own default
+const DEFAULT_JOURNAL_MODE: &str = "wal";
+const DEFAULT_SYNCHRONOUS: &str = "full";
+
 const SCHEMA: &str = include_str!("schema.sql");
 
+/// Schema changes applied on top of `schema.sql`, in order. The number of
+/// applied migrations is tracked in `PRAGMA user_version`.
+const MIGRATIONS: &[&str] = &[
+    include_str!("migrations/0001_namespaces.sql"),
+    include_str!("migrations/0002_card_indexes.sql"),
+    include_str!("migrations/0003_card_schedule.sql"),
+    include_str!("migrations/0004_card_pr_url.sql"),
+    include_str!("migrations/0005_comment_kind.sql"),
+    include_str!("migrations/0006_comment_meta.sql"),
+    include_str!("migrations/0007_card_plan_key.sql"),
+    include_str!("migrations/0008_soft_delete_comments_items.sql"),
+    include_str!("migrations/0009_comment_blobs.sql"),
+    include_str!("migrations/0010_comment_spill.sql"),
+    include_str!("migrations/0011_checklist_position.sql"),
+    include_str!("migrations/0012_board_group.sql"),
+    include_str!("migrations/0013_board_pinned_note.sql"),
+    include_str!("migrations/0014_event_via.sql"),
+];
+
+/// First 16 hex digits of the SHA-256 of a value's JSON, for ETags
+fn content_hash(value: &impl serde::Serialize) -> String {
+    use sha2::{Digest, Sha256};
+    let json = serde_json::to_vec(value).unwrap();
+    Sha256::digest(&json)[..8]
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Writes `apply_plan()` still has to make for one declared card
+struct PlanDelta {
+    change: PlanCardChange,
+    name: Option<String>,
+    description: Option<String>,
+    assignee: Option<String>,
+    tags: Vec<String>,
+    checklist: Vec<String>,
+    depends_on: Vec<String>, // plan keys
+}
+
+const CARD_COLUMNS: &str = "id, board_id, name, description, status, assigned_to, created_at, updated_at, deleted_at, due_at, estimate_minutes, pr_url, plan_key";
+
+// Bodies moved to `blobs` are read back in full; `text` only holds their preview.
+// Spilled comments (over the board's size limit) stay as the preview, with the full size.
+const COMMENT_COLUMNS: &str = "id, card_id, author, \
+    CASE WHEN spilled = 1 THEN text ELSE COALESCE((SELECT content FROM blobs WHERE hash = comments.blob_hash), text) END, \
+    created_at, kind, meta, \
+    CASE WHEN spilled = 1 THEN (SELECT size FROM blobs WHERE hash = comments.blob_hash) END";
+
+/// Comment bodies at least this large are stored once in `blobs`
+const BLOB_MIN_BYTES: usize = 8 * 1024;
+/// Characters of a blob-stored body kept on the comment row (for mention matching)
+const BLOB_PREVIEW_CHARS: usize = 500;
+
+/// Who a `Database` acts for. Kept apart from the connection so whatever
+/// owns the connection (one CLI run today) decides how long it lives.
+pub struct Identity {
+    pub namespace: String,
+    pub actor: Option<String>, // agent performing this run, if configured
+    pub budget: Option<MutationBudget>, // applies only when `actor` is set
+    pub via: Option<String>,   // who is acting as `actor` with `--as`
+}
+
+impl Identity {
+    pub fn from_cli(cli: &Cli) -> Result<Self, AgentBoardError> {
+        Ok(Self {
+            namespace: cli.get_namespace(),
+            actor: cli.get_agent_id().ok(),
+            budget: cli.get_mutation_budget()?,
+            via: cli.get_principal(),
+        })
+    }
+}
+
 pub struct Database {
     conn: Connection,
+    path: PathBuf,
+    namespace: String,
+    actor: Option<String>,          // agent performing this run, if configured
+    budget: Option<MutationBudget>, // applies only when `actor` is set
+    via: Option<String>,            // who is acting as `actor` with `--as`
+    scope: Option<AgentScope>,      // the actor's restrictions, if any
+    cache: Option<LocalCache>,      // set when working on a temporary copy of `path`
+    single_writer: bool,
+    // Held from this run's first change until it exits, with `single_writer`
+    writer_lock: std::sync::Mutex<Option<std::fs::File>>,
+    // Hooks fired inside a transaction, started only once it commits
+    deferred_hooks: std::sync::Mutex<Option<Vec<crate::hooks::Dispatch>>>,
+    // Open savepoints inside that transaction, each with how many hooks were
+    // already deferred when it started
+    savepoints: std::sync::Mutex<Vec<usize>>,
+    // Set while writing synthetic data (`bench`), so hooks and notifiers stay quiet
+    hooks_muted: std::sync::atomic::AtomicBool,
 }
 
 impl Database {
-    pub async fn load(_cli: &Cli) -> Result<Self, AgentBoardError> {
-        let path = Self::get_db_path()?;
+    /// Open the database at `AGENT_BOARD_DB_PATH` (or the default path) for
+    /// the identity given on the command line
+    pub async fn load(cli: &Cli) -> Result<Self, AgentBoardError> {
+        Self::open(
+            &Self::get_db_path()?,
+            Identity::from_cli(cli)?,
+            cli.get_local_cache(),
+        )
+        .await
+    }
 
-        // Ensure parent directory exists
-        if let Some(parent) = path.parent() {
-            std::fs::create_dir_all(parent)?;
+    /// Open a database file for `identity`; the connection lives as long as
+    /// the returned value. With `local_cache`, commands work on a temporary
+    /// copy that `save()` writes back. A read-only file is always read
+    /// through such a copy, with changes refused.
+    pub async fn open(
+        path: &Path,
+        identity: Identity,
+        local_cache: bool,
+    ) -> Result<Self, AgentBoardError> {
+        let mount = storage::mount_of(path);
+        let network = mount
+            .as_ref()
+            .filter(|m| m.is_network())
+            .map(|m| m.fs_type.clone());
+        let read_only = storage::is_read_only(path, mount.as_ref());
+        if read_only && !path.exists() {
+            return Err(AgentBoardError::PermissionDenied(format!(
+                "{} is on a read-only filesystem and holds no database yet; set AGENT_BOARD_DB_PATH to a writable location",
+                path.display()
+            )));
+        }
+
+        let cache = if local_cache || read_only {
+            Some(LocalCache::create(path, read_only)?)
+        } else {
+            // Ensure parent directory exists
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| {
+                    AgentBoardError::General(format!(
+                        "Cannot create {}: {} (set AGENT_BOARD_DB_PATH to a writable location)",
+                        parent.display(),
+                        e
+                    ))
+                })?;
+            }
+            None
+        };
+
+        let working = cache.as_ref().map_or(path, |c| c.copy.as_path());
+        let conn = match Self::connect(working).await {
+            Ok(conn) => conn,
+            Err(e) => {
+                return Err(match &network {
+                    Some(fs_type) if cache.is_none() => AgentBoardError::General(format!(
+                        "{}\n\n{} is on a {} filesystem, where SQLite file locking is unreliable. \
+                         Rerun with --local-cache (or AGENT_BOARD_LOCAL_CACHE=1) to work on a local copy, \
+                         or set AGENT_BOARD_DB_PATH to a local disk.",
+                        e,
+                        path.display(),
+                        fs_type
+                    )),
+                    _ => e,
+                });
+            }
+        };
+
+        let mut db = Self {
+            conn,
+            path: path.to_path_buf(),
+            namespace: identity.namespace,
+            actor: identity.actor,
+            budget: identity.budget,
+            via: identity.via,
+            scope: None,
+            cache,
+            single_writer: false,
+            writer_lock: std::sync::Mutex::new(None),
+            deferred_hooks: std::sync::Mutex::new(None),
+            savepoints: std::sync::Mutex::new(Vec::new()),
+            hooks_muted: std::sync::atomic::AtomicBool::new(false),
+        };
+        db.single_writer =
+            db.db_setting(DB_SETTING_SINGLE_WRITER).await?.as_deref() == Some("true");
+        db.apply_durability(network.is_some()).await?;
+        if read_only {
+            db.conn
+                .query("PRAGMA query_only = 1", ())
+                .await
+                .map_err(|e| {
+                    AgentBoardError::General(format!("PRAGMA query_only failed: {}", e))
+                })?;
+        }
+        if let (Some(actor), Some(via)) = (&db.actor, &db.via) {
+            // `--as` is for humans: an agent borrowing another identity would
+            // step outside its own scope, budget, and audit trail
+            let mut rows = db
+                .conn
+                .query("SELECT 1 FROM agents WHERE id = ?1", [via.as_str()])
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+            if rows
+                .next()
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+                .is_some()
+            {
+                return Err(AgentBoardError::PermissionDenied(format!(
+                    "agent {} can't act as another agent; --as is for humans (unset AGENT_BOARD_AGENT_ID)",
+                    via
+                )));
+            }
+            drop(rows);
+            db.get_agent(actor).await?;
+        }
+        if let Some(actor) = &db.actor {
+            db.scope = db.agent_scope(actor).await?;
+        }
+        Ok(db)
+    }
+
+    /// Apply the `config db` journal mode and synchronous level (or their
+    /// defaults) to this connection. WAL needs memory shared between readers,
+    /// so network filesystems default to a rollback journal, and a local cache
+    /// always uses one to stay a single file that can be copied back.
+    async fn apply_durability(&self, network: bool) -> Result<(), AgentBoardError> {
+        let configured = self.db_setting(DB_SETTING_JOURNAL_MODE).await?;
+        let journal_mode = match configured {
+            _ if self.cache.is_some() => "delete".to_string(),
+            Some(mode) => mode,
+            None if network => "delete".to_string(),
+            None => DEFAULT_JOURNAL_MODE.to_string(),
+        };
+        let synchronous = self
+            .db_setting(DB_SETTING_SYNCHRONOUS)
+            .await?
+            .unwrap_or_else(|| DEFAULT_SYNCHRONOUS.into());
+        // Both values were checked against a fixed list when set
+        for pragma in [
+            format!("PRAGMA journal_mode = {}", journal_mode),
+            format!("PRAGMA synchronous = {}", synchronous),
+        ] {
+            // journal_mode answers with a row, so run both as queries
+            self.conn
+                .query(&pragma, ())
+                .await
+                .map_err(|e| AgentBoardError::General(format!("{} failed: {}", pragma, e)))?;
+        }
+        Ok(())
+    }
+
+    /// Database-wide settings from `config db`
+    pub async fn db_settings(&self) -> Result<Vec<BoardSetting>, AgentBoardError> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT key, value, updated_at FROM db_settings ORDER BY key",
+                (),
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        let mut settings = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            settings.push(BoardSetting {
+                key: row.get::<String>(0).unwrap_or_default(),
+                value: row.get::<String>(1).unwrap_or_default(),
+                updated_at: Self::parse_datetime(&row.get::<String>(2).unwrap_or_default()),
+            });
+        }
+        Ok(settings)
+    }
+
+    pub async fn db_setting(&self, key: &str) -> Result<Option<String>, AgentBoardError> {
+        Ok(self
+            .db_settings()
+            .await?
+            .into_iter()
+            .find(|s| s.key == key)
+            .map(|s| s.value))
+    }
+
+    /// Column widths from the `width.<column>` settings, keyed by column
+    pub async fn column_widths(&self) -> Result<HashMap<String, usize>, AgentBoardError> {
+        Ok(self
+            .db_settings()
+            .await?
+            .into_iter()
+            .filter_map(|s| {
+                let column = s.key.strip_prefix(DB_SETTING_WIDTH_PREFIX)?.to_string();
+                Some((column, s.value.parse().ok()?))
+            })
+            .collect())
+    }
+
+    /// Set (or with None, remove) a database-wide setting; it takes effect
+    /// the next time the database is opened
+    pub async fn set_db_setting(
+        &self,
+        key: &str,
+        value: Option<&str>,
+    ) -> Result<(), AgentBoardError> {
+        self.check_scope(None, false)?;
+        self.check_budget().await?;
+        if let Some(column) = key.strip_prefix(DB_SETTING_WIDTH_PREFIX) {
+            if !TABLE_COLUMNS.contains(&column) {
+                return Err(AgentBoardError::InvalidArgs(format!(
+                    "Unknown table column '{}': use one of {}",
+                    column,
+                    TABLE_COLUMNS.join(", ")
+                )));
+            }
+            if let Some(value) = value
+                && value
+                    .parse::<usize>()
+                    .map_or(true, |w| w < MIN_COLUMN_WIDTH)
+            {
+                return Err(AgentBoardError::InvalidArgs(format!(
+                    "Invalid {} '{}': use a width of at least {}",
+                    key, value, MIN_COLUMN_WIDTH
+                )));
+            }
+            return self.store_db_setting(key, value).await;
+        }
+        // Zone names are case-sensitive, so kept as typed
+        if key == DB_SETTING_TIMEZONE {
+            if let Some(zone) = value
+                && !crate::dates::is_valid_zone(zone)
+            {
+                return Err(AgentBoardError::InvalidArgs(format!(
+                    "Invalid {} '{}': use utc, local, an offset like +05:30, or a zone name like Europe/Berlin",
+                    key, zone
+                )));
+            }
+            return self.store_db_setting(key, value).await;
+        }
+        // A command line, kept as typed
+        if key == DB_SETTING_EMBEDDING_COMMAND || key == DB_SETTING_DIGEST_COMMAND {
+            if value.is_some_and(|v| v.trim().is_empty()) {
+                return Err(AgentBoardError::InvalidArgs(format!(
+                    "{} cannot be empty",
+                    key
+                )));
+            }
+            return self.store_db_setting(key, value).await;
+        }
+        let allowed = match key {
+            DB_SETTING_JOURNAL_MODE => JOURNAL_MODES,
+            DB_SETTING_SYNCHRONOUS => SYNCHRONOUS_LEVELS,
+            DB_SETTING_SINGLE_WRITER => &["true", "false"],
+            _ => {
+                return Err(AgentBoardError::InvalidArgs(format!(
+                    "Unknown database setting '{}': use {}, {}, {}, {}, {}, {}, or {}<column>",
+                    key,
+                    DB_SETTING_JOURNAL_MODE,
+                    DB_SETTING_SYNCHRONOUS,
+                    DB_SETTING_SINGLE_WRITER,
+                    DB_SETTING_TIMEZONE,
+                    DB_SETTING_EMBEDDING_COMMAND,
+                    DB_SETTING_DIGEST_COMMAND,
+                    DB_SETTING_WIDTH_PREFIX
+                )));
+            }
+        };
+        let value = value.map(str::to_lowercase);
+        if let Some(value) = &value
+            && !allowed.contains(&value.as_str())
+        {
+            return Err(AgentBoardError::InvalidArgs(format!(
+                "Invalid {} '{}': use one of {}",
+                key,
+                value,
+                allowed.join(", ")
+            )));
+        }
+        self.store_db_setting(key, value.as_deref()).await
+    }
+
+    /// Write (or with None, delete) a validated `db_settings` row
+    async fn store_db_setting(
+        &self,
+        key: &str,
+        value: Option<&str>,
+    ) -> Result<(), AgentBoardError> {
+        match value {
+            Some(value) => {
+                self.conn
+                    .execute(
+                        "INSERT OR REPLACE INTO db_settings (key, value, updated_at) VALUES (?1, ?2, ?3)",
+                        [key, value, Utc::now().to_rfc3339().as_str()],
+                    )
+                    .await
+                    .map_err(|e| AgentBoardError::General(format!("Set setting failed: {}", e)))?;
+            }
+            None => {
+                self.conn
+                    .execute("DELETE FROM db_settings WHERE key = ?1", [key])
+                    .await
+                    .map_err(|e| {
+                        AgentBoardError::General(format!("Unset setting failed: {}", e))
+                    })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The settings in effect on this connection, read back from SQLite
+    pub async fn db_status(&self) -> Result<DbStatus, AgentBoardError> {
+        let mut values = Vec::new();
+        for pragma in ["journal_mode", "synchronous", "user_version"] {
+            let mut rows = self
+                .conn
+                .query(&format!("PRAGMA {}", pragma), ())
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+            let value = match rows
+                .next()
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+            {
+                Some(row) => row.get_value(0).unwrap_or(libsql::Value::Null),
+                None => libsql::Value::Null,
+            };
+            values.push(value);
         }
+        let journal_mode = match &values[0] {
+            libsql::Value::Text(mode) => mode.clone(),
+            _ => String::new(),
+        };
+        let synchronous = match &values[1] {
+            libsql::Value::Integer(level) => SYNCHRONOUS_LEVELS
+                .get(*level as usize)
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| level.to_string()),
+            _ => String::new(),
+        };
+        let schema_version = match &values[2] {
+            libsql::Value::Integer(version) => *version as usize,
+            _ => 0,
+        };
+        Ok(DbStatus {
+            path: self.path.to_string_lossy().to_string(),
+            size_bytes: std::fs::metadata(&self.path).map(|m| m.len()).unwrap_or(0),
+            journal_mode,
+            synchronous,
+            schema_version,
+            filesystem: storage::mount_of(&self.path).map(|m| m.fs_type),
+            local_cache: self.cache.is_some(),
+            read_only: self.read_only_path().is_some(),
+            single_writer: self.single_writer,
+        })
+    }
 
-        let db = Builder::new_local(&path)
+    /// Open a database file, initializing the schema and applying migrations
+    async fn connect(path: &Path) -> Result<Connection, AgentBoardError> {
+        let db = Builder::new_local(path)
             .build()
             .await
             .map_err(|e| AgentBoardError::General(format!("Failed to open database: {}", e)))?;
@@ -30,19 +574,154 @@ impl Database {
             .connect()
             .map_err(|e| AgentBoardError::General(format!("Failed to connect: {}", e)))?;
 
+        // Hooks may call back into agent-board while this process still writes
+        conn.busy_timeout(std::time::Duration::from_secs(5))
+            .map_err(|e| AgentBoardError::General(format!("Failed to connect: {}", e)))?;
+
         // Initialize schema
         conn.execute_batch(SCHEMA)
             .await
             .map_err(|e| AgentBoardError::General(format!("Failed to initialize schema: {}", e)))?;
 
-        Ok(Self { conn })
+        Self::migrate(&conn).await?;
+
+        Ok(conn)
+    }
+
+    async fn migrate(conn: &Connection) -> Result<(), AgentBoardError> {
+        // Scoped so the cursor is released before migrations alter tables
+        let version = {
+            let mut rows = conn
+                .query("PRAGMA user_version", ())
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+            match rows
+                .next()
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+            {
+                Some(row) => row.get::<i64>(0).unwrap_or(0) as usize,
+                None => 0,
+            }
+        };
+
+        for (i, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+            conn.execute_batch(&format!(
+                "BEGIN; {} PRAGMA user_version = {}; COMMIT;",
+                migration,
+                i + 1
+            ))
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Migration {} failed: {}", i + 1, e)))?;
+        }
+        Ok(())
     }
 
     pub async fn save(&self) -> Result<(), AgentBoardError> {
-        // SQLite auto-commits, nothing to do here
+        // SQLite auto-commits; only a local cache has anything left to write
+        if let Some(cache) = &self.cache
+            && !cache.read_only
+            && self.conn.total_changes() > 0
+        {
+            cache.sync_back()?;
+        }
+        Ok(())
+    }
+
+    /// Stop (or resume) starting hooks and notifiers for the changes this
+    /// run makes; events are still recorded
+    pub fn mute_hooks(&self, muted: bool) {
+        self.hooks_muted
+            .store(muted, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// The agent this run acts as: `--as`, else `AGENT_BOARD_AGENT_ID`
+    pub fn actor(&self) -> Option<&str> {
+        self.actor.as_deref()
+    }
+
+    /// The database file, when it can't be written and is read through a
+    /// temporary copy
+    pub fn read_only_path(&self) -> Option<&Path> {
+        self.cache
+            .as_ref()
+            .filter(|c| c.read_only)
+            .map(|c| c.target.as_path())
+    }
+
+    /// Start a transaction, or inside one a savepoint, so methods that make
+    /// several changes can be composed into a larger all-or-nothing batch
+    pub(crate) async fn begin(&self) -> Result<(), AgentBoardError> {
+        let pending = self.deferred_hooks.lock().unwrap().as_ref().map(Vec::len);
+        if let Some(pending) = pending {
+            let depth = self.savepoints.lock().unwrap().len() + 1;
+            self.conn
+                .execute(&format!("SAVEPOINT nested_{}", depth), ())
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Savepoint failed: {}", e)))?;
+            self.savepoints.lock().unwrap().push(pending);
+            return Ok(());
+        }
+        self.conn
+            .execute("BEGIN", ())
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Begin transaction failed: {}", e)))?;
+        *self.deferred_hooks.lock().unwrap() = Some(Vec::new());
+        Ok(())
+    }
+
+    pub(crate) async fn commit(&self) -> Result<(), AgentBoardError> {
+        let depth = self.savepoints.lock().unwrap().len();
+        if depth > 0 {
+            self.conn
+                .execute(&format!("RELEASE nested_{}", depth), ())
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Commit failed: {}", e)))?;
+            self.savepoints.lock().unwrap().pop();
+            return Ok(());
+        }
+        let result = self
+            .conn
+            .execute("COMMIT", ())
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Commit failed: {}", e)));
+        let deferred = self.deferred_hooks.lock().unwrap().take();
+        result?;
+        for dispatch in deferred.unwrap_or_default() {
+            dispatch.run();
+        }
         Ok(())
     }
 
+    /// Whether `begin()` has opened a transaction that hasn't ended yet
+    fn in_transaction(&self) -> bool {
+        self.deferred_hooks.lock().unwrap().is_some()
+    }
+
+    pub(crate) async fn rollback(&self) {
+        let depth = self.savepoints.lock().unwrap().len();
+        if depth > 0 {
+            // Best effort, as below; the enclosing transaction decides the rest
+            let _ = self
+                .conn
+                .execute(&format!("ROLLBACK TO nested_{}", depth), ())
+                .await;
+            let _ = self
+                .conn
+                .execute(&format!("RELEASE nested_{}", depth), ())
+                .await;
+            let pending = self.savepoints.lock().unwrap().pop().unwrap_or_default();
+            if let Some(deferred) = self.deferred_hooks.lock().unwrap().as_mut() {
+                deferred.truncate(pending);
+            }
+            return;
+        }
+        // Best effort: the original error is more useful than a rollback failure
+        let _ = self.conn.execute("ROLLBACK", ()).await;
+        // Events were rolled back too, so their hooks must not run
+        self.deferred_hooks.lock().unwrap().take();
+    }
+
     fn get_db_path() -> Result<PathBuf, AgentBoardError> {
         // Check for custom path in env
         if let Ok(custom_path) = std::env::var("AGENT_BOARD_DB_PATH") {
@@ -55,6 +734,128 @@ impl Database {
         Ok(home.join(".agent-board").join("data.db"))
     }
 
+    fn snapshots_dir() -> Result<PathBuf, AgentBoardError> {
+        let path = Self::get_db_path()?;
+        let parent = path.parent().map(PathBuf::from).unwrap_or_default();
+        Ok(parent.join("snapshots"))
+    }
+
+    fn snapshot_path(name: &str) -> Result<PathBuf, AgentBoardError> {
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+            && !name.starts_with('.');
+        if !valid {
+            return Err(AgentBoardError::InvalidArgs(format!(
+                "Invalid snapshot name '{}': use letters, digits, '-', '_' or '.'",
+                name
+            )));
+        }
+        Ok(Self::snapshots_dir()?.join(format!("{}.db", name)))
+    }
+
+    // Snapshot operations
+    pub async fn create_snapshot(&self, name: &str) -> Result<Snapshot, AgentBoardError> {
+        let path = Self::snapshot_path(name)?;
+        if path.exists() {
+            return Err(AgentBoardError::InvalidArgs(format!(
+                "Snapshot '{}' already exists",
+                name
+            )));
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        // VACUUM INTO writes a consistent, compacted copy of the live database
+        self.conn
+            .execute("VACUUM INTO ?1", [path.to_string_lossy().as_ref()])
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Snapshot failed: {}", e)))?;
+
+        Self::read_snapshot(name, &path)
+    }
+
+    pub fn list_snapshots() -> Result<Vec<Snapshot>, AgentBoardError> {
+        let dir = Self::snapshots_dir()?;
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut snapshots = Vec::new();
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("db") {
+                continue;
+            }
+            if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
+                snapshots.push(Self::read_snapshot(name, &path)?);
+            }
+        }
+        snapshots.sort_by_key(|s| std::cmp::Reverse(s.created_at));
+        Ok(snapshots)
+    }
+
+    /// Replace the database file with a snapshot. Takes the open database so
+    /// the current state, including commits still in the WAL, is first saved
+    /// as a `pre-restore-*` snapshot with `VACUUM INTO`; the file is only
+    /// replaced once that backup and the snapshot both check out, and after
+    /// the connection is closed.
+    pub async fn restore_snapshot(
+        self,
+        name: &str,
+    ) -> Result<(Snapshot, Snapshot), AgentBoardError> {
+        // Scoped agents may not roll the whole database back
+        self.check_scope(None, false)?;
+        let source = Self::snapshot_path(name)?;
+        if !source.exists() {
+            return Err(AgentBoardError::NotFound(format!(
+                "Snapshot not found: {}",
+                name
+            )));
+        }
+        let restored = Self::read_snapshot(name, &source)?;
+
+        let backup_name = format!("pre-restore-{}", Utc::now().format("%Y%m%d%H%M%S"));
+        let backup_path = Self::snapshot_path(&backup_name)?;
+        if backup_path.exists() {
+            return Err(AgentBoardError::InvalidArgs(format!(
+                "Snapshot '{}' already exists; try again in a second",
+                backup_name
+            )));
+        }
+        self.conn
+            .execute("VACUUM INTO ?1", [backup_path.to_string_lossy().as_ref()])
+            .await
+            .map_err(|e| {
+                AgentBoardError::General(format!("Backup before restore failed: {}", e))
+            })?;
+        let backup = Self::read_snapshot(&backup_name, &backup_path)?;
+
+        let db_path = Self::get_db_path()?;
+        drop(self);
+        std::fs::copy(&source, &db_path)?;
+        for suffix in ["-wal", "-shm"] {
+            let _ = std::fs::remove_file(storage::sidecar(&db_path, suffix));
+        }
+        Ok((restored, backup))
+    }
+
+    fn read_snapshot(name: &str, path: &Path) -> Result<Snapshot, AgentBoardError> {
+        let metadata = std::fs::metadata(path)?;
+        let created_at = metadata
+            .modified()
+            .map(DateTime::<Utc>::from)
+            .unwrap_or_else(|_| Utc::now());
+        Ok(Snapshot {
+            name: name.to_string(),
+            path: path.to_string_lossy().to_string(),
+            size_bytes: metadata.len(),
+            created_at,
+        })
+    }
+
     fn generate_id(prefix: &str) -> String {
         format!(
             "{}_{}",
@@ -78,6 +879,28 @@ impl Database {
         }
     }
 
+    fn event_kind_from_str(s: &str) -> EventKind {
+        match s {
+            "card_created" => EventKind::CardCreated,
+            "card_assigned" => EventKind::CardAssigned,
+            "comment_added" => EventKind::CommentAdded,
+            "card_stale" => EventKind::CardStale,
+            "card_reopened" => EventKind::CardReopened,
+            "card_due_soon" => EventKind::CardDueSoon,
+            _ => EventKind::CardMoved,
+        }
+    }
+
+    fn comment_kind_from_str(s: &str) -> Option<CommentKind> {
+        match s {
+            "status_update" => Some(CommentKind::StatusUpdate),
+            "question" => Some(CommentKind::Question),
+            "review" => Some(CommentKind::Review),
+            "blocker" => Some(CommentKind::Blocker),
+            _ => None,
+        }
+    }
+
     fn generate_agent_name() -> String {
         let mut generator = names::Generator::default();
         generator
@@ -93,19 +916,24 @@ impl Database {
         working_directory: String,
         description: Option<String>,
     ) -> Result<Agent, AgentBoardError> {
+        self.check_scope(None, false)?;
+        self.check_budget().await?;
         let agent_name = name.unwrap_or_else(Self::generate_agent_name);
         let id = Self::generate_id("agent");
         let now = Utc::now().to_rfc3339();
 
         self.conn
             .execute(
-                "INSERT INTO agents (id, name, command, working_directory, description, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-                libsql::params![id.as_str(), agent_name.as_str(), command.as_str(), working_directory.as_str(), description.clone().unwrap_or_default().as_str(), now.as_str(), now.as_str()],
+                "INSERT INTO agents (id, name, command, working_directory, description, created_at, updated_at, namespace) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                libsql::params![id.as_str(), agent_name.as_str(), command.as_str(), working_directory.as_str(), description.clone().unwrap_or_default().as_str(), now.as_str(), now.as_str(), self.namespace.as_str()],
             )
             .await
             .map_err(|e| {
                 if e.to_string().contains("UNIQUE constraint failed") {
-                    AgentBoardError::InvalidArgs(format!("Agent name '{}' already exists", agent_name))
+                    AgentBoardError::InvalidArgs(format!(
+                        "Agent name '{}' already exists in namespace '{}'",
+                        agent_name, self.namespace
+                    ))
                 } else {
                     AgentBoardError::General(format!("Insert failed: {}", e))
                 }
@@ -117,7 +945,7 @@ impl Database {
     pub async fn get_agent(&self, agent_id: &str) -> Result<Agent, AgentBoardError> {
         let mut rows = self.conn
             .query(
-                "SELECT id, name, command, working_directory, description, created_at, updated_at, deactivated_at FROM agents WHERE id = ?1 AND deactivated_at IS NULL",
+                "SELECT id, name, command, working_directory, description, created_at, updated_at, deactivated_at, namespace FROM agents WHERE id = ?1 AND deactivated_at IS NULL",
                 [agent_id],
             )
             .await
@@ -128,7 +956,7 @@ impl Database {
             .await
             .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
         {
-            Ok(Agent {
+            let mut agents = vec![Agent {
                 id: row.get::<String>(0).unwrap_or_default(),
                 name: row.get::<String>(1).unwrap_or_default(),
                 command: row.get::<String>(2).unwrap_or_default(),
@@ -141,7 +969,17 @@ impl Database {
                     .ok()
                     .flatten()
                     .map(|s| Self::parse_datetime(&s)),
-            })
+                namespace: row.get::<String>(8).unwrap_or_default(),
+                capabilities: Vec::new(),
+                extra_workdirs: Vec::new(),
+                scope: None,
+                activity: None,
+            }];
+            drop(rows);
+            self.attach_capabilities(&mut agents).await?;
+            self.attach_workdirs(&mut agents).await?;
+            agents[0].scope = self.agent_scope(agent_id).await?;
+            Ok(agents.remove(0))
         } else {
             Err(AgentBoardError::NotFound(format!(
                 "Agent not found: {}",
@@ -150,15 +988,37 @@ impl Database {
         }
     }
 
+    /// Map every known agent ID to its name, for human-readable output
+    pub async fn agent_names(&self) -> Result<HashMap<String, String>, AgentBoardError> {
+        let mut rows = self
+            .conn
+            .query("SELECT id, name FROM agents", ())
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+
+        let mut names = HashMap::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            names.insert(
+                row.get::<String>(0).unwrap_or_default(),
+                row.get::<String>(1).unwrap_or_default(),
+            );
+        }
+        Ok(names)
+    }
+
     pub async fn list_agents(&self, include_inactive: bool) -> Result<Vec<Agent>, AgentBoardError> {
         let query = if include_inactive {
-            "SELECT id, name, command, working_directory, description, created_at, updated_at, deactivated_at FROM agents ORDER BY created_at DESC"
+            "SELECT id, name, command, working_directory, description, created_at, updated_at, deactivated_at, namespace FROM agents WHERE namespace = ?1 ORDER BY created_at DESC, id DESC"
         } else {
-            "SELECT id, name, command, working_directory, description, created_at, updated_at, deactivated_at FROM agents WHERE deactivated_at IS NULL ORDER BY created_at DESC"
+            "SELECT id, name, command, working_directory, description, created_at, updated_at, deactivated_at, namespace FROM agents WHERE namespace = ?1 AND deactivated_at IS NULL ORDER BY created_at DESC, id DESC"
         };
         let mut rows = self
             .conn
-            .query(query, ())
+            .query(query, [self.namespace.as_str()])
             .await
             .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
 
@@ -181,18 +1041,293 @@ impl Database {
                     .ok()
                     .flatten()
                     .map(|s| Self::parse_datetime(&s)),
+                namespace: row.get::<String>(8).unwrap_or_default(),
+                capabilities: Vec::new(),
+                extra_workdirs: Vec::new(),
+                scope: None,
+                activity: None,
             });
         }
+        drop(rows);
+        self.attach_capabilities(&mut agents).await?;
+        self.attach_workdirs(&mut agents).await?;
+        self.attach_activity(&mut agents).await?;
+        for agent in agents.iter_mut() {
+            agent.scope = self.agent_scope(&agent.id).await?;
+        }
         Ok(agents)
     }
 
-    pub async fn update_agent(
-        &self,
-        agent_id: &str,
-        update: AgentUpdate,
-    ) -> Result<(), AgentBoardError> {
-        // Verify agent exists
-        self.get_agent(agent_id).await?;
+    /// Open-card count, last event, and current in-progress card for a set
+    /// of agents, in one query
+    async fn attach_activity(&self, agents: &mut [Agent]) -> Result<(), AgentBoardError> {
+        if agents.is_empty() {
+            return Ok(());
+        }
+        let query = format!(
+            "SELECT a.id, COALESCE(o.open_cards, 0), l.last_at, p.id, p.name FROM agents a \
+             LEFT JOIN (SELECT assigned_to, COUNT(*) AS open_cards FROM cards \
+                        WHERE deleted_at IS NULL AND status != 'done' GROUP BY assigned_to) o \
+               ON o.assigned_to = a.id \
+             LEFT JOIN (SELECT actor, MAX(created_at) AS last_at FROM events GROUP BY actor) l \
+               ON l.actor = a.id \
+             LEFT JOIN (SELECT assigned_to, id, name, ROW_NUMBER() OVER ( \
+                          PARTITION BY assigned_to ORDER BY updated_at DESC, id DESC) AS rank \
+                        FROM cards WHERE deleted_at IS NULL AND status = 'in_progress') p \
+               ON p.assigned_to = a.id AND p.rank = 1 \
+             WHERE a.id IN ({})",
+            Self::placeholders(agents.len())
+        );
+        let params: Vec<libsql::Value> = agents.iter().map(|a| a.id.clone().into()).collect();
+        let mut rows = self
+            .conn
+            .query(&query, libsql::params_from_iter(params))
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+
+        let mut activity: HashMap<String, AgentActivity> = HashMap::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            activity.insert(
+                row.get::<String>(0).unwrap_or_default(),
+                AgentActivity {
+                    open_cards: row.get::<i64>(1).unwrap_or(0) as usize,
+                    last_active_at: row
+                        .get::<Option<String>>(2)
+                        .ok()
+                        .flatten()
+                        .map(|s| Self::parse_datetime(&s)),
+                    current_card_id: row.get::<Option<String>>(3).ok().flatten(),
+                    current_card_name: row.get::<Option<String>>(4).ok().flatten(),
+                },
+            );
+        }
+        for agent in agents.iter_mut() {
+            agent.activity = Some(activity.remove(&agent.id).unwrap_or_default());
+        }
+        Ok(())
+    }
+
+    /// Load capabilities for a set of agents in one query
+    async fn attach_capabilities(&self, agents: &mut [Agent]) -> Result<(), AgentBoardError> {
+        if agents.is_empty() {
+            return Ok(());
+        }
+        let query = format!(
+            "SELECT agent_id, capability FROM agent_capabilities WHERE agent_id IN ({}) ORDER BY capability",
+            Self::placeholders(agents.len())
+        );
+        let params: Vec<libsql::Value> = agents.iter().map(|a| a.id.clone().into()).collect();
+        let mut rows = self
+            .conn
+            .query(&query, libsql::params_from_iter(params))
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+
+        let mut capabilities: HashMap<String, Vec<String>> = HashMap::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            capabilities
+                .entry(row.get::<String>(0).unwrap_or_default())
+                .or_default()
+                .push(row.get::<String>(1).unwrap_or_default());
+        }
+        for agent in agents.iter_mut() {
+            agent.capabilities = capabilities.remove(&agent.id).unwrap_or_default();
+        }
+        Ok(())
+    }
+
+    /// Load extra working directories for a set of agents in one query
+    async fn attach_workdirs(&self, agents: &mut [Agent]) -> Result<(), AgentBoardError> {
+        if agents.is_empty() {
+            return Ok(());
+        }
+        let query = format!(
+            "SELECT agent_id, path FROM agent_workdirs WHERE agent_id IN ({}) ORDER BY path",
+            Self::placeholders(agents.len())
+        );
+        let params: Vec<libsql::Value> = agents.iter().map(|a| a.id.clone().into()).collect();
+        let mut rows = self
+            .conn
+            .query(&query, libsql::params_from_iter(params))
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+
+        let mut workdirs: HashMap<String, Vec<String>> = HashMap::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            workdirs
+                .entry(row.get::<String>(0).unwrap_or_default())
+                .or_default()
+                .push(row.get::<String>(1).unwrap_or_default());
+        }
+        for agent in agents.iter_mut() {
+            agent.extra_workdirs = workdirs.remove(&agent.id).unwrap_or_default();
+        }
+        Ok(())
+    }
+
+    /// An agent's restrictions, None when it has none
+    async fn agent_scope(&self, agent_id: &str) -> Result<Option<AgentScope>, AgentBoardError> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT access, board_id FROM agent_scopes WHERE agent_id = ?1",
+                [agent_id],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        else {
+            return Ok(None);
+        };
+        let access = match row.get::<String>(0).unwrap_or_default().as_str() {
+            "comment-only" => AgentAccess::CommentOnly,
+            "read-only" => AgentAccess::ReadOnly,
+            _ => AgentAccess::Full,
+        };
+        Ok(Some(AgentScope {
+            access,
+            board_id: row.get::<Option<String>>(1).ok().flatten(),
+        }))
+    }
+
+    /// Refuse a change when the database is read-only or the acting agent's
+    /// scope doesn't allow it. `board_id` is the board being changed, None
+    /// for changes outside any one board (agents, new boards, sync);
+    /// `comment` marks comments and reactions. Every change passes through
+    /// here first, so this is also where a single writer waits its turn;
+    /// bookkeeping that read-only runs still make (usage, brief cursors)
+    /// takes the lock through `lock_writer` instead.
+    pub fn check_scope(
+        &self,
+        board_id: Option<&str>,
+        comment: bool,
+    ) -> Result<(), AgentBoardError> {
+        if let Some(path) = self.read_only_path() {
+            return Err(AgentBoardError::PermissionDenied(format!(
+                "{} is read-only, so changes can't be saved; copy it somewhere writable and set AGENT_BOARD_DB_PATH",
+                path.display()
+            )));
+        }
+        if let (Some(actor), Some(scope)) = (&self.actor, &self.scope) {
+            match scope.access {
+                AgentAccess::ReadOnly => {
+                    return Err(AgentBoardError::PermissionDenied(format!(
+                        "agent {} is read-only; a human can lift this with `update agent {} --access full`",
+                        actor, actor
+                    )));
+                }
+                AgentAccess::CommentOnly if !comment => {
+                    return Err(AgentBoardError::PermissionDenied(format!(
+                        "agent {} may only comment; a human can lift this with `update agent {} --access full`",
+                        actor, actor
+                    )));
+                }
+                _ => {}
+            }
+            if let Some(allowed) = &scope.board_id
+                && board_id != Some(allowed.as_str())
+            {
+                return Err(AgentBoardError::PermissionDenied(format!(
+                    "agent {} may only change board {}",
+                    actor, allowed
+                )));
+            }
+        }
+        self.lock_writer()
+    }
+
+    /// With `single_writer`, wait for `<db>.lock` so concurrent runs make
+    /// their changes one at a time instead of failing on a busy database.
+    /// The lock is held until this run exits.
+    fn lock_writer(&self) -> Result<(), AgentBoardError> {
+        if !self.single_writer {
+            return Ok(());
+        }
+        let mut held = self.writer_lock.lock().unwrap();
+        if held.is_some() {
+            return Ok(());
+        }
+        let path = storage::sidecar(&self.path, ".lock");
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .map_err(|e| {
+                AgentBoardError::General(format!("Failed to open {}: {}", path.display(), e))
+            })?;
+        let deadline =
+            std::time::Instant::now() + std::time::Duration::from_secs(WRITER_LOCK_TIMEOUT_SECS);
+        loop {
+            match file.try_lock() {
+                Ok(()) => break,
+                Err(std::fs::TryLockError::WouldBlock) if std::time::Instant::now() < deadline => {
+                    std::thread::sleep(std::time::Duration::from_millis(50));
+                }
+                Err(std::fs::TryLockError::WouldBlock) => {
+                    return Err(AgentBoardError::General(format!(
+                        "Another agent-board process has held the writer lock ({}) for {}s; try again",
+                        path.display(),
+                        WRITER_LOCK_TIMEOUT_SECS
+                    )));
+                }
+                Err(std::fs::TryLockError::Error(e)) => {
+                    return Err(AgentBoardError::General(format!(
+                        "Failed to lock {}: {}",
+                        path.display(),
+                        e
+                    )));
+                }
+            }
+        }
+        *held = Some(file);
+        Ok(())
+    }
+
+    /// `check_scope` for a change to a card, checked against its board
+    async fn check_card_scope(&self, card_id: &str, comment: bool) -> Result<(), AgentBoardError> {
+        if self.scope.is_none() {
+            // Only the board restriction needs the card
+            return self.check_scope(None, comment);
+        }
+        let card = self.get_card(card_id).await?;
+        self.check_scope(Some(&card.board_id), comment)
+    }
+
+    pub async fn update_agent(
+        &self,
+        agent_id: &str,
+        update: AgentUpdate,
+    ) -> Result<(), AgentBoardError> {
+        self.check_scope(None, false)?;
+        self.check_budget().await?;
+        // Verify agent exists
+        let agent = self.get_agent(agent_id).await?;
+        let primary = update
+            .working_directory
+            .clone()
+            .unwrap_or(agent.working_directory);
+        if update.remove_workdirs.contains(&primary) {
+            return Err(AgentBoardError::InvalidArgs(format!(
+                "'{}' is the agent's main working directory; change it with --workdir",
+                primary
+            )));
+        }
 
         let now = Utc::now().to_rfc3339();
 
@@ -238,35 +1373,205 @@ impl Database {
                 .await
                 .map_err(|e| AgentBoardError::General(format!("Update failed: {}", e)))?;
         }
+        for path in update.add_workdirs {
+            if path.trim().is_empty() {
+                return Err(AgentBoardError::InvalidArgs(
+                    "Working directory cannot be empty".into(),
+                ));
+            }
+            if path == primary {
+                continue;
+            }
+            self.conn
+                .execute(
+                    "INSERT OR IGNORE INTO agent_workdirs (agent_id, path) VALUES (?1, ?2)",
+                    [agent_id, path.as_str()],
+                )
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Insert workdir failed: {}", e)))?;
+        }
+        for path in update.remove_workdirs {
+            self.conn
+                .execute(
+                    "DELETE FROM agent_workdirs WHERE agent_id = ?1 AND path = ?2",
+                    [agent_id, path.as_str()],
+                )
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Remove workdir failed: {}", e)))?;
+        }
+        for capability in update.add_capabilities {
+            let capability = capability.trim().to_lowercase();
+            if capability.is_empty() {
+                return Err(AgentBoardError::InvalidArgs(
+                    "Capability cannot be empty".into(),
+                ));
+            }
+            self.conn
+                .execute(
+                    "INSERT OR IGNORE INTO agent_capabilities (agent_id, capability) VALUES (?1, ?2)",
+                    [agent_id, capability.as_str()],
+                )
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Insert capability failed: {}", e)))?;
+        }
+        for capability in update.remove_capabilities {
+            self.conn
+                .execute(
+                    "DELETE FROM agent_capabilities WHERE agent_id = ?1 AND capability = ?2",
+                    [agent_id, capability.trim().to_lowercase().as_str()],
+                )
+                .await
+                .map_err(|e| {
+                    AgentBoardError::General(format!("Remove capability failed: {}", e))
+                })?;
+        }
+        if update.access.is_some() || update.scope_board.is_some() {
+            let current = self.agent_scope(agent_id).await?.unwrap_or_default();
+            let scope = AgentScope {
+                access: update.access.unwrap_or(current.access),
+                board_id: update.scope_board.unwrap_or(current.board_id),
+            };
+            if scope == AgentScope::default() {
+                self.conn
+                    .execute("DELETE FROM agent_scopes WHERE agent_id = ?1", [agent_id])
+                    .await
+                    .map_err(|e| AgentBoardError::General(format!("Update failed: {}", e)))?;
+            } else {
+                let access = scope.access.to_string();
+                self.conn
+                    .execute(
+                        "INSERT OR REPLACE INTO agent_scopes (agent_id, access, board_id) VALUES (?1, ?2, ?3)",
+                        libsql::params![agent_id, access.as_str(), scope.board_id],
+                    )
+                    .await
+                    .map_err(|e| AgentBoardError::General(format!("Update failed: {}", e)))?;
+            }
+        }
         Ok(())
     }
 
-    pub async fn unregister_agent(&self, agent_id: &str) -> Result<(), AgentBoardError> {
-        // Verify agent exists
-        self.get_agent(agent_id).await?;
+    /// Deactivate an agent and hand off its open cards: reassigned to
+    /// `reassign_to` if given, otherwise unassigned. Each affected card gets a
+    /// comment explaining the hand-off. Returns the affected cards.
+    pub async fn unregister_agent(
+        &self,
+        agent_id: &str,
+        reassign_to: Option<&str>,
+    ) -> Result<Vec<Card>, AgentBoardError> {
+        self.check_scope(None, false)?;
+        self.check_budget().await?;
+        // Verify agents exist
+        let agent = self.get_agent(agent_id).await?;
+        let target = match reassign_to {
+            Some(id) if id == agent_id => {
+                return Err(AgentBoardError::InvalidArgs(
+                    "Cannot reassign cards to the agent being deleted".into(),
+                ));
+            }
+            Some(id) => Some(self.get_agent(id).await?),
+            None => None,
+        };
+
+        self.begin().await?;
+        let result = self.unregister_agent_rows(&agent, target.as_ref()).await;
+        match result {
+            Ok(cards) => {
+                self.commit().await?;
+                Ok(cards)
+            }
+            Err(e) => {
+                self.rollback().await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn unregister_agent_rows(
+        &self,
+        agent: &Agent,
+        target: Option<&Agent>,
+    ) -> Result<Vec<Card>, AgentBoardError> {
+        let mut cards = self
+            .query_cards(
+                "assigned_to = ?1 AND deleted_at IS NULL AND status != 'done'",
+                vec![agent.id.clone().into()],
+            )
+            .await?;
 
         let now = Utc::now().to_rfc3339();
+        let target_id = target.map(|t| t.id.clone());
+        let note = match target {
+            Some(t) => format!(
+                "Reassigned from {} ({}) to {} ({}): the agent was deleted",
+                agent.name, agent.id, t.name, t.id
+            ),
+            None => format!(
+                "Unassigned from {} ({}): the agent was deleted",
+                agent.name, agent.id
+            ),
+        };
+        for card in &mut cards {
+            self.conn
+                .execute(
+                    "UPDATE cards SET assigned_to = ?1, updated_at = ?2 WHERE id = ?3",
+                    libsql::params![target_id.as_deref(), now.as_str(), card.id.as_str()],
+                )
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Update failed: {}", e)))?;
+            self.record_assignment(&card.id, target_id.as_deref(), Some(&agent.id))
+                .await?;
+            self.emit(
+                EventKind::CardAssigned,
+                card,
+                serde_json::json!({ "name": card.name, "from": agent.id, "to": target_id }),
+            )
+            .await?;
+            self.add_comment(&card.id, note.clone(), self.actor.clone(), None, None)
+                .await?;
+            card.assigned_to = target_id.clone();
+        }
+
         self.conn
             .execute(
                 "UPDATE agents SET deactivated_at = ?1, updated_at = ?1 WHERE id = ?2",
-                [&now, agent_id],
+                [now.as_str(), agent.id.as_str()],
             )
             .await
             .map_err(|e| AgentBoardError::General(format!("Unregister failed: {}", e)))?;
 
-        Ok(())
+        Ok(cards)
     }
 
     // Board operations
-    pub async fn list_boards(&self, include_deleted: bool) -> Result<Vec<Board>, AgentBoardError> {
-        let query = if include_deleted {
-            "SELECT id, name, description, created_at, updated_at, deleted_at FROM boards ORDER BY created_at DESC"
-        } else {
-            "SELECT id, name, description, created_at, updated_at, deleted_at FROM boards WHERE deleted_at IS NULL ORDER BY created_at DESC"
+    pub async fn list_boards(&self, filter: &BoardFilter) -> Result<Vec<Board>, AgentBoardError> {
+        let mut conditions = vec!["b.namespace = ?1".to_string()];
+        let mut params: Vec<libsql::Value> = vec![self.namespace.clone().into()];
+        if !filter.include_deleted {
+            conditions.push("b.deleted_at IS NULL".to_string());
+        }
+        Self::push_date_conditions(&filter.dates, &mut conditions, &mut params);
+        if let Some(group) = &filter.group {
+            params.push(group.clone().into());
+            conditions.push(format!("b.group_name = ?{} COLLATE NOCASE", params.len()));
+        }
+
+        let order = match filter.sort {
+            BoardSort::Name => "b.name COLLATE NOCASE, b.created_at DESC, b.id DESC",
+            BoardSort::Created => "b.created_at DESC, b.id DESC",
+            BoardSort::Recent => "u.used_at IS NULL, u.used_at DESC, b.created_at DESC, b.id DESC",
         };
+        let query = format!(
+            "SELECT b.id, b.name, b.description, b.created_at, b.updated_at, b.deleted_at, b.namespace, s.board_id IS NOT NULL, u.used_at, b.group_name, b.pinned_note \
+             FROM boards b \
+             LEFT JOIN board_stars s ON s.board_id = b.id \
+             LEFT JOIN board_usage u ON u.board_id = b.id \
+             WHERE {} ORDER BY b.group_name IS NULL, b.group_name COLLATE NOCASE, s.board_id IS NULL, {}",
+            conditions.join(" AND "),
+            order
+        );
         let mut rows = self
             .conn
-            .query(query, ())
+            .query(&query, params)
             .await
             .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
 
@@ -287,14 +1592,32 @@ impl Database {
                     .ok()
                     .flatten()
                     .map(|s| Self::parse_datetime(&s)),
+                namespace: row.get::<String>(6).unwrap_or_default(),
+                starred: row.get::<i64>(7).unwrap_or(0) != 0,
+                last_used_at: row
+                    .get::<Option<String>>(8)
+                    .ok()
+                    .flatten()
+                    .map(|s| Self::parse_datetime(&s)),
+                group: row.get::<Option<String>>(9).ok().flatten(),
+                pinned_note: row.get::<Option<String>>(10).ok().flatten(),
+                card_counts: None,
+                etag: None,
             });
         }
+        drop(rows);
+
+        let ids: Vec<String> = boards.iter().map(|b| b.id.clone()).collect();
+        let mut counts = self.card_counts_by_board(&ids).await?;
+        for board in &mut boards {
+            board.card_counts = Some(counts.remove(&board.id).unwrap_or_default());
+        }
         Ok(boards)
     }
 
     pub async fn get_board(&self, board_id: &str) -> Result<Board, AgentBoardError> {
         let mut rows = self.conn
-            .query("SELECT id, name, description, created_at, updated_at, deleted_at FROM boards WHERE id = ?1 AND deleted_at IS NULL", [board_id])
+            .query("SELECT id, name, description, created_at, updated_at, deleted_at, namespace, group_name, pinned_note FROM boards WHERE id = ?1 AND deleted_at IS NULL", [board_id])
             .await
             .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
 
@@ -314,6 +1637,13 @@ impl Database {
                     .ok()
                     .flatten()
                     .map(|s| Self::parse_datetime(&s)),
+                namespace: row.get::<String>(6).unwrap_or_default(),
+                starred: false,
+                last_used_at: None,
+                group: row.get::<Option<String>>(7).ok().flatten(),
+                pinned_note: row.get::<Option<String>>(8).ok().flatten(),
+                card_counts: None,
+                etag: None,
             })
         } else {
             Err(AgentBoardError::NotFound(format!(
@@ -323,10 +1653,107 @@ impl Database {
         }
     }
 
-    pub async fn delete_board(&self, board_id: &str) -> Result<(), AgentBoardError> {
+    /// Find a board in the current namespace by ID or exact name
+    pub async fn resolve_board(&self, name_or_id: &str) -> Result<Board, AgentBoardError> {
+        if name_or_id.starts_with("board_")
+            && let Ok(board) = self.get_board(name_or_id).await
+        {
+            return Ok(board);
+        }
+        let mut matches: Vec<Board> = self
+            .list_boards(&BoardFilter::default())
+            .await?
+            .into_iter()
+            .filter(|b| b.name == name_or_id)
+            .collect();
+        match matches.len() {
+            0 => Err(AgentBoardError::NotFound(format!(
+                "Board not found: {}",
+                name_or_id
+            ))),
+            1 => Ok(matches.remove(0)),
+            _ => Err(AgentBoardError::InvalidArgs(format!(
+                "Board name {} is ambiguous ({}); use the board ID",
+                name_or_id,
+                matches
+                    .iter()
+                    .map(|b| b.id.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))),
+        }
+    }
+
+    /// Star or (with `remove`) unstar a board so `list boards` shows it first
+    pub async fn star_board(&self, board_id: &str, remove: bool) -> Result<(), AgentBoardError> {
+        self.check_scope(Some(board_id), false)?;
+        self.get_board(board_id).await?;
+        self.check_budget().await?;
+        let result = if remove {
+            self.conn
+                .execute("DELETE FROM board_stars WHERE board_id = ?1", [board_id])
+                .await
+        } else {
+            self.conn
+                .execute(
+                    "INSERT OR IGNORE INTO board_stars (board_id, starred_at) VALUES (?1, ?2)",
+                    [board_id, Utc::now().to_rfc3339().as_str()],
+                )
+                .await
+        };
+        result.map_err(|e| AgentBoardError::General(format!("Star failed: {}", e)))?;
+        Ok(())
+    }
+
+    /// Record that a command just used this board, for `list boards --sort recent`
+    pub async fn touch_board(&self, board_id: &str) -> Result<(), AgentBoardError> {
+        // Usage is bookkeeping; reading a read-only database shouldn't fail on it
+        if self.read_only_path().is_some() {
+            return Ok(());
+        }
+        self.lock_writer()?;
+        self.conn
+            .execute(
+                "INSERT INTO board_usage (board_id, used_at) VALUES (?1, ?2)
+                 ON CONFLICT(board_id) DO UPDATE SET used_at = excluded.used_at",
+                [board_id, Utc::now().to_rfc3339().as_str()],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Usage update failed: {}", e)))?;
+        Ok(())
+    }
+
+    /// Soft-delete a board and its cards. `yes` and `confirm` satisfy
+    /// `delete.require_confirmation` (see `authorize_removal`); a token is
+    /// only used up if the delete goes through.
+    pub async fn delete_board(
+        &self,
+        board_id: &str,
+        yes: bool,
+        confirm: Option<&str>,
+    ) -> Result<(), AgentBoardError> {
+        self.check_scope(Some(board_id), false)?;
+        self.check_budget().await?;
         // Verify board exists
         self.get_board(board_id).await?;
 
+        self.begin().await?;
+        let result = async {
+            self.authorize_removal(board_id, "delete", yes, confirm)
+                .await?;
+            self.delete_board_rows(board_id).await
+        }
+        .await;
+        match result {
+            Ok(()) => self.commit().await,
+            Err(e) => {
+                self.rollback().await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn delete_board_rows(&self, board_id: &str) -> Result<(), AgentBoardError> {
         let now = Utc::now().to_rfc3339();
         self.conn
             .execute(
@@ -345,713 +1772,7362 @@ impl Database {
             .await
             .map_err(|e| AgentBoardError::General(format!("Delete cards failed: {}", e)))?;
 
+        self.forget_review_board(board_id).await?;
         Ok(())
     }
 
-    pub async fn update_board(
-        &self,
-        board_id: &str,
-        name: Option<String>,
-        description: Option<String>,
-    ) -> Result<(), AgentBoardError> {
-        // Verify board exists
-        self.get_board(board_id).await?;
-
-        let now = Utc::now().to_rfc3339();
-
-        if let Some(n) = name {
-            self.conn
-                .execute(
-                    "UPDATE boards SET name = ?1, updated_at = ?2 WHERE id = ?3",
-                    [&n, &now, board_id],
-                )
-                .await
-                .map_err(|e| AgentBoardError::General(format!("Update failed: {}", e)))?;
-        }
-        if let Some(d) = description {
-            self.conn
-                .execute(
-                    "UPDATE boards SET description = ?1, updated_at = ?2 WHERE id = ?3",
-                    [&d, &now, board_id],
-                )
-                .await
-                .map_err(|e| AgentBoardError::General(format!("Update failed: {}", e)))?;
-        }
-        Ok(())
-    }
-
-    pub async fn create_board(
-        &self,
-        name: String,
-        description: Option<String>,
-    ) -> Result<Board, AgentBoardError> {
-        let id = Self::generate_id("board");
-        let now = Utc::now().to_rfc3339();
-
+    /// Drop `review.board` policies that send review cards to `board_id`
+    async fn forget_review_board(&self, board_id: &str) -> Result<(), AgentBoardError> {
         self.conn
             .execute(
-                "INSERT INTO boards (id, name, description, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5)",
-                libsql::params![id.as_str(), name.as_str(), description.clone().unwrap_or_default().as_str(), now.as_str(), now.as_str()],
+                "DELETE FROM board_settings WHERE key = ?1 AND value = ?2",
+                [SETTING_REVIEW_BOARD, board_id],
             )
             .await
-            .map_err(|e| AgentBoardError::General(format!("Insert failed: {}", e)))?;
-
-        self.get_board(&id).await
+            .map_err(|e| AgentBoardError::General(format!("Delete setting failed: {}", e)))?;
+        Ok(())
     }
 
-    pub async fn get_board_summary(&self, board_id: &str) -> Result<BoardSummary, AgentBoardError> {
-        // Verify board exists
-        self.get_board(board_id).await?;
-
-        let mut rows = self.conn
-            .query(
-                "SELECT status, COUNT(*) as cnt FROM cards WHERE board_id = ?1 AND deleted_at IS NULL GROUP BY status",
-                [board_id],
-            )
-            .await
-            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
-
-        let mut summary = BoardSummary::default();
-        while let Some(row) = rows
-            .next()
-            .await
-            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
-        {
-            let status: String = row.get(0).unwrap_or_default();
-            let count: i64 = row.get(1).unwrap_or(0);
-            match status.as_str() {
-                "todo" => summary.todo_count = count as usize,
-                "in_progress" => summary.in_progress_count = count as usize,
-                "pending_review" => summary.pending_review_count = count as usize,
-                "done" => summary.done_count = count as usize,
-                _ => {}
+    /// Count what deleting a board would touch. A soft delete only affects
+    /// cards that are not already deleted; a purge removes everything.
+    pub async fn board_impact(
+        &self,
+        board_id: &str,
+        purge: bool,
+    ) -> Result<BoardImpact, AgentBoardError> {
+        let board_name = if purge {
+            let mut rows = self
+                .conn
+                .query("SELECT name FROM boards WHERE id = ?1", [board_id])
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+            match rows
+                .next()
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+            {
+                Some(row) => row.get::<String>(0).unwrap_or_default(),
+                None => {
+                    return Err(AgentBoardError::NotFound(format!(
+                        "Board not found: {}",
+                        board_id
+                    )));
+                }
             }
-        }
-        summary.total_cards = summary.todo_count
-            + summary.in_progress_count
-            + summary.pending_review_count
-            + summary.done_count;
-        Ok(summary)
-    }
-
-    // Card operations - helper to load full card with tags and checklists
-    async fn load_card_full(&self, card_id: &str) -> Result<Card, AgentBoardError> {
-        self.load_card_full_with_deleted(card_id, false).await
-    }
+        } else {
+            self.get_board(board_id).await?.name
+        };
 
-    async fn load_card_full_with_deleted(
-        &self,
-        card_id: &str,
-        include_deleted: bool,
-    ) -> Result<Card, AgentBoardError> {
-        let query = if include_deleted {
-            "SELECT id, board_id, name, description, status, assigned_to, created_at, updated_at, deleted_at FROM cards WHERE id = ?1"
+        let cards = if purge {
+            "SELECT id FROM cards WHERE board_id = ?1"
         } else {
-            "SELECT id, board_id, name, description, status, assigned_to, created_at, updated_at, deleted_at FROM cards WHERE id = ?1 AND deleted_at IS NULL"
+            "SELECT id FROM cards WHERE board_id = ?1 AND deleted_at IS NULL"
         };
+        let query = format!(
+            "SELECT ({cards_count}), \
+             (SELECT COUNT(*) FROM comments WHERE card_id IN ({cards})), \
+             (SELECT COUNT(*) FROM checklist_items WHERE card_id IN ({cards}))",
+            cards_count = cards.replacen("SELECT id", "SELECT COUNT(*)", 1),
+            cards = cards
+        );
         let mut rows = self
             .conn
-            .query(query, [card_id])
+            .query(&query, [board_id])
             .await
             .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
-
         let row = rows
             .next()
             .await
             .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
-            .ok_or_else(|| AgentBoardError::NotFound(format!("Card not found: {}", card_id)))?;
-
-        let id: String = row.get(0).unwrap_or_default();
-        let board_id: String = row.get(1).unwrap_or_default();
-        let name: String = row.get(2).unwrap_or_default();
-        let description: Option<String> = row.get::<Option<String>>(3).ok().flatten();
-        let status = Self::status_from_str(&row.get::<String>(4).unwrap_or_default());
-        let assigned_to: Option<String> = row.get::<Option<String>>(5).ok().flatten();
-        let created_at = Self::parse_datetime(&row.get::<String>(6).unwrap_or_default());
-        let updated_at = Self::parse_datetime(&row.get::<String>(7).unwrap_or_default());
-        let deleted_at: Option<DateTime<Utc>> = row
-            .get::<Option<String>>(8)
-            .ok()
-            .flatten()
-            .map(|s| Self::parse_datetime(&s));
-
-        // Load tags
-        let mut tag_rows = self
-            .conn
-            .query(
-                "SELECT tag FROM card_tags WHERE card_id = ?1",
-                [id.as_str()],
-            )
-            .await
-            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
-        let mut tags = Vec::new();
-        while let Some(tag_row) = tag_rows
-            .next()
-            .await
-            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
-        {
-            tags.push(tag_row.get::<String>(0).unwrap_or_default());
-        }
+            .ok_or_else(|| AgentBoardError::General("Impact query returned no rows".into()))?;
 
-        // Load checklist items
-        let checklist = self.load_checklist_for_card(&id).await?;
+        Ok(BoardImpact {
+            board_id: board_id.to_string(),
+            board_name,
+            cards: row.get::<i64>(0).unwrap_or(0) as usize,
+            comments: row.get::<i64>(1).unwrap_or(0) as usize,
+            checklist_items: row.get::<i64>(2).unwrap_or(0) as usize,
+        })
+    }
 
-        Ok(Card {
-            id,
-            board_id,
-            name,
-            description,
-            status,
-            assigned_to,
-            tags,
-            checklist,
-            created_at,
-            updated_at,
-            deleted_at,
+    /// Issue a single-use token for deleting or purging a board, bound to this
+    /// board, action, and actor
+    pub async fn request_removal(
+        &self,
+        board_id: &str,
+        action: &str,
+    ) -> Result<RemovalToken, AgentBoardError> {
+        self.check_scope(Some(board_id), false)?;
+        let impact = self.board_impact(board_id, action == "purge").await?;
+        self.check_budget().await?;
+        let token = Self::generate_id("confirm");
+        let expires_at = Utc::now() + chrono::Duration::minutes(REMOVAL_TOKEN_MINUTES);
+        self.conn
+            .execute(
+                "INSERT INTO removal_tokens (token, board_id, action, actor, expires_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+                [
+                    token.as_str(),
+                    board_id,
+                    action,
+                    self.actor.as_deref().unwrap_or(""),
+                    expires_at.to_rfc3339().as_str(),
+                ],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Insert failed: {}", e)))?;
+        Ok(RemovalToken {
+            token,
+            action: action.to_string(),
+            expires_at,
+            impact,
         })
     }
 
-    /// Load checklist items directly for a card (simplified model - one checklist per card)
-    async fn load_checklist_for_card(
+    /// Enforce `delete.require_confirmation` before deleting or purging a
+    /// board. Humans may pass `yes`; agents need an unexpired token they
+    /// requested for this board and action, which is used up here, so
+    /// callers run it inside the removal's transaction.
+    async fn authorize_removal(
         &self,
-        card_id: &str,
-    ) -> Result<Vec<ChecklistItem>, AgentBoardError> {
-        let mut item_rows = self
+        board_id: &str,
+        action: &str,
+        yes: bool,
+        confirm: Option<&str>,
+    ) -> Result<(), AgentBoardError> {
+        // Read directly: purge also applies to boards that are already deleted
+        let mut rows = self
             .conn
             .query(
-                "SELECT id, text, checked FROM checklist_items WHERE card_id = ?1",
-                [card_id],
+                "SELECT value FROM board_settings WHERE board_id = ?1 AND key = ?2",
+                [board_id, SETTING_DELETE_REQUIRE_CONFIRMATION],
             )
             .await
             .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
-
-        let mut items = Vec::new();
-        while let Some(item_row) = item_rows
+        let required = match rows
             .next()
             .await
             .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
         {
-            items.push(ChecklistItem {
-                id: item_row.get::<String>(0).unwrap_or_default(),
-                text: item_row.get::<String>(1).unwrap_or_default(),
-                checked: item_row.get::<i64>(2).unwrap_or(0) != 0,
-            });
+            Some(row) => row.get::<String>(0).unwrap_or_default() == "true",
+            None => false,
+        };
+        drop(rows);
+        if !required {
+            return Ok(());
         }
-        Ok(items)
-    }
-
-    pub async fn get_card(&self, card_id: &str) -> Result<Card, AgentBoardError> {
-        self.load_card_full(card_id).await
-    }
-
-    pub async fn list_cards(
-        &self,
-        board_id: &str,
-        status: Option<Status>,
-        assigned_to: Option<&str>,
-        tags: &[String],
-        include_deleted: bool,
-    ) -> Result<Vec<Card>, AgentBoardError> {
-        // Verify board exists (allow deleted boards when include_deleted is true)
-        if include_deleted {
-            // Check if board exists at all (including deleted)
-            let mut rows = self
+        if let Some(token) = confirm {
+            let deleted = self
                 .conn
-                .query("SELECT id FROM boards WHERE id = ?1", [board_id])
-                .await
-                .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
-            if rows
-                .next()
+                .execute(
+                    "DELETE FROM removal_tokens WHERE token = ?1 AND board_id = ?2 AND action = ?3 AND actor = ?4 AND expires_at >= ?5",
+                    [
+                        token,
+                        board_id,
+                        action,
+                        self.actor.as_deref().unwrap_or(""),
+                        Utc::now().to_rfc3339().as_str(),
+                    ],
+                )
                 .await
-                .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
-                .is_none()
-            {
-                return Err(AgentBoardError::NotFound(format!(
-                    "Board not found: {}",
-                    board_id
+                .map_err(|e| AgentBoardError::General(format!("Delete failed: {}", e)))?;
+            if deleted == 0 {
+                return Err(AgentBoardError::PolicyViolation(format!(
+                    "Confirmation token {} is not valid for {} board {} (wrong board, action, or identity, already used, or expired); request a new one with --request",
+                    token, action, board_id
                 )));
             }
-        } else {
-            self.get_board(board_id).await?;
+            return Ok(());
         }
+        match &self.actor {
+            None if yes => Ok(()),
+            None => Err(AgentBoardError::PolicyViolation(format!(
+                "Board {} requires confirmation to {}: pass --yes",
+                board_id, action
+            ))),
+            Some(_) => Err(AgentBoardError::PolicyViolation(format!(
+                "Board {} requires two-step confirmation for agents: run `{} board {} --request`, then pass --confirm <token> within {} minutes",
+                board_id, action, board_id, REMOVAL_TOKEN_MINUTES
+            ))),
+        }
+    }
 
-        let deleted_filter = if include_deleted {
-            ""
-        } else {
-            " AND deleted_at IS NULL"
-        };
-
-        // Build tag filter using subquery for AND logic (card must have ALL specified tags)
-        let tag_filter = if tags.is_empty() {
-            String::new()
-        } else {
-            let tag_conditions: Vec<String> = tags
-                .iter()
-                .map(|t| {
-                    format!(
-                        "EXISTS (SELECT 1 FROM card_tags WHERE card_id = cards.id AND tag = '{}')",
-                        t
-                    )
-                })
-                .collect();
-            format!(" AND {}", tag_conditions.join(" AND "))
-        };
-
-        let query = match (&status, &assigned_to) {
-            (Some(s), Some(a)) => {
-                format!(
-                    "SELECT id FROM cards WHERE board_id = '{}' AND status = '{}' AND assigned_to = '{}'{}{}",
-                    board_id, s, a, deleted_filter, tag_filter
-                )
-            }
-            (Some(s), None) => {
-                format!(
-                    "SELECT id FROM cards WHERE board_id = '{}' AND status = '{}'{}{}",
-                    board_id, s, deleted_filter, tag_filter
-                )
-            }
-            (None, Some(a)) => {
-                format!(
-                    "SELECT id FROM cards WHERE board_id = '{}' AND assigned_to = '{}'{}{}",
-                    board_id, a, deleted_filter, tag_filter
-                )
-            }
-            (None, None) => {
-                format!(
-                    "SELECT id FROM cards WHERE board_id = '{}'{}{}",
-                    board_id, deleted_filter, tag_filter
-                )
-            }
-        };
-
+    /// Permanently remove a board (including soft-deleted ones) and everything
+    /// on it. `yes` and `confirm` work as for `delete_board`.
+    pub async fn purge_board(
+        &self,
+        board_id: &str,
+        yes: bool,
+        confirm: Option<&str>,
+    ) -> Result<(), AgentBoardError> {
+        self.check_scope(Some(board_id), false)?;
+        self.check_budget().await?;
         let mut rows = self
             .conn
-            .query(&query, ())
+            .query("SELECT id FROM boards WHERE id = ?1", [board_id])
             .await
             .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
-
-        let mut cards = Vec::new();
-        while let Some(row) = rows
+        if rows
             .next()
             .await
             .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+            .is_none()
         {
-            let card_id: String = row.get(0).unwrap_or_default();
-            cards.push(
-                self.load_card_full_with_deleted(&card_id, include_deleted)
-                    .await?,
-            );
+            return Err(AgentBoardError::NotFound(format!(
+                "Board not found: {}",
+                board_id
+            )));
         }
-        Ok(cards)
-    }
+        drop(rows);
 
-    pub async fn get_cards_by_assignee(
-        &self,
-        session_id: &str,
-        board_id: Option<&str>,
-        status: Option<Status>,
-    ) -> Result<Vec<Card>, AgentBoardError> {
-        let query = match (&board_id, &status) {
-            (Some(b), Some(s)) => {
-                format!(
-                    "SELECT id FROM cards WHERE assigned_to = '{}' AND board_id = '{}' AND status = '{}' AND deleted_at IS NULL",
-                    session_id, b, s
-                )
-            }
-            (Some(b), None) => {
-                format!(
-                    "SELECT id FROM cards WHERE assigned_to = '{}' AND board_id = '{}' AND deleted_at IS NULL",
-                    session_id, b
-                )
-            }
-            (None, Some(s)) => {
-                format!(
-                    "SELECT id FROM cards WHERE assigned_to = '{}' AND status = '{}' AND deleted_at IS NULL",
-                    session_id, s
-                )
-            }
-            (None, None) => {
-                format!(
-                    "SELECT id FROM cards WHERE assigned_to = '{}' AND deleted_at IS NULL",
-                    session_id
-                )
+        self.begin().await?;
+        let result = async {
+            self.authorize_removal(board_id, "purge", yes, confirm)
+                .await?;
+            self.purge_board_rows(board_id).await
+        }
+        .await;
+        match result {
+            Ok(()) => self.commit().await,
+            Err(e) => {
+                self.rollback().await;
+                Err(e)
             }
-        };
-
-        let mut rows = self
-            .conn
-            .query(&query, ())
-            .await
-            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        }
+    }
 
-        let mut cards = Vec::new();
-        while let Some(row) = rows
-            .next()
-            .await
-            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
-        {
-            let card_id: String = row.get(0).unwrap_or_default();
-            cards.push(self.load_card_full(&card_id).await?);
+    async fn purge_board_rows(&self, board_id: &str) -> Result<(), AgentBoardError> {
+        for statement in [
+            "DELETE FROM comment_reactions WHERE comment_id IN (SELECT id FROM comments WHERE card_id IN (SELECT id FROM cards WHERE board_id = ?1))",
+            "DELETE FROM comments WHERE card_id IN (SELECT id FROM cards WHERE board_id = ?1)",
+            "DELETE FROM assignments WHERE card_id IN (SELECT id FROM cards WHERE board_id = ?1)",
+            "DELETE FROM card_revisions WHERE card_id IN (SELECT id FROM cards WHERE board_id = ?1)",
+            "DELETE FROM card_gates WHERE card_id IN (SELECT id FROM cards WHERE board_id = ?1)",
+            "DELETE FROM card_dependencies WHERE card_id IN (SELECT id FROM cards WHERE board_id = ?1) OR depends_on IN (SELECT id FROM cards WHERE board_id = ?1)",
+            "DELETE FROM checklist_items WHERE card_id IN (SELECT id FROM cards WHERE board_id = ?1)",
+            "DELETE FROM card_tags WHERE card_id IN (SELECT id FROM cards WHERE board_id = ?1)",
+            "DELETE FROM card_labels WHERE card_id IN (SELECT id FROM cards WHERE board_id = ?1)",
+            "DELETE FROM labels WHERE board_id = ?1",
+            "DELETE FROM cards WHERE board_id = ?1",
+            "DELETE FROM board_settings WHERE board_id = ?1",
+            "DELETE FROM board_stars WHERE board_id = ?1",
+            "DELETE FROM board_usage WHERE board_id = ?1",
+            "DELETE FROM removal_tokens WHERE board_id = ?1",
+            "DELETE FROM events WHERE board_id = ?1",
+            "DELETE FROM hooks WHERE board_id = ?1",
+            "DELETE FROM notifiers WHERE board_id = ?1",
+            "DELETE FROM boards WHERE id = ?1",
+        ] {
+            self.conn
+                .execute(statement, [board_id])
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Purge failed: {}", e)))?;
         }
-        Ok(cards)
+        self.forget_review_board(board_id).await
     }
 
-    pub async fn create_card(
+    pub async fn update_board(
         &self,
         board_id: &str,
-        name: String,
+        name: Option<String>,
         description: Option<String>,
-        status: Status,
-    ) -> Result<Card, AgentBoardError> {
+        group: Option<Option<String>>,
+        pinned_note: Option<Option<String>>,
+    ) -> Result<(), AgentBoardError> {
+        self.check_scope(Some(board_id), false)?;
+        self.check_budget().await?;
         // Verify board exists
         self.get_board(board_id).await?;
 
-        let id = Self::generate_id("card");
-        let now = Utc::now().to_rfc3339();
-        let status_str = status.to_string();
-
-        self.conn
-            .execute(
-                "INSERT INTO cards (id, board_id, name, description, status, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-                [&id, board_id, &name, &description.clone().unwrap_or_default(), &status_str, &now, &now],
-            )
-            .await
-            .map_err(|e| AgentBoardError::General(format!("Insert failed: {}", e)))?;
-
-        self.get_card(&id).await
-    }
-
-    pub async fn update_card(
-        &self,
-        card_id: &str,
-        update: crate::models::CardUpdate,
-    ) -> Result<(), AgentBoardError> {
-        // Verify card exists
-        self.get_card(card_id).await?;
-
         let now = Utc::now().to_rfc3339();
 
-        if let Some(n) = update.name {
+        if let Some(n) = name {
             self.conn
                 .execute(
-                    "UPDATE cards SET name = ?1, updated_at = ?2 WHERE id = ?3",
-                    [&n, &now, card_id],
+                    "UPDATE boards SET name = ?1, updated_at = ?2 WHERE id = ?3",
+                    [&n, &now, board_id],
                 )
                 .await
                 .map_err(|e| AgentBoardError::General(format!("Update failed: {}", e)))?;
         }
-        if let Some(d) = update.description {
+        if let Some(d) = description {
             self.conn
                 .execute(
-                    "UPDATE cards SET description = ?1, updated_at = ?2 WHERE id = ?3",
-                    [&d, &now, card_id],
+                    "UPDATE boards SET description = ?1, updated_at = ?2 WHERE id = ?3",
+                    [&d, &now, board_id],
                 )
                 .await
                 .map_err(|e| AgentBoardError::General(format!("Update failed: {}", e)))?;
         }
-        if let Some(s) = update.status {
+        if let Some(g) = group {
+            let g = g.as_deref().map(str::trim);
+            if g == Some("") {
+                return Err(AgentBoardError::InvalidArgs(
+                    "Group name cannot be empty; use 'null' to remove the board from its group"
+                        .to_string(),
+                ));
+            }
             self.conn
                 .execute(
-                    "UPDATE cards SET status = ?1, updated_at = ?2 WHERE id = ?3",
-                    [&s.to_string(), &now, card_id],
+                    "UPDATE boards SET group_name = ?1, updated_at = ?2 WHERE id = ?3",
+                    libsql::params![g, now.as_str(), board_id],
                 )
                 .await
                 .map_err(|e| AgentBoardError::General(format!("Update failed: {}", e)))?;
         }
-        if let Some(sid) = update.session_id {
-            match sid {
-                Some(s) => {
-                    self.conn
-                        .execute(
-                            "UPDATE cards SET assigned_to = ?1, updated_at = ?2 WHERE id = ?3",
-                            [&s, &now, card_id],
-                        )
-                        .await
-                        .map_err(|e| AgentBoardError::General(format!("Update failed: {}", e)))?;
-                }
-                None => {
-                    self.conn
-                        .execute(
-                            "UPDATE cards SET assigned_to = NULL, updated_at = ?1 WHERE id = ?2",
-                            [&now, card_id],
-                        )
-                        .await
-                        .map_err(|e| AgentBoardError::General(format!("Update failed: {}", e)))?;
-                }
+        if let Some(note) = pinned_note {
+            let note = note.as_deref().map(str::trim);
+            if note == Some("") {
+                return Err(AgentBoardError::InvalidArgs(
+                    "Pinned note cannot be empty; use 'null' to unpin it".to_string(),
+                ));
             }
-        }
-        for tag in update.add_tags {
             self.conn
                 .execute(
-                    "INSERT OR IGNORE INTO card_tags (card_id, tag) VALUES (?1, ?2)",
-                    [card_id, &tag],
+                    "UPDATE boards SET pinned_note = ?1, updated_at = ?2 WHERE id = ?3",
+                    libsql::params![note, now.as_str(), board_id],
                 )
                 .await
-                .map_err(|e| AgentBoardError::General(format!("Insert tag failed: {}", e)))?;
+                .map_err(|e| AgentBoardError::General(format!("Update failed: {}", e)))?;
         }
-        for tag in update.remove_tags {
-            self.conn
-                .execute(
-                    "DELETE FROM card_tags WHERE card_id = ?1 AND tag = ?2",
-                    [card_id, &tag],
-                )
-                .await
-                .map_err(|e| AgentBoardError::General(format!("Delete tag failed: {}", e)))?;
-        }
-
-        Ok(())
-    }
-
-    pub async fn delete_card(&self, card_id: &str) -> Result<(), AgentBoardError> {
-        // Verify card exists
-        self.get_card(card_id).await?;
-
-        let now = Utc::now().to_rfc3339();
-        self.conn
-            .execute(
-                "UPDATE cards SET deleted_at = ?1, updated_at = ?1 WHERE id = ?2",
-                [&now, card_id],
-            )
-            .await
-            .map_err(|e| AgentBoardError::General(format!("Delete failed: {}", e)))?;
-
         Ok(())
     }
 
-    // Checklist operations (simplified - items added directly to card)
-    pub async fn add_checklist_items(
+    pub async fn board_settings(
         &self,
-        card_id: &str,
-        items: Vec<String>,
-    ) -> Result<Vec<ChecklistItem>, AgentBoardError> {
-        // Verify card exists
-        self.get_card(card_id).await?;
-
-        let mut checklist_items = Vec::new();
-        for item_text in items {
-            let item_id = Self::generate_id("item");
-            self.conn
-                .execute(
-                    "INSERT INTO checklist_items (id, card_id, text, checked) VALUES (?1, ?2, ?3, 0)",
-                    libsql::params![item_id.as_str(), card_id, item_text.as_str()],
-                )
-                .await
-                .map_err(|e| AgentBoardError::General(format!("Insert item failed: {}", e)))?;
-            checklist_items.push(ChecklistItem {
-                id: item_id,
-                text: item_text,
-                checked: false,
-            });
-        }
-
-        // Update card's updated_at
-        let now = Utc::now().to_rfc3339();
-        self.conn
-            .execute(
-                "UPDATE cards SET updated_at = ?1 WHERE id = ?2",
-                [&now, card_id],
-            )
-            .await
-            .map_err(|e| AgentBoardError::General(format!("Update failed: {}", e)))?;
-
-        Ok(checklist_items)
-    }
+        board_id: &str,
+    ) -> Result<Vec<BoardSetting>, AgentBoardError> {
+        self.get_board(board_id).await?;
 
-    pub async fn delete_checklist_item(&self, item_id: &str) -> Result<(), AgentBoardError> {
-        // Get card_id for timestamp update
         let mut rows = self
             .conn
             .query(
-                "SELECT card_id FROM checklist_items WHERE id = ?1",
-                [item_id],
+                "SELECT key, value, updated_at FROM board_settings WHERE board_id = ?1 ORDER BY key",
+                [board_id],
             )
             .await
             .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
-
-        let card_id = if let Some(row) = rows
+        let mut settings = Vec::new();
+        while let Some(row) = rows
             .next()
             .await
             .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
         {
-            row.get::<String>(0).unwrap_or_default()
-        } else {
-            return Err(AgentBoardError::NotFound(format!(
-                "Checklist item not found: {}",
-                item_id
-            )));
-        };
-
-        // Delete item
-        self.conn
-            .execute("DELETE FROM checklist_items WHERE id = ?1", [item_id])
-            .await
-            .map_err(|e| AgentBoardError::General(format!("Delete item failed: {}", e)))?;
-
-        // Update card timestamp
-        let now = Utc::now().to_rfc3339();
-        self.conn
-            .execute(
-                "UPDATE cards SET updated_at = ?1 WHERE id = ?2",
-                [now.as_str(), card_id.as_str()],
-            )
-            .await
-            .map_err(|e| {
-                AgentBoardError::General(format!("Update card timestamp failed: {}", e))
-            })?;
-
-        Ok(())
+            settings.push(BoardSetting {
+                key: row.get::<String>(0).unwrap_or_default(),
+                value: row.get::<String>(1).unwrap_or_default(),
+                updated_at: Self::parse_datetime(&row.get::<String>(2).unwrap_or_default()),
+            });
+        }
+        Ok(settings)
     }
 
-    pub async fn check_item(&self, item_id: &str, checked: bool) -> Result<(), AgentBoardError> {
-        let checked_val = if checked { 1 } else { 0 };
-
-        let result = self
-            .conn
-            .execute(
-                "UPDATE checklist_items SET checked = ?1 WHERE id = ?2",
-                libsql::params![checked_val, item_id],
-            )
-            .await
-            .map_err(|e| AgentBoardError::General(format!("Update failed: {}", e)))?;
+    pub async fn board_setting(
+        &self,
+        board_id: &str,
+        key: &str,
+    ) -> Result<Option<String>, AgentBoardError> {
+        Ok(self
+            .board_settings(board_id)
+            .await?
+            .into_iter()
+            .find(|s| s.key == key)
+            .map(|s| s.value))
+    }
 
-        if result == 0 {
-            return Err(AgentBoardError::NotFound(format!(
-                "Checklist item not found: {}",
-                item_id
+    /// Set a board setting, or remove it when `value` is None
+    pub async fn set_board_setting(
+        &self,
+        board_id: &str,
+        key: &str,
+        value: Option<&str>,
+    ) -> Result<(), AgentBoardError> {
+        self.check_scope(Some(board_id), false)?;
+        self.check_budget().await?;
+        self.get_board(board_id).await?;
+        if let Some(actor) = &self.actor
+            && HUMAN_ONLY_SETTINGS.contains(&key)
+        {
+            return Err(AgentBoardError::PermissionDenied(format!(
+                "{} can only be changed by a human, but this run is agent {} (unset AGENT_BOARD_AGENT_ID)",
+                key, actor
+            )));
+        }
+        if key.is_empty()
+            || !key
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '.')
+        {
+            return Err(AgentBoardError::InvalidArgs(format!(
+                "Invalid setting key: {}. Use lowercase letters, digits, '_' and '.'",
+                key
+            )));
+        }
+        if let (Some(status), Some(v)) = (key.strip_prefix(SETTING_WIP_PREFIX), value)
+            && (!["todo", "in_progress", "pending_review"].contains(&status)
+                || v.parse::<usize>().is_err())
+        {
+            return Err(AgentBoardError::InvalidArgs(format!(
+                "{} must be wip.todo, wip.in_progress, or wip.pending_review with a card count",
+                key
+            )));
+        }
+        if let (Some(status), Some(v)) = (key.strip_prefix(SETTING_STALE_PREFIX), value)
+            && (!["todo", "in_progress", "pending_review"].contains(&status)
+                || crate::parse_duration(v).is_none_or(|d| d.num_seconds() <= 0))
+        {
+            return Err(AgentBoardError::InvalidArgs(format!(
+                "{} must be stale.todo, stale.in_progress, or stale.pending_review with a duration (e.g., 24h, 3d)",
+                key
+            )));
+        }
+        if [SETTING_DUE_REMIND_BEFORE, SETTING_RETENTION_EVENTS].contains(&key)
+            && value.is_some_and(|v| crate::parse_duration(v).is_none_or(|d| d.num_seconds() <= 0))
+        {
+            return Err(AgentBoardError::InvalidArgs(format!(
+                "{} must be a duration (e.g., 24h, 90d)",
+                key
+            )));
+        }
+        if key == SETTING_COMMENT_MAX_BYTES
+            && value.is_some_and(|v| v.parse::<usize>().map_or(true, |n| n < BLOB_PREVIEW_CHARS))
+        {
+            return Err(AgentBoardError::InvalidArgs(format!(
+                "{} must be a byte count of at least {}",
+                key, BLOB_PREVIEW_CHARS
+            )));
+        }
+        // Stored as the ID so renaming the review board doesn't break the policy
+        let review_board;
+        let value = match value {
+            Some(v) if key == SETTING_REVIEW_BOARD => {
+                review_board = self.resolve_board(v).await?.id;
+                if review_board == board_id {
+                    return Err(AgentBoardError::InvalidArgs(format!(
+                        "{} must name another board",
+                        key
+                    )));
+                }
+                Some(review_board.as_str())
+            }
+            _ => value,
+        };
+        if key == SETTING_CARDS_DEDUPE && value.is_some_and(|v| !DEDUPE_MODES.contains(&v)) {
+            return Err(AgentBoardError::InvalidArgs(format!(
+                "{} must be one of {}",
+                key,
+                DEDUPE_MODES.join(", ")
+            )));
+        }
+        if BOOLEAN_SETTINGS.contains(&key) && !matches!(value, None | Some("true") | Some("false"))
+        {
+            return Err(AgentBoardError::InvalidArgs(format!(
+                "{} must be true or false",
+                key
             )));
         }
 
-        // Update the card's updated_at directly (items now reference card_id)
-        self.conn
-            .execute(
-                "UPDATE cards SET updated_at = ?1 WHERE id = (SELECT card_id FROM checklist_items WHERE id = ?2)",
-                [&Utc::now().to_rfc3339(), item_id],
-            )
-            .await
-            .map_err(|e| AgentBoardError::General(format!("Update card timestamp failed: {}", e)))?;
-
+        match value {
+            Some(v) => {
+                let now = Utc::now().to_rfc3339();
+                self.conn
+                    .execute(
+                        "INSERT INTO board_settings (board_id, key, value, updated_at) VALUES (?1, ?2, ?3, ?4)
+                         ON CONFLICT (board_id, key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+                        [board_id, key, v, now.as_str()],
+                    )
+                    .await
+                    .map_err(|e| AgentBoardError::General(format!("Update failed: {}", e)))?;
+            }
+            None => {
+                self.conn
+                    .execute(
+                        "DELETE FROM board_settings WHERE board_id = ?1 AND key = ?2",
+                        [board_id, key],
+                    )
+                    .await
+                    .map_err(|e| AgentBoardError::General(format!("Delete failed: {}", e)))?;
+            }
+        }
         Ok(())
     }
 
-    // Comment operations
-    pub async fn add_comment(
+    pub async fn create_board(
         &self,
-        card_id: &str,
-        text: String,
-        author: Option<String>,
-    ) -> Result<Comment, AgentBoardError> {
-        // Verify card exists
-        self.get_card(card_id).await?;
-
-        let id = Self::generate_id("comment");
-        let now = Utc::now();
-        let now_str = now.to_rfc3339();
+        name: String,
+        description: Option<String>,
+    ) -> Result<Board, AgentBoardError> {
+        self.check_scope(None, false)?;
+        self.check_budget().await?;
+        let id = Self::generate_id("board");
+        let now = Utc::now().to_rfc3339();
 
         self.conn
             .execute(
-                "INSERT INTO comments (id, card_id, author, text, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
-                [&id, card_id, &author.clone().unwrap_or_default(), &text, &now_str],
+                "INSERT INTO boards (id, name, description, created_at, updated_at, namespace) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                libsql::params![id.as_str(), name.as_str(), description.clone().unwrap_or_default().as_str(), now.as_str(), now.as_str(), self.namespace.as_str()],
             )
             .await
-            .map_err(|e| AgentBoardError::General(format!("Insert comment failed: {}", e)))?;
+            .map_err(|e| AgentBoardError::General(format!("Insert failed: {}", e)))?;
 
-        Ok(Comment {
-            id,
-            card_id: card_id.to_string(),
-            author,
-            text,
-            created_at: now,
-        })
+        self.get_board(&id).await
     }
 
-    pub async fn list_comments(&self, card_id: &str) -> Result<Vec<Comment>, AgentBoardError> {
-        // Verify card exists
-        self.get_card(card_id).await?;
+    pub async fn get_board_summary(&self, board_id: &str) -> Result<BoardSummary, AgentBoardError> {
+        // Verify board exists
+        self.get_board(board_id).await?;
 
         let mut rows = self.conn
             .query(
-                "SELECT id, card_id, author, text, created_at FROM comments WHERE card_id = ?1 ORDER BY created_at ASC",
-                [card_id],
+                "SELECT status, COUNT(*) as cnt FROM cards WHERE board_id = ?1 AND deleted_at IS NULL GROUP BY status",
+                [board_id],
             )
             .await
             .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
 
-        let mut comments = Vec::new();
+        let mut summary = BoardSummary::default();
         while let Some(row) = rows
             .next()
             .await
             .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
         {
-            comments.push(Comment {
-                id: row.get::<String>(0).unwrap_or_default(),
-                card_id: row.get::<String>(1).unwrap_or_default(),
-                author: row.get::<Option<String>>(2).ok().flatten(),
-                text: row.get::<String>(3).unwrap_or_default(),
-                created_at: Self::parse_datetime(&row.get::<String>(4).unwrap_or_default()),
-            });
+            let status: String = row.get(0).unwrap_or_default();
+            let count: i64 = row.get(1).unwrap_or(0);
+            match status.as_str() {
+                "todo" => summary.todo_count = count as usize,
+                "in_progress" => summary.in_progress_count = count as usize,
+                "pending_review" => summary.pending_review_count = count as usize,
+                "done" => summary.done_count = count as usize,
+                _ => {}
+            }
         }
-        Ok(comments)
-    }
+        summary.total_cards = summary.todo_count
+            + summary.in_progress_count
+            + summary.pending_review_count
+            + summary.done_count;
+        drop(rows);
 
-    pub async fn delete_comment(&self, comment_id: &str) -> Result<(), AgentBoardError> {
-        let result = self
+        // Cards carry no completion timestamp, so a done card's updated_at
+        // stands in for when it was finished
+        let week_ago = (Utc::now() - chrono::Duration::days(7)).to_rfc3339();
+        let mut rows = self
             .conn
-            .execute("DELETE FROM comments WHERE id = ?1", [comment_id])
+            .query(
+                "SELECT assigned_to,
+                        SUM(status != 'done'),
+                        SUM(status = 'in_progress'),
+                        SUM(status = 'done' AND updated_at >= ?2)
+                 FROM cards
+                 WHERE board_id = ?1 AND deleted_at IS NULL AND assigned_to IS NOT NULL
+                 GROUP BY assigned_to
+                 ORDER BY 2 DESC, assigned_to",
+                [board_id, week_ago.as_str()],
+            )
             .await
-            .map_err(|e| AgentBoardError::General(format!("Delete comment failed: {}", e)))?;
-
-        if result == 0 {
-            return Err(AgentBoardError::NotFound(format!(
-                "Comment not found: {}",
-                comment_id
-            )));
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            summary.agents.push(AgentLoad {
+                agent_id: row.get::<String>(0).unwrap_or_default(),
+                open: row.get::<i64>(1).unwrap_or(0) as usize,
+                in_progress: row.get::<i64>(2).unwrap_or(0) as usize,
+                done_this_week: row.get::<i64>(3).unwrap_or(0) as usize,
+            });
         }
-
-        Ok(())
+        Ok(summary)
     }
 
-    /// Get comment counts for multiple cards at once
-    pub async fn get_comment_counts(
-        &self,
-        card_ids: &[String],
-    ) -> Result<std::collections::HashMap<String, usize>, AgentBoardError> {
-        use std::collections::HashMap;
-
-        if card_ids.is_empty() {
-            return Ok(HashMap::new());
+    // Card operations
+    fn card_from_row(row: &libsql::Row) -> Card {
+        Card {
+            id: row.get::<String>(0).unwrap_or_default(),
+            board_id: row.get::<String>(1).unwrap_or_default(),
+            name: row.get::<String>(2).unwrap_or_default(),
+            description: row.get::<Option<String>>(3).ok().flatten(),
+            status: Self::status_from_str(&row.get::<String>(4).unwrap_or_default()),
+            assigned_to: row.get::<Option<String>>(5).ok().flatten(),
+            tags: Vec::new(),
+            labels: Vec::new(),
+            checklist: Vec::new(),
+            depends_on: Vec::new(),
+            created_at: Self::parse_datetime(&row.get::<String>(6).unwrap_or_default()),
+            updated_at: Self::parse_datetime(&row.get::<String>(7).unwrap_or_default()),
+            deleted_at: row
+                .get::<Option<String>>(8)
+                .ok()
+                .flatten()
+                .map(|s| Self::parse_datetime(&s)),
+            due_at: row
+                .get::<Option<String>>(9)
+                .ok()
+                .flatten()
+                .map(|s| Self::parse_datetime(&s)),
+            estimate_minutes: row.get::<Option<i64>>(10).ok().flatten(),
+            pr_url: row.get::<Option<String>>(11).ok().flatten(),
+            plan_key: row.get::<Option<String>>(12).ok().flatten(),
+            comment_count: None,
+            etag: None,
         }
+    }
 
-        let placeholders: Vec<String> = card_ids
-            .iter()
-            .enumerate()
-            .map(|(i, _)| format!("?{}", i + 1))
-            .collect();
+    /// Fetch core card columns only (no tags or checklist) in a single query
+    async fn query_cards(
+        &self,
+        conditions: &str,
+        params: Vec<libsql::Value>,
+    ) -> Result<Vec<Card>, AgentBoardError> {
         let query = format!(
-            "SELECT card_id, COUNT(*) as cnt FROM comments WHERE card_id IN ({}) GROUP BY card_id",
-            placeholders.join(", ")
+            "SELECT {} FROM cards WHERE {} ORDER BY created_at ASC, id ASC",
+            CARD_COLUMNS, conditions
         );
-
-        let params: Vec<libsql::Value> = card_ids
-            .iter()
-            .map(|id| libsql::Value::from(id.clone()))
-            .collect();
-
         let mut rows = self
             .conn
             .query(&query, libsql::params_from_iter(params))
             .await
             .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
 
-        let mut counts = HashMap::new();
+        let mut cards = Vec::new();
         while let Some(row) = rows
             .next()
             .await
             .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
         {
-            let card_id: String = row.get(0).unwrap_or_default();
-            let count: i64 = row.get(1).unwrap_or(0);
-            counts.insert(card_id, count as usize);
+            cards.push(Self::card_from_row(&row));
         }
-        Ok(counts)
+        Ok(cards)
+    }
+
+    /// Load tags, labels, checklist items, dependencies, and comment counts for
+    /// a set of cards (one query each)
+    async fn hydrate_cards(&self, cards: &mut [Card]) -> Result<(), AgentBoardError> {
+        self.attach_tags(cards).await?;
+        self.attach_labels(cards).await?;
+
+        if cards.is_empty() {
+            return Ok(());
+        }
+        let query = format!(
+            "SELECT card_id, id, text, checked FROM checklist_items WHERE card_id IN ({}) AND deleted_at IS NULL ORDER BY position, id",
+            Self::placeholders(cards.len())
+        );
+        let params: Vec<libsql::Value> = cards.iter().map(|c| c.id.clone().into()).collect();
+        let mut rows = self
+            .conn
+            .query(&query, libsql::params_from_iter(params))
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+
+        let mut items: HashMap<String, Vec<ChecklistItem>> = HashMap::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            items
+                .entry(row.get::<String>(0).unwrap_or_default())
+                .or_default()
+                .push(ChecklistItem {
+                    id: row.get::<String>(1).unwrap_or_default(),
+                    text: row.get::<String>(2).unwrap_or_default(),
+                    checked: row.get::<i64>(3).unwrap_or(0) != 0,
+                });
+        }
+        for card in cards.iter_mut() {
+            card.checklist = items.remove(&card.id).unwrap_or_default();
+        }
+        drop(rows);
+
+        let query = format!(
+            "SELECT card_id, depends_on FROM card_dependencies WHERE card_id IN ({}) ORDER BY created_at, depends_on",
+            Self::placeholders(cards.len())
+        );
+        let params: Vec<libsql::Value> = cards.iter().map(|c| c.id.clone().into()).collect();
+        let mut rows = self
+            .conn
+            .query(&query, libsql::params_from_iter(params))
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+
+        let mut deps: HashMap<String, Vec<String>> = HashMap::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            deps.entry(row.get::<String>(0).unwrap_or_default())
+                .or_default()
+                .push(row.get::<String>(1).unwrap_or_default());
+        }
+        for card in cards.iter_mut() {
+            card.depends_on = deps.remove(&card.id).unwrap_or_default();
+        }
+        drop(rows);
+
+        let card_ids: Vec<String> = cards.iter().map(|c| c.id.clone()).collect();
+        let counts = self.get_comment_counts(&card_ids).await?;
+        for card in cards.iter_mut() {
+            card.comment_count = Some(counts.get(&card.id).copied().unwrap_or(0));
+        }
+        Ok(())
+    }
+
+    /// Load tags for a set of cards in one query
+    pub async fn attach_tags(&self, cards: &mut [Card]) -> Result<(), AgentBoardError> {
+        if cards.is_empty() {
+            return Ok(());
+        }
+        let query = format!(
+            "SELECT card_id, tag FROM card_tags WHERE card_id IN ({}) ORDER BY tag",
+            Self::placeholders(cards.len())
+        );
+        let params: Vec<libsql::Value> = cards.iter().map(|c| c.id.clone().into()).collect();
+        let mut rows = self
+            .conn
+            .query(&query, libsql::params_from_iter(params))
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+
+        let mut tags: HashMap<String, Vec<String>> = HashMap::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            tags.entry(row.get::<String>(0).unwrap_or_default())
+                .or_default()
+                .push(row.get::<String>(1).unwrap_or_default());
+        }
+        for card in cards.iter_mut() {
+            card.tags = tags.remove(&card.id).unwrap_or_default();
+        }
+        Ok(())
+    }
+
+    /// Tags on a board's live cards, most used first; `prefix` narrows the list for completion
+    pub async fn board_tags(
+        &self,
+        board_id: &str,
+        prefix: Option<&str>,
+    ) -> Result<Vec<TagCount>, AgentBoardError> {
+        self.get_board(board_id).await?;
+        let prefix = prefix.unwrap_or("");
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT t.tag, COUNT(*) FROM card_tags t JOIN cards c ON c.id = t.card_id
+                 WHERE c.board_id = ?1 AND c.deleted_at IS NULL AND substr(t.tag, 1, length(?2)) = ?2
+                 GROUP BY t.tag ORDER BY COUNT(*) DESC, t.tag",
+                [board_id, prefix],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+
+        let mut tags = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            tags.push(TagCount {
+                tag: row.get::<String>(0).unwrap_or_default(),
+                cards: row.get::<i64>(1).unwrap_or_default() as usize,
+            });
+        }
+        Ok(tags)
+    }
+
+    /// Load labels for a set of cards in one query
+    pub async fn attach_labels(&self, cards: &mut [Card]) -> Result<(), AgentBoardError> {
+        if cards.is_empty() {
+            return Ok(());
+        }
+        let query = format!(
+            "SELECT cl.card_id, l.id, l.name, l.color FROM card_labels cl JOIN labels l ON l.id = cl.label_id
+             WHERE cl.card_id IN ({}) ORDER BY l.name",
+            Self::placeholders(cards.len())
+        );
+        let params: Vec<libsql::Value> = cards.iter().map(|c| c.id.clone().into()).collect();
+        let mut rows = self
+            .conn
+            .query(&query, libsql::params_from_iter(params))
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+
+        let mut labels: HashMap<String, Vec<Label>> = HashMap::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            labels
+                .entry(row.get::<String>(0).unwrap_or_default())
+                .or_default()
+                .push(Label {
+                    id: row.get::<String>(1).unwrap_or_default(),
+                    name: row.get::<String>(2).unwrap_or_default(),
+                    color: row.get::<String>(3).unwrap_or_default(),
+                });
+        }
+        for card in cards.iter_mut() {
+            card.labels = labels.remove(&card.id).unwrap_or_default();
+        }
+        Ok(())
+    }
+
+    fn placeholders(count: usize) -> String {
+        (1..=count)
+            .map(|i| format!("?{}", i))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    async fn load_card_full(&self, card_id: &str) -> Result<Card, AgentBoardError> {
+        let mut cards = self
+            .query_cards("id = ?1 AND deleted_at IS NULL", vec![card_id.into()])
+            .await?;
+        if cards.is_empty() {
+            return Err(AgentBoardError::NotFound(format!(
+                "Card not found: {}",
+                card_id
+            )));
+        }
+        self.hydrate_cards(&mut cards).await?;
+        Ok(cards.remove(0))
+    }
+
+    /// ETag of a card as `get` shows it: its fields (checklist, tags, labels,
+    /// dependencies) and its comments with their reactions
+    pub fn card_etag(card: &Card, comments: &[Comment]) -> String {
+        content_hash(&(card, comments))
+    }
+
+    /// ETag of a board: its fields plus the ETag of every live card on it
+    pub async fn board_etag(&self, board: &Board) -> Result<String, AgentBoardError> {
+        let cards = self.list_cards(&board.id, &CardFilter::default()).await?;
+        let mut etags = Vec::with_capacity(cards.len());
+        for card in &cards {
+            let comments = self.list_comments(&card.id).await?;
+            etags.push(Self::card_etag(card, &comments));
+        }
+        Ok(content_hash(&(board, etags)))
+    }
+
+    pub async fn get_card(&self, card_id: &str) -> Result<Card, AgentBoardError> {
+        self.load_card_full(card_id).await
+    }
+
+    pub async fn list_cards(
+        &self,
+        board_id: &str,
+        filter: &CardFilter,
+    ) -> Result<Vec<Card>, AgentBoardError> {
+        let mut cards = self.list_cards_brief(board_id, filter).await?;
+        self.hydrate_cards(&mut cards).await?;
+        Ok(cards)
+    }
+
+    /// List cards with core columns only; `tags` and `checklist` are left empty
+    pub async fn list_cards_brief(
+        &self,
+        board_id: &str,
+        filter: &CardFilter,
+    ) -> Result<Vec<Card>, AgentBoardError> {
+        // Verify board exists (allow deleted boards when include_deleted is true)
+        if filter.include_deleted {
+            // Check if board exists at all (including deleted)
+            let mut rows = self
+                .conn
+                .query("SELECT id FROM boards WHERE id = ?1", [board_id])
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+            if rows
+                .next()
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+                .is_none()
+            {
+                return Err(AgentBoardError::NotFound(format!(
+                    "Board not found: {}",
+                    board_id
+                )));
+            }
+        } else {
+            self.get_board(board_id).await?;
+        }
+
+        let mut conditions = vec!["board_id = ?1".to_string()];
+        let mut params: Vec<libsql::Value> = vec![board_id.into()];
+        if let Some(s) = &filter.status {
+            params.push(s.to_string().into());
+            conditions.push(format!("status = ?{}", params.len()));
+        }
+        if let Some(a) = &filter.assigned_to {
+            params.push(a.clone().into());
+            conditions.push(format!("assigned_to = ?{}", params.len()));
+        }
+        // Card must have ALL specified tags
+        for tag in &filter.tags {
+            params.push(tag.clone().into());
+            conditions.push(format!(
+                "EXISTS (SELECT 1 FROM card_tags WHERE card_id = cards.id AND tag = ?{})",
+                params.len()
+            ));
+        }
+        if !filter.include_deleted {
+            conditions.push("deleted_at IS NULL".to_string());
+        }
+        Self::push_date_conditions(&filter.dates, &mut conditions, &mut params);
+
+        self.query_cards(&conditions.join(" AND "), params).await
+    }
+
+    /// Add created/updated bounds; timestamps are stored as RFC 3339 UTC
+    /// strings, so they compare correctly as text
+    fn push_date_conditions(
+        dates: &DateRange,
+        conditions: &mut Vec<String>,
+        params: &mut Vec<libsql::Value>,
+    ) {
+        let bounds = [
+            ("created_at >", dates.created_after),
+            ("created_at <", dates.created_before),
+            ("updated_at >=", dates.updated_since),
+        ];
+        for (comparison, bound) in bounds {
+            if let Some(ts) = bound {
+                params.push(ts.to_rfc3339().into());
+                conditions.push(format!("{} ?{}", comparison, params.len()));
+            }
+        }
+    }
+
+    /// Cards assigned to `session_id`, or to anyone when it is None
+    pub async fn get_cards_by_assignee(
+        &self,
+        session_id: Option<&str>,
+        board_id: Option<&str>,
+        status: Option<Status>,
+    ) -> Result<Vec<Card>, AgentBoardError> {
+        let mut conditions = vec!["deleted_at IS NULL".to_string()];
+        let mut params: Vec<libsql::Value> = Vec::new();
+        match session_id {
+            Some(id) => {
+                params.push(id.into());
+                conditions.push(format!("assigned_to = ?{}", params.len()));
+            }
+            None => conditions.push("assigned_to IS NOT NULL".to_string()),
+        }
+        if let Some(b) = board_id {
+            params.push(b.into());
+            conditions.push(format!("board_id = ?{}", params.len()));
+        }
+        if let Some(s) = status {
+            params.push(s.to_string().into());
+            conditions.push(format!("status = ?{}", params.len()));
+        }
+
+        let mut cards = self.query_cards(&conditions.join(" AND "), params).await?;
+        self.hydrate_cards(&mut cards).await?;
+        Ok(cards)
+    }
+
+    /// The open card on a board whose name is most like `name`, with its
+    /// score, when it reaches `similarity::DUPLICATE_THRESHOLD`
+    pub async fn find_duplicate_card(
+        &self,
+        board_id: &str,
+        name: &str,
+    ) -> Result<Option<(Card, f64)>, AgentBoardError> {
+        let mut best = self
+            .similar_cards(
+                name,
+                Some(board_id),
+                true,
+                similarity::DUPLICATE_THRESHOLD,
+                1,
+            )
+            .await?;
+        Ok(best.pop().map(|s| (s.card, s.score)))
+    }
+
+    /// Cards whose names score at least `min_score` against `text`, best
+    /// match first, across every board unless one is given
+    pub async fn similar_cards(
+        &self,
+        text: &str,
+        board_id: Option<&str>,
+        open_only: bool,
+        min_score: f64,
+        limit: usize,
+    ) -> Result<Vec<SimilarCard>, AgentBoardError> {
+        let boards = match board_id {
+            Some(id) => vec![self.get_board(id).await?],
+            None => self.list_boards(&BoardFilter::default()).await?,
+        };
+        let mut similar = Vec::new();
+        for board in &boards {
+            for card in self
+                .list_cards_brief(&board.id, &CardFilter::default())
+                .await?
+            {
+                if open_only && card.status == Status::Done {
+                    continue;
+                }
+                let score = similarity::similarity(text, &card.name);
+                if score >= min_score && score > 0.0 {
+                    similar.push(SimilarCard {
+                        card,
+                        board_name: board.name.clone(),
+                        score,
+                    });
+                }
+            }
+        }
+        similar.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.card.id.cmp(&b.card.id))
+        });
+        similar.truncate(limit);
+        Ok(similar)
+    }
+
+    /// Store (or replace) a card's embedding
+    #[cfg(feature = "semantic")]
+    pub async fn set_card_embedding(
+        &self,
+        card_id: &str,
+        vector: &[f32],
+    ) -> Result<(), AgentBoardError> {
+        self.check_card_scope(card_id, false).await?;
+        self.get_card(card_id).await?;
+        self.conn
+            .execute(
+                "INSERT OR REPLACE INTO card_embeddings (card_id, embedding, dims, updated_at)
+                 VALUES (?1, vector32(?2), ?3, ?4)",
+                libsql::params![
+                    card_id,
+                    crate::embedding::to_json(vector),
+                    vector.len() as i64,
+                    Utc::now().to_rfc3339()
+                ],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Embedding update failed: {}", e)))?;
+        Ok(())
+    }
+
+    /// Live cards on a board with no embedding, or one older than their
+    /// last change
+    #[cfg(feature = "semantic")]
+    pub async fn cards_needing_embedding(
+        &self,
+        board_id: &str,
+    ) -> Result<Vec<Card>, AgentBoardError> {
+        let mut stale = std::collections::HashSet::new();
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT c.id FROM cards c LEFT JOIN card_embeddings e ON e.card_id = c.id
+                 WHERE c.board_id = ?1 AND c.deleted_at IS NULL
+                   AND (e.card_id IS NULL OR e.updated_at < c.updated_at)",
+                [board_id],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            stale.insert(row.get::<String>(0).unwrap_or_default());
+        }
+        drop(rows);
+        Ok(self
+            .list_cards(board_id, &CardFilter::default())
+            .await?
+            .into_iter()
+            .filter(|c| stale.contains(&c.id))
+            .collect())
+    }
+
+    /// Embedded cards closest to `vector` by cosine distance, best first;
+    /// the score is 1 - distance. Cards embedded with a different length
+    /// (another model) are skipped
+    #[cfg(feature = "semantic")]
+    pub async fn semantic_search(
+        &self,
+        vector: &[f32],
+        board_id: Option<&str>,
+        open_only: bool,
+        limit: usize,
+    ) -> Result<Vec<SimilarCard>, AgentBoardError> {
+        let mut conditions = vec![
+            "b.namespace = ?1".to_string(),
+            "b.deleted_at IS NULL".to_string(),
+            "c.deleted_at IS NULL".to_string(),
+            "e.dims = ?3".to_string(),
+        ];
+        let mut params: Vec<libsql::Value> = vec![
+            self.namespace.clone().into(),
+            crate::embedding::to_json(vector).into(),
+            (vector.len() as i64).into(),
+            (limit as i64).into(),
+        ];
+        if let Some(board_id) = board_id {
+            self.get_board(board_id).await?;
+            params.push(board_id.to_string().into());
+            conditions.push(format!("c.board_id = ?{}", params.len()));
+        }
+        if open_only {
+            conditions.push("c.status != 'done'".to_string());
+        }
+        let query = format!(
+            "SELECT c.id, b.name, vector_distance_cos(e.embedding, vector32(?2)) AS distance \
+             FROM card_embeddings e \
+             JOIN cards c ON c.id = e.card_id \
+             JOIN boards b ON b.id = c.board_id \
+             WHERE {} ORDER BY distance, c.id LIMIT ?4",
+            conditions.join(" AND ")
+        );
+        let mut rows = self
+            .conn
+            .query(&query, params)
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        let mut hits = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            hits.push((
+                row.get::<String>(0).unwrap_or_default(),
+                row.get::<String>(1).unwrap_or_default(),
+                row.get::<f64>(2).unwrap_or(1.0),
+            ));
+        }
+        drop(rows);
+        let mut found = Vec::with_capacity(hits.len());
+        for (card_id, board_name, distance) in hits {
+            found.push(SimilarCard {
+                card: self.get_card(&card_id).await?,
+                board_name,
+                score: 1.0 - distance,
+            });
+        }
+        Ok(found)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_card(
+        &self,
+        board_id: &str,
+        name: String,
+        description: Option<String>,
+        status: Status,
+        due_at: Option<DateTime<Utc>>,
+        estimate_minutes: Option<i64>,
+        labels: &[String],
+    ) -> Result<Card, AgentBoardError> {
+        self.check_scope(Some(board_id), false)?;
+        self.check_budget().await?;
+        // Verify board exists
+        self.get_board(board_id).await?;
+        // Resolve labels up front so a typo doesn't leave an unlabeled card behind
+        let mut resolved = Vec::with_capacity(labels.len());
+        for name in labels {
+            resolved.push(self.get_label(board_id, name).await?);
+        }
+
+        self.begin().await?;
+        let result = self
+            .insert_card(
+                board_id,
+                name,
+                description,
+                status,
+                due_at,
+                estimate_minutes,
+                &resolved,
+            )
+            .await;
+        match result {
+            Ok(card) => {
+                self.commit().await?;
+                Ok(card)
+            }
+            Err(e) => {
+                self.rollback().await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Insert a card with its labels and emit card_created; callers check
+    /// scope and budget
+    #[allow(clippy::too_many_arguments)]
+    async fn insert_card(
+        &self,
+        board_id: &str,
+        name: String,
+        description: Option<String>,
+        status: Status,
+        due_at: Option<DateTime<Utc>>,
+        estimate_minutes: Option<i64>,
+        labels: &[Label],
+    ) -> Result<Card, AgentBoardError> {
+        let id = Self::generate_id("card");
+        let now = Utc::now().to_rfc3339();
+        let status_str = status.to_string();
+
+        self.conn
+            .execute(
+                "INSERT INTO cards (id, board_id, name, description, status, created_at, updated_at, due_at, estimate_minutes) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                libsql::params![
+                    id.as_str(),
+                    board_id,
+                    name.as_str(),
+                    description.unwrap_or_default(),
+                    status_str.as_str(),
+                    now.as_str(),
+                    now.as_str(),
+                    due_at.map(|d| d.to_rfc3339()),
+                    estimate_minutes
+                ],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Insert failed: {}", e)))?;
+        for label in labels {
+            self.conn
+                .execute(
+                    "INSERT OR IGNORE INTO card_labels (card_id, label_id) VALUES (?1, ?2)",
+                    [id.as_str(), label.id.as_str()],
+                )
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Insert label failed: {}", e)))?;
+        }
+
+        let card = self.get_card(&id).await?;
+        self.emit(
+            EventKind::CardCreated,
+            &card,
+            serde_json::json!({ "name": card.name, "status": card.status }),
+        )
+        .await?;
+        Ok(card)
+    }
+
+    /// Apply `update` in one transaction (or the caller's), so a move never
+    /// lands without its side effects: events, released dependents, and
+    /// review cards
+    pub async fn update_card(
+        &self,
+        card_id: &str,
+        update: crate::models::CardUpdate,
+    ) -> Result<(), AgentBoardError> {
+        self.check_budget().await?;
+        // Verify card exists
+        let current = self.get_card(card_id).await?;
+        self.check_scope(Some(&current.board_id), false)?;
+
+        if self.in_transaction() {
+            return self.apply_card_update(&current, update).await;
+        }
+        self.begin().await?;
+        let result = self.apply_card_update(&current, update).await;
+        match result {
+            Ok(()) => self.commit().await,
+            Err(e) => {
+                self.rollback().await;
+                Err(e)
+            }
+        }
+    }
+
+    /// The writes behind `update_card()`, after its budget and scope checks
+    async fn apply_card_update(
+        &self,
+        current: &Card,
+        update: crate::models::CardUpdate,
+    ) -> Result<(), AgentBoardError> {
+        let card_id = current.id.as_str();
+        if update.status == Some(Status::Done) && current.status != Status::Done {
+            self.check_done_policy(current, &update).await?;
+        }
+        let mut add_labels = Vec::with_capacity(update.add_labels.len());
+        for name in &update.add_labels {
+            add_labels.push(self.get_label(&current.board_id, name).await?);
+        }
+        let mut remove_labels = Vec::with_capacity(update.remove_labels.len());
+        for name in &update.remove_labels {
+            remove_labels.push(self.get_label(&current.board_id, name).await?);
+        }
+
+        let now = Utc::now().to_rfc3339();
+
+        if let Some(n) = update.name {
+            self.conn
+                .execute(
+                    "UPDATE cards SET name = ?1, updated_at = ?2 WHERE id = ?3",
+                    [&n, &now, card_id],
+                )
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Update failed: {}", e)))?;
+        }
+        let description = match (update.description, update.append_description) {
+            (Some(d), Some(extra)) => Some(Self::append_text(&d, &extra)),
+            (Some(d), None) => Some(d),
+            (None, Some(extra)) => Some(match current.description.as_deref() {
+                Some(existing) => Self::append_text(existing, &extra),
+                None => extra,
+            }),
+            (None, None) => None,
+        };
+        if let Some(d) = description
+            && current.description.as_deref() != Some(d.as_str())
+        {
+            self.conn
+                .execute(
+                    "INSERT INTO card_revisions (id, card_id, field, value, replaced_by, replaced_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    libsql::params![
+                        Self::generate_id("rev"),
+                        card_id,
+                        HistoryField::Description.to_string(),
+                        current.description.clone(),
+                        self.actor.clone(),
+                        now.as_str()
+                    ],
+                )
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Record revision failed: {}", e)))?;
+            self.conn
+                .execute(
+                    "UPDATE cards SET description = ?1, updated_at = ?2 WHERE id = ?3",
+                    [&d, &now, card_id],
+                )
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Update failed: {}", e)))?;
+        }
+        if let Some(s) = update.status {
+            self.conn
+                .execute(
+                    "UPDATE cards SET status = ?1, updated_at = ?2 WHERE id = ?3",
+                    [&s.to_string(), &now, card_id],
+                )
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Update failed: {}", e)))?;
+            if s != current.status {
+                self.emit(
+                    EventKind::CardMoved,
+                    current,
+                    serde_json::json!({ "name": current.name, "from": current.status, "to": s }),
+                )
+                .await?;
+            }
+            if s == Status::Done && current.status != Status::Done {
+                self.unblock_dependents(current).await?;
+                self.close_review_card(current).await?;
+            }
+            if s == Status::PendingReview && current.status != Status::PendingReview {
+                self.open_review_card(current).await?;
+            }
+        }
+        if let Some(due) = update.due_at {
+            self.conn
+                .execute(
+                    "UPDATE cards SET due_at = ?1, updated_at = ?2 WHERE id = ?3",
+                    libsql::params![due.map(|d| d.to_rfc3339()), now.as_str(), card_id],
+                )
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Update failed: {}", e)))?;
+        }
+        if let Some(pr_url) = &update.pr_url {
+            self.conn
+                .execute(
+                    "UPDATE cards SET pr_url = ?1, updated_at = ?2 WHERE id = ?3",
+                    libsql::params![pr_url.as_deref(), now.as_str(), card_id],
+                )
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Update failed: {}", e)))?;
+        }
+        if let Some(estimate) = update.estimate_minutes {
+            self.conn
+                .execute(
+                    "UPDATE cards SET estimate_minutes = ?1, updated_at = ?2 WHERE id = ?3",
+                    libsql::params![estimate, now.as_str(), card_id],
+                )
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Update failed: {}", e)))?;
+        }
+        if let Some(sid) = update.session_id {
+            if sid != current.assigned_to {
+                self.record_assignment(card_id, sid.as_deref(), current.assigned_to.as_deref())
+                    .await?;
+                self.emit(
+                    EventKind::CardAssigned,
+                    current,
+                    serde_json::json!({ "name": current.name, "from": current.assigned_to, "to": sid }),
+                )
+                .await?;
+            }
+            match sid {
+                Some(s) => {
+                    self.conn
+                        .execute(
+                            "UPDATE cards SET assigned_to = ?1, updated_at = ?2 WHERE id = ?3",
+                            [&s, &now, card_id],
+                        )
+                        .await
+                        .map_err(|e| AgentBoardError::General(format!("Update failed: {}", e)))?;
+                }
+                None => {
+                    self.conn
+                        .execute(
+                            "UPDATE cards SET assigned_to = NULL, updated_at = ?1 WHERE id = ?2",
+                            [&now, card_id],
+                        )
+                        .await
+                        .map_err(|e| AgentBoardError::General(format!("Update failed: {}", e)))?;
+                }
+            }
+        }
+        for tag in update.add_tags {
+            self.conn
+                .execute(
+                    "INSERT OR IGNORE INTO card_tags (card_id, tag) VALUES (?1, ?2)",
+                    [card_id, &tag],
+                )
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Insert tag failed: {}", e)))?;
+        }
+        for label in add_labels {
+            self.conn
+                .execute(
+                    "INSERT OR IGNORE INTO card_labels (card_id, label_id) VALUES (?1, ?2)",
+                    [card_id, &label.id],
+                )
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Insert label failed: {}", e)))?;
+        }
+        for label in remove_labels {
+            self.conn
+                .execute(
+                    "DELETE FROM card_labels WHERE card_id = ?1 AND label_id = ?2",
+                    [card_id, &label.id],
+                )
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Remove label failed: {}", e)))?;
+        }
+        for tag in update.remove_tags {
+            self.conn
+                .execute(
+                    "DELETE FROM card_tags WHERE card_id = ?1 AND tag = ?2",
+                    [card_id, &tag],
+                )
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Delete tag failed: {}", e)))?;
+        }
+
+        Ok(())
+    }
+
+    /// Add and remove tags on every card on a board matching `filter`, all or
+    /// nothing; with `dry_run` only returns the cards that would change
+    pub async fn bulk_tag(
+        &self,
+        board_id: &str,
+        filter: &CardFilter,
+        add_tags: &[String],
+        remove_tags: &[String],
+        dry_run: bool,
+    ) -> Result<Vec<Card>, AgentBoardError> {
+        if !dry_run {
+            self.check_scope(Some(board_id), false)?;
+        }
+        if add_tags.is_empty() && remove_tags.is_empty() {
+            return Err(AgentBoardError::InvalidArgs(
+                "Nothing to apply: pass --add-tag and/or --remove-tag".into(),
+            ));
+        }
+        let cards = self.list_cards_brief(board_id, filter).await?;
+        if dry_run || cards.is_empty() {
+            return Ok(cards);
+        }
+
+        self.begin().await?;
+        for card in &cards {
+            let update = CardUpdate {
+                add_tags: add_tags.to_vec(),
+                remove_tags: remove_tags.to_vec(),
+                ..Default::default()
+            };
+            if let Err(e) = self.update_card(&card.id, update).await {
+                self.rollback().await;
+                return Err(e);
+            }
+        }
+        self.commit().await?;
+        Ok(cards)
+    }
+
+    /// Evaluate hygiene `rules` against a board's live cards
+    pub async fn check_board(
+        &self,
+        board_id: &str,
+        rules: &[CheckRule],
+    ) -> Result<CheckReport, AgentBoardError> {
+        let cards = self
+            .list_cards_brief(board_id, &CardFilter::default())
+            .await?;
+        let settings = self.board_settings(board_id).await?;
+        let now = Utc::now();
+
+        let mut violations = Vec::new();
+        for rule in rules {
+            match rule {
+                CheckRule::Overdue => {
+                    for card in &cards {
+                        if let Some(due) = card.due_at
+                            && due < now
+                            && card.status != Status::Done
+                        {
+                            violations.push(CheckViolation {
+                                rule: *rule,
+                                card_id: Some(card.id.clone()),
+                                message: format!(
+                                    "{} was due {}",
+                                    card.name,
+                                    due.format("%Y-%m-%d %H:%M")
+                                ),
+                            });
+                        }
+                    }
+                }
+                CheckRule::UnassignedInProgress => {
+                    for card in &cards {
+                        if card.status == Status::InProgress && card.assigned_to.is_none() {
+                            violations.push(CheckViolation {
+                                rule: *rule,
+                                card_id: Some(card.id.clone()),
+                                message: format!("{} is in progress with no assignee", card.name),
+                            });
+                        }
+                    }
+                }
+                CheckRule::WipExceeded => {
+                    for setting in &settings {
+                        let Some(status) = setting.key.strip_prefix(SETTING_WIP_PREFIX) else {
+                            continue;
+                        };
+                        let limit: usize = setting.value.parse().unwrap_or(usize::MAX);
+                        let count = cards
+                            .iter()
+                            .filter(|c| c.status.to_string() == status)
+                            .count();
+                        if count > limit {
+                            violations.push(CheckViolation {
+                                rule: *rule,
+                                card_id: None,
+                                message: format!("{} cards in {} (limit {})", count, status, limit),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(CheckReport {
+            board_id: board_id.to_string(),
+            rules: rules.to_vec(),
+            passed: violations.is_empty(),
+            violations,
+        })
+    }
+
+    /// Collect hygiene findings for a board: cards lacking descriptions or
+    /// owners, reviews waiting since before `stale_before`, one-off tags, and
+    /// active agents with no activity since `idle_since`
+    pub async fn audit_board(
+        &self,
+        board_id: &str,
+        stale_before: DateTime<Utc>,
+        idle_since: DateTime<Utc>,
+    ) -> Result<AuditReport, AgentBoardError> {
+        let cards = self.list_cards(board_id, &CardFilter::default()).await?;
+        let item = |card: &Card| AuditItem {
+            id: card.id.clone(),
+            detail: card.name.clone(),
+        };
+
+        // When each card last entered review (falls back to its last update)
+        let mut entered_review: HashMap<String, DateTime<Utc>> = HashMap::new();
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT card_id, MAX(created_at) FROM events
+                 WHERE board_id = ?1 AND event = 'card_moved' AND json_extract(data, '$.to') = 'pending_review'
+                 GROUP BY card_id",
+                [board_id],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            entered_review.insert(
+                row.get::<String>(0).unwrap_or_default(),
+                Self::parse_datetime(&row.get::<String>(1).unwrap_or_default()),
+            );
+        }
+        drop(rows);
+
+        let mut tag_cards: std::collections::BTreeMap<&str, Vec<&Card>> =
+            std::collections::BTreeMap::new();
+        for card in &cards {
+            for tag in &card.tags {
+                tag_cards.entry(tag).or_default().push(card);
+            }
+        }
+
+        let active = self
+            .count_by_agent(
+                "SELECT actor, COUNT(*) FROM events WHERE actor IS NOT NULL AND created_at >= ?1 GROUP BY actor
+                 UNION ALL
+                 SELECT author, COUNT(*) FROM comments WHERE author != '' AND created_at >= ?1 AND deleted_at IS NULL GROUP BY author",
+                vec![idle_since.to_rfc3339().into()],
+            )
+            .await?;
+        let idle_agents = self
+            .list_agents(false)
+            .await?
+            .into_iter()
+            .filter(|a| !active.contains_key(&a.id))
+            .map(|a| AuditItem {
+                id: a.id,
+                detail: a.name,
+            })
+            .collect();
+
+        Ok(AuditReport {
+            board_id: board_id.to_string(),
+            missing_description: cards
+                .iter()
+                .filter(|c| c.description.as_deref().is_none_or(|d| d.trim().is_empty()))
+                .map(item)
+                .collect(),
+            unassigned_in_progress: cards
+                .iter()
+                .filter(|c| c.status == Status::InProgress && c.assigned_to.is_none())
+                .map(item)
+                .collect(),
+            stale_review: cards
+                .iter()
+                .filter(|c| c.status == Status::PendingReview)
+                .filter(|c| {
+                    entered_review.get(&c.id).copied().unwrap_or(c.updated_at) < stale_before
+                })
+                .map(item)
+                .collect(),
+            single_use_tags: tag_cards
+                .into_iter()
+                .filter(|(_, cards)| cards.len() == 1)
+                .map(|(tag, cards)| AuditItem {
+                    id: tag.to_string(),
+                    detail: cards[0].id.clone(),
+                })
+                .collect(),
+            idle_agents,
+        })
+    }
+
+    /// What `apply_plan()` would do, without writing anything
+    pub async fn plan_diff(
+        &self,
+        board_id: &str,
+        plan: &Plan,
+    ) -> Result<PlanDiff, AgentBoardError> {
+        let (deltas, orphaned) = self.plan_deltas(board_id, plan).await?;
+        let mut cards: Vec<PlanCardChange> = deltas.into_iter().map(|d| d.change).collect();
+        cards.extend(orphaned);
+        Ok(PlanDiff {
+            board_id: board_id.to_string(),
+            cards,
+        })
+    }
+
+    /// Create and update cards so the board matches the plan, in one
+    /// transaction. Cards are matched by `plan_key`; fields a plan leaves out
+    /// are not touched, and tags, checklist items and dependencies are only
+    /// ever added, so work recorded on the board since the last apply survives.
+    pub async fn apply_plan(
+        &self,
+        board_id: &str,
+        plan: &Plan,
+    ) -> Result<PlanDiff, AgentBoardError> {
+        self.check_scope(Some(board_id), false)?;
+        let (deltas, orphaned) = self.plan_deltas(board_id, plan).await?;
+        self.begin().await?;
+        let result = self.apply_plan_deltas(board_id, plan, deltas).await;
+        match result {
+            Ok(mut cards) => {
+                self.commit().await?;
+                cards.extend(orphaned);
+                Ok(PlanDiff {
+                    board_id: board_id.to_string(),
+                    cards,
+                })
+            }
+            Err(e) => {
+                self.rollback().await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn apply_plan_deltas(
+        &self,
+        board_id: &str,
+        plan: &Plan,
+        deltas: Vec<PlanDelta>,
+    ) -> Result<Vec<PlanCardChange>, AgentBoardError> {
+        let mut ids: HashMap<String, String> = HashMap::new();
+        let mut changes = Vec::with_capacity(deltas.len());
+        for (declared, mut delta) in plan.cards.iter().zip(deltas) {
+            let card_id = match delta.change.card_id.clone() {
+                Some(id) => id,
+                None => {
+                    let card = self
+                        .create_card(
+                            board_id,
+                            declared.name.clone(),
+                            declared.description.clone(),
+                            declared.status.clone().unwrap_or(Status::Todo),
+                            None,
+                            None,
+                            &[],
+                        )
+                        .await?;
+                    self.conn
+                        .execute(
+                            "UPDATE cards SET plan_key = ?1 WHERE id = ?2",
+                            [declared.key.as_str(), card.id.as_str()],
+                        )
+                        .await
+                        .map_err(|e| AgentBoardError::General(format!("Update failed: {}", e)))?;
+                    delta.change.card_id = Some(card.id.clone());
+                    card.id
+                }
+            };
+            if delta.name.is_some()
+                || delta.description.is_some()
+                || delta.assignee.is_some()
+                || !delta.tags.is_empty()
+            {
+                let update = CardUpdate {
+                    name: delta.name.take(),
+                    description: delta.description.take(),
+                    session_id: delta.assignee.take().map(Some),
+                    add_tags: std::mem::take(&mut delta.tags),
+                    ..Default::default()
+                };
+                self.update_card(&card_id, update).await?;
+            }
+            if !delta.checklist.is_empty() {
+                self.add_checklist_items(&card_id, std::mem::take(&mut delta.checklist))
+                    .await?;
+            }
+            ids.insert(declared.key.clone(), card_id);
+            changes.push(delta);
+        }
+
+        // Dependencies last, once every declared card has an ID
+        for delta in &changes {
+            let card_id = delta.change.card_id.as_deref().unwrap_or_default();
+            for key in &delta.depends_on {
+                self.add_dependency(card_id, &ids[key]).await?;
+            }
+        }
+        Ok(changes.into_iter().map(|d| d.change).collect())
+    }
+
+    /// Per declared card, the writes still needed; plus the board's keyed
+    /// cards the plan no longer mentions (reported, never deleted)
+    async fn plan_deltas(
+        &self,
+        board_id: &str,
+        plan: &Plan,
+    ) -> Result<(Vec<PlanDelta>, Vec<PlanCardChange>), AgentBoardError> {
+        self.get_board(board_id).await?;
+        for declared in &plan.cards {
+            if let Some(agent_id) = &declared.assignee {
+                self.get_agent(agent_id).await.map_err(|_| {
+                    AgentBoardError::NotFound(format!(
+                        "Plan card '{}': no active agent with ID {}",
+                        declared.key, agent_id
+                    ))
+                })?;
+            }
+        }
+
+        let cards = self.list_cards(board_id, &CardFilter::default()).await?;
+        let by_key: HashMap<&str, &Card> = cards
+            .iter()
+            .filter_map(|c| c.plan_key.as_deref().map(|key| (key, c)))
+            .collect();
+
+        let mut deltas = Vec::with_capacity(plan.cards.len());
+        for declared in &plan.cards {
+            let existing = by_key.get(declared.key.as_str()).copied();
+            let mut delta = PlanDelta {
+                change: PlanCardChange {
+                    key: declared.key.clone(),
+                    card_id: existing.map(|c| c.id.clone()),
+                    name: declared.name.clone(),
+                    action: PlanAction::Unchanged,
+                    changes: Vec::new(),
+                },
+                name: None,
+                description: None,
+                assignee: None,
+                tags: Vec::new(),
+                checklist: Vec::new(),
+                depends_on: Vec::new(),
+            };
+            let notes = &mut delta.change.changes;
+            match existing {
+                None => {
+                    delta.change.action = PlanAction::Create;
+                    delta.assignee = declared.assignee.clone();
+                    delta.tags = declared.tags.clone();
+                    delta.checklist = declared.checklist.clone();
+                    delta.depends_on = declared.depends_on.clone();
+                    if let Some(status) = &declared.status {
+                        notes.push(format!("status: {}", status));
+                    }
+                }
+                Some(card) => {
+                    if card.name != declared.name {
+                        notes.push(format!("name: \"{}\" -> \"{}\"", card.name, declared.name));
+                        delta.name = Some(declared.name.clone());
+                    }
+                    if declared.description.is_some() && card.description != declared.description {
+                        notes.push("description changed".to_string());
+                        delta.description = declared.description.clone();
+                    }
+                    if declared.assignee.is_some() && card.assigned_to != declared.assignee {
+                        delta.assignee = declared.assignee.clone();
+                    }
+                    delta.tags = declared
+                        .tags
+                        .iter()
+                        .filter(|t| !card.tags.contains(t))
+                        .cloned()
+                        .collect();
+                    delta.checklist = declared
+                        .checklist
+                        .iter()
+                        .filter(|text| !card.checklist.iter().any(|i| &&i.text == text))
+                        .cloned()
+                        .collect();
+                    delta.depends_on = declared
+                        .depends_on
+                        .iter()
+                        .filter(|key| {
+                            by_key
+                                .get(key.as_str())
+                                .is_none_or(|dep| !card.depends_on.contains(&dep.id))
+                        })
+                        .cloned()
+                        .collect();
+                }
+            }
+            if let Some(agent_id) = &delta.assignee {
+                notes.push(format!("assignee: {}", agent_id));
+            }
+            notes.extend(delta.tags.iter().map(|t| format!("+tag {}", t)));
+            notes.extend(
+                delta
+                    .checklist
+                    .iter()
+                    .map(|t| format!("+checklist \"{}\"", t)),
+            );
+            notes.extend(
+                delta
+                    .depends_on
+                    .iter()
+                    .map(|k| format!("+depends_on {}", k)),
+            );
+            if delta.change.action == PlanAction::Unchanged && !delta.change.changes.is_empty() {
+                delta.change.action = PlanAction::Update;
+            }
+            deltas.push(delta);
+        }
+
+        let orphaned = cards
+            .iter()
+            .filter_map(|card| {
+                let key = card.plan_key.as_ref()?;
+                if plan.cards.iter().any(|c| &c.key == key) {
+                    return None;
+                }
+                Some(PlanCardChange {
+                    key: key.clone(),
+                    card_id: Some(card.id.clone()),
+                    name: card.name.clone(),
+                    action: PlanAction::Orphaned,
+                    changes: Vec::new(),
+                })
+            })
+            .collect();
+        Ok((deltas, orphaned))
+    }
+
+    /// Comment on a card and move it to `status` in one transaction, so the
+    /// move never lands without its explanation (or vice versa)
+    pub async fn close_card(
+        &self,
+        card_id: &str,
+        comment: String,
+        status: Status,
+    ) -> Result<Comment, AgentBoardError> {
+        self.check_card_scope(card_id, false).await?;
+        self.begin().await?;
+        let result = async {
+            let comment = self
+                .add_comment(card_id, comment, self.actor.clone(), None, None)
+                .await?;
+            let update = CardUpdate {
+                status: Some(status),
+                ..Default::default()
+            };
+            self.update_card(card_id, update).await?;
+            Ok(comment)
+        }
+        .await;
+        match result {
+            Ok(comment) => {
+                self.commit().await?;
+                Ok(comment)
+            }
+            Err(e) => {
+                self.rollback().await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Move a done card back to `to` (todo or in_progress) with a reason
+    /// comment, recording a `card_reopened` event, all in one transaction
+    pub async fn reopen_card(
+        &self,
+        card_id: &str,
+        reason: String,
+        to: Status,
+    ) -> Result<Comment, AgentBoardError> {
+        self.check_card_scope(card_id, false).await?;
+        if reason.trim().is_empty() {
+            return Err(AgentBoardError::InvalidArgs(
+                "A reason is required to reopen a card".into(),
+            ));
+        }
+        if !matches!(to, Status::Todo | Status::InProgress) {
+            return Err(AgentBoardError::InvalidArgs(format!(
+                "Reopened cards go back to todo or in_progress, not {}",
+                to
+            )));
+        }
+        let card = self.get_card(card_id).await?;
+        if card.status != Status::Done {
+            return Err(AgentBoardError::InvalidArgs(format!(
+                "Card {} is {}, only done cards can be reopened",
+                card_id, card.status
+            )));
+        }
+
+        self.begin().await?;
+        let result = async {
+            let comment = self
+                .add_comment(
+                    card_id,
+                    format!("Reopened: {}", reason.trim()),
+                    self.actor.clone(),
+                    None,
+                    None,
+                )
+                .await?;
+            let update = CardUpdate {
+                status: Some(to.clone()),
+                ..Default::default()
+            };
+            self.update_card(card_id, update).await?;
+            self.emit(
+                EventKind::CardReopened,
+                &card,
+                serde_json::json!({
+                    "name": card.name,
+                    "from": card.status,
+                    "to": to,
+                    "reason": reason.trim(),
+                    "assigned_to": card.assigned_to,
+                    "comment_id": comment.id,
+                }),
+            )
+            .await?;
+            Ok(comment)
+        }
+        .await;
+        match result {
+            Ok(comment) => {
+                self.commit().await?;
+                Ok(comment)
+            }
+            Err(e) => {
+                self.rollback().await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Reopens on a board since `since`, newest first
+    pub async fn reopened_cards(
+        &self,
+        board_id: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<Reopen>, AgentBoardError> {
+        self.get_board(board_id).await?;
+        let filter = EventFilter {
+            board_id: Some(board_id.to_string()),
+            event: Some(EventKind::CardReopened),
+            since: Some(since),
+            ..Default::default()
+        };
+        let mut events = self.list_events(&filter).await?;
+        events.reverse();
+        Ok(events
+            .into_iter()
+            .map(|event| {
+                let field = |key: &str| {
+                    event
+                        .data
+                        .get(key)
+                        .and_then(|v| v.as_str())
+                        .map(str::to_string)
+                };
+                Reopen {
+                    card_name: field("name").unwrap_or_default(),
+                    assigned_to: field("assigned_to"),
+                    to: Self::status_from_str(&field("to").unwrap_or_default()),
+                    reason: field("reason").unwrap_or_default(),
+                    card_id: event.card_id,
+                    reopened_at: event.created_at,
+                    reopened_by: event.actor,
+                }
+            })
+            .collect())
+    }
+
+    /// Net card changes on a board between `since` and `until`, from the event
+    /// log: cards created, and cards whose status or assignee ended up
+    /// different from where it started. Moves that cancel out are left out.
+    pub async fn board_diff(
+        &self,
+        board_id: &str,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<BoardDiff, AgentBoardError> {
+        let board = self.get_board(board_id).await?;
+        let filter = EventFilter {
+            board_id: Some(board_id.to_string()),
+            since: Some(since),
+            ..Default::default()
+        };
+        let events = self.list_events(&filter).await?;
+
+        #[derive(Default)]
+        struct Track {
+            name: String,
+            added: bool,
+            status: Option<(Option<String>, String)>, // first from, last to
+            assignee: Option<(Option<String>, Option<String>)>,
+            status_at: Option<(DateTime<Utc>, Option<String>)>,
+            assignee_at: Option<(DateTime<Utc>, Option<String>)>,
+        }
+        let mut order: Vec<String> = Vec::new();
+        let mut tracks: HashMap<String, Track> = HashMap::new();
+        for event in events.into_iter().filter(|e| e.created_at <= until) {
+            let text = |key: &str| {
+                event
+                    .data
+                    .get(key)
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string)
+            };
+            let track = tracks.entry(event.card_id.clone()).or_insert_with(|| {
+                order.push(event.card_id.clone());
+                Track::default()
+            });
+            if let Some(name) = text("name") {
+                track.name = name;
+            }
+            let stamp = Some((event.created_at, event.actor.clone()));
+            match event.event {
+                EventKind::CardCreated => {
+                    track.added = true;
+                    track.status = Some((None, text("status").unwrap_or_default()));
+                    track.status_at = stamp;
+                }
+                EventKind::CardMoved => {
+                    let to = text("to").unwrap_or_default();
+                    let from = track
+                        .status
+                        .take()
+                        .map_or_else(|| text("from"), |(from, _)| from);
+                    track.status = Some((from, to));
+                    track.status_at = stamp;
+                }
+                EventKind::CardAssigned => {
+                    let to = text("to");
+                    let from = track
+                        .assignee
+                        .take()
+                        .map_or_else(|| text("from"), |(from, _)| from);
+                    track.assignee = Some((from, to));
+                    track.assignee_at = stamp;
+                }
+                // Reopens also record a card_moved; comments and staleness aren't changes
+                _ => {}
+            }
+        }
+
+        let mut changes = Vec::new();
+        for card_id in order {
+            let track = tracks.remove(&card_id).unwrap_or_default();
+            let change = |kind, from, to, (at, by): (DateTime<Utc>, Option<String>)| BoardChange {
+                card_id: card_id.clone(),
+                card_name: track.name.clone(),
+                kind,
+                from,
+                to,
+                at,
+                by,
+            };
+            if let (Some((from, to)), Some(stamp)) = (&track.status, &track.status_at) {
+                let done = Status::Done.to_string();
+                let kind = if track.added {
+                    Some(ChangeKind::Added)
+                } else if from.as_deref() == Some(to.as_str()) {
+                    None
+                } else if *to == done {
+                    Some(ChangeKind::Completed)
+                } else if from.as_deref() == Some(done.as_str()) {
+                    Some(ChangeKind::Reopened)
+                } else {
+                    Some(ChangeKind::Moved)
+                };
+                if let Some(kind) = kind {
+                    changes.push(change(kind, from.clone(), Some(to.clone()), stamp.clone()));
+                }
+            }
+            if let (Some((from, to)), Some(stamp)) = (&track.assignee, &track.assignee_at)
+                && from != to
+            {
+                changes.push(change(
+                    ChangeKind::Reassigned,
+                    from.clone(),
+                    to.clone(),
+                    stamp.clone(),
+                ));
+            }
+        }
+        changes.sort_by(|a, b| a.kind.cmp(&b.kind).then(a.at.cmp(&b.at)));
+
+        Ok(BoardDiff {
+            board_id: board.id,
+            board_name: board.name,
+            since,
+            until,
+            changes,
+        })
+    }
+
+    /// What a board's stakeholders want to hear about `since`..`until`: cards
+    /// completed and started, cards that are blocked now, and open cards due
+    /// before `due_before`
+    pub async fn digest(
+        &self,
+        board_id: &str,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        due_before: DateTime<Utc>,
+    ) -> Result<Digest, AgentBoardError> {
+        let diff = self.board_diff(board_id, since, until).await?;
+        // By where each card ended up, including cards added during the window
+        let ended_in = |status: Status| {
+            let status = status.to_string();
+            diff.changes
+                .iter()
+                .filter(|c| c.kind != ChangeKind::Reassigned && c.to.as_deref() == Some(&status))
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        let completed = ended_in(Status::Done);
+        let started = ended_in(Status::InProgress);
+        let blocked = self.blocked_cards(board_id, None).await?;
+        let mut due_soon: Vec<Card> = self
+            .list_cards(board_id, &CardFilter::default())
+            .await?
+            .into_iter()
+            .filter(|c| c.status != Status::Done && c.due_at.is_some_and(|d| d <= due_before))
+            .collect();
+        due_soon.sort_by(|a, b| (a.due_at, &a.id).cmp(&(b.due_at, &b.id)));
+
+        Ok(Digest {
+            board_id: diff.board_id,
+            board_name: diff.board_name,
+            since,
+            until,
+            completed,
+            started,
+            blocked,
+            due_soon,
+            due_before,
+        })
+    }
+
+    /// Create imported cards in one transaction. Boards are matched by name in
+    /// the current namespace and created when missing; cards without a board
+    /// go to `default_board` (a board name too).
+    pub async fn import_cards(
+        &self,
+        cards: &[ImportedCard],
+        default_board: &str,
+        dry_run: bool,
+    ) -> Result<ImportReport, AgentBoardError> {
+        let mut report = ImportReport {
+            dry_run,
+            ..Default::default()
+        };
+        // Resolve every board before writing so an ambiguous name fails early
+        let mut boards: HashMap<&str, Option<String>> = HashMap::new();
+        for card in cards {
+            let name = card.board.as_deref().unwrap_or(default_board);
+            if boards.contains_key(name) {
+                continue;
+            }
+            let id = match self.resolve_board(name).await {
+                Ok(board) => Some(board.id),
+                Err(AgentBoardError::NotFound(_)) => {
+                    report.boards_created.push(name.to_string());
+                    None
+                }
+                Err(e) => return Err(e),
+            };
+            boards.insert(name, id);
+        }
+        report.cards_created = cards.len();
+        report.comments_added = cards.iter().map(|c| c.comments.len()).sum();
+        if dry_run {
+            return Ok(report);
+        }
+
+        self.begin().await?;
+        let result = async {
+            for (name, id) in boards.iter_mut() {
+                if id.is_none() {
+                    *id = Some(self.create_board(name.to_string(), None).await?.id);
+                }
+            }
+            for card in cards {
+                let name = card.board.as_deref().unwrap_or(default_board);
+                let board_id = boards[name].as_deref().unwrap_or_default();
+                let created = self
+                    .create_card(
+                        board_id,
+                        card.name.clone(),
+                        card.description.clone(),
+                        card.status.clone(),
+                        card.due_at,
+                        None,
+                        &[],
+                    )
+                    .await?;
+                if !card.tags.is_empty() {
+                    let update = CardUpdate {
+                        add_tags: card.tags.clone(),
+                        ..Default::default()
+                    };
+                    self.update_card(&created.id, update).await?;
+                }
+                for text in &card.comments {
+                    self.add_comment(&created.id, text.clone(), None, None, None)
+                        .await?;
+                }
+            }
+            Ok(())
+        }
+        .await;
+        match result {
+            Ok(()) => {
+                self.commit().await?;
+                Ok(report)
+            }
+            Err(e) => {
+                self.rollback().await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Fail with every unmet criterion when the board's definition of done
+    /// (`done.require_*` settings) isn't satisfied by `card` after `update`
+    async fn check_done_policy(
+        &self,
+        card: &Card,
+        update: &CardUpdate,
+    ) -> Result<(), AgentBoardError> {
+        let settings = self.board_settings(&card.board_id).await?;
+        let required = |key: &str| settings.iter().any(|s| s.key == key && s.value == "true");
+
+        let mut unmet = Vec::new();
+        if required(SETTING_DONE_REQUIRE_CHECKLIST) {
+            let unchecked = card.checklist.iter().filter(|i| !i.checked).count();
+            if unchecked > 0 {
+                unmet.push(format!(
+                    "{} of {} checklist items unchecked",
+                    unchecked,
+                    card.checklist.len()
+                ));
+            }
+        }
+        let open_gates = self
+            .list_gates(&card.id)
+            .await?
+            .into_iter()
+            .filter(|g| g.approved_at.is_none())
+            .count();
+        if open_gates > 0 {
+            unmet.push(format!(
+                "{} gate(s) awaiting human approval (`gate approve {}`)",
+                open_gates, card.id
+            ));
+        }
+        if required(SETTING_DONE_REQUIRE_COMMENT) && self.list_comments(&card.id).await?.is_empty()
+        {
+            unmet.push("no comments (add one explaining the work)".to_string());
+        }
+        let pr_url = match &update.pr_url {
+            Some(url) => url.as_ref(),
+            None => card.pr_url.as_ref(),
+        };
+        if required(SETTING_DONE_REQUIRE_PR_URL) && pr_url.is_none() {
+            unmet.push("no PR URL (set with --pr-url)".to_string());
+        }
+
+        if unmet.is_empty() {
+            return Ok(());
+        }
+        Err(AgentBoardError::PolicyViolation(format!(
+            "{} can't be done yet:\n  - {}",
+            card.id,
+            unmet.join("\n  - ")
+        )))
+    }
+
+    /// After `done_card` is finished, release cards that were waiting only on
+    /// it, on boards with `auto_unblock` enabled: comment on each, and move
+    /// cards held with the `blocked` tag back to todo
+    async fn unblock_dependents(&self, done_card: &Card) -> Result<(), AgentBoardError> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT c.id, EXISTS (SELECT 1 FROM card_tags t WHERE t.card_id = c.id AND t.tag = 'blocked')
+                 FROM card_dependencies d JOIN cards c ON c.id = d.card_id
+                 WHERE d.depends_on = ?1 AND c.deleted_at IS NULL AND c.status != 'done'
+                   AND EXISTS (SELECT 1 FROM board_settings s
+                               WHERE s.board_id = c.board_id AND s.key = ?2 AND s.value = 'true')
+                   AND NOT EXISTS (SELECT 1 FROM card_dependencies o JOIN cards b ON b.id = o.depends_on
+                                   WHERE o.card_id = c.id AND b.status != 'done' AND b.deleted_at IS NULL)
+                 ORDER BY c.created_at, c.id",
+                [done_card.id.as_str(), SETTING_AUTO_UNBLOCK],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        let mut released: Vec<(String, bool)> = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            let tagged = row.get::<i64>(1).unwrap_or_default();
+            released.push((row.get::<String>(0).unwrap_or_default(), tagged != 0));
+        }
+        drop(rows);
+
+        let now = Utc::now().to_rfc3339();
+        let note = format!(
+            "Unblocked: last dependency {} ({}) is done",
+            done_card.id, done_card.name
+        );
+        for (card_id, tagged_blocked) in released {
+            if tagged_blocked {
+                self.conn
+                    .execute(
+                        "DELETE FROM card_tags WHERE card_id = ?1 AND tag = 'blocked'",
+                        [card_id.as_str()],
+                    )
+                    .await
+                    .map_err(|e| AgentBoardError::General(format!("Delete tag failed: {}", e)))?;
+                let card = self.get_card(&card_id).await?;
+                self.conn
+                    .execute(
+                        "UPDATE cards SET status = 'todo', updated_at = ?1 WHERE id = ?2",
+                        [now.as_str(), card_id.as_str()],
+                    )
+                    .await
+                    .map_err(|e| AgentBoardError::General(format!("Update failed: {}", e)))?;
+                if card.status != Status::Todo {
+                    self.emit(
+                        EventKind::CardMoved,
+                        &card,
+                        serde_json::json!({ "name": card.name, "from": card.status, "to": Status::Todo }),
+                    )
+                    .await?;
+                }
+            }
+            self.add_comment(&card_id, note.clone(), self.actor.clone(), None, None)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// On boards with `review.board` set, give a card that just entered
+    /// pending_review a todo card on the review board, unless its last review
+    /// card is still open. The review card is the policy's doing, so the
+    /// acting agent's board scope doesn't apply to it.
+    async fn open_review_card(&self, card: &Card) -> Result<(), AgentBoardError> {
+        let Some(review_board) = self
+            .board_setting(&card.board_id, SETTING_REVIEW_BOARD)
+            .await?
+        else {
+            return Ok(());
+        };
+        // A review card on a deleted board would never be seen
+        match self.get_board(&review_board).await {
+            Ok(_) => {}
+            Err(AgentBoardError::NotFound(_)) => {
+                eprintln!(
+                    "Warning: review board {} no longer exists; no review card opened for {}",
+                    review_board, card.id
+                );
+                return Ok(());
+            }
+            Err(e) => return Err(e),
+        }
+        if let Some(open) = self.open_review_card_id(&card.id).await? {
+            self.insert_comment(
+                &open,
+                format!("Back in review: {} ({})", card.id, card.name),
+                self.actor.clone(),
+                None,
+                None,
+            )
+            .await?;
+            return Ok(());
+        }
+        let board = self.get_board(&card.board_id).await?;
+        let current = self.get_card(&card.id).await?;
+        let mut description = format!(
+            "Review {} ({}) from board {}.",
+            card.id, card.name, board.name
+        );
+        if let Some(pr_url) = &current.pr_url {
+            description.push_str(&format!("\nPR: {}", pr_url));
+        }
+        let review = self
+            .insert_card(
+                &review_board,
+                format!("Review: {}", card.name),
+                Some(description),
+                Status::Todo,
+                None,
+                None,
+                &[],
+            )
+            .await?;
+        self.conn
+            .execute(
+                "INSERT OR REPLACE INTO review_cards (card_id, review_card_id, created_at) VALUES (?1, ?2, ?3)",
+                [card.id.as_str(), review.id.as_str(), Utc::now().to_rfc3339().as_str()],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Insert failed: {}", e)))?;
+        self.add_comment(
+            &card.id,
+            format!("Review card {} opened on board {}", review.id, review_board),
+            self.actor.clone(),
+            None,
+            None,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Move the open review card of a card that just finished to done,
+    /// through the same path as any other move: the review board's done
+    /// policy and gates still apply, and its dependents are released. If the
+    /// policy refuses, the review card stays open.
+    async fn close_review_card(&self, card: &Card) -> Result<(), AgentBoardError> {
+        let Some(review_id) = self.open_review_card_id(&card.id).await? else {
+            return Ok(());
+        };
+        // Comment first, so it counts toward `done.require_comment`
+        self.insert_comment(
+            &review_id,
+            format!("Closed: {} ({}) is done", card.id, card.name),
+            self.actor.clone(),
+            None,
+            None,
+        )
+        .await?;
+        let review = self.get_card(&review_id).await?;
+        let update = CardUpdate {
+            status: Some(Status::Done),
+            ..Default::default()
+        };
+        match Box::pin(self.apply_card_update(&review, update)).await {
+            Err(AgentBoardError::PolicyViolation(reason)) => {
+                eprintln!("Warning: review card left open: {}", reason);
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
+    /// The card's linked review card, if it exists and isn't done
+    async fn open_review_card_id(&self, card_id: &str) -> Result<Option<String>, AgentBoardError> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT r.review_card_id FROM review_cards r JOIN cards c ON c.id = r.review_card_id
+                 WHERE r.card_id = ?1 AND c.deleted_at IS NULL AND c.status != 'done'",
+                [card_id],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        match rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            Some(row) => Ok(row.get::<String>(0).ok()),
+            None => Ok(None),
+        }
+    }
+
+    async fn record_assignment(
+        &self,
+        card_id: &str,
+        agent_id: Option<&str>,
+        previous_agent_id: Option<&str>,
+    ) -> Result<(), AgentBoardError> {
+        self.conn
+            .execute(
+                "INSERT INTO assignments (id, card_id, agent_id, previous_agent_id, assigned_by, assigned_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                libsql::params![
+                    Self::generate_id("assign"),
+                    card_id,
+                    agent_id,
+                    previous_agent_id,
+                    self.actor.as_deref(),
+                    Utc::now().to_rfc3339()
+                ],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Insert assignment failed: {}", e)))?;
+        Ok(())
+    }
+
+    /// Assignment changes for a card, oldest first
+    pub async fn list_assignments(
+        &self,
+        card_id: &str,
+    ) -> Result<Vec<Assignment>, AgentBoardError> {
+        // Verify card exists
+        self.get_card(card_id).await?;
+
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT id, card_id, agent_id, previous_agent_id, assigned_by, assigned_at FROM assignments WHERE card_id = ?1 ORDER BY assigned_at ASC, id ASC",
+                [card_id],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+
+        let mut assignments = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            assignments.push(Assignment {
+                id: row.get::<String>(0).unwrap_or_default(),
+                card_id: row.get::<String>(1).unwrap_or_default(),
+                agent_id: row.get::<Option<String>>(2).ok().flatten(),
+                previous_agent_id: row.get::<Option<String>>(3).ok().flatten(),
+                assigned_by: row.get::<Option<String>>(4).ok().flatten(),
+                assigned_at: Self::parse_datetime(&row.get::<String>(5).unwrap_or_default()),
+            });
+        }
+        Ok(assignments)
+    }
+
+    /// Earlier values of a card field, oldest first
+    pub async fn list_revisions(
+        &self,
+        card_id: &str,
+        field: HistoryField,
+    ) -> Result<Vec<CardRevision>, AgentBoardError> {
+        // Verify card exists
+        self.get_card(card_id).await?;
+
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT id, card_id, value, replaced_by, replaced_at FROM card_revisions WHERE card_id = ?1 AND field = ?2 ORDER BY replaced_at ASC, id ASC",
+                [card_id, field.to_string().as_str()],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+
+        let mut revisions = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            revisions.push(CardRevision {
+                id: row.get::<String>(0).unwrap_or_default(),
+                card_id: row.get::<String>(1).unwrap_or_default(),
+                field,
+                value: row.get::<Option<String>>(2).ok().flatten(),
+                replaced_by: row.get::<Option<String>>(3).ok().flatten(),
+                replaced_at: Self::parse_datetime(&row.get::<String>(4).unwrap_or_default()),
+            });
+        }
+        Ok(revisions)
+    }
+
+    // Gate operations
+
+    /// Require a sign-off before the card can move past pending_review
+    pub async fn create_gate(
+        &self,
+        card_id: &str,
+        requirement: GateRequirement,
+    ) -> Result<Gate, AgentBoardError> {
+        let card = self.get_card(card_id).await?;
+        self.check_scope(Some(&card.board_id), false)?;
+        self.check_budget().await?;
+        if card.status == Status::Done {
+            return Err(AgentBoardError::InvalidArgs(format!(
+                "{} is already done; gates only hold back cards that aren't",
+                card_id
+            )));
+        }
+
+        let gate = Gate {
+            id: Self::generate_id("gate"),
+            card_id: card_id.to_string(),
+            requirement,
+            created_by: self.actor.clone(),
+            created_at: Utc::now(),
+            approved_by: None,
+            approved_at: None,
+        };
+        self.conn
+            .execute(
+                "INSERT INTO card_gates (id, card_id, requirement, created_by, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+                libsql::params![
+                    gate.id.as_str(),
+                    card_id,
+                    requirement.to_string(),
+                    gate.created_by.as_deref(),
+                    gate.created_at.to_rfc3339()
+                ],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Insert gate failed: {}", e)))?;
+        Ok(gate)
+    }
+
+    /// Approve every open gate on a card. Only a human (no agent identity
+    /// configured) may approve; `approver` is recorded as who signed off.
+    pub async fn approve_gates(
+        &self,
+        card_id: &str,
+        approver: &str,
+    ) -> Result<Vec<Gate>, AgentBoardError> {
+        if let Some(actor) = &self.actor {
+            return Err(AgentBoardError::PermissionDenied(format!(
+                "gates need a human sign-off, but this run is agent {} (unset AGENT_BOARD_AGENT_ID)",
+                actor
+            )));
+        }
+        self.check_card_scope(card_id, false).await?;
+        let open: Vec<Gate> = self
+            .list_gates(card_id)
+            .await?
+            .into_iter()
+            .filter(|g| g.approved_at.is_none())
+            .collect();
+        if open.is_empty() {
+            return Err(AgentBoardError::NotFound(format!(
+                "No open gates on {}",
+                card_id
+            )));
+        }
+
+        let now = Utc::now();
+        self.conn
+            .execute(
+                "UPDATE card_gates SET approved_by = ?1, approved_at = ?2 WHERE card_id = ?3 AND approved_at IS NULL",
+                [approver, now.to_rfc3339().as_str(), card_id],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Approve gate failed: {}", e)))?;
+        Ok(open
+            .into_iter()
+            .map(|g| Gate {
+                approved_by: Some(approver.to_string()),
+                approved_at: Some(now),
+                ..g
+            })
+            .collect())
+    }
+
+    /// Gates on a card, oldest first
+    pub async fn list_gates(&self, card_id: &str) -> Result<Vec<Gate>, AgentBoardError> {
+        // Verify card exists
+        self.get_card(card_id).await?;
+
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT id, card_id, created_by, created_at, approved_by, approved_at FROM card_gates WHERE card_id = ?1 ORDER BY created_at ASC, id ASC",
+                [card_id],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+
+        let mut gates = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            gates.push(Gate {
+                id: row.get::<String>(0).unwrap_or_default(),
+                card_id: row.get::<String>(1).unwrap_or_default(),
+                requirement: GateRequirement::Human, // the only requirement so far
+                created_by: row.get::<Option<String>>(2).ok().flatten(),
+                created_at: Self::parse_datetime(&row.get::<String>(3).unwrap_or_default()),
+                approved_by: row.get::<Option<String>>(4).ok().flatten(),
+                approved_at: row
+                    .get::<Option<String>>(5)
+                    .ok()
+                    .flatten()
+                    .map(|s| Self::parse_datetime(&s)),
+            });
+        }
+        Ok(gates)
+    }
+
+    /// Join appended text onto existing text as a new paragraph
+    fn append_text(existing: &str, extra: &str) -> String {
+        if existing.trim().is_empty() {
+            extra.to_string()
+        } else {
+            format!("{}\n\n{}", existing.trim_end(), extra)
+        }
+    }
+
+    pub async fn delete_card(&self, card_id: &str) -> Result<(), AgentBoardError> {
+        self.check_card_scope(card_id, false).await?;
+        self.check_budget().await?;
+        // Verify card exists
+        self.get_card(card_id).await?;
+
+        let now = Utc::now().to_rfc3339();
+        self.conn
+            .execute(
+                "UPDATE cards SET deleted_at = ?1, updated_at = ?1 WHERE id = ?2",
+                [&now, card_id],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Delete failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    // Checklist operations (simplified - items added directly to card)
+    pub async fn add_checklist_items(
+        &self,
+        card_id: &str,
+        items: Vec<String>,
+    ) -> Result<Vec<ChecklistItem>, AgentBoardError> {
+        self.check_card_scope(card_id, false).await?;
+        self.check_budget().await?;
+        // Verify card exists
+        self.get_card(card_id).await?;
+
+        let mut checklist_items = Vec::new();
+        for item_text in items {
+            let item_id = Self::generate_id("item");
+            self.conn
+                .execute(
+                    "INSERT INTO checklist_items (id, card_id, text, checked, position)
+                     VALUES (?1, ?2, ?3, 0, (SELECT COALESCE(MAX(position), 0) + 1 FROM checklist_items WHERE card_id = ?2))",
+                    libsql::params![item_id.as_str(), card_id, item_text.as_str()],
+                )
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Insert item failed: {}", e)))?;
+            checklist_items.push(ChecklistItem {
+                id: item_id,
+                text: item_text,
+                checked: false,
+            });
+        }
+
+        // Update card's updated_at
+        let now = Utc::now().to_rfc3339();
+        self.conn
+            .execute(
+                "UPDATE cards SET updated_at = ?1 WHERE id = ?2",
+                [&now, card_id],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Update failed: {}", e)))?;
+
+        Ok(checklist_items)
+    }
+
+    /// The card a (not deleted) checklist item belongs to
+    async fn checklist_item_card(&self, item_id: &str) -> Result<String, AgentBoardError> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT card_id FROM checklist_items WHERE id = ?1 AND deleted_at IS NULL",
+                [item_id],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+
+        if let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            Ok(row.get::<String>(0).unwrap_or_default())
+        } else {
+            Err(AgentBoardError::NotFound(format!(
+                "Checklist item not found: {}",
+                item_id
+            )))
+        }
+    }
+
+    pub async fn delete_checklist_item(&self, item_id: &str) -> Result<(), AgentBoardError> {
+        // Get card_id for timestamp update
+        let card_id = self.checklist_item_card(item_id).await?;
+        self.check_card_scope(&card_id, false).await?;
+        self.check_budget().await?;
+
+        // Soft delete: the item disappears from the card but stays in the database
+        let now = Utc::now().to_rfc3339();
+        self.conn
+            .execute(
+                "UPDATE checklist_items SET deleted_at = ?1 WHERE id = ?2",
+                [now.as_str(), item_id],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Delete item failed: {}", e)))?;
+
+        // Update card timestamp
+        self.conn
+            .execute(
+                "UPDATE cards SET updated_at = ?1 WHERE id = ?2",
+                [now.as_str(), card_id.as_str()],
+            )
+            .await
+            .map_err(|e| {
+                AgentBoardError::General(format!("Update card timestamp failed: {}", e))
+            })?;
+
+        Ok(())
+    }
+
+    /// Get a checklist item along with the ID of the card it belongs to
+    pub async fn get_checklist_item(
+        &self,
+        item_id: &str,
+    ) -> Result<(ChecklistItem, String), AgentBoardError> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT id, text, checked, card_id FROM checklist_items WHERE id = ?1 AND deleted_at IS NULL",
+                [item_id],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+
+        if let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            Ok((
+                ChecklistItem {
+                    id: row.get::<String>(0).unwrap_or_default(),
+                    text: row.get::<String>(1).unwrap_or_default(),
+                    checked: row.get::<i64>(2).unwrap_or(0) != 0,
+                },
+                row.get::<String>(3).unwrap_or_default(),
+            ))
+        } else {
+            Err(AgentBoardError::NotFound(format!(
+                "Checklist item not found: {}",
+                item_id
+            )))
+        }
+    }
+
+    pub async fn check_item(&self, item_id: &str, checked: bool) -> Result<(), AgentBoardError> {
+        let card_id = self.checklist_item_card(item_id).await?;
+        self.check_card_scope(&card_id, false).await?;
+        self.check_budget().await?;
+        let checked_val = if checked { 1 } else { 0 };
+
+        let result = self
+            .conn
+            .execute(
+                "UPDATE checklist_items SET checked = ?1 WHERE id = ?2 AND deleted_at IS NULL",
+                libsql::params![checked_val, item_id],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Update failed: {}", e)))?;
+
+        if result == 0 {
+            return Err(AgentBoardError::NotFound(format!(
+                "Checklist item not found: {}",
+                item_id
+            )));
+        }
+
+        // Update the card's updated_at directly (items now reference card_id)
+        self.conn
+            .execute(
+                "UPDATE cards SET updated_at = ?1 WHERE id = (SELECT card_id FROM checklist_items WHERE id = ?2)",
+                [&Utc::now().to_rfc3339(), item_id],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Update card timestamp failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    // Dependency operations
+    pub async fn add_dependency(
+        &self,
+        card_id: &str,
+        depends_on: &str,
+    ) -> Result<(), AgentBoardError> {
+        self.check_card_scope(card_id, false).await?;
+        self.check_budget().await?;
+        if card_id == depends_on {
+            return Err(AgentBoardError::InvalidArgs(
+                "A card cannot depend on itself".into(),
+            ));
+        }
+        // Verify cards exist
+        self.get_card(card_id).await?;
+        self.get_card(depends_on).await?;
+
+        // Reject the edge if depends_on already (transitively) depends on card_id
+        let mut rows = self
+            .conn
+            .query(
+                "WITH RECURSIVE upstream(id) AS (
+                    SELECT ?1
+                    UNION
+                    SELECT d.depends_on FROM card_dependencies d JOIN upstream u ON d.card_id = u.id
+                 )
+                 SELECT 1 FROM upstream WHERE id = ?2",
+                [depends_on, card_id],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        if rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+            .is_some()
+        {
+            return Err(AgentBoardError::InvalidArgs(format!(
+                "{} already depends on {}; adding this would create a cycle",
+                depends_on, card_id
+            )));
+        }
+        drop(rows);
+
+        let now = Utc::now().to_rfc3339();
+        self.conn
+            .execute(
+                "INSERT OR IGNORE INTO card_dependencies (card_id, depends_on, created_at) VALUES (?1, ?2, ?3)",
+                [card_id, depends_on, now.as_str()],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Insert dependency failed: {}", e)))?;
+        Ok(())
+    }
+
+    pub async fn remove_dependency(
+        &self,
+        card_id: &str,
+        depends_on: &str,
+    ) -> Result<(), AgentBoardError> {
+        self.check_card_scope(card_id, false).await?;
+        self.check_budget().await?;
+        let removed = self
+            .conn
+            .execute(
+                "DELETE FROM card_dependencies WHERE card_id = ?1 AND depends_on = ?2",
+                [card_id, depends_on],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Delete dependency failed: {}", e)))?;
+        if removed == 0 {
+            return Err(AgentBoardError::NotFound(format!(
+                "Dependency not found: {} -> {}",
+                card_id, depends_on
+            )));
+        }
+        Ok(())
+    }
+
+    /// Cards on a board plus any cards on other boards they depend on
+    pub async fn dependency_graph(&self, board_id: &str) -> Result<Vec<Card>, AgentBoardError> {
+        let mut cards = self.list_cards(board_id, &CardFilter::default()).await?;
+        let known: std::collections::HashSet<String> = cards.iter().map(|c| c.id.clone()).collect();
+        let external: Vec<libsql::Value> = cards
+            .iter()
+            .flat_map(|c| c.depends_on.iter())
+            .filter(|id| !known.contains(*id))
+            .map(|id| id.clone().into())
+            .collect();
+        if !external.is_empty() {
+            let condition = format!(
+                "id IN ({}) AND deleted_at IS NULL",
+                Self::placeholders(external.len())
+            );
+            cards.extend(self.query_cards(&condition, external).await?);
+        }
+        Ok(cards)
+    }
+
+    /// A card with its board summary, assignee, dependency neighborhood (up
+    /// to `depth` links each way) and last `comment_limit` comments
+    pub async fn describe_card(
+        &self,
+        card_id: &str,
+        depth: usize,
+        comment_limit: usize,
+    ) -> Result<CardContext, AgentBoardError> {
+        let card = self.get_card(card_id).await?;
+        let board = self.get_board(&card.board_id).await?;
+        let board_summary = self.get_board_summary(&card.board_id).await?;
+        let assignee = match &card.assigned_to {
+            Some(id) => self.get_agent(id).await.ok(),
+            None => None,
+        };
+        let depends_on = self
+            .related_cards(card_id, "card_id", "depends_on", depth)
+            .await?;
+        let dependents = self
+            .related_cards(card_id, "depends_on", "card_id", depth)
+            .await?;
+        let filter = CommentFilter {
+            limit: Some(comment_limit),
+            ..Default::default()
+        };
+        let recent_comments = self.list_comments_filtered(card_id, &filter).await?;
+        Ok(CardContext {
+            card,
+            board,
+            board_summary,
+            assignee,
+            depends_on,
+            dependents,
+            recent_comments,
+        })
+    }
+
+    /// Live cards linked to `card_id` within `depth` dependency hops, walking
+    /// from column `from` to column `to` of card_dependencies
+    async fn related_cards(
+        &self,
+        card_id: &str,
+        from: &str,
+        to: &str,
+        depth: usize,
+    ) -> Result<Vec<RelatedCard>, AgentBoardError> {
+        let query = format!(
+            "WITH RECURSIVE linked(id, depth) AS (
+                SELECT {to}, 1 FROM card_dependencies WHERE {from} = ?1
+                UNION
+                SELECT d.{to}, l.depth + 1 FROM card_dependencies d
+                JOIN linked l ON d.{from} = l.id WHERE l.depth < ?2
+             )
+             SELECT c.id, c.name, c.status, c.assigned_to, MIN(l.depth) AS depth
+             FROM linked l JOIN cards c ON c.id = l.id
+             WHERE c.deleted_at IS NULL AND c.id != ?1
+             GROUP BY c.id ORDER BY depth, c.created_at, c.id",
+        );
+        let mut rows = self
+            .conn
+            .query(&query, libsql::params![card_id, depth as i64])
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+
+        let mut related = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            related.push(RelatedCard {
+                id: row.get::<String>(0).unwrap_or_default(),
+                name: row.get::<String>(1).unwrap_or_default(),
+                status: Self::status_from_str(&row.get::<String>(2).unwrap_or_default()),
+                assigned_to: row.get::<Option<String>>(3).ok().flatten(),
+                depth: row.get::<i64>(4).unwrap_or(1) as usize,
+            });
+        }
+        Ok(related)
+    }
+
+    /// Open cards on a board that wait on unfinished dependencies, carry the
+    /// `blocked` tag, or (with `stale_before`) haven't been updated since then
+    pub async fn blocked_cards(
+        &self,
+        board_id: &str,
+        stale_before: Option<DateTime<Utc>>,
+    ) -> Result<Vec<BlockedCard>, AgentBoardError> {
+        let cards = self.list_cards(board_id, &CardFilter::default()).await?;
+
+        let mut waiting: HashMap<String, Vec<String>> = HashMap::new();
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT d.card_id, d.depends_on FROM card_dependencies d
+                 JOIN cards c ON c.id = d.card_id
+                 JOIN cards b ON b.id = d.depends_on
+                 WHERE c.board_id = ?1 AND b.status != 'done' AND b.deleted_at IS NULL
+                 ORDER BY d.card_id, d.depends_on",
+                [board_id],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            waiting
+                .entry(row.get::<String>(0).unwrap_or_default())
+                .or_default()
+                .push(row.get::<String>(1).unwrap_or_default());
+        }
+
+        Ok(cards
+            .into_iter()
+            .filter(|card| card.status != Status::Done)
+            .filter_map(|card| {
+                let waiting_on = waiting.remove(&card.id).unwrap_or_default();
+                let tagged_blocked = card.tags.iter().any(|t| t == TAG_BLOCKED);
+                let stale_since = stale_before
+                    .filter(|cutoff| card.updated_at < *cutoff)
+                    .map(|_| card.updated_at);
+                if waiting_on.is_empty() && !tagged_blocked && stale_since.is_none() {
+                    return None;
+                }
+                Some(BlockedCard {
+                    card,
+                    waiting_on,
+                    tagged_blocked,
+                    stale_since,
+                })
+            })
+            .collect())
+    }
+
+    /// Open cards on a board with due dates, projected against their
+    /// assignees' queues at `capacity` minutes of work a day (see `schedule`)
+    pub async fn schedule_report(
+        &self,
+        board_id: &str,
+        capacity: i64,
+    ) -> Result<Vec<ScheduleEntry>, AgentBoardError> {
+        let cards = self.list_cards(board_id, &CardFilter::default()).await?;
+        let assigned = self.get_cards_by_assignee(None, None, None).await?;
+        let agents: Vec<String> = self
+            .list_agents(false)
+            .await?
+            .into_iter()
+            .map(|a| a.id)
+            .collect();
+        Ok(schedule::project(
+            &cards,
+            &assigned,
+            &agents,
+            capacity,
+            Utc::now(),
+        ))
+    }
+
+    /// The board's critical path (see `schedule::critical_path`), following
+    /// dependencies onto other boards
+    pub async fn critical_path(&self, board_id: &str) -> Result<CriticalPath, AgentBoardError> {
+        let cards = self.dependency_graph(board_id).await?;
+        let path: Vec<Card> = schedule::critical_path(&cards)
+            .into_iter()
+            .cloned()
+            .collect();
+        Ok(CriticalPath {
+            board_id: board_id.to_string(),
+            total_minutes: path.iter().filter_map(|c| c.estimate_minutes).sum(),
+            cards: path,
+        })
+    }
+
+    /// One board, or every board, with its `key` duration policy; boards
+    /// without one are left out
+    async fn duration_policies(
+        &self,
+        board_id: Option<&str>,
+        key: &str,
+    ) -> Result<Vec<(Board, String, chrono::Duration)>, AgentBoardError> {
+        let boards = match board_id {
+            Some(id) => vec![self.get_board(id).await?],
+            None => self.list_boards(&BoardFilter::default()).await?,
+        };
+        let mut policies = Vec::new();
+        for board in boards {
+            if let Some(value) = self.board_setting(&board.id, key).await?
+                && let Some(limit) = crate::parse_duration(&value)
+            {
+                policies.push((board, format!("{}={}", key, value), limit));
+            }
+        }
+        Ok(policies)
+    }
+
+    /// Open cards due within their board's `due.remind_before` policy (or
+    /// already overdue) that haven't been reminded about for their current
+    /// due date; unless `dry_run`, comment on each and emit `card_due_soon`
+    pub async fn remind_due_soon(
+        &self,
+        board_id: Option<&str>,
+        dry_run: bool,
+    ) -> Result<Vec<DueReminder>, AgentBoardError> {
+        let policies = self
+            .duration_policies(board_id, SETTING_DUE_REMIND_BEFORE)
+            .await?;
+        if !dry_run {
+            for (board, _, _) in &policies {
+                self.check_scope(Some(&board.id), false)?;
+            }
+        }
+
+        let mut reminded: HashMap<String, String> = HashMap::new();
+        let mut rows = self
+            .conn
+            .query("SELECT card_id, due_at FROM due_reminders", ())
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            reminded.insert(
+                row.get(0).unwrap_or_default(),
+                row.get(1).unwrap_or_default(),
+            );
+        }
+        drop(rows);
+
+        let now = Utc::now();
+        let mut due = Vec::new();
+        for (board, policy, before) in &policies {
+            for card in self.list_cards(&board.id, &CardFilter::default()).await? {
+                let Some(due_at) = card.due_at else {
+                    continue;
+                };
+                if card.status == Status::Done
+                    || due_at - now > *before
+                    || reminded.get(&card.id) == Some(&due_at.to_rfc3339())
+                {
+                    continue;
+                }
+                due.push(DueReminder {
+                    card,
+                    policy: policy.clone(),
+                });
+            }
+        }
+        due.sort_by_key(|d| d.card.due_at);
+        if dry_run || due.is_empty() {
+            return Ok(due);
+        }
+
+        self.begin().await?;
+        let result = async {
+            for entry in &due {
+                let due_at = entry.card.due_at.unwrap_or(now);
+                let when = if due_at <= now { "Overdue" } else { "Due soon" };
+                self.add_comment(
+                    &entry.card.id,
+                    format!(
+                        "{}: due {} (policy {}).",
+                        when,
+                        due_at.format("%Y-%m-%d %H:%M"),
+                        entry.policy
+                    ),
+                    self.actor.clone(),
+                    Some(CommentKind::StatusUpdate),
+                    None,
+                )
+                .await?;
+                self.conn
+                    .execute(
+                        "INSERT INTO due_reminders (card_id, due_at, reminded_at) VALUES (?1, ?2, ?3)
+                         ON CONFLICT(card_id) DO UPDATE SET due_at = excluded.due_at, reminded_at = excluded.reminded_at",
+                        [
+                            entry.card.id.as_str(),
+                            due_at.to_rfc3339().as_str(),
+                            now.to_rfc3339().as_str(),
+                        ],
+                    )
+                    .await
+                    .map_err(|e| {
+                        AgentBoardError::General(format!("Save reminder failed: {}", e))
+                    })?;
+                self.emit(
+                    EventKind::CardDueSoon,
+                    &entry.card,
+                    serde_json::json!({
+                        "name": entry.card.name,
+                        "status": entry.card.status,
+                        "due_at": due_at,
+                        "assigned_to": entry.card.assigned_to,
+                        "policy": entry.policy,
+                    }),
+                )
+                .await?;
+            }
+            Ok(())
+        }
+        .await;
+        match result {
+            Ok(()) => {
+                self.commit().await?;
+                Ok(due)
+            }
+            Err(e) => {
+                self.rollback().await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Delete (or with `dry_run`, count) events older than each board's
+    /// `retention.events` policy
+    pub async fn prune_events(
+        &self,
+        board_id: Option<&str>,
+        dry_run: bool,
+    ) -> Result<Vec<PrunedEvents>, AgentBoardError> {
+        let policies = self
+            .duration_policies(board_id, SETTING_RETENTION_EVENTS)
+            .await?;
+        if !dry_run {
+            for (board, _, _) in &policies {
+                self.check_scope(Some(&board.id), false)?;
+            }
+            self.check_budget().await?;
+        }
+        let now = Utc::now();
+        let mut pruned = Vec::new();
+        for (board, policy, keep) in policies {
+            let cutoff = (now - keep).to_rfc3339();
+            let events = if dry_run {
+                let mut rows = self
+                    .conn
+                    .query(
+                        "SELECT COUNT(*) FROM events WHERE board_id = ?1 AND created_at < ?2",
+                        [board.id.as_str(), cutoff.as_str()],
+                    )
+                    .await
+                    .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+                match rows
+                    .next()
+                    .await
+                    .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+                {
+                    Some(row) => row.get::<i64>(0).unwrap_or(0) as usize,
+                    None => 0,
+                }
+            } else {
+                self.conn
+                    .execute(
+                        "DELETE FROM events WHERE board_id = ?1 AND created_at < ?2",
+                        [board.id.as_str(), cutoff.as_str()],
+                    )
+                    .await
+                    .map_err(|e| AgentBoardError::General(format!("Delete failed: {}", e)))?
+                    as usize
+            };
+            pruned.push(PrunedEvents {
+                board_id: board.id,
+                board_name: board.name,
+                policy,
+                events,
+            });
+        }
+        Ok(pruned)
+    }
+
+    /// Find open cards idle for longer than their board's `stale.<status>`
+    /// policy and, unless `dry_run`, tag them `stale`, comment on them and
+    /// emit `card_stale` (so hooks can notify someone). Cards already tagged
+    /// `stale` are skipped until the tag is removed.
+    pub async fn sweep_stale(
+        &self,
+        board_id: Option<&str>,
+        dry_run: bool,
+    ) -> Result<Vec<StaleCard>, AgentBoardError> {
+        let boards = match board_id {
+            Some(id) => vec![self.get_board(id).await?],
+            None => self.list_boards(&BoardFilter::default()).await?,
+        };
+        if !dry_run {
+            for board in &boards {
+                self.check_scope(Some(&board.id), false)?;
+            }
+        }
+
+        let now = Utc::now();
+        let mut stale = Vec::new();
+        for board in &boards {
+            let policies: HashMap<String, (String, chrono::Duration)> = self
+                .board_settings(&board.id)
+                .await?
+                .into_iter()
+                .filter_map(|s| {
+                    let status = s.key.strip_prefix(SETTING_STALE_PREFIX)?.to_string();
+                    let limit = crate::parse_duration(&s.value)?;
+                    Some((status, (format!("{}={}", s.key, s.value), limit)))
+                })
+                .collect();
+            if policies.is_empty() {
+                continue;
+            }
+
+            let mut active: HashMap<String, String> = HashMap::new();
+            let mut rows = self
+                .conn
+                .query(
+                    "SELECT c.id, MAX(c.updated_at, COALESCE((SELECT MAX(e.created_at) FROM events e WHERE e.card_id = c.id), ''),
+                                         COALESCE((SELECT MAX(m.created_at) FROM comments m WHERE m.card_id = c.id), ''))
+                     FROM cards c WHERE c.board_id = ?1 AND c.deleted_at IS NULL",
+                    [board.id.as_str()],
+                )
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+            while let Some(row) = rows
+                .next()
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+            {
+                active.insert(
+                    row.get(0).unwrap_or_default(),
+                    row.get(1).unwrap_or_default(),
+                );
+            }
+            drop(rows);
+
+            for card in self.list_cards(&board.id, &CardFilter::default()).await? {
+                let Some((policy, limit)) = policies.get(&card.status.to_string()) else {
+                    continue;
+                };
+                if card.tags.iter().any(|t| t == TAG_STALE) {
+                    continue;
+                }
+                let active_at = active
+                    .get(&card.id)
+                    .map(|at| Self::parse_datetime(at))
+                    .unwrap_or(card.updated_at);
+                if now - active_at > *limit {
+                    stale.push(StaleCard {
+                        card,
+                        active_at,
+                        policy: policy.clone(),
+                    });
+                }
+            }
+        }
+        stale.sort_by_key(|s| s.active_at);
+        if dry_run || stale.is_empty() {
+            return Ok(stale);
+        }
+
+        self.begin().await?;
+        let result = async {
+            for entry in &stale {
+                let idle = (now - entry.active_at).num_minutes();
+                let update = CardUpdate {
+                    add_tags: vec![TAG_STALE.to_string()],
+                    ..Default::default()
+                };
+                self.update_card(&entry.card.id, update).await?;
+                self.add_comment(
+                    &entry.card.id,
+                    format!(
+                        "Marked stale: no activity in {} since {} (policy {}). Remove the `stale` tag when work resumes.",
+                        entry.card.status,
+                        entry.active_at.format("%Y-%m-%d %H:%M"),
+                        entry.policy
+                    ),
+                    self.actor.clone(),
+                    Some(CommentKind::StatusUpdate),
+                    None,
+                )
+                .await?;
+                self.emit(
+                    EventKind::CardStale,
+                    &entry.card,
+                    serde_json::json!({
+                        "name": entry.card.name,
+                        "status": entry.card.status,
+                        "active_at": entry.active_at,
+                        "idle_minutes": idle,
+                        "policy": entry.policy,
+                    }),
+                )
+                .await?;
+            }
+            Ok(())
+        }
+        .await;
+        match result {
+            Ok(()) => {
+                self.commit().await?;
+                Ok(stale)
+            }
+            Err(e) => {
+                self.rollback().await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Time spent in each status per card, rebuilt from `card_created` and
+    /// `card_moved` events. Cards older than the event log start in the
+    /// status their first move left (or their current one) at `created_at`.
+    pub async fn status_spans(
+        &self,
+        cards: &[Card],
+    ) -> Result<HashMap<String, Vec<StatusSpan>>, AgentBoardError> {
+        if cards.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let query = format!(
+            "SELECT card_id, event, data, created_at FROM events
+             WHERE card_id IN ({}) AND event IN ('card_created', 'card_moved')
+             ORDER BY created_at ASC, id ASC",
+            Self::placeholders(cards.len())
+        );
+        let params: Vec<libsql::Value> = cards.iter().map(|c| c.id.clone().into()).collect();
+        let mut rows = self
+            .conn
+            .query(&query, libsql::params_from_iter(params))
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+
+        let created: HashMap<&str, DateTime<Utc>> = cards
+            .iter()
+            .map(|c| (c.id.as_str(), c.created_at))
+            .collect();
+        let mut entries: HashMap<String, Vec<(Status, DateTime<Utc>)>> = HashMap::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            let card_id: String = row.get(0).unwrap_or_default();
+            let event: String = row.get(1).unwrap_or_default();
+            let data: serde_json::Value =
+                serde_json::from_str(&row.get::<String>(2).unwrap_or_default()).unwrap_or_default();
+            let at = Self::parse_datetime(&row.get::<String>(3).unwrap_or_default());
+            let field = |key: &str| Self::status_from_str(data[key].as_str().unwrap_or_default());
+            let card_entries = entries.entry(card_id.clone()).or_default();
+            if event == "card_created" {
+                card_entries.push((field("status"), at));
+            } else {
+                if card_entries.is_empty() {
+                    card_entries.push((field("from"), created[card_id.as_str()]));
+                }
+                card_entries.push((field("to"), at));
+            }
+        }
+
+        let now = Utc::now();
+        Ok(cards
+            .iter()
+            .map(|card| {
+                let card_entries = entries
+                    .remove(&card.id)
+                    .unwrap_or_else(|| vec![(card.status.clone(), card.created_at)]);
+                let spans = card_entries
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (status, entered_at))| {
+                        let left_at = card_entries.get(i + 1).map(|(_, at)| *at);
+                        StatusSpan {
+                            status: status.clone(),
+                            entered_at: *entered_at,
+                            left_at,
+                            minutes: (left_at.unwrap_or(now) - *entered_at).num_minutes(),
+                        }
+                    })
+                    .collect();
+                (card.id.clone(), spans)
+            })
+            .collect())
+    }
+
+    /// Open cards by time in their current status, longest first; cards that
+    /// entered it before `threshold_before` are flagged
+    pub async fn aging_cards(
+        &self,
+        board_id: &str,
+        threshold_before: DateTime<Utc>,
+    ) -> Result<Vec<AgingCard>, AgentBoardError> {
+        self.get_board(board_id).await?;
+        let cards: Vec<Card> = self
+            .list_cards(board_id, &CardFilter::default())
+            .await?
+            .into_iter()
+            .filter(|card| card.status != Status::Done)
+            .collect();
+        let mut spans = self.status_spans(&cards).await?;
+
+        let mut aging: Vec<AgingCard> = cards
+            .into_iter()
+            .map(|card| {
+                let current = spans
+                    .remove(&card.id)
+                    .and_then(|s| s.into_iter().last())
+                    .map(|s| (s.entered_at, s.minutes))
+                    .unwrap_or((card.created_at, 0));
+                AgingCard {
+                    in_status_since: current.0,
+                    minutes_in_status: current.1,
+                    over_threshold: current.0 < threshold_before,
+                    card,
+                }
+            })
+            .collect();
+        aging.sort_by_key(|a| a.in_status_since);
+        Ok(aging)
+    }
+
+    /// Cards waiting in pending_review on one board or every board in the
+    /// namespace, longest in review first
+    pub async fn review_queue(
+        &self,
+        board_id: Option<&str>,
+    ) -> Result<Vec<ReviewQueueEntry>, AgentBoardError> {
+        let boards = match board_id {
+            Some(id) => vec![self.get_board(id).await?],
+            None => self.list_boards(&BoardFilter::default()).await?,
+        };
+        let filter = CardFilter {
+            status: Some(Status::PendingReview),
+            ..Default::default()
+        };
+        let mut cards = Vec::new();
+        for board in &boards {
+            for card in self.list_cards(&board.id, &filter).await? {
+                cards.push((board.name.clone(), card));
+            }
+        }
+        let just_cards: Vec<Card> = cards.iter().map(|(_, c)| c.clone()).collect();
+        let mut spans = self.status_spans(&just_cards).await?;
+
+        let mut queue = Vec::with_capacity(cards.len());
+        for (board_name, card) in cards {
+            let (since, minutes) = spans
+                .remove(&card.id)
+                .and_then(|s| s.into_iter().last())
+                .map(|s| (s.entered_at, s.minutes))
+                .unwrap_or((card.updated_at, 0));
+            let gates = self.list_gates(&card.id).await?;
+            let reviewer = match gates.iter().find_map(|g| g.approved_by.clone()) {
+                Some(approver) => Some(approver),
+                None => match self.last_review_author(&card.id, since).await? {
+                    Some(author) => Some(author),
+                    None => (!gates.is_empty()).then(|| GateRequirement::Human.to_string()),
+                },
+            };
+            queue.push(ReviewQueueEntry {
+                card,
+                board_name,
+                in_review_since: since,
+                minutes_in_review: minutes,
+                reviewer,
+            });
+        }
+        queue.sort_by_key(|e| e.in_review_since);
+        Ok(queue)
+    }
+
+    /// Author of the newest `review` comment on a card since `since`; a
+    /// comment made without an agent identity is a human's
+    async fn last_review_author(
+        &self,
+        card_id: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Option<String>, AgentBoardError> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT author FROM comments
+                 WHERE card_id = ?1 AND kind = 'review' AND deleted_at IS NULL AND created_at >= ?2
+                 ORDER BY created_at DESC, id DESC LIMIT 1",
+                libsql::params![card_id, since.to_rfc3339()],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        match rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            Some(row) => Ok(Some(
+                row.get::<Option<String>>(0)
+                    .ok()
+                    .flatten()
+                    .filter(|a| !a.is_empty())
+                    .unwrap_or_else(|| "human".to_string()),
+            )),
+            None => Ok(None),
+        }
+    }
+
+    // Label operations
+
+    pub async fn create_label(
+        &self,
+        board_id: &str,
+        name: &str,
+        color: &str,
+    ) -> Result<Label, AgentBoardError> {
+        self.check_scope(Some(board_id), false)?;
+        self.check_budget().await?;
+        self.get_board(board_id).await?;
+        let name = Self::validate_label_name(name)?;
+        Self::validate_label_color(color)?;
+        if self.get_label(board_id, name).await.is_ok() {
+            return Err(AgentBoardError::InvalidArgs(format!(
+                "Label already exists on {}: {}",
+                board_id, name
+            )));
+        }
+
+        let label = Label {
+            id: Self::generate_id("label"),
+            name: name.to_string(),
+            color: color.to_string(),
+        };
+        self.conn
+            .execute(
+                "INSERT INTO labels (id, board_id, name, color, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+                [
+                    label.id.as_str(),
+                    board_id,
+                    name,
+                    color,
+                    Utc::now().to_rfc3339().as_str(),
+                ],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Insert label failed: {}", e)))?;
+        Ok(label)
+    }
+
+    /// Look up a label by name on a board
+    pub async fn get_label(&self, board_id: &str, name: &str) -> Result<Label, AgentBoardError> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT id, name, color FROM labels WHERE board_id = ?1 AND name = ?2",
+                [board_id, name],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        match rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            Some(row) => Ok(Label {
+                id: row.get::<String>(0).unwrap_or_default(),
+                name: row.get::<String>(1).unwrap_or_default(),
+                color: row.get::<String>(2).unwrap_or_default(),
+            }),
+            None => Err(AgentBoardError::NotFound(format!(
+                "Label not found on {}: {} (create it with `label create`)",
+                board_id, name
+            ))),
+        }
+    }
+
+    pub async fn list_labels(&self, board_id: &str) -> Result<Vec<Label>, AgentBoardError> {
+        self.get_board(board_id).await?;
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT id, name, color FROM labels WHERE board_id = ?1 ORDER BY name",
+                [board_id],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        let mut labels = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            labels.push(Label {
+                id: row.get::<String>(0).unwrap_or_default(),
+                name: row.get::<String>(1).unwrap_or_default(),
+                color: row.get::<String>(2).unwrap_or_default(),
+            });
+        }
+        Ok(labels)
+    }
+
+    /// Rename a label; cards keep it because they link by label ID
+    pub async fn rename_label(
+        &self,
+        board_id: &str,
+        name: &str,
+        new_name: &str,
+    ) -> Result<Label, AgentBoardError> {
+        self.check_scope(Some(board_id), false)?;
+        self.check_budget().await?;
+        let mut label = self.get_label(board_id, name).await?;
+        let new_name = Self::validate_label_name(new_name)?;
+        if new_name != name && self.get_label(board_id, new_name).await.is_ok() {
+            return Err(AgentBoardError::InvalidArgs(format!(
+                "Label already exists on {}: {}",
+                board_id, new_name
+            )));
+        }
+        self.conn
+            .execute(
+                "UPDATE labels SET name = ?1 WHERE id = ?2",
+                [new_name, label.id.as_str()],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Update label failed: {}", e)))?;
+        label.name = new_name.to_string();
+        Ok(label)
+    }
+
+    /// Delete a label and remove it from every card
+    pub async fn delete_label(&self, board_id: &str, name: &str) -> Result<(), AgentBoardError> {
+        self.check_scope(Some(board_id), false)?;
+        self.check_budget().await?;
+        let label = self.get_label(board_id, name).await?;
+        for statement in [
+            "DELETE FROM card_labels WHERE label_id = ?1",
+            "DELETE FROM labels WHERE id = ?1",
+        ] {
+            self.conn
+                .execute(statement, [label.id.as_str()])
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Delete label failed: {}", e)))?;
+        }
+        Ok(())
+    }
+
+    fn validate_label_name(name: &str) -> Result<&str, AgentBoardError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(AgentBoardError::InvalidArgs(
+                "Label name cannot be empty".into(),
+            ));
+        }
+        Ok(name)
+    }
+
+    fn validate_label_color(color: &str) -> Result<(), AgentBoardError> {
+        color.parse::<colored::Color>().map(|_| ()).map_err(|_| {
+            AgentBoardError::InvalidArgs(format!(
+                "Unknown color: {}. Use black, red, green, yellow, blue, magenta, cyan, white, or a \"bright \" variant",
+                color
+            ))
+        })
+    }
+
+    // Comment operations
+    pub async fn add_comment(
+        &self,
+        card_id: &str,
+        text: String,
+        author: Option<String>,
+        kind: Option<CommentKind>,
+        meta: Option<Box<serde_json::value::RawValue>>,
+    ) -> Result<Comment, AgentBoardError> {
+        self.check_card_scope(card_id, true).await?;
+        self.check_budget().await?;
+        self.insert_comment(card_id, text, author, kind, meta).await
+    }
+
+    /// Store a comment and emit comment_added; callers check scope and budget
+    async fn insert_comment(
+        &self,
+        card_id: &str,
+        text: String,
+        author: Option<String>,
+        kind: Option<CommentKind>,
+        meta: Option<Box<serde_json::value::RawValue>>,
+    ) -> Result<Comment, AgentBoardError> {
+        // Verify card exists
+        let card = self.get_card(card_id).await?;
+
+        let id = Self::generate_id("comment");
+        let now = Utc::now();
+        let now_str = now.to_rfc3339();
+
+        let max_bytes = self
+            .board_setting(&card.board_id, SETTING_COMMENT_MAX_BYTES)
+            .await?
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_COMMENT_MAX_BYTES);
+        let full_size = (text.len() > max_bytes).then_some(text.len());
+        let (stored_text, blob_hash) = if text.len() >= BLOB_MIN_BYTES || full_size.is_some() {
+            let hash = self.store_blob(&text).await?;
+            let preview: String = text.chars().take(BLOB_PREVIEW_CHARS).collect();
+            (preview, Some(hash))
+        } else {
+            (text.clone(), None)
+        };
+        // Oversized comments are listed as their preview plus a pointer to the full text
+        let (stored_text, text) = match full_size {
+            Some(size) => {
+                let preview = format!(
+                    "{}\n\n[… truncated: {} bytes in full, see `agent-board get {}`]",
+                    stored_text.trim_end(),
+                    size,
+                    id
+                );
+                (preview.clone(), preview)
+            }
+            None => (stored_text, text),
+        };
+        self.conn
+            .execute(
+                "INSERT INTO comments (id, card_id, author, text, created_at, kind, meta, blob_hash, spilled) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                libsql::params![
+                    id.as_str(),
+                    card_id,
+                    author.clone().unwrap_or_default(),
+                    stored_text,
+                    now_str.as_str(),
+                    kind.map(|k| k.to_string()),
+                    meta.as_ref().map(|m| m.get().to_string()),
+                    blob_hash,
+                    full_size.is_some() as i64
+                ],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Insert comment failed: {}", e)))?;
+
+        let comment = Comment {
+            id,
+            card_id: card_id.to_string(),
+            author,
+            text,
+            created_at: now,
+            kind,
+            meta,
+            full_size,
+            reactions: Vec::new(),
+        };
+        self.emit(
+            EventKind::CommentAdded,
+            &card,
+            serde_json::json!({ "name": card.name, "comment": comment }),
+        )
+        .await?;
+        Ok(comment)
+    }
+
+    /// Store content under its SHA-256 hash; identical content is kept once
+    async fn store_blob(&self, content: &str) -> Result<String, AgentBoardError> {
+        use sha2::{Digest, Sha256};
+        let hash: String = Sha256::digest(content.as_bytes())
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        self.conn
+            .execute(
+                "INSERT OR IGNORE INTO blobs (hash, content, size, created_at) VALUES (?1, ?2, ?3, ?4)",
+                libsql::params![
+                    hash.as_str(),
+                    content,
+                    content.len() as i64,
+                    Utc::now().to_rfc3339()
+                ],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Insert blob failed: {}", e)))?;
+        Ok(hash)
+    }
+
+    /// Remove blobs no comment refers to (e.g. after `purge board`).
+    /// Soft-deleted comments keep their blobs.
+    pub async fn gc_blobs(&self) -> Result<BlobGcReport, AgentBoardError> {
+        self.check_scope(None, false)?;
+        self.check_budget().await?;
+        let orphaned = "hash NOT IN (SELECT blob_hash FROM comments WHERE blob_hash IS NOT NULL)";
+        let mut rows = self
+            .conn
+            .query(
+                &format!("SELECT COUNT(*), COALESCE(SUM(size), 0) FROM blobs WHERE {orphaned}"),
+                (),
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        let (blobs_removed, bytes_freed) = match rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            Some(row) => (
+                row.get::<i64>(0).unwrap_or(0) as usize,
+                row.get::<i64>(1).unwrap_or(0) as u64,
+            ),
+            None => (0, 0),
+        };
+        drop(rows);
+        self.conn
+            .execute(&format!("DELETE FROM blobs WHERE {orphaned}"), ())
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Delete failed: {}", e)))?;
+        Ok(BlobGcReport {
+            blobs_removed,
+            bytes_freed,
+        })
+    }
+
+    pub async fn list_comments(&self, card_id: &str) -> Result<Vec<Comment>, AgentBoardError> {
+        self.list_comments_filtered(card_id, &CommentFilter::default())
+            .await
+    }
+
+    /// List comments on a card, optionally narrowed by author and time window
+    pub async fn list_comments_filtered(
+        &self,
+        card_id: &str,
+        filter: &CommentFilter,
+    ) -> Result<Vec<Comment>, AgentBoardError> {
+        // Verify card exists
+        self.get_card(card_id).await?;
+
+        let mut conditions = vec!["card_id = ?1".to_string(), "deleted_at IS NULL".to_string()];
+        let mut params: Vec<libsql::Value> = vec![card_id.into()];
+        if let Some(ref author) = filter.author {
+            params.push(author.clone().into());
+            conditions.push(format!("author = ?{}", params.len()));
+        }
+        if let Some(since) = filter.since {
+            params.push(since.to_rfc3339().into());
+            conditions.push(format!("created_at >= ?{}", params.len()));
+        }
+        if let Some(kind) = filter.kind {
+            params.push(kind.to_string().into());
+            conditions.push(format!("kind = ?{}", params.len()));
+        }
+        // Newest first so LIMIT keeps the most recent comments
+        let mut sql = format!(
+            "SELECT {} FROM comments WHERE {} ORDER BY created_at DESC, id DESC",
+            COMMENT_COLUMNS,
+            conditions.join(" AND ")
+        );
+        if let Some(limit) = filter.limit {
+            sql.push_str(&format!(" LIMIT {}", limit));
+        }
+
+        let mut rows = self
+            .conn
+            .query(&sql, params)
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+
+        let mut comments = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            comments.push(Self::comment_from_row(&row));
+        }
+        if !filter.reverse {
+            comments.reverse();
+        }
+        if filter.full {
+            self.expand_comments(&mut comments).await?;
+        }
+        self.attach_reactions(&mut comments).await?;
+        Ok(comments)
+    }
+
+    fn comment_from_row(row: &libsql::Row) -> Comment {
+        Comment {
+            id: row.get::<String>(0).unwrap_or_default(),
+            card_id: row.get::<String>(1).unwrap_or_default(),
+            author: row.get::<Option<String>>(2).ok().flatten(),
+            text: row.get::<String>(3).unwrap_or_default(),
+            created_at: Self::parse_datetime(&row.get::<String>(4).unwrap_or_default()),
+            kind: row
+                .get::<Option<String>>(5)
+                .ok()
+                .flatten()
+                .and_then(|s| Self::comment_kind_from_str(&s)),
+            meta: row
+                .get::<Option<String>>(6)
+                .ok()
+                .flatten()
+                .and_then(|s| serde_json::value::RawValue::from_string(s).ok()),
+            full_size: row.get::<Option<i64>>(7).ok().flatten().map(|n| n as usize),
+            reactions: Vec::new(),
+        }
+    }
+
+    /// Replace the previews of spilled comments with their full text
+    async fn expand_comments(&self, comments: &mut [Comment]) -> Result<(), AgentBoardError> {
+        let ids: Vec<libsql::Value> = comments
+            .iter()
+            .filter(|c| c.full_size.is_some())
+            .map(|c| c.id.clone().into())
+            .collect();
+        if ids.is_empty() {
+            return Ok(());
+        }
+        let mut rows = self
+            .conn
+            .query(
+                &format!(
+                    "SELECT c.id, b.content FROM comments c JOIN blobs b ON b.hash = c.blob_hash WHERE c.id IN ({})",
+                    Self::placeholders(ids.len())
+                ),
+                ids,
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        let mut bodies = HashMap::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            bodies.insert(
+                row.get::<String>(0).unwrap_or_default(),
+                row.get::<String>(1).unwrap_or_default(),
+            );
+        }
+        for comment in comments.iter_mut() {
+            if let Some(body) = bodies.remove(&comment.id) {
+                comment.text = body;
+            }
+        }
+        Ok(())
+    }
+
+    /// Get a single comment by ID
+    pub async fn get_comment(&self, comment_id: &str) -> Result<Comment, AgentBoardError> {
+        let mut rows = self
+            .conn
+            .query(
+                &format!(
+                    "SELECT {} FROM comments WHERE id = ?1 AND deleted_at IS NULL",
+                    COMMENT_COLUMNS
+                ),
+                [comment_id],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+
+        if let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            let mut comments = vec![Self::comment_from_row(&row)];
+            drop(rows);
+            self.expand_comments(&mut comments).await?;
+            self.attach_reactions(&mut comments).await?;
+            Ok(comments.remove(0))
+        } else {
+            Err(AgentBoardError::NotFound(format!(
+                "Comment not found: {}",
+                comment_id
+            )))
+        }
+    }
+
+    /// Soft-delete a comment. Only its author, or a human (no agent identity
+    /// configured), may delete it.
+    pub async fn delete_comment(&self, comment_id: &str) -> Result<(), AgentBoardError> {
+        let comment = self.get_comment(comment_id).await?;
+        self.check_card_scope(&comment.card_id, true).await?;
+        if let Some(actor) = &self.actor
+            && comment.author.as_deref() != Some(actor.as_str())
+        {
+            return Err(AgentBoardError::PermissionDenied(format!(
+                "{} was written by {}; only its author or a human can delete it",
+                comment_id,
+                comment
+                    .author
+                    .as_deref()
+                    .filter(|a| !a.is_empty())
+                    .unwrap_or("a human")
+            )));
+        }
+        self.check_budget().await?;
+
+        self.conn
+            .execute(
+                "UPDATE comments SET deleted_at = ?1 WHERE id = ?2",
+                [Utc::now().to_rfc3339().as_str(), comment_id],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Delete comment failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Add (or with `remove`, take back) the current agent's reaction to a
+    /// comment. Reacting twice with the same emoji is a no-op.
+    pub async fn react(
+        &self,
+        comment_id: &str,
+        emoji: &str,
+        remove: bool,
+    ) -> Result<Comment, AgentBoardError> {
+        let emoji = emoji.trim();
+        if emoji.is_empty() || emoji.chars().count() > 32 || emoji.contains(char::is_whitespace) {
+            return Err(AgentBoardError::InvalidArgs(format!(
+                "Invalid reaction '{}': use a single emoji or short token like +1",
+                emoji
+            )));
+        }
+        // Verify comment exists
+        let comment = self.get_comment(comment_id).await?;
+        self.check_card_scope(&comment.card_id, true).await?;
+        self.check_budget().await?;
+
+        let author = self.actor.clone().unwrap_or_default();
+        if remove {
+            self.conn
+                .execute(
+                    "DELETE FROM comment_reactions WHERE comment_id = ?1 AND emoji = ?2 AND author = ?3",
+                    [comment_id, emoji, author.as_str()],
+                )
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Remove reaction failed: {}", e)))?;
+        } else {
+            self.conn
+                .execute(
+                    "INSERT OR IGNORE INTO comment_reactions (comment_id, emoji, author, created_at) VALUES (?1, ?2, ?3, ?4)",
+                    [comment_id, emoji, author.as_str(), Utc::now().to_rfc3339().as_str()],
+                )
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Add reaction failed: {}", e)))?;
+        }
+        self.get_comment(comment_id).await
+    }
+
+    /// Fill in `reactions` for a batch of comments, most used emoji first
+    async fn attach_reactions(&self, comments: &mut [Comment]) -> Result<(), AgentBoardError> {
+        if comments.is_empty() {
+            return Ok(());
+        }
+        let query = format!(
+            "SELECT comment_id, emoji, author FROM comment_reactions WHERE comment_id IN ({}) ORDER BY created_at ASC, emoji, author",
+            Self::placeholders(comments.len())
+        );
+        let params: Vec<libsql::Value> = comments
+            .iter()
+            .map(|c| libsql::Value::from(c.id.clone()))
+            .collect();
+        let mut rows = self
+            .conn
+            .query(&query, libsql::params_from_iter(params))
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+
+        let mut by_comment: HashMap<String, Vec<Reaction>> = HashMap::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            let comment_id: String = row.get(0).unwrap_or_default();
+            let emoji: String = row.get(1).unwrap_or_default();
+            let author: String = row.get(2).unwrap_or_default();
+            let reactions = by_comment.entry(comment_id).or_default();
+            let reaction = match reactions.iter_mut().position(|r| r.emoji == emoji) {
+                Some(i) => &mut reactions[i],
+                None => {
+                    reactions.push(Reaction {
+                        emoji,
+                        count: 0,
+                        authors: Vec::new(),
+                    });
+                    reactions.last_mut().unwrap()
+                }
+            };
+            reaction.count += 1;
+            if !author.is_empty() {
+                reaction.authors.push(author);
+            }
+        }
+        for comment in comments.iter_mut() {
+            if let Some(mut reactions) = by_comment.remove(&comment.id) {
+                reactions.sort_by_key(|r| std::cmp::Reverse(r.count));
+                comment.reactions = reactions;
+            }
+        }
+        Ok(())
+    }
+
+    /// Get comment counts for multiple cards at once
+    pub async fn get_comment_counts(
+        &self,
+        card_ids: &[String],
+    ) -> Result<HashMap<String, usize>, AgentBoardError> {
+        if card_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let query = format!(
+            "SELECT card_id, COUNT(*) as cnt FROM comments WHERE card_id IN ({}) AND deleted_at IS NULL GROUP BY card_id",
+            Self::placeholders(card_ids.len())
+        );
+
+        let params: Vec<libsql::Value> = card_ids
+            .iter()
+            .map(|id| libsql::Value::from(id.clone()))
+            .collect();
+
+        let mut rows = self
+            .conn
+            .query(&query, libsql::params_from_iter(params))
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+
+        let mut counts = HashMap::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            let card_id: String = row.get(0).unwrap_or_default();
+            let count: i64 = row.get(1).unwrap_or(0);
+            counts.insert(card_id, count as usize);
+        }
+        Ok(counts)
+    }
+
+    /// Live card counts by status for each board, in one query
+    pub async fn card_counts_by_board(
+        &self,
+        board_ids: &[String],
+    ) -> Result<HashMap<String, StatusCounts>, AgentBoardError> {
+        if board_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let query = format!(
+            "SELECT board_id, status, COUNT(*) FROM cards WHERE board_id IN ({}) AND deleted_at IS NULL GROUP BY board_id, status",
+            Self::placeholders(board_ids.len())
+        );
+        let params: Vec<libsql::Value> = board_ids
+            .iter()
+            .map(|id| libsql::Value::from(id.clone()))
+            .collect();
+        let mut rows = self
+            .conn
+            .query(&query, libsql::params_from_iter(params))
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+
+        let mut counts: HashMap<String, StatusCounts> = HashMap::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            let board_id: String = row.get(0).unwrap_or_default();
+            let status = Self::status_from_str(&row.get::<String>(1).unwrap_or_default());
+            let count: i64 = row.get(2).unwrap_or(0);
+            counts
+                .entry(board_id)
+                .or_default()
+                .add(&status, count as usize);
+        }
+        Ok(counts)
+    }
+
+    /// Record today's card counts for one board, or every board in the
+    /// namespace. Running it again the same day overwrites that day's row.
+    pub async fn record_board_stats(
+        &self,
+        board_id: Option<&str>,
+    ) -> Result<Vec<BoardStats>, AgentBoardError> {
+        let boards = match board_id {
+            Some(id) => vec![self.get_board(id).await?],
+            None => self.list_boards(&BoardFilter::default()).await?,
+        };
+        for board in &boards {
+            self.check_scope(Some(&board.id), false)?;
+        }
+        let board_ids: Vec<String> = boards.iter().map(|b| b.id.clone()).collect();
+        let mut counts = self.card_counts_by_board(&board_ids).await?;
+
+        let now = Utc::now();
+        let day = now.date_naive();
+        let mut recorded = Vec::with_capacity(board_ids.len());
+        for board_id in board_ids {
+            let counts = counts.remove(&board_id).unwrap_or_default();
+            self.conn
+                .execute(
+                    "INSERT OR REPLACE INTO board_stats (board_id, day, todo, in_progress, pending_review, done, recorded_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                    libsql::params![
+                        board_id.as_str(),
+                        day.to_string(),
+                        counts.todo as i64,
+                        counts.in_progress as i64,
+                        counts.pending_review as i64,
+                        counts.done as i64,
+                        now.to_rfc3339()
+                    ],
+                )
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Insert failed: {}", e)))?;
+            recorded.push(BoardStats {
+                board_id,
+                day,
+                counts,
+            });
+        }
+        Ok(recorded)
+    }
+
+    /// Recorded daily counts for a board from `since` on, oldest first
+    pub async fn board_stats(
+        &self,
+        board_id: &str,
+        since: NaiveDate,
+    ) -> Result<Vec<BoardStats>, AgentBoardError> {
+        self.get_board(board_id).await?;
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT day, todo, in_progress, pending_review, done FROM board_stats
+                 WHERE board_id = ?1 AND day >= ?2 ORDER BY day",
+                libsql::params![board_id, since.to_string()],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        let mut stats = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            let Ok(day) =
+                NaiveDate::parse_from_str(&row.get::<String>(0).unwrap_or_default(), "%Y-%m-%d")
+            else {
+                continue;
+            };
+            let mut counts = StatusCounts::default();
+            for (i, status) in [
+                Status::Todo,
+                Status::InProgress,
+                Status::PendingReview,
+                Status::Done,
+            ]
+            .iter()
+            .enumerate()
+            {
+                counts.add(status, row.get::<i64>(i as i32 + 1).unwrap_or(0) as usize);
+            }
+            stats.push(BoardStats {
+                board_id: board_id.to_string(),
+                day,
+                counts,
+            });
+        }
+        Ok(stats)
+    }
+
+    // Event and hook operations
+
+    /// Refuse a mutation once the agent has used up its budget, otherwise
+    /// record it in `mutation_log`. Every public writer calls this after its
+    /// scope check, including ones that emit no event (renames, deletes,
+    /// settings), so the log rather than `events` is what gets counted.
+    /// Bookkeeping (`touch_board()`, `brief()`, embeddings, stats snapshots)
+    /// is exempt.
+    async fn check_budget(&self) -> Result<(), AgentBoardError> {
+        let (Some(actor), Some(budget)) = (&self.actor, &self.budget) else {
+            return Ok(());
+        };
+        // Counted against whoever runs the command, so `--as` can't start afresh
+        let key = self.via.as_deref().unwrap_or(actor);
+        let now = Utc::now();
+        let since = now
+            .checked_sub_signed(budget.window)
+            .unwrap_or(DateTime::<Utc>::MIN_UTC)
+            .to_rfc3339();
+        self.conn
+            .execute(
+                "DELETE FROM mutation_log WHERE actor = ?1 AND created_at < ?2",
+                [key, since.as_str()],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Budget update failed: {}", e)))?;
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT COUNT(*) FROM mutation_log WHERE actor = ?1 AND created_at >= ?2",
+                [key, since.as_str()],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        let used = match rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            Some(row) => row.get::<i64>(0).unwrap_or(0) as usize,
+            None => 0,
+        };
+        drop(rows);
+        if used >= budget.limit {
+            return Err(AgentBoardError::RateLimited(format!(
+                "{} made {} mutations in the last {} minutes (budget {}); wait or ask a human to raise AGENT_BOARD_MUTATION_BUDGET",
+                key,
+                used,
+                budget.window.num_minutes(),
+                budget.limit
+            )));
+        }
+        self.conn
+            .execute(
+                "INSERT INTO mutation_log (actor, created_at) VALUES (?1, ?2)",
+                [key, now.to_rfc3339().as_str()],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Budget update failed: {}", e)))?;
+        Ok(())
+    }
+
+    /// Record an event and start the hooks that match it
+    async fn emit(
+        &self,
+        kind: EventKind,
+        card: &Card,
+        data: serde_json::Value,
+    ) -> Result<(), AgentBoardError> {
+        let event = Event {
+            id: Self::generate_id("event"),
+            event: kind,
+            board_id: card.board_id.clone(),
+            card_id: card.id.clone(),
+            actor: self.actor.clone(),
+            via: self.via.clone(),
+            data,
+            created_at: Utc::now(),
+        };
+        self.conn
+            .execute(
+                "INSERT INTO events (id, event, board_id, card_id, actor, via, data, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                libsql::params![
+                    event.id.as_str(),
+                    kind.to_string(),
+                    event.board_id.as_str(),
+                    event.card_id.as_str(),
+                    event.actor.as_deref(),
+                    event.via.as_deref(),
+                    event.data.to_string(),
+                    event.created_at.to_rfc3339()
+                ],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Insert event failed: {}", e)))?;
+
+        let hooks = self
+            .query_hooks(
+                "event = ?1 AND (board_id IS NULL OR board_id = ?2)",
+                vec![kind.to_string().into(), event.board_id.clone().into()],
+            )
+            .await?;
+        let moved_to = event.data.get("to").and_then(|v| v.as_str());
+        for hook in hooks {
+            let matches_status = match &hook.status {
+                Some(s) => kind == EventKind::CardMoved && moved_to == Some(s.to_string().as_str()),
+                None => true,
+            };
+            if !matches_status {
+                continue;
+            }
+            self.dispatch(crate::hooks::Dispatch::Hook(hook, event.clone()));
+        }
+
+        if kind == EventKind::CardMoved
+            && let Some(to) = moved_to
+        {
+            let notifiers: Vec<Notifier> = self
+                .query_notifiers("board_id = ?1", vec![event.board_id.clone().into()])
+                .await?
+                .into_iter()
+                .filter(|n| n.statuses.iter().any(|s| s.to_string() == to))
+                .collect();
+            // The assignee did the work; fall back to whoever moved the card
+            let agent = match card.assigned_to.as_deref().or(event.actor.as_deref()) {
+                Some(id) if !notifiers.is_empty() => match self.get_agent(id).await {
+                    Ok(agent) => agent.name,
+                    Err(_) => id.to_string(),
+                },
+                Some(id) => id.to_string(),
+                None => "nobody".to_string(),
+            };
+            for notifier in notifiers {
+                let payload = crate::hooks::message(&notifier, &event, card, &agent);
+                self.dispatch(crate::hooks::Dispatch::Notifier(notifier, payload));
+            }
+        }
+        Ok(())
+    }
+
+    /// Run now, or after the open transaction commits
+    fn dispatch(&self, dispatch: crate::hooks::Dispatch) {
+        if self.hooks_muted.load(std::sync::atomic::Ordering::Relaxed) {
+            return;
+        }
+        match self.deferred_hooks.lock().unwrap().as_mut() {
+            Some(deferred) => deferred.push(dispatch),
+            None => dispatch.run(),
+        }
+    }
+
+    pub async fn list_events(&self, filter: &EventFilter) -> Result<Vec<Event>, AgentBoardError> {
+        let mut conditions = vec!["1 = 1".to_string()];
+        let mut params: Vec<libsql::Value> = Vec::new();
+        if let Some(b) = &filter.board_id {
+            params.push(b.clone().into());
+            conditions.push(format!("board_id = ?{}", params.len()));
+        }
+        if let Some(c) = &filter.card_id {
+            params.push(c.clone().into());
+            conditions.push(format!("card_id = ?{}", params.len()));
+        }
+        if let Some(kind) = filter.event {
+            params.push(kind.to_string().into());
+            conditions.push(format!("event = ?{}", params.len()));
+        }
+        if let Some(since) = filter.since {
+            params.push(since.to_rfc3339().into());
+            conditions.push(format!("created_at >= ?{}", params.len()));
+        }
+        // Newest N, returned oldest first
+        let mut sql = format!(
+            "SELECT id, event, board_id, card_id, actor, data, created_at, via FROM events WHERE {} ORDER BY created_at DESC, id DESC",
+            conditions.join(" AND ")
+        );
+        if let Some(limit) = filter.limit {
+            sql.push_str(&format!(" LIMIT {}", limit));
+        }
+
+        let mut rows = self
+            .conn
+            .query(&sql, params)
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        let mut events = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            events.push(Self::event_from_row(&row));
+        }
+        events.reverse();
+        Ok(events)
+    }
+
+    fn event_from_row(row: &libsql::Row) -> Event {
+        Event {
+            id: row.get::<String>(0).unwrap_or_default(),
+            event: Self::event_kind_from_str(&row.get::<String>(1).unwrap_or_default()),
+            board_id: row.get::<String>(2).unwrap_or_default(),
+            card_id: row.get::<String>(3).unwrap_or_default(),
+            actor: row.get::<Option<String>>(4).ok().flatten(),
+            via: row.get::<Option<String>>(7).ok().flatten(),
+            data: serde_json::from_str(&row.get::<String>(5).unwrap_or_default())
+                .unwrap_or_default(),
+            created_at: Self::parse_datetime(&row.get::<String>(6).unwrap_or_default()),
+        }
+    }
+
+    /// Per-agent completions, cycle time, comments, and held cards since
+    /// `since`. Deactivated agents are only listed with activity.
+    pub async fn agent_stats(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<AgentStats>, AgentBoardError> {
+        let in_namespace = "c.board_id IN (SELECT id FROM boards WHERE namespace = ?1)";
+        let since_str = since.to_rfc3339();
+
+        // Latest move to done per card in the window, credited to the assignee;
+        // the cycle starts at the first move to in_progress (or creation)
+        let mut cycles: HashMap<String, Vec<i64>> = HashMap::new();
+        let mut rows = self
+            .conn
+            .query(
+                &format!(
+                    "SELECT c.assigned_to, MAX(e.created_at),
+                        COALESCE((SELECT MIN(s.created_at) FROM events s
+                                  WHERE s.card_id = e.card_id AND s.event = 'card_moved'
+                                    AND json_extract(s.data, '$.to') = 'in_progress'), c.created_at)
+                     FROM events e JOIN cards c ON c.id = e.card_id
+                     WHERE e.event = 'card_moved' AND json_extract(e.data, '$.to') = 'done'
+                       AND e.created_at >= ?2 AND c.assigned_to IS NOT NULL AND {}
+                     GROUP BY e.card_id",
+                    in_namespace
+                ),
+                [self.namespace.as_str(), since_str.as_str()],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            let agent_id: String = row.get(0).unwrap_or_default();
+            let done_at = Self::parse_datetime(&row.get::<String>(1).unwrap_or_default());
+            let started_at = Self::parse_datetime(&row.get::<String>(2).unwrap_or_default());
+            cycles
+                .entry(agent_id)
+                .or_default()
+                .push((done_at - started_at).num_minutes().max(0));
+        }
+        drop(rows);
+
+        let comments = self
+            .count_by_agent(
+                &format!(
+                    "SELECT m.author, COUNT(*) FROM comments m JOIN cards c ON c.id = m.card_id
+                     WHERE m.created_at >= ?2 AND m.author != '' AND m.deleted_at IS NULL AND {} GROUP BY m.author",
+                    in_namespace
+                ),
+                vec![self.namespace.clone().into(), since_str.into()],
+            )
+            .await?;
+        let holding = self
+            .count_by_agent(
+                &format!(
+                    "SELECT c.assigned_to, COUNT(*) FROM cards c
+                     WHERE c.assigned_to IS NOT NULL AND c.deleted_at IS NULL AND c.status != 'done'
+                       AND {} GROUP BY c.assigned_to",
+                    in_namespace
+                ),
+                vec![self.namespace.clone().into()],
+            )
+            .await?;
+
+        let mut result = Vec::new();
+        for agent in self.list_agents(true).await? {
+            let durations = cycles.remove(&agent.id).unwrap_or_default();
+            let stats = AgentStats {
+                completed: durations.len(),
+                avg_cycle_minutes: (!durations.is_empty())
+                    .then(|| durations.iter().sum::<i64>() / durations.len() as i64),
+                comments: comments.get(&agent.id).copied().unwrap_or(0),
+                holding: holding.get(&agent.id).copied().unwrap_or(0),
+                agent_id: agent.id,
+                name: agent.name,
+            };
+            let idle = stats.completed == 0 && stats.comments == 0 && stats.holding == 0;
+            if agent.deactivated_at.is_none() || !idle {
+                result.push(stats);
+            }
+        }
+        result.sort_by(|a, b| {
+            b.completed
+                .cmp(&a.completed)
+                .then(b.comments.cmp(&a.comments))
+                .then(a.name.cmp(&b.name))
+        });
+        Ok(result)
+    }
+
+    /// Run a `SELECT agent_id, COUNT(*) ... GROUP BY` query into a map
+    async fn count_by_agent(
+        &self,
+        sql: &str,
+        params: Vec<libsql::Value>,
+    ) -> Result<HashMap<String, usize>, AgentBoardError> {
+        let mut rows = self
+            .conn
+            .query(sql, params)
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        let mut counts = HashMap::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            counts.insert(
+                row.get::<String>(0).unwrap_or_default(),
+                row.get::<i64>(1).unwrap_or(0) as usize,
+            );
+        }
+        Ok(counts)
+    }
+
+    /// Most recently active cards (by update or event, whichever is later)
+    /// on live boards in the namespace, each with its latest event
+    pub async fn recent_cards(
+        &self,
+        board_id: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<RecentCard>, AgentBoardError> {
+        if let Some(b) = board_id {
+            self.get_board(b).await?;
+        }
+
+        let mut conditions = vec![
+            "c.deleted_at IS NULL".to_string(),
+            "c.board_id IN (SELECT id FROM boards WHERE namespace = ?1 AND deleted_at IS NULL)"
+                .to_string(),
+        ];
+        let mut params: Vec<libsql::Value> = vec![self.namespace.clone().into()];
+        if let Some(b) = board_id {
+            params.push(b.into());
+            conditions.push(format!("c.board_id = ?{}", params.len()));
+        }
+        let sql = format!(
+            "SELECT c.id, MAX(c.updated_at, COALESCE((SELECT MAX(e.created_at) FROM events e WHERE e.card_id = c.id), '')) AS active_at
+             FROM cards c WHERE {} ORDER BY active_at DESC, c.id DESC LIMIT {}",
+            conditions.join(" AND "),
+            limit
+        );
+        let mut rows = self
+            .conn
+            .query(&sql, params)
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        let mut order: Vec<(String, DateTime<Utc>)> = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            order.push((
+                row.get::<String>(0).unwrap_or_default(),
+                Self::parse_datetime(&row.get::<String>(1).unwrap_or_default()),
+            ));
+        }
+        drop(rows);
+        if order.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let ids: Vec<libsql::Value> = order.iter().map(|(id, _)| id.clone().into()).collect();
+        let placeholders = Self::placeholders(ids.len());
+        let mut cards: HashMap<String, Card> = self
+            .query_cards(&format!("id IN ({})", placeholders), ids.clone())
+            .await?
+            .into_iter()
+            .map(|c| (c.id.clone(), c))
+            .collect();
+
+        let mut latest: HashMap<String, Event> = HashMap::new();
+        let mut rows = self
+            .conn
+            .query(
+                &format!(
+                    "SELECT id, event, board_id, card_id, actor, data, created_at, via FROM events WHERE card_id IN ({}) ORDER BY created_at ASC, id ASC",
+                    placeholders
+                ),
+                ids,
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            let event = Self::event_from_row(&row);
+            latest.insert(event.card_id.clone(), event);
+        }
+
+        Ok(order
+            .into_iter()
+            .filter_map(|(id, active_at)| {
+                let card = cards.remove(&id)?;
+                Some(RecentCard {
+                    last_event: latest.remove(&id),
+                    card,
+                    active_at,
+                })
+            })
+            .collect())
+    }
+
+    /// Assigned cards, new comments and mentions, and newly unblocked cards
+    /// since the agent's last brief (or `since`); unless `peek`, the agent's
+    /// cursor moves to now
+    pub async fn brief(
+        &self,
+        agent_id: &str,
+        since: Option<DateTime<Utc>>,
+        peek: bool,
+    ) -> Result<Brief, AgentBoardError> {
+        let agent = self.get_agent(agent_id).await?;
+        let generated_at = Utc::now();
+        let since = match since {
+            Some(since) => Some(since),
+            None => self.last_brief(agent_id).await?,
+        };
+        let since_str = since.map(|s| s.to_rfc3339()).unwrap_or_default();
+
+        let mut cards = self
+            .get_cards_by_assignee(Some(agent_id), None, None)
+            .await?;
+        cards.retain(|c| c.status != Status::Done);
+        let card_ids: Vec<libsql::Value> = cards.iter().map(|c| c.id.clone().into()).collect();
+
+        let mut comments = Vec::new();
+        let mut unblocked = Vec::new();
+        if !cards.is_empty() {
+            let placeholders = (3..card_ids.len() + 3)
+                .map(|i| format!("?{}", i))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let mut params: Vec<libsql::Value> = vec![agent_id.into(), since_str.clone().into()];
+            params.extend(card_ids.iter().cloned());
+            comments = self
+                .query_comments(
+                    &format!(
+                        "author != ?1 AND created_at > ?2 AND deleted_at IS NULL AND card_id IN ({})",
+                        placeholders
+                    ),
+                    params,
+                )
+                .await?;
+
+            // Cards whose dependencies are all done, with when the last one got there
+            let mut rows = self
+                .conn
+                .query(
+                    &format!(
+                        "SELECT d.card_id, MAX(COALESCE((SELECT MAX(e.created_at) FROM events e
+                                WHERE e.card_id = d.depends_on AND e.event = 'card_moved'
+                                  AND json_extract(e.data, '$.to') = 'done'), ''))
+                         FROM card_dependencies d JOIN cards c ON c.id = d.depends_on
+                         WHERE d.card_id IN ({})
+                         GROUP BY d.card_id HAVING SUM(c.status != 'done') = 0",
+                        Self::placeholders(card_ids.len())
+                    ),
+                    card_ids,
+                )
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+            while let Some(row) = rows
+                .next()
+                .await
+                .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+            {
+                let done_at = row.get::<String>(1).unwrap_or_default();
+                if since.is_none() || done_at > since_str {
+                    unblocked.push(row.get::<String>(0).unwrap_or_default());
+                }
+            }
+        }
+
+        // Matches `text` as stored, so only the preview of a blob-stored body counts
+        let mentions = self
+            .query_comments(
+                "author != ?1 AND created_at > ?2 AND deleted_at IS NULL
+                 AND (instr(text, '@' || ?3) > 0 OR instr(text, '@' || ?1) > 0)
+                 AND card_id IN (SELECT id FROM cards WHERE deleted_at IS NULL
+                                 AND (assigned_to IS NULL OR assigned_to != ?1))",
+                vec![agent_id.into(), since_str.into(), agent.name.clone().into()],
+            )
+            .await?;
+
+        // Shown every time, not just when new: a pin matters until it's removed
+        let mut pinned_notes = Vec::new();
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT id, name, pinned_note FROM boards
+                 WHERE pinned_note IS NOT NULL AND deleted_at IS NULL
+                   AND id IN (SELECT board_id FROM cards WHERE assigned_to = ?1
+                              AND deleted_at IS NULL AND status != 'done')
+                 ORDER BY name COLLATE NOCASE",
+                [agent_id],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            pinned_notes.push(PinnedNote {
+                board_id: row.get::<String>(0).unwrap_or_default(),
+                board_name: row.get::<String>(1).unwrap_or_default(),
+                note: row.get::<String>(2).unwrap_or_default(),
+            });
+        }
+        drop(rows);
+
+        if !peek && self.read_only_path().is_none() {
+            self.lock_writer()?;
+            self.conn
+                .execute(
+                    "INSERT INTO agent_briefs (agent_id, briefed_at) VALUES (?1, ?2)
+                     ON CONFLICT(agent_id) DO UPDATE SET briefed_at = excluded.briefed_at",
+                    [agent_id, generated_at.to_rfc3339().as_str()],
+                )
+                .await
+                .map_err(|e| {
+                    AgentBoardError::General(format!("Save brief cursor failed: {}", e))
+                })?;
+        }
+
+        Ok(Brief {
+            agent_id: agent_id.to_string(),
+            since,
+            generated_at,
+            cards,
+            comments,
+            mentions,
+            unblocked,
+            pinned_notes,
+        })
+    }
+
+    async fn last_brief(&self, agent_id: &str) -> Result<Option<DateTime<Utc>>, AgentBoardError> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT briefed_at FROM agent_briefs WHERE agent_id = ?1",
+                [agent_id],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        Ok(rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+            .map(|row| Self::parse_datetime(&row.get::<String>(0).unwrap_or_default())))
+    }
+
+    /// Comments matching a condition, oldest first
+    async fn query_comments(
+        &self,
+        condition: &str,
+        params: Vec<libsql::Value>,
+    ) -> Result<Vec<Comment>, AgentBoardError> {
+        let mut rows = self
+            .conn
+            .query(
+                &format!(
+                    "SELECT {} FROM comments WHERE {} ORDER BY created_at, id",
+                    COMMENT_COLUMNS, condition
+                ),
+                params,
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        let mut comments = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            comments.push(Self::comment_from_row(&row));
+        }
+        Ok(comments)
+    }
+
+    async fn query_hooks(
+        &self,
+        condition: &str,
+        params: Vec<libsql::Value>,
+    ) -> Result<Vec<Hook>, AgentBoardError> {
+        let mut rows = self
+            .conn
+            .query(
+                &format!(
+                    "SELECT id, event, command, board_id, status, created_at FROM hooks WHERE {} ORDER BY created_at ASC, id ASC",
+                    condition
+                ),
+                params,
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        let mut hooks = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            hooks.push(Hook {
+                id: row.get::<String>(0).unwrap_or_default(),
+                event: Self::event_kind_from_str(&row.get::<String>(1).unwrap_or_default()),
+                command: row.get::<String>(2).unwrap_or_default(),
+                board_id: row.get::<Option<String>>(3).ok().flatten(),
+                status: row
+                    .get::<Option<String>>(4)
+                    .ok()
+                    .flatten()
+                    .map(|s| Self::status_from_str(&s)),
+                created_at: Self::parse_datetime(&row.get::<String>(5).unwrap_or_default()),
+            });
+        }
+        Ok(hooks)
+    }
+
+    pub async fn create_hook(
+        &self,
+        event: EventKind,
+        command: String,
+        board_id: Option<String>,
+        status: Option<Status>,
+    ) -> Result<Hook, AgentBoardError> {
+        self.check_scope(None, false)?;
+        self.check_budget().await?;
+        if status.is_some() && event != EventKind::CardMoved {
+            return Err(AgentBoardError::InvalidArgs(
+                "--status only applies to card-moved hooks".to_string(),
+            ));
+        }
+        if let Some(b) = &board_id {
+            self.get_board(b).await?;
+        }
+
+        let hook = Hook {
+            id: Self::generate_id("hook"),
+            event,
+            command,
+            board_id,
+            status,
+            created_at: Utc::now(),
+        };
+        self.conn
+            .execute(
+                "INSERT INTO hooks (id, event, command, board_id, status, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                libsql::params![
+                    hook.id.as_str(),
+                    hook.event.to_string(),
+                    hook.command.as_str(),
+                    hook.board_id.as_deref(),
+                    hook.status.as_ref().map(|s| s.to_string()),
+                    hook.created_at.to_rfc3339()
+                ],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Insert hook failed: {}", e)))?;
+        Ok(hook)
+    }
+
+    pub async fn list_hooks(&self) -> Result<Vec<Hook>, AgentBoardError> {
+        self.query_hooks("1 = 1", Vec::new()).await
+    }
+
+    pub async fn delete_hook(&self, hook_id: &str) -> Result<(), AgentBoardError> {
+        self.check_scope(None, false)?;
+        self.check_budget().await?;
+        let result = self
+            .conn
+            .execute("DELETE FROM hooks WHERE id = ?1", [hook_id])
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Delete hook failed: {}", e)))?;
+
+        if result == 0 {
+            return Err(AgentBoardError::NotFound(format!(
+                "Hook not found: {}",
+                hook_id
+            )));
+        }
+        Ok(())
+    }
+
+    async fn query_notifiers(
+        &self,
+        condition: &str,
+        params: Vec<libsql::Value>,
+    ) -> Result<Vec<Notifier>, AgentBoardError> {
+        let mut rows = self
+            .conn
+            .query(
+                &format!(
+                    "SELECT id, board_id, kind, url, statuses, created_at FROM notifiers WHERE {} ORDER BY created_at ASC, id ASC",
+                    condition
+                ),
+                params,
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        let mut notifiers = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            notifiers.push(Notifier {
+                id: row.get::<String>(0).unwrap_or_default(),
+                board_id: row.get::<String>(1).unwrap_or_default(),
+                kind: match row.get::<String>(2).unwrap_or_default().as_str() {
+                    "discord" => NotifierKind::Discord,
+                    _ => NotifierKind::Slack,
+                },
+                url: row.get::<String>(3).unwrap_or_default(),
+                statuses: row
+                    .get::<String>(4)
+                    .unwrap_or_default()
+                    .split(',')
+                    .filter(|s| !s.is_empty())
+                    .map(Self::status_from_str)
+                    .collect(),
+                created_at: Self::parse_datetime(&row.get::<String>(5).unwrap_or_default()),
+            });
+        }
+        Ok(notifiers)
+    }
+
+    /// Post to a Slack or Discord webhook when the board's cards enter one of
+    /// `statuses` (default: pending_review and done)
+    pub async fn create_notifier(
+        &self,
+        board_id: &str,
+        kind: NotifierKind,
+        url: String,
+        mut statuses: Vec<Status>,
+    ) -> Result<Notifier, AgentBoardError> {
+        self.check_scope(Some(board_id), false)?;
+        self.check_budget().await?;
+        self.get_board(board_id).await?;
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            return Err(AgentBoardError::InvalidArgs(format!(
+                "Webhook URL must start with https:// or http://: {}",
+                url
+            )));
+        }
+        if statuses.is_empty() {
+            statuses = vec![Status::PendingReview, Status::Done];
+        }
+        let mut unique = Vec::new();
+        for status in statuses {
+            if !unique.contains(&status) {
+                unique.push(status);
+            }
+        }
+
+        let notifier = Notifier {
+            id: Self::generate_id("notifier"),
+            board_id: board_id.to_string(),
+            kind,
+            url,
+            statuses: unique,
+            created_at: Utc::now(),
+        };
+        let statuses: Vec<String> = notifier.statuses.iter().map(|s| s.to_string()).collect();
+        self.conn
+            .execute(
+                "INSERT INTO notifiers (id, board_id, kind, url, statuses, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                libsql::params![
+                    notifier.id.as_str(),
+                    notifier.board_id.as_str(),
+                    notifier.kind.to_string(),
+                    notifier.url.as_str(),
+                    statuses.join(","),
+                    notifier.created_at.to_rfc3339()
+                ],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Insert notifier failed: {}", e)))?;
+        Ok(notifier)
+    }
+
+    pub async fn list_notifiers(
+        &self,
+        board_id: Option<&str>,
+    ) -> Result<Vec<Notifier>, AgentBoardError> {
+        match board_id {
+            Some(b) => {
+                self.query_notifiers("board_id = ?1", vec![b.to_string().into()])
+                    .await
+            }
+            None => self.query_notifiers("1 = 1", Vec::new()).await,
+        }
+    }
+
+    pub async fn delete_notifier(&self, notifier_id: &str) -> Result<(), AgentBoardError> {
+        let notifier = self
+            .query_notifiers("id = ?1", vec![notifier_id.to_string().into()])
+            .await?
+            .pop()
+            .ok_or_else(|| {
+                AgentBoardError::NotFound(format!("Notifier not found: {}", notifier_id))
+            })?;
+        self.check_scope(Some(&notifier.board_id), false)?;
+        self.check_budget().await?;
+        self.conn
+            .execute("DELETE FROM notifiers WHERE id = ?1", [notifier_id])
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Delete notifier failed: {}", e)))?;
+        Ok(())
+    }
+
+    // Sync operations
+
+    /// Merge agents, boards, cards, and comments from another agent-board
+    /// database. Rows missing locally are inserted; rows present on both sides
+    /// take the version with the newer `updated_at`.
+    pub async fn merge_from(&self, other_path: &str) -> Result<MergeReport, AgentBoardError> {
+        self.check_scope(None, false)?;
+        self.check_budget().await?;
+        let source = PathBuf::from(other_path);
+        if !source.exists() {
+            return Err(AgentBoardError::NotFound(format!(
+                "Database not found: {}",
+                other_path
+            )));
+        }
+
+        // Work on a migrated copy so the other database is never modified
+        let copy = std::env::temp_dir().join(format!("agent-board-merge-{}.db", Uuid::new_v4()));
+        std::fs::copy(&source, &copy)?;
+        // A WAL database keeps recent commits in its -wal file until checkpointed
+        let (source_wal, copy_wal) = (
+            storage::sidecar(&source, "-wal"),
+            storage::sidecar(&copy, "-wal"),
+        );
+        if source_wal.exists() {
+            std::fs::copy(&source_wal, &copy_wal)?;
+        }
+        let result = self.merge_from_copy(&copy).await;
+        let _ = std::fs::remove_file(&copy);
+        let _ = std::fs::remove_file(&copy_wal);
+        result
+    }
+
+    async fn merge_from_copy(&self, copy: &Path) -> Result<MergeReport, AgentBoardError> {
+        drop(Self::connect(copy).await?);
+
+        self.conn
+            .execute(
+                "ATTACH DATABASE ?1 AS other",
+                [copy.to_string_lossy().as_ref()],
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Attach failed: {}", e)))?;
+
+        self.begin().await?;
+        let result = self.merge_attached().await;
+        match result {
+            Ok(_) => self.commit().await?,
+            Err(_) => self.rollback().await,
+        }
+
+        self.conn
+            .execute("DETACH DATABASE other", ())
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Detach failed: {}", e)))?;
+
+        result
+    }
+
+    async fn merge_attached(&self) -> Result<MergeReport, AgentBoardError> {
+        const AGENT_COLUMNS: &str = "id, name, command, working_directory, description, created_at, updated_at, deactivated_at, namespace";
+        const BOARD_COLUMNS: &str = "id, name, description, created_at, updated_at, deleted_at, namespace, group_name, pinned_note";
+
+        let mut report = MergeReport::default();
+
+        // Same row edited on both sides at the same instant: keep local, report it
+        report.conflicts.extend(
+            self.merge_conflicts(
+                "agents",
+                "a.name IS NOT b.name OR a.command IS NOT b.command OR a.description IS NOT b.description",
+                "agent modified on both sides at the same time, kept local version",
+            )
+            .await?,
+        );
+        report.conflicts.extend(
+            self.merge_conflicts(
+                "boards",
+                "a.name IS NOT b.name OR a.description IS NOT b.description OR a.deleted_at IS NOT b.deleted_at",
+                "board modified on both sides at the same time, kept local version",
+            )
+            .await?,
+        );
+        report.conflicts.extend(
+            self.merge_conflicts(
+                "cards",
+                "a.name IS NOT b.name OR a.description IS NOT b.description OR a.status IS NOT b.status OR a.assigned_to IS NOT b.assigned_to OR a.deleted_at IS NOT b.deleted_at",
+                "card modified on both sides at the same time, kept local version",
+            )
+            .await?,
+        );
+
+        // Agent names are unique per namespace, so a different agent with the
+        // same name on the other side cannot be merged automatically
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT o.id, o.name FROM other.agents o JOIN main.agents m ON m.namespace = o.namespace AND m.name = o.name AND m.id != o.id ORDER BY o.name, o.id",
+                (),
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            report.conflicts.push(MergeConflict {
+                entity_id: row.get::<String>(0).unwrap_or_default(),
+                reason: format!(
+                    "agent name '{}' is already used by another local agent",
+                    row.get::<String>(1).unwrap_or_default()
+                ),
+            });
+        }
+
+        let (added, updated) = self.merge_table("agents", AGENT_COLUMNS).await?;
+        report.agents_added = added;
+        report.agents_updated = updated;
+
+        let (added, updated) = self.merge_table("boards", BOARD_COLUMNS).await?;
+        report.boards_added = added;
+        report.boards_updated = updated;
+
+        // Cards taken from the other side bring their tags and checklist along
+        self.conn
+            .execute_batch(
+                "DROP TABLE IF EXISTS temp.merged_cards;
+                 CREATE TEMP TABLE merged_cards AS
+                    SELECT o.id FROM other.cards o LEFT JOIN main.cards m ON m.id = o.id
+                    WHERE m.id IS NULL OR o.updated_at > m.updated_at;",
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Merge failed: {}", e)))?;
+
+        let (added, updated) = self.merge_table("cards", CARD_COLUMNS).await?;
+        report.cards_added = added;
+        report.cards_updated = updated;
+
+        self.conn
+            .execute_batch(
+                "DELETE FROM main.card_tags WHERE card_id IN (SELECT id FROM temp.merged_cards);
+                 INSERT OR IGNORE INTO main.card_tags (card_id, tag)
+                    SELECT card_id, tag FROM other.card_tags WHERE card_id IN (SELECT id FROM temp.merged_cards);
+                 DELETE FROM main.checklist_items WHERE card_id IN (SELECT id FROM temp.merged_cards);
+                 INSERT OR IGNORE INTO main.checklist_items (id, card_id, text, checked, deleted_at, position)
+                    SELECT id, card_id, text, checked, deleted_at, position FROM other.checklist_items WHERE card_id IN (SELECT id FROM temp.merged_cards);
+                 DROP TABLE temp.merged_cards;",
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Merge failed: {}", e)))?;
+
+        // Comment text is immutable, so any comment missing locally is simply added;
+        // a deletion on either side wins
+        self.conn
+            .execute(
+                "UPDATE main.comments SET deleted_at = (SELECT o.deleted_at FROM other.comments o WHERE o.id = main.comments.id)
+                 WHERE deleted_at IS NULL
+                   AND EXISTS (SELECT 1 FROM other.comments o WHERE o.id = main.comments.id AND o.deleted_at IS NOT NULL)",
+                (),
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Merge comments failed: {}", e)))?;
+        report.comments_added = self
+            .conn
+            .execute(
+                "INSERT INTO main.comments (id, card_id, author, text, created_at, kind, meta, deleted_at, blob_hash, spilled)
+                 SELECT o.id, o.card_id, o.author, o.text, o.created_at, o.kind, o.meta, o.deleted_at, o.blob_hash, o.spilled FROM other.comments o
+                 WHERE o.card_id IN (SELECT id FROM main.cards)
+                   AND NOT EXISTS (SELECT 1 FROM main.comments m WHERE m.id = o.id)",
+                (),
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Merge comments failed: {}", e)))?
+            as usize;
+
+        // Blobs are keyed by content, so any the merged comments need can be copied as-is
+        self.conn
+            .execute(
+                "INSERT OR IGNORE INTO main.blobs (hash, content, size, created_at)
+                 SELECT hash, content, size, created_at FROM other.blobs
+                 WHERE hash IN (SELECT blob_hash FROM main.comments)",
+                (),
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Merge blobs failed: {}", e)))?;
+
+        // Reactions are unioned; the same agent reacting on both sides counts once
+        self.conn
+            .execute(
+                "INSERT OR IGNORE INTO main.comment_reactions (comment_id, emoji, author, created_at)
+                 SELECT comment_id, emoji, author, created_at FROM other.comment_reactions
+                 WHERE comment_id IN (SELECT id FROM main.comments)",
+                (),
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Merge reactions failed: {}", e)))?;
+
+        // Dependencies are unioned (cycles spanning both sides are not re-checked)
+        self.conn
+            .execute(
+                "INSERT OR IGNORE INTO main.card_dependencies (card_id, depends_on, created_at)
+                 SELECT card_id, depends_on, created_at FROM other.card_dependencies
+                 WHERE card_id IN (SELECT id FROM main.cards) AND depends_on IN (SELECT id FROM main.cards)",
+                (),
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Merge dependencies failed: {}", e)))?;
+
+        // Labels are matched by board and name: a label created on both sides
+        // keeps its local ID, and the other side's cards are relabelled to it
+        self.conn
+            .execute_batch(
+                "INSERT OR IGNORE INTO main.labels (id, board_id, name, color, created_at)
+                    SELECT id, board_id, name, color, created_at FROM other.labels
+                    WHERE board_id IN (SELECT id FROM main.boards);
+                 INSERT OR IGNORE INTO main.card_labels (card_id, label_id)
+                    SELECT cl.card_id, m.id FROM other.card_labels cl
+                    JOIN other.labels o ON o.id = cl.label_id
+                    JOIN main.labels m ON m.board_id = o.board_id AND m.name = o.name
+                    WHERE cl.card_id IN (SELECT id FROM main.cards);",
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Merge labels failed: {}", e)))?;
+
+        // Settings already set locally win; missing ones are added
+        self.conn
+            .execute(
+                "INSERT OR IGNORE INTO main.board_settings (board_id, key, value, updated_at)
+                 SELECT board_id, key, value, updated_at FROM other.board_settings
+                 WHERE board_id IN (SELECT id FROM main.boards)",
+                (),
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Merge board settings failed: {}", e)))?;
+
+        // Events are append-only too; hooks stay local to each database
+        self.conn
+            .execute(
+                "INSERT OR IGNORE INTO main.events (id, event, board_id, card_id, actor, via, data, created_at)
+                 SELECT id, event, board_id, card_id, actor, via, data, created_at FROM other.events
+                 WHERE card_id IN (SELECT id FROM main.cards)",
+                (),
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Merge events failed: {}", e)))?;
+
+        // Revisions are append-only as well
+        self.conn
+            .execute(
+                "INSERT OR IGNORE INTO main.card_revisions (id, card_id, field, value, replaced_by, replaced_at)
+                 SELECT id, card_id, field, value, replaced_by, replaced_at FROM other.card_revisions
+                 WHERE card_id IN (SELECT id FROM main.cards)",
+                (),
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Merge revisions failed: {}", e)))?;
+
+        // Capabilities only accumulate
+        self.conn
+            .execute(
+                "INSERT OR IGNORE INTO main.agent_capabilities (agent_id, capability)
+                 SELECT agent_id, capability FROM other.agent_capabilities
+                 WHERE agent_id IN (SELECT id FROM main.agents)",
+                (),
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Merge capabilities failed: {}", e)))?;
+        self.conn
+            .execute(
+                "INSERT OR IGNORE INTO main.agent_workdirs (agent_id, path)
+                 SELECT agent_id, path FROM other.agent_workdirs
+                 WHERE agent_id IN (SELECT id FROM main.agents)",
+                (),
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Merge workdirs failed: {}", e)))?;
+
+        // Scopes: a restriction set here is never loosened by the other side
+        self.conn
+            .execute(
+                "INSERT OR IGNORE INTO main.agent_scopes (agent_id, access, board_id)
+                 SELECT agent_id, access, board_id FROM other.agent_scopes
+                 WHERE agent_id IN (SELECT id FROM main.agents)",
+                (),
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Merge scopes failed: {}", e)))?;
+
+        // Review links only accumulate; each side's cards carry their own status
+        self.conn
+            .execute(
+                "INSERT OR IGNORE INTO main.review_cards (card_id, review_card_id, created_at)
+                 SELECT card_id, review_card_id, created_at FROM other.review_cards
+                 WHERE card_id IN (SELECT id FROM main.cards) AND review_card_id IN (SELECT id FROM main.cards)",
+                (),
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Merge review links failed: {}", e)))?;
+
+        // Daily stats: the later snapshot of a day wins
+        self.conn
+            .execute(
+                "INSERT INTO main.board_stats (board_id, day, todo, in_progress, pending_review, done, recorded_at)
+                 SELECT board_id, day, todo, in_progress, pending_review, done, recorded_at FROM other.board_stats
+                 WHERE board_id IN (SELECT id FROM main.boards)
+                 ON CONFLICT (board_id, day) DO UPDATE SET
+                    todo = excluded.todo, in_progress = excluded.in_progress,
+                    pending_review = excluded.pending_review, done = excluded.done,
+                    recorded_at = excluded.recorded_at
+                 WHERE excluded.recorded_at > board_stats.recorded_at",
+                (),
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Merge board stats failed: {}", e)))?;
+
+        // Gates: union both sides; an approval on either side wins
+        self.conn
+            .execute_batch(
+                "INSERT OR IGNORE INTO main.card_gates (id, card_id, requirement, created_by, created_at, approved_by, approved_at)
+                    SELECT id, card_id, requirement, created_by, created_at, approved_by, approved_at FROM other.card_gates
+                    WHERE card_id IN (SELECT id FROM main.cards);
+                 UPDATE main.card_gates SET
+                    approved_by = (SELECT o.approved_by FROM other.card_gates o WHERE o.id = main.card_gates.id),
+                    approved_at = (SELECT o.approved_at FROM other.card_gates o WHERE o.id = main.card_gates.id)
+                    WHERE approved_at IS NULL
+                      AND EXISTS (SELECT 1 FROM other.card_gates o WHERE o.id = main.card_gates.id AND o.approved_at IS NOT NULL);",
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Merge gates failed: {}", e)))?;
+
+        // Assignment history is append-only, so union both sides
+        self.conn
+            .execute(
+                "INSERT OR IGNORE INTO main.assignments (id, card_id, agent_id, previous_agent_id, assigned_by, assigned_at)
+                 SELECT id, card_id, agent_id, previous_agent_id, assigned_by, assigned_at FROM other.assignments
+                 WHERE card_id IN (SELECT id FROM main.cards)",
+                (),
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Merge assignments failed: {}", e)))?;
+
+        Ok(report)
+    }
+
+    /// Insert rows missing locally and overwrite rows that are newer on the
+    /// other side. Returns (added, updated).
+    async fn merge_table(
+        &self,
+        table: &str,
+        columns: &str,
+    ) -> Result<(usize, usize), AgentBoardError> {
+        let updated = self
+            .conn
+            .execute(
+                &format!(
+                    "UPDATE main.{table} SET ({columns}) = (SELECT {columns} FROM other.{table} o WHERE o.id = main.{table}.id)
+                     WHERE id IN (SELECT o.id FROM other.{table} o JOIN main.{table} m ON m.id = o.id WHERE o.updated_at > m.updated_at)"
+                ),
+                (),
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Merge {} failed: {}", table, e)))?;
+
+        let added = self
+            .conn
+            .execute(
+                &format!(
+                    "INSERT OR IGNORE INTO main.{table} ({columns}) SELECT {columns} FROM other.{table} o
+                     WHERE NOT EXISTS (SELECT 1 FROM main.{table} m WHERE m.id = o.id)"
+                ),
+                (),
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Merge {} failed: {}", table, e)))?;
+
+        Ok((added as usize, updated as usize))
+    }
+
+    async fn merge_conflicts(
+        &self,
+        table: &str,
+        differs: &str,
+        reason: &str,
+    ) -> Result<Vec<MergeConflict>, AgentBoardError> {
+        let mut rows = self
+            .conn
+            .query(
+                &format!(
+                    "SELECT a.id FROM main.{table} a JOIN other.{table} b ON a.id = b.id WHERE a.updated_at = b.updated_at AND ({differs})"
+                ),
+                (),
+            )
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Query failed: {}", e)))?;
+
+        let mut conflicts = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| AgentBoardError::General(format!("Row fetch failed: {}", e)))?
+        {
+            conflicts.push(MergeConflict {
+                entity_id: row.get::<String>(0).unwrap_or_default(),
+                reason: reason.to_string(),
+            });
+        }
+        Ok(conflicts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh database in the temp dir, removed (with its sidecars) on drop
+    struct TempDb {
+        db: Database,
+        path: PathBuf,
+    }
+
+    impl Drop for TempDb {
+        fn drop(&mut self) {
+            for suffix in ["", "-wal", "-shm", ".lock"] {
+                let _ = std::fs::remove_file(storage::sidecar(&self.path, suffix));
+            }
+        }
+    }
+
+    async fn temp_db() -> TempDb {
+        let path = std::env::temp_dir().join(format!("agent-board-test-{}.db", Uuid::new_v4()));
+        let identity = Identity {
+            namespace: "default".into(),
+            actor: None,
+            budget: None,
+            via: None,
+        };
+        let db = Database::open(&path, identity, false).await.unwrap();
+        TempDb { db, path }
+    }
+
+    fn ids(cards: &[Card]) -> Vec<String> {
+        cards.iter().map(|c| c.id.clone()).collect()
+    }
+
+    #[tokio::test]
+    async fn cards_with_equal_created_at_list_by_id_across_vacuum() {
+        let t = temp_db().await;
+        let board = t.db.create_board("Ties".into(), None).await.unwrap();
+        for name in ["one", "two", "three", "four"] {
+            t.db.create_card(&board.id, name.into(), None, Status::Todo, None, None, &[])
+                .await
+                .unwrap();
+        }
+        t.db.conn
+            .execute(
+                "UPDATE cards SET created_at = '2026-01-01T00:00:00+00:00'",
+                (),
+            )
+            .await
+            .unwrap();
+
+        let listed = ids(&t
+            .db
+            .list_cards(&board.id, &CardFilter::default())
+            .await
+            .unwrap());
+        let mut sorted = listed.clone();
+        sorted.sort();
+        assert_eq!(listed, sorted);
+
+        t.db.conn.execute("VACUUM", ()).await.unwrap();
+        let again = ids(&t
+            .db
+            .list_cards(&board.id, &CardFilter::default())
+            .await
+            .unwrap());
+        assert_eq!(again, listed);
+    }
+
+    #[tokio::test]
+    async fn checklist_keeps_insertion_order() {
+        let t = temp_db().await;
+        let board = t.db.create_board("Checklist".into(), None).await.unwrap();
+        let card =
+            t.db.create_card(
+                &board.id,
+                "card".into(),
+                None,
+                Status::Todo,
+                None,
+                None,
+                &[],
+            )
+            .await
+            .unwrap();
+        let texts = ["zebra", "apple", "mango"];
+        t.db.add_checklist_items(&card.id, texts.iter().map(|s| s.to_string()).collect())
+            .await
+            .unwrap();
+        t.db.add_checklist_items(&card.id, vec!["banana".into()])
+            .await
+            .unwrap();
+        t.db.conn.execute("VACUUM", ()).await.unwrap();
+
+        let listed: Vec<String> =
+            t.db.get_card(&card.id)
+                .await
+                .unwrap()
+                .checklist
+                .into_iter()
+                .map(|item| item.text)
+                .collect();
+        assert_eq!(listed, ["zebra", "apple", "mango", "banana"]);
+    }
+
+    #[tokio::test]
+    async fn comments_with_equal_created_at_list_by_id() {
+        let t = temp_db().await;
+        let board = t.db.create_board("Comments".into(), None).await.unwrap();
+        let card =
+            t.db.create_card(
+                &board.id,
+                "card".into(),
+                None,
+                Status::Todo,
+                None,
+                None,
+                &[],
+            )
+            .await
+            .unwrap();
+        for text in ["first", "second", "third"] {
+            t.db.add_comment(&card.id, text.into(), None, None, None)
+                .await
+                .unwrap();
+        }
+        t.db.conn
+            .execute(
+                "UPDATE comments SET created_at = '2026-01-01T00:00:00+00:00'",
+                (),
+            )
+            .await
+            .unwrap();
+
+        let listed: Vec<String> =
+            t.db.list_comments(&card.id)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|c| c.id)
+                .collect();
+        let mut sorted = listed.clone();
+        sorted.sort();
+        assert_eq!(listed, sorted);
+    }
+
+    #[tokio::test]
+    async fn column_widths_are_validated_and_read_back() {
+        let t = temp_db().await;
+        t.db.set_db_setting("width.name", Some("60")).await.unwrap();
+        assert!(
+            t.db.set_db_setting("width.color", Some("60"))
+                .await
+                .is_err()
+        );
+        assert!(t.db.set_db_setting("width.board", Some("3")).await.is_err());
+        assert!(
+            t.db.set_db_setting("width.board", Some("wide"))
+                .await
+                .is_err()
+        );
+        let widths = t.db.column_widths().await.unwrap();
+        assert_eq!(widths, HashMap::from([("name".to_string(), 60)]));
+    }
+
+    #[tokio::test]
+    async fn duplicate_is_the_closest_open_card() {
+        let t = temp_db().await;
+        let board = t.db.create_board("Dupes".into(), None).await.unwrap();
+        let done = t.db.create_card(
+            &board.id,
+            "Fix login timeout".into(),
+            None,
+            Status::Done,
+            None,
+            None,
+            &[],
+        );
+        done.await.unwrap();
+        let open = t.db.create_card(
+            &board.id,
+            "Fix the login timeout".into(),
+            None,
+            Status::Todo,
+            None,
+            None,
+            &[],
+        );
+        let open = open.await.unwrap();
+        let (found, score) =
+            t.db.find_duplicate_card(&board.id, "fix login timeout")
+                .await
+                .unwrap()
+                .unwrap();
+        assert_eq!(found.id, open.id);
+        assert!(score < 1.0);
+        assert!(
+            t.db.find_duplicate_card(&board.id, "Write release notes")
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn similar_cards_rank_best_match_first_across_boards() {
+        let t = temp_db().await;
+        let web = t.db.create_board("Web".into(), None).await.unwrap();
+        let api = t.db.create_board("Api".into(), None).await.unwrap();
+        for (board, name) in [
+            (&web, "Login page styling"),
+            (&api, "Fix login timeout"),
+            (&web, "Write release notes"),
+        ] {
+            t.db.create_card(&board.id, name.into(), None, Status::Todo, None, None, &[])
+                .await
+                .unwrap();
+        }
+        let similar =
+            t.db.similar_cards("fix the login timeout", None, false, 0.1, 10)
+                .await
+                .unwrap();
+        let names: Vec<&str> = similar.iter().map(|s| s.card.name.as_str()).collect();
+        assert_eq!(names, ["Fix login timeout", "Login page styling"]);
+        assert_eq!(similar[0].board_name, "Api");
+    }
+
+    async fn card_etag(db: &Database, card_id: &str) -> String {
+        let card = db.get_card(card_id).await.unwrap();
+        Database::card_etag(&card, &db.list_comments(card_id).await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn etags_change_only_with_content() {
+        let t = temp_db().await;
+        let board = t.db.create_board("Etags".into(), None).await.unwrap();
+        let card =
+            t.db.create_card(
+                &board.id,
+                "Task".into(),
+                None,
+                Status::Todo,
+                None,
+                None,
+                &[],
+            )
+            .await
+            .unwrap();
+        let before = card_etag(&t.db, &card.id).await;
+        let board_before = t.db.board_etag(&board).await.unwrap();
+        assert_eq!(before, card_etag(&t.db, &card.id).await);
+
+        t.db.add_comment(&card.id, "progress".into(), None, None, None)
+            .await
+            .unwrap();
+        assert_ne!(before, card_etag(&t.db, &card.id).await);
+        assert_ne!(board_before, t.db.board_etag(&board).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn agent_activity_counts_open_cards_and_current_work() {
+        let t = temp_db().await;
+        let agent =
+            t.db.register_agent(Some("alice".into()), "cmd".into(), "/".into(), None)
+                .await
+                .unwrap();
+        let board = t.db.create_board("Fleet".into(), None).await.unwrap();
+        for (name, status) in [
+            ("Doing", Status::InProgress),
+            ("Queued", Status::Todo),
+            ("Shipped", Status::Done),
+        ] {
+            let card =
+                t.db.create_card(&board.id, name.into(), None, status, None, None, &[])
+                    .await
+                    .unwrap();
+            let update = CardUpdate {
+                session_id: Some(Some(agent.id.clone())),
+                ..Default::default()
+            };
+            t.db.update_card(&card.id, update).await.unwrap();
+        }
+        let agents = t.db.list_agents(false).await.unwrap();
+        let activity = agents[0].activity.clone().unwrap();
+        assert_eq!(activity.open_cards, 2);
+        assert_eq!(activity.current_card_name.as_deref(), Some("Doing"));
+    }
+
+    #[tokio::test]
+    async fn extra_workdirs_are_added_and_removed() {
+        let t = temp_db().await;
+        let agent =
+            t.db.register_agent(None, "cmd".into(), "/repo".into(), None)
+                .await
+                .unwrap();
+        let update = AgentUpdate {
+            add_workdirs: vec!["/repo-wt".into(), "/repo/api".into(), "/repo".into()],
+            ..Default::default()
+        };
+        t.db.update_agent(&agent.id, update).await.unwrap();
+        let agent = t.db.get_agent(&agent.id).await.unwrap();
+        assert_eq!(agent.extra_workdirs, ["/repo-wt", "/repo/api"]);
+
+        let update = AgentUpdate {
+            remove_workdirs: vec!["/repo".into()],
+            ..Default::default()
+        };
+        assert!(t.db.update_agent(&agent.id, update).await.is_err());
+        let update = AgentUpdate {
+            remove_workdirs: vec!["/repo/api".into()],
+            ..Default::default()
+        };
+        t.db.update_agent(&agent.id, update).await.unwrap();
+        let agents = t.db.list_agents(false).await.unwrap();
+        assert_eq!(agents[0].extra_workdirs, ["/repo-wt"]);
+    }
+
+    #[tokio::test]
+    async fn pinned_note_reaches_briefs_until_unpinned() {
+        let t = temp_db().await;
+        let agent =
+            t.db.register_agent(None, "cmd".into(), "/".into(), None)
+                .await
+                .unwrap();
+        let board = t.db.create_board("Release".into(), None).await.unwrap();
+        let card =
+            t.db.create_card(
+                &board.id,
+                "Ship".into(),
+                None,
+                Status::Todo,
+                None,
+                None,
+                &[],
+            )
+            .await
+            .unwrap();
+        let update = CardUpdate {
+            session_id: Some(Some(agent.id.clone())),
+            ..Default::default()
+        };
+        t.db.update_card(&card.id, update).await.unwrap();
+
+        let pin = Some(Some("Freeze: bugfixes only".to_string()));
+        t.db.update_board(&board.id, None, None, None, pin)
+            .await
+            .unwrap();
+        let brief = t.db.brief(&agent.id, None, true).await.unwrap();
+        assert_eq!(brief.pinned_notes.len(), 1);
+        assert_eq!(brief.pinned_notes[0].note, "Freeze: bugfixes only");
+
+        let blank = Some(Some("  ".to_string()));
+        assert!(
+            t.db.update_board(&board.id, None, None, None, blank)
+                .await
+                .is_err()
+        );
+        t.db.update_board(&board.id, None, None, None, Some(None))
+            .await
+            .unwrap();
+        assert!(
+            t.db.get_board(&board.id)
+                .await
+                .unwrap()
+                .pinned_note
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn sweep_reminds_once_per_due_date_and_prunes_old_events() {
+        let t = temp_db().await;
+        let board = t.db.create_board("Ops".into(), None).await.unwrap();
+        let card =
+            t.db.create_card(
+                &board.id,
+                "Renew cert".into(),
+                None,
+                Status::Todo,
+                None,
+                None,
+                &[],
+            )
+            .await
+            .unwrap();
+        let due = |hours| CardUpdate {
+            due_at: Some(Some(Utc::now() + chrono::Duration::hours(hours))),
+            ..Default::default()
+        };
+        t.db.update_card(&card.id, due(48)).await.unwrap();
+        t.db.set_board_setting(&board.id, SETTING_DUE_REMIND_BEFORE, Some("24h"))
+            .await
+            .unwrap();
+        assert!(t.db.remind_due_soon(None, false).await.unwrap().is_empty());
+
+        t.db.update_card(&card.id, due(2)).await.unwrap();
+        assert_eq!(t.db.remind_due_soon(None, false).await.unwrap().len(), 1);
+        assert!(t.db.remind_due_soon(None, false).await.unwrap().is_empty());
+        t.db.update_card(&card.id, due(1)).await.unwrap();
+        assert_eq!(t.db.remind_due_soon(None, true).await.unwrap().len(), 1);
+
+        t.db.conn
+            .execute(
+                "UPDATE events SET created_at = '2020-01-01T00:00:00+00:00'",
+                (),
+            )
+            .await
+            .unwrap();
+        t.db.set_board_setting(&board.id, SETTING_RETENTION_EVENTS, Some("30d"))
+            .await
+            .unwrap();
+        let pruned = t.db.prune_events(Some(&board.id), false).await.unwrap();
+        assert!(pruned[0].events > 0);
+        assert_eq!(t.db.prune_events(None, true).await.unwrap()[0].events, 0);
+    }
+
+    #[tokio::test]
+    async fn notifiers_default_to_review_and_done_and_list_per_board() {
+        let t = temp_db().await;
+        let ops = t.db.create_board("Ops".into(), None).await.unwrap();
+        let web = t.db.create_board("Web".into(), None).await.unwrap();
+        let url = "https://hooks.slack.com/services/T0/B0/x".to_string();
+        assert!(matches!(
+            t.db.create_notifier(
+                &ops.id,
+                NotifierKind::Slack,
+                "hooks.slack.com".into(),
+                vec![]
+            )
+            .await,
+            Err(AgentBoardError::InvalidArgs(_))
+        ));
+
+        let slack =
+            t.db.create_notifier(&ops.id, NotifierKind::Slack, url.clone(), vec![])
+                .await
+                .unwrap();
+        assert_eq!(slack.statuses, [Status::PendingReview, Status::Done]);
+        let discord =
+            t.db.create_notifier(
+                &web.id,
+                NotifierKind::Discord,
+                url,
+                vec![Status::Done, Status::Done],
+            )
+            .await
+            .unwrap();
+        assert_eq!(discord.statuses, [Status::Done]);
+
+        let listed = t.db.list_notifiers(Some(&web.id)).await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].kind, NotifierKind::Discord);
+        assert_eq!(listed[0].statuses, [Status::Done]);
+
+        t.db.delete_notifier(&slack.id).await.unwrap();
+        assert_eq!(t.db.list_notifiers(None).await.unwrap().len(), 1);
+        assert!(matches!(
+            t.db.delete_notifier(&slack.id).await,
+            Err(AgentBoardError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn digest_sorts_cards_by_where_they_ended_up() {
+        let t = temp_db().await;
+        let board = t.db.create_board("Ops".into(), None).await.unwrap();
+        let mut cards = Vec::new();
+        for name in ["Ship", "Docs", "Deploy", "Renew cert", "Backlog"] {
+            let card =
+                t.db.create_card(&board.id, name.into(), None, Status::Todo, None, None, &[])
+                    .await
+                    .unwrap();
+            cards.push(card.id);
+        }
+        let status = |s| CardUpdate {
+            status: Some(s),
+            ..Default::default()
+        };
+        t.db.update_card(&cards[0], status(Status::Done))
+            .await
+            .unwrap();
+        t.db.update_card(&cards[1], status(Status::InProgress))
+            .await
+            .unwrap();
+        t.db.add_dependency(&cards[2], &cards[1]).await.unwrap();
+        let due = |days| CardUpdate {
+            due_at: Some(Some(Utc::now() + chrono::Duration::days(days))),
+            ..Default::default()
+        };
+        t.db.update_card(&cards[3], due(2)).await.unwrap();
+        t.db.update_card(&cards[4], due(30)).await.unwrap();
+
+        let now = Utc::now();
+        let digest =
+            t.db.digest(
+                &board.id,
+                now - chrono::Duration::days(1),
+                now,
+                now + chrono::Duration::days(7),
+            )
+            .await
+            .unwrap();
+        let changed = |changes: &[BoardChange]| -> Vec<String> {
+            changes.iter().map(|c| c.card_id.clone()).collect()
+        };
+        assert_eq!(changed(&digest.completed), [cards[0].clone()]);
+        assert_eq!(changed(&digest.started), [cards[1].clone()]);
+        assert_eq!(digest.blocked.len(), 1);
+        assert_eq!(digest.blocked[0].card.id, cards[2]);
+        assert_eq!(ids(&digest.due_soon), [cards[3].clone()]);
+    }
+
+    #[tokio::test]
+    async fn acting_as_an_agent_records_who_ran_the_command() {
+        let t = temp_db().await;
+        let agent =
+            t.db.register_agent(Some("alice".into()), "cmd".into(), "/".into(), None)
+                .await
+                .unwrap();
+        let supervisor =
+            t.db.register_agent(Some("sup".into()), "cmd".into(), "/".into(), None)
+                .await
+                .unwrap();
+        let board = t.db.create_board("Ops".into(), None).await.unwrap();
+        let card =
+            t.db.create_card(
+                &board.id,
+                "Ship".into(),
+                None,
+                Status::Todo,
+                None,
+                None,
+                &[],
+            )
+            .await
+            .unwrap();
+        let acting_as = |via: &str| Identity {
+            namespace: "default".into(),
+            actor: Some(agent.id.clone()),
+            budget: None,
+            via: Some(via.to_string()),
+        };
+
+        let db = Database::open(&t.path, acting_as("dana"), false)
+            .await
+            .unwrap();
+        db.add_comment(
+            &card.id,
+            "on it".into(),
+            db.actor().map(str::to_string),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let events = t.db.list_events(&EventFilter::default()).await.unwrap();
+        let comment = events
+            .iter()
+            .find(|e| e.event == EventKind::CommentAdded)
+            .unwrap();
+        assert_eq!(comment.actor.as_deref(), Some(agent.id.as_str()));
+        assert_eq!(comment.via.as_deref(), Some("dana"));
+
+        // Only humans may act as an agent, even an unscoped one
+        assert!(matches!(
+            Database::open(&t.path, acting_as(&supervisor.id), false).await,
+            Err(AgentBoardError::PermissionDenied(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn mutation_budget_counts_renames_and_deletes() {
+        let t = temp_db().await;
+        let agent =
+            t.db.register_agent(Some("loop".into()), "cmd".into(), "/".into(), None)
+                .await
+                .unwrap();
+        let board = t.db.create_board("Ops".into(), None).await.unwrap();
+        let mut cards = Vec::new();
+        for name in ["one", "two", "three", "four", "five"] {
+            cards.push(
+                t.db.create_card(&board.id, name.into(), None, Status::Todo, None, None, &[])
+                    .await
+                    .unwrap(),
+            );
+        }
+        let identity = Identity {
+            namespace: "default".into(),
+            actor: Some(agent.id.clone()),
+            budget: Some(MutationBudget {
+                limit: 3,
+                window: chrono::Duration::hours(1),
+            }),
+            via: None,
+        };
+        let db = Database::open(&t.path, identity, false).await.unwrap();
+
+        let rename = CardUpdate {
+            name: Some("renamed".into()),
+            ..Default::default()
+        };
+        db.update_card(&cards[0].id, rename).await.unwrap();
+        db.delete_card(&cards[1].id).await.unwrap();
+        db.delete_card(&cards[2].id).await.unwrap();
+        assert!(matches!(
+            db.delete_card(&cards[3].id).await,
+            Err(AgentBoardError::RateLimited(_))
+        ));
+        assert!(matches!(
+            db.purge_board(&board.id, false, None).await,
+            Err(AgentBoardError::RateLimited(_))
+        ));
+        let left =
+            t.db.list_cards(&board.id, &CardFilter::default())
+                .await
+                .unwrap();
+        assert_eq!(
+            ids(&left),
+            vec![
+                cards[0].id.clone(),
+                cards[3].id.clone(),
+                cards[4].id.clone()
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn review_cards_skip_removed_boards_and_respect_review_gates() {
+        let t = temp_db().await;
+        let work = t.db.create_board("Work".into(), None).await.unwrap();
+        let review = t.db.create_board("Review".into(), None).await.unwrap();
+        t.db.set_board_setting(&work.id, SETTING_REVIEW_BOARD, Some(&review.id))
+            .await
+            .unwrap();
+        let to = |status: Status| CardUpdate {
+            status: Some(status),
+            ..Default::default()
+        };
+
+        // A gate on the review card keeps it open without failing the original
+        let card =
+            t.db.create_card(
+                &work.id,
+                "Ship".into(),
+                None,
+                Status::InProgress,
+                None,
+                None,
+                &[],
+            )
+            .await
+            .unwrap();
+        t.db.update_card(&card.id, to(Status::PendingReview))
+            .await
+            .unwrap();
+        let review_id = t.db.open_review_card_id(&card.id).await.unwrap().unwrap();
+        t.db.create_gate(&review_id, GateRequirement::Human)
+            .await
+            .unwrap();
+        t.db.update_card(&card.id, to(Status::Done)).await.unwrap();
+        assert_eq!(t.db.get_card(&card.id).await.unwrap().status, Status::Done);
+        assert_eq!(
+            t.db.get_card(&review_id).await.unwrap().status,
+            Status::Todo
+        );
+
+        // A policy left pointing at a purged board no longer opens review cards
+        let other =
+            t.db.create_card(
+                &work.id,
+                "Docs".into(),
+                None,
+                Status::InProgress,
+                None,
+                None,
+                &[],
+            )
+            .await
+            .unwrap();
+        t.db.conn
+            .execute(
+                "UPDATE board_settings SET value = 'board_gone' WHERE board_id = ?1 AND key = ?2",
+                [work.id.as_str(), SETTING_REVIEW_BOARD],
+            )
+            .await
+            .unwrap();
+        t.db.update_card(&other.id, to(Status::PendingReview))
+            .await
+            .unwrap();
+        assert_eq!(
+            t.db.get_card(&other.id).await.unwrap().status,
+            Status::PendingReview
+        );
+        assert!(t.db.open_review_card_id(&other.id).await.unwrap().is_none());
+
+        // Deleting the review board drops the policy
+        t.db.set_board_setting(&work.id, SETTING_REVIEW_BOARD, Some(&review.id))
+            .await
+            .unwrap();
+        t.db.delete_board(&review.id, false, None).await.unwrap();
+        assert!(
+            t.db.board_setting(&work.id, SETTING_REVIEW_BOARD)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn agents_cannot_change_settings_that_guard_against_them() {
+        let t = temp_db().await;
+        let agent =
+            t.db.register_agent(Some("loop".into()), "cmd".into(), "/".into(), None)
+                .await
+                .unwrap();
+        let board = t.db.create_board("Ops".into(), None).await.unwrap();
+        t.db.set_board_setting(&board.id, SETTING_DELETE_REQUIRE_CONFIRMATION, Some("true"))
+            .await
+            .unwrap();
+        let identity = Identity {
+            namespace: "default".into(),
+            actor: Some(agent.id.clone()),
+            budget: None,
+            via: None,
+        };
+        let db = Database::open(&t.path, identity, false).await.unwrap();
+
+        for (key, value) in [
+            (SETTING_DELETE_REQUIRE_CONFIRMATION, None),
+            (SETTING_DONE_REQUIRE_PR_URL, Some("false")),
+        ] {
+            assert!(matches!(
+                db.set_board_setting(&board.id, key, value).await,
+                Err(AgentBoardError::PermissionDenied(_))
+            ));
+        }
+        db.set_board_setting(&board.id, SETTING_AUTO_UNBLOCK, Some("true"))
+            .await
+            .unwrap();
+        assert_eq!(
+            t.db.board_setting(&board.id, SETTING_DELETE_REQUIRE_CONFIRMATION)
+                .await
+                .unwrap()
+                .as_deref(),
+            Some("true")
+        );
+    }
+
+    fn rate_limited<T>(result: Result<T, AgentBoardError>) -> bool {
+        matches!(result, Err(AgentBoardError::RateLimited(_)))
+    }
+
+    #[tokio::test]
+    async fn an_agent_over_budget_is_refused_on_every_write() {
+        let t = temp_db().await;
+        let agent =
+            t.db.register_agent(Some("loop".into()), "cmd".into(), "/".into(), None)
+                .await
+                .unwrap();
+        let other =
+            t.db.register_agent(Some("peer".into()), "cmd".into(), "/".into(), None)
+                .await
+                .unwrap();
+        let board = t.db.create_board("Ops".into(), None).await.unwrap();
+        let card =
+            t.db.create_card(&board.id, "One".into(), None, Status::Todo, None, None, &[])
+                .await
+                .unwrap();
+        let blocker =
+            t.db.create_card(&board.id, "Two".into(), None, Status::Todo, None, None, &[])
+                .await
+                .unwrap();
+        t.db.add_dependency(&card.id, &blocker.id).await.unwrap();
+        let item =
+            t.db.add_checklist_items(&card.id, vec!["check".into()])
+                .await
+                .unwrap()
+                .remove(0);
+        let comment =
+            t.db.add_comment(&card.id, "mine".into(), Some(agent.id.clone()), None, None)
+                .await
+                .unwrap();
+        t.db.create_label(&board.id, "bug", "red").await.unwrap();
+        let hook =
+            t.db.create_hook(EventKind::CardCreated, "true".into(), None, None)
+                .await
+                .unwrap();
+        let notifier =
+            t.db.create_notifier(
+                &board.id,
+                NotifierKind::Slack,
+                "https://example.com/hook".into(),
+                Vec::new(),
+            )
+            .await
+            .unwrap();
+
+        let limited = |actor: &str, via: Option<&str>, limit: usize| Identity {
+            namespace: "default".into(),
+            actor: Some(actor.to_string()),
+            budget: Some(MutationBudget {
+                limit,
+                window: chrono::Duration::hours(1),
+            }),
+            via: via.map(str::to_string),
+        };
+        let db = Database::open(&t.path, limited(&agent.id, None, 0), false)
+            .await
+            .unwrap();
+        let rename = || CardUpdate {
+            name: Some("renamed".into()),
+            ..Default::default()
+        };
+        assert!(rate_limited(db.create_board("New".into(), None).await));
+        assert!(rate_limited(
+            db.update_board(&board.id, Some("Renamed".into()), None, None, None)
+                .await
+        ));
+        assert!(rate_limited(
+            db.set_board_setting(&board.id, SETTING_AUTO_UNBLOCK, Some("true"))
+                .await
+        ));
+        assert!(rate_limited(db.star_board(&board.id, false).await));
+        assert!(rate_limited(db.request_removal(&board.id, "delete").await));
+        assert!(rate_limited(db.delete_board(&board.id, false, None).await));
+        assert!(rate_limited(db.purge_board(&board.id, false, None).await));
+        assert!(rate_limited(
+            db.create_card(
+                &board.id,
+                "Three".into(),
+                None,
+                Status::Todo,
+                None,
+                None,
+                &[]
+            )
+            .await
+        ));
+        assert!(rate_limited(db.update_card(&card.id, rename()).await));
+        assert!(rate_limited(
+            db.bulk_tag(&board.id, &CardFilter::default(), &["x".into()], &[], false)
+                .await
+        ));
+        assert!(rate_limited(
+            db.close_card(&card.id, "done".into(), Status::Done).await
+        ));
+        assert!(rate_limited(db.delete_card(&card.id).await));
+        assert!(rate_limited(
+            db.add_checklist_items(&card.id, vec!["more".into()]).await
+        ));
+        assert!(rate_limited(db.check_item(&item.id, true).await));
+        assert!(rate_limited(db.delete_checklist_item(&item.id).await));
+        assert!(rate_limited(db.add_dependency(&blocker.id, &card.id).await));
+        assert!(rate_limited(
+            db.remove_dependency(&card.id, &blocker.id).await
+        ));
+        assert!(rate_limited(
+            db.create_label(&board.id, "ops", "blue").await
+        ));
+        assert!(rate_limited(
+            db.rename_label(&board.id, "bug", "defect").await
+        ));
+        assert!(rate_limited(db.delete_label(&board.id, "bug").await));
+        assert!(rate_limited(
+            db.add_comment(&card.id, "hi".into(), Some(agent.id.clone()), None, None)
+                .await
+        ));
+        assert!(rate_limited(db.react(&comment.id, "👍", false).await));
+        assert!(rate_limited(db.delete_comment(&comment.id).await));
+        assert!(rate_limited(
+            db.create_gate(&card.id, GateRequirement::Human).await
+        ));
+        assert!(rate_limited(
+            db.create_hook(EventKind::CardMoved, "true".into(), None, None)
+                .await
+        ));
+        assert!(rate_limited(db.delete_hook(&hook.id).await));
+        assert!(rate_limited(
+            db.create_notifier(
+                &board.id,
+                NotifierKind::Discord,
+                "https://example.com/d".into(),
+                Vec::new()
+            )
+            .await
+        ));
+        assert!(rate_limited(db.delete_notifier(&notifier.id).await));
+        assert!(rate_limited(
+            db.register_agent(Some("third".into()), "cmd".into(), "/".into(), None)
+                .await
+        ));
+        assert!(rate_limited(
+            db.update_agent(&other.id, AgentUpdate::default()).await
+        ));
+        assert!(rate_limited(
+            db.set_db_setting(DB_SETTING_TIMEZONE, Some("UTC")).await
+        ));
+        assert!(rate_limited(db.gc_blobs().await));
+
+        // The count follows whoever runs the command, not the agent it acts as
+        let db = Database::open(&t.path, limited(&agent.id, Some("dana"), 1), false)
+            .await
+            .unwrap();
+        db.update_card(&card.id, rename()).await.unwrap();
+        let db = Database::open(&t.path, limited(&other.id, Some("dana"), 1), false)
+            .await
+            .unwrap();
+        assert!(rate_limited(db.update_card(&card.id, rename()).await));
+    }
+
+    #[tokio::test]
+    async fn a_failed_apply_batch_rolls_back_the_operations_before_it() {
+        let t = temp_db().await;
+        let board = t.db.create_board("Ops".into(), None).await.unwrap();
+        let card =
+            t.db.create_card(
+                &board.id,
+                "Ship".into(),
+                None,
+                Status::InProgress,
+                None,
+                None,
+                &[],
+            )
+            .await
+            .unwrap();
+        let ops = crate::ops::parse(&format!(
+            r#"[
+                {{"op": "create_card", "board": "{board}", "name": "First"}},
+                {{"op": "done", "card": "{card}", "comment": "shipped"}},
+                {{"op": "update_card", "card": "card_nope", "status": "done"}},
+                {{"op": "create_card", "board": "{board}", "name": "Never"}}
+            ]"#,
+            board = board.id,
+            card = card.id
+        ))
+        .unwrap();
+
+        let (results, error) = crate::ops::run(&t.db, ops, None, false).await;
+        assert!(matches!(error, Some(AgentBoardError::NotFound(_))));
+        assert_eq!(results.len(), 3);
+        assert!(results.iter().all(|r| !r.ok && r.id.is_none()));
+        let cards =
+            t.db.list_cards(&board.id, &CardFilter::default())
+                .await
+                .unwrap();
+        assert_eq!(ids(&cards), vec![card.id.clone()]);
+        assert_eq!(cards[0].status, Status::InProgress);
+        assert!(t.db.list_comments(&card.id).await.unwrap().is_empty());
+
+        // The connection is usable again, outside any transaction
+        t.db.create_card(
+            &board.id,
+            "After".into(),
+            None,
+            Status::Todo,
+            None,
+            None,
+            &[],
+        )
+        .await
+        .unwrap();
+        assert!(!t.db.in_transaction());
+    }
+
+    #[tokio::test]
+    async fn a_board_scoped_agent_only_changes_its_own_board() {
+        let t = temp_db().await;
+        let agent =
+            t.db.register_agent(Some("scoped".into()), "cmd".into(), "/".into(), None)
+                .await
+                .unwrap();
+        let home = t.db.create_board("Home".into(), None).await.unwrap();
+        let away = t.db.create_board("Away".into(), None).await.unwrap();
+        let home_card =
+            t.db.create_card(&home.id, "Mine".into(), None, Status::Todo, None, None, &[])
+                .await
+                .unwrap();
+        let away_card =
+            t.db.create_card(
+                &away.id,
+                "Theirs".into(),
+                None,
+                Status::Todo,
+                None,
+                None,
+                &[],
+            )
+            .await
+            .unwrap();
+        t.db.update_agent(
+            &agent.id,
+            AgentUpdate {
+                scope_board: Some(Some(home.id.clone())),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let identity = Identity {
+            namespace: "default".into(),
+            actor: Some(agent.id.clone()),
+            budget: None,
+            via: None,
+        };
+        let db = Database::open(&t.path, identity, false).await.unwrap();
+        let denied = |result: Result<(), AgentBoardError>| {
+            matches!(result, Err(AgentBoardError::PermissionDenied(_)))
+        };
+        let rename = || CardUpdate {
+            name: Some("renamed".into()),
+            ..Default::default()
+        };
+        let move_to = |status: Status| CardUpdate {
+            status: Some(status),
+            ..Default::default()
+        };
+
+        assert!(denied(
+            db.create_card(&away.id, "New".into(), None, Status::Todo, None, None, &[])
+                .await
+                .map(|_| ())
+        ));
+        assert!(denied(db.update_card(&away_card.id, rename()).await));
+        assert!(denied(
+            db.update_card(&away_card.id, move_to(Status::InProgress))
+                .await
+        ));
+        assert!(denied(db.delete_card(&away_card.id).await));
+        assert!(denied(
+            db.create_board("Other".into(), None).await.map(|_| ())
+        ));
+        let away_after = t.db.get_card(&away_card.id).await.unwrap();
+        assert_eq!(away_after.name, "Theirs");
+        assert_eq!(away_after.status, Status::Todo);
+
+        db.create_card(&home.id, "New".into(), None, Status::Todo, None, None, &[])
+            .await
+            .unwrap();
+        db.update_card(&home_card.id, rename()).await.unwrap();
+        db.update_card(&home_card.id, move_to(Status::InProgress))
+            .await
+            .unwrap();
+        db.delete_card(&home_card.id).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn every_kind_of_write_takes_the_single_writer_lock() {
+        let t = temp_db().await;
+        t.db.set_db_setting(DB_SETTING_SINGLE_WRITER, Some("true"))
+            .await
+            .unwrap();
+        let board = t.db.create_board("Locked".into(), None).await.unwrap();
+        let card =
+            t.db.create_card(
+                &board.id,
+                "Ship".into(),
+                None,
+                Status::Todo,
+                None,
+                None,
+                &[],
+            )
+            .await
+            .unwrap();
+        let lock_path = storage::sidecar(&t.path, ".lock");
+        let lock_is_held = || {
+            std::fs::File::open(&lock_path)
+                .is_ok_and(|file| matches!(file.try_lock(), Err(std::fs::TryLockError::WouldBlock)))
+        };
+        let reopen = || async {
+            let identity = Identity {
+                namespace: "default".into(),
+                actor: None,
+                budget: None,
+                via: None,
+            };
+            Database::open(&t.path, identity, false).await.unwrap()
+        };
+
+        // t.db read the setting before it was on, so it never locks
+        assert!(!lock_is_held());
+
+        let db = reopen().await;
+        db.star_board(&board.id, false).await.unwrap();
+        assert!(lock_is_held());
+        drop(db);
+        assert!(!lock_is_held());
+
+        let db = reopen().await;
+        db.touch_board(&board.id).await.unwrap();
+        assert!(lock_is_held());
+        drop(db);
+
+        let db = reopen().await;
+        db.request_removal(&board.id, "delete").await.unwrap();
+        assert!(lock_is_held());
+        drop(db);
+
+        let db = reopen().await;
+        db.create_gate(&card.id, GateRequirement::Human)
+            .await
+            .unwrap();
+        drop(db);
+        let db = reopen().await;
+        db.approve_gates(&card.id, "dana").await.unwrap();
+        assert!(lock_is_held());
+        drop(db);
+        assert!(!lock_is_held());
+    }
+
+    #[tokio::test]
+    async fn an_agent_removal_token_is_single_use_and_expires() {
+        let t = temp_db().await;
+        let agent =
+            t.db.register_agent(Some("loop".into()), "cmd".into(), "/".into(), None)
+                .await
+                .unwrap();
+        let kept = t.db.create_board("Kept".into(), None).await.unwrap();
+        let doomed = t.db.create_board("Doomed".into(), None).await.unwrap();
+        for board in [&kept, &doomed] {
+            t.db.set_board_setting(&board.id, SETTING_DELETE_REQUIRE_CONFIRMATION, Some("true"))
+                .await
+                .unwrap();
+        }
+        let identity = Identity {
+            namespace: "default".into(),
+            actor: Some(agent.id.clone()),
+            budget: None,
+            via: None,
+        };
+        let db = Database::open(&t.path, identity, false).await.unwrap();
+        let refused = |result: Result<(), AgentBoardError>| {
+            matches!(result, Err(AgentBoardError::PolicyViolation(_)))
+        };
+
+        // --yes is for humans, and the policy itself is off limits to agents
+        assert!(refused(db.delete_board(&kept.id, true, None).await));
+        assert!(matches!(
+            db.set_board_setting(&kept.id, SETTING_DELETE_REQUIRE_CONFIRMATION, None)
+                .await,
+            Err(AgentBoardError::PermissionDenied(_))
+        ));
+
+        // A token only covers the board and action it was issued for
+        let token = db.request_removal(&kept.id, "delete").await.unwrap().token;
+        assert!(refused(db.purge_board(&kept.id, false, Some(&token)).await));
+        assert!(refused(
+            db.delete_board(&doomed.id, false, Some(&token)).await
+        ));
+
+        // Expired tokens are refused
+        t.db.conn
+            .execute(
+                "UPDATE removal_tokens SET expires_at = ?1 WHERE token = ?2",
+                [
+                    (Utc::now() - chrono::Duration::seconds(1))
+                        .to_rfc3339()
+                        .as_str(),
+                    token.as_str(),
+                ],
+            )
+            .await
+            .unwrap();
+        assert!(refused(
+            db.delete_board(&kept.id, false, Some(&token)).await
+        ));
+        assert!(t.db.get_board(&kept.id).await.is_ok());
+
+        // A fresh token works once
+        let token = db
+            .request_removal(&doomed.id, "delete")
+            .await
+            .unwrap()
+            .token;
+        db.delete_board(&doomed.id, false, Some(&token))
+            .await
+            .unwrap();
+        assert!(t.db.get_board(&doomed.id).await.is_err());
+        assert!(refused(
+            db.purge_board(&doomed.id, false, Some(&token)).await
+        ));
+
+        // A removal that fails leaves its token for another try
+        let token = db.request_removal(&doomed.id, "purge").await.unwrap().token;
+        t.db.conn
+            .execute_batch(
+                "CREATE TRIGGER keep_boards BEFORE DELETE ON boards
+                 BEGIN SELECT RAISE(ABORT, 'kept'); END",
+            )
+            .await
+            .unwrap();
+        assert!(
+            db.purge_board(&doomed.id, false, Some(&token))
+                .await
+                .is_err()
+        );
+        t.db.conn
+            .execute_batch("DROP TRIGGER keep_boards")
+            .await
+            .unwrap();
+        db.purge_board(&doomed.id, false, Some(&token))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn merging_a_diverged_copy_remaps_labels_and_adds_nothing_twice() {
+        let t = temp_db().await;
+        let board = t.db.create_board("Shared".into(), None).await.unwrap();
+        let card = |name: &str| {
+            let (db, board) = (&t.db, board.id.clone());
+            let name = name.to_string();
+            async move {
+                db.create_card(&board, name, None, Status::Todo, None, None, &[])
+                    .await
+                    .unwrap()
+            }
+        };
+        let first = card("first").await;
+        let second = card("second").await;
+        t.db.create_label(&board.id, "bug", "red").await.unwrap();
+        t.db.add_comment(&first.id, "before the copy".into(), None, None, None)
+            .await
+            .unwrap();
+
+        let other_path =
+            std::env::temp_dir().join(format!("agent-board-test-{}.db", Uuid::new_v4()));
+        t.db.conn
+            .execute("VACUUM INTO ?1", [other_path.to_string_lossy().as_ref()])
+            .await
+            .unwrap();
+        let other = TempDb {
+            db: Database::open(
+                &other_path,
+                Identity {
+                    namespace: "default".into(),
+                    actor: None,
+                    budget: None,
+                    via: None,
+                },
+                false,
+            )
+            .await
+            .unwrap(),
+            path: other_path,
+        };
+        let labels = |names: &[&str]| CardUpdate {
+            add_labels: names.iter().map(|n| n.to_string()).collect(),
+            ..Default::default()
+        };
+
+        // Both sides create "urgent" and the same dependency independently
+        let local_urgent = t.db.create_label(&board.id, "urgent", "red").await.unwrap();
+        t.db.update_card(&first.id, labels(&["urgent"]))
+            .await
+            .unwrap();
+        t.db.add_dependency(&second.id, &first.id).await.unwrap();
+        other
+            .db
+            .create_label(&board.id, "urgent", "yellow")
+            .await
+            .unwrap();
+        other
+            .db
+            .update_card(&second.id, labels(&["bug", "urgent"]))
+            .await
+            .unwrap();
+        other
+            .db
+            .add_dependency(&second.id, &first.id)
+            .await
+            .unwrap();
+        let third = other
+            .db
+            .create_card(
+                &board.id,
+                "third".into(),
+                None,
+                Status::Todo,
+                None,
+                None,
+                &[],
+            )
+            .await
+            .unwrap();
+        other
+            .db
+            .update_card(&third.id, labels(&["urgent"]))
+            .await
+            .unwrap();
+        other
+            .db
+            .add_dependency(&third.id, &second.id)
+            .await
+            .unwrap();
+        other
+            .db
+            .add_comment(&first.id, "after the copy".into(), None, None, None)
+            .await
+            .unwrap();
+
+        let other_path = other.path.to_string_lossy().to_string();
+        let report = t.db.merge_from(&other_path).await.unwrap();
+        assert_eq!(report.cards_added, 1);
+        assert_eq!(report.comments_added, 1);
+
+        let names: Vec<String> =
+            t.db.list_labels(&board.id)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|l| l.name)
+                .collect();
+        assert_eq!(names, ["bug", "urgent"]);
+        let label_ids = |card: Card| {
+            let mut ids: Vec<(String, String)> =
+                card.labels.into_iter().map(|l| (l.name, l.id)).collect();
+            ids.sort();
+            ids
+        };
+        let bug = t.db.list_labels(&board.id).await.unwrap()[0].id.clone();
+        let urgent = ("urgent".to_string(), local_urgent.id.clone());
+        assert_eq!(
+            label_ids(t.db.get_card(&second.id).await.unwrap()),
+            [("bug".to_string(), bug), urgent.clone()]
+        );
+        assert_eq!(label_ids(t.db.get_card(&third.id).await.unwrap()), [urgent]);
+        assert_eq!(
+            t.db.get_card(&second.id).await.unwrap().depends_on,
+            [first.id.as_str()]
+        );
+        assert_eq!(
+            t.db.get_card(&third.id).await.unwrap().depends_on,
+            [second.id.as_str()]
+        );
+        assert_eq!(t.db.list_comments(&first.id).await.unwrap().len(), 2);
+
+        // Merging again changes nothing
+        let report = t.db.merge_from(&other_path).await.unwrap();
+        assert_eq!((report.cards_added, report.comments_added), (0, 0));
+        assert_eq!(t.db.list_comments(&first.id).await.unwrap().len(), 2);
+        assert_eq!(t.db.list_labels(&board.id).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn a_pending_gate_holds_a_card_until_a_human_approves_it() {
+        let t = temp_db().await;
+        let board = t.db.create_board("Gated".into(), None).await.unwrap();
+        let card =
+            t.db.create_card(
+                &board.id,
+                "Ship".into(),
+                None,
+                Status::PendingReview,
+                None,
+                None,
+                &[],
+            )
+            .await
+            .unwrap();
+        let to = |status: Status| CardUpdate {
+            status: Some(status),
+            ..Default::default()
+        };
+        let blocked = |result: Result<(), AgentBoardError>| matches!(result, Err(AgentBoardError::PolicyViolation(m)) if m.contains("awaiting human approval"));
+
+        // Nothing to approve before a gate exists
+        assert!(matches!(
+            t.db.approve_gates(&card.id, "dana").await,
+            Err(AgentBoardError::NotFound(_))
+        ));
+
+        let first =
+            t.db.create_gate(&card.id, GateRequirement::Human)
+                .await
+                .unwrap();
+        let second =
+            t.db.create_gate(&card.id, GateRequirement::Human)
+                .await
+                .unwrap();
+        assert!(blocked(t.db.update_card(&card.id, to(Status::Done)).await));
+        assert_eq!(
+            t.db.get_card(&card.id).await.unwrap().status,
+            Status::PendingReview
+        );
+
+        // Agents can't sign off
+        let agent =
+            t.db.register_agent(Some("loop".into()), "cmd".into(), "/".into(), None)
+                .await
+                .unwrap();
+        let identity = Identity {
+            namespace: "default".into(),
+            actor: Some(agent.id),
+            budget: None,
+            via: None,
+        };
+        let db = Database::open(&t.path, identity, false).await.unwrap();
+        assert!(matches!(
+            db.approve_gates(&card.id, "loop").await,
+            Err(AgentBoardError::PermissionDenied(_))
+        ));
+        assert!(blocked(db.update_card(&card.id, to(Status::Done)).await));
+
+        // One approval clears every open gate, listed oldest first
+        let approved = t.db.approve_gates(&card.id, "dana").await.unwrap();
+        let gate_ids: Vec<&str> = approved.iter().map(|g| g.id.as_str()).collect();
+        assert_eq!(gate_ids, [first.id.as_str(), second.id.as_str()]);
+        assert!(
+            approved
+                .iter()
+                .all(|g| g.approved_by.as_deref() == Some("dana"))
+        );
+        assert!(matches!(
+            t.db.approve_gates(&card.id, "dana").await,
+            Err(AgentBoardError::NotFound(_))
+        ));
+
+        // A gate added after the approval needs its own
+        let third =
+            t.db.create_gate(&card.id, GateRequirement::Human)
+                .await
+                .unwrap();
+        assert!(blocked(db.update_card(&card.id, to(Status::Done)).await));
+        let approved = t.db.approve_gates(&card.id, "erin").await.unwrap();
+        assert_eq!(approved.len(), 1);
+        assert_eq!(approved[0].id, third.id);
+        let gates = t.db.list_gates(&card.id).await.unwrap();
+        let approvers: Vec<Option<&str>> = gates.iter().map(|g| g.approved_by.as_deref()).collect();
+        assert_eq!(approvers, [Some("dana"), Some("dana"), Some("erin")]);
+
+        db.update_card(&card.id, to(Status::Done)).await.unwrap();
+        assert_eq!(t.db.get_card(&card.id).await.unwrap().status, Status::Done);
+        assert!(matches!(
+            t.db.create_gate(&card.id, GateRequirement::Human).await,
+            Err(AgentBoardError::InvalidArgs(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn a_database_from_before_any_migration_upgrades_in_place() {
+        let path = std::env::temp_dir().join(format!("agent-board-test-{}.db", Uuid::new_v4()));
+        {
+            let legacy = Builder::new_local(&path).build().await.unwrap();
+            let conn = legacy.connect().unwrap();
+            conn.execute_batch(include_str!("../tests/fixtures/schema_v0.sql"))
+                .await
+                .unwrap();
+        }
+        let identity = |namespace: &str| Identity {
+            namespace: namespace.into(),
+            actor: None,
+            budget: None,
+            via: None,
+        };
+        let t = TempDb {
+            db: Database::open(&path, identity("default"), false)
+                .await
+                .unwrap(),
+            path,
+        };
+
+        let mut rows = t.db.conn.query("PRAGMA user_version", ()).await.unwrap();
+        let version = rows.next().await.unwrap().unwrap().get::<i64>(0).unwrap();
+        assert_eq!(version as usize, MIGRATIONS.len());
+        drop(rows);
+        for (table, column) in [
+            ("boards", "namespace"),
+            ("boards", "group_name"),
+            ("agents", "namespace"),
+            ("cards", "due_at"),
+            ("cards", "pr_url"),
+            ("cards", "plan_key"),
+            ("comments", "kind"),
+            ("comments", "deleted_at"),
+            ("checklist_items", "position"),
+            ("events", "via"),
+        ] {
+            let mut rows =
+                t.db.conn
+                    .query(
+                        &format!("SELECT 1 FROM pragma_table_info('{table}') WHERE name = ?1"),
+                        [column],
+                    )
+                    .await
+                    .unwrap();
+            assert!(
+                rows.next().await.unwrap().is_some(),
+                "{table}.{column} is missing"
+            );
+        }
+
+        // Existing rows land in the default namespace
+        let boards = t.db.list_boards(&BoardFilter::default()).await.unwrap();
+        assert_eq!(boards.len(), 1);
+        assert_eq!(boards[0].name, "Legacy");
+        let agent = t.db.get_agent("agent_000000000001").await.unwrap();
+        assert_eq!(agent.namespace, "default");
+
+        // Checklist positions are backfilled in insertion order, not id order
+        let card = t.db.get_card("card_000000000001").await.unwrap();
+        let items: Vec<(&str, bool)> = card
+            .checklist
+            .iter()
+            .map(|i| (i.text.as_str(), i.checked))
+            .collect();
+        assert_eq!(
+            items,
+            [("first", true), ("second", false), ("third", false)]
+        );
+        assert_eq!(card.tags, ["backend"]);
+        assert_eq!(card.assigned_to.as_deref(), Some("agent_000000000001"));
+        assert_eq!((card.due_at, card.pr_url), (None, None));
+        let comments = t.db.list_comments(&card.id).await.unwrap();
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].kind, None);
+
+        // Agent names are now unique per namespace rather than globally
+        let other = Database::open(&t.path, identity("other"), false)
+            .await
+            .unwrap();
+        assert!(
+            other
+                .list_boards(&BoardFilter::default())
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        other
+            .register_agent(Some("loop".into()), "cmd".into(), "/".into(), None)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn a_card_is_created_with_its_labels_or_not_at_all() {
+        let t = temp_db().await;
+        let board = t.db.create_board("Labelled".into(), None).await.unwrap();
+        for name in ["bug", "urgent"] {
+            t.db.create_label(&board.id, name, "red").await.unwrap();
+        }
+
+        let labels = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        assert!(matches!(
+            t.db.create_card(
+                &board.id,
+                "Typo".into(),
+                None,
+                Status::Todo,
+                None,
+                None,
+                &labels(&["bug", "urgnet"]),
+            )
+            .await,
+            Err(AgentBoardError::NotFound(_))
+        ));
+        let cards =
+            t.db.list_cards(&board.id, &CardFilter::default())
+                .await
+                .unwrap();
+        assert!(cards.is_empty());
+
+        let card =
+            t.db.create_card(
+                &board.id,
+                "Fix".into(),
+                None,
+                Status::Todo,
+                None,
+                None,
+                &labels(&["urgent", "bug"]),
+            )
+            .await
+            .unwrap();
+        let mut names: Vec<String> = card.labels.into_iter().map(|l| l.name).collect();
+        names.sort();
+        assert_eq!(names, ["bug", "urgent"]);
+
+        // One event for the new card, not a create followed by an update
+        let filter = EventFilter {
+            card_id: Some(card.id.clone()),
+            ..Default::default()
+        };
+        let events = t.db.list_events(&filter).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event, EventKind::CardCreated);
     }
 }
@@ -0,0 +1,197 @@
+//! `digest`: a board summary for stakeholders who don't use the CLI.
+//!
+//! `--format email` renders a complete message (Subject and MIME headers, then
+//! an HTML body with inline styles, since mail clients drop stylesheets).
+//! `--send` pipes that message to the `digest.command` database setting with
+//! `sh -c`, so delivery goes through whatever the host already uses:
+//! `sendmail -t`, `msmtp -t`, or a script that talks SMTP. Agents appear by
+//! name rather than ID.
+
+use crate::AgentBoardError;
+use crate::models::{BoardChange, Card, Digest};
+use crate::output::agent_name;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// `Board: 2 completed, 1 started, 0 blocked, 3 due soon`
+pub fn subject(digest: &Digest) -> String {
+    format!(
+        "{}: {} completed, {} started, {} blocked, {} due soon",
+        digest.board_name,
+        digest.completed.len(),
+        digest.started.len(),
+        digest.blocked.len(),
+        digest.due_soon.len()
+    )
+}
+
+/// The whole message, addressed to `to` when given
+pub fn email(digest: &Digest, to: &[String]) -> String {
+    let mut message = String::new();
+    if !to.is_empty() {
+        message.push_str(&format!("To: {}\r\n", to.join(", ")));
+    }
+    message.push_str(&format!("Subject: {}\r\n", encode_header(&subject(digest))));
+    message.push_str("MIME-Version: 1.0\r\n");
+    message.push_str("Content-Type: text/html; charset=utf-8\r\n\r\n");
+    message.push_str(&html(digest));
+    message
+}
+
+fn html(digest: &Digest) -> String {
+    let mut body = String::from(
+        "<!DOCTYPE html>\n<html>\n<body style=\"font-family: -apple-system, Helvetica, Arial, sans-serif; color: #222;\">\n",
+    );
+    body.push_str(&format!("<h2>{}</h2>\n", escape(&digest.board_name)));
+    body.push_str(&format!(
+        "<p style=\"color: #666;\">{} to {} UTC</p>\n",
+        digest.since.format("%Y-%m-%d %H:%M"),
+        digest.until.format("%Y-%m-%d %H:%M")
+    ));
+
+    let changes = |items: &[BoardChange]| -> Vec<String> {
+        items
+            .iter()
+            .map(|c| match &c.by {
+                Some(by) => format!(
+                    "<b>{}</b> &middot; {}",
+                    escape(&c.card_name),
+                    escape(agent_name(by))
+                ),
+                None => format!("<b>{}</b>", escape(&c.card_name)),
+            })
+            .collect()
+    };
+    section(&mut body, "Completed", &changes(&digest.completed));
+    section(&mut body, "Started", &changes(&digest.started));
+    let blocked: Vec<String> = digest
+        .blocked
+        .iter()
+        .map(|b| {
+            let mut reasons = Vec::new();
+            // Card IDs mean nothing to readers outside the CLI
+            match b.waiting_on.len() {
+                0 => {}
+                1 => reasons.push("waiting on 1 unfinished card".to_string()),
+                n => reasons.push(format!("waiting on {} unfinished cards", n)),
+            }
+            if b.tagged_blocked {
+                reasons.push("tagged blocked".to_string());
+            }
+            format!(
+                "<b>{}</b>{} &middot; {}",
+                escape(&b.card.name),
+                owner(&b.card),
+                escape(&reasons.join("; "))
+            )
+        })
+        .collect();
+    section(&mut body, "Blocked", &blocked);
+    let due: Vec<String> = digest
+        .due_soon
+        .iter()
+        .map(|c| {
+            let due_at = c.due_at.unwrap_or(digest.until);
+            let overdue = if due_at <= digest.until {
+                " <span style=\"color: #c0392b;\">overdue</span>"
+            } else {
+                ""
+            };
+            format!(
+                "<b>{}</b>{} &middot; due {}{}",
+                escape(&c.name),
+                owner(c),
+                due_at.format("%Y-%m-%d %H:%M"),
+                overdue
+            )
+        })
+        .collect();
+    section(
+        &mut body,
+        &format!("Due by {}", digest.due_before.format("%Y-%m-%d")),
+        &due,
+    );
+
+    body.push_str(&format!(
+        "<p style=\"color: #999; font-size: 12px;\">Generated by agent-board for board {}</p>\n</body>\n</html>\n",
+        escape(&digest.board_id)
+    ));
+    body
+}
+
+fn owner(card: &Card) -> String {
+    match &card.assigned_to {
+        Some(agent) => format!(" ({})", escape(agent_name(agent))),
+        None => String::new(),
+    }
+}
+
+fn section(body: &mut String, title: &str, items: &[String]) {
+    body.push_str(&format!("<h3>{} ({})</h3>\n", escape(title), items.len()));
+    if items.is_empty() {
+        body.push_str("<p style=\"color: #666;\">None.</p>\n");
+        return;
+    }
+    body.push_str("<ul>\n");
+    for item in items {
+        body.push_str(&format!("<li>{}</li>\n", item));
+    }
+    body.push_str("</ul>\n");
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// RFC 2047 Q-encoding for headers that aren't plain ASCII
+fn encode_header(text: &str) -> String {
+    if text.is_ascii() {
+        return text.to_string();
+    }
+    let encoded: String = text
+        .bytes()
+        .map(|b| match b {
+            b' ' => "_".to_string(),
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'.' | b',' | b':' => (b as char).to_string(),
+            _ => format!("={:02X}", b),
+        })
+        .collect();
+    format!("=?UTF-8?Q?{}?=", encoded)
+}
+
+/// Pipe `message` to the mail command and wait for it to finish
+pub fn send(command: &str, message: &str) -> Result<(), AgentBoardError> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::from(std::io::stderr()))
+        .spawn()
+        .map_err(|e| AgentBoardError::General(format!("Mail command failed to start: {}", e)))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(message.as_bytes())?;
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(AgentBoardError::General(format!(
+            "Mail command exited with {}",
+            status
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_html_and_encodes_non_ascii_subjects() {
+        assert_eq!(escape("<a & \"b\">"), "&lt;a &amp; &quot;b&quot;&gt;");
+        assert_eq!(encode_header("Ops: 1 done"), "Ops: 1 done");
+        assert_eq!(encode_header("Café: 1"), "=?UTF-8?Q?Caf=C3=A9:_1?=");
+    }
+}
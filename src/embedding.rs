@@ -0,0 +1,94 @@
+//! Card embeddings for `embed` and `search --semantic` (the `semantic`
+//! cargo feature).
+//!
+//! An embedding is a JSON array of numbers, read from a file or printed by
+//! the `embedding.command` database setting: that command runs with
+//! `sh -c`, gets the text to embed on stdin, and prints the vector on stdout
+//! (a bare array, or an object with an `embedding` array). Vectors are
+//! stored with libSQL's `vector32()` and compared by cosine distance.
+
+use crate::AgentBoardError;
+use crate::models::Card;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// What gets embedded for a card: its name, then its description
+pub fn card_text(card: &Card) -> String {
+    match card.description.as_deref() {
+        Some(desc) if !desc.is_empty() => format!("{}\n\n{}", card.name, desc),
+        _ => card.name.clone(),
+    }
+}
+
+/// Parse `[0.1, ...]` or `{"embedding": [0.1, ...]}`
+pub fn parse(text: &str) -> Result<Vec<f32>, AgentBoardError> {
+    let value: serde_json::Value = serde_json::from_str(text.trim())
+        .map_err(|e| AgentBoardError::InvalidArgs(format!("Embedding is not JSON: {}", e)))?;
+    let array = match &value {
+        serde_json::Value::Object(map) => map.get("embedding"),
+        _ => Some(&value),
+    }
+    .and_then(|v| v.as_array());
+    let vector: Option<Vec<f32>> = array.map(|items| {
+        items
+            .iter()
+            .filter_map(|n| n.as_f64().map(|n| n as f32))
+            .collect()
+    });
+    match vector {
+        Some(v) if !v.is_empty() && Some(v.len()) == array.map(Vec::len) => Ok(v),
+        _ => Err(AgentBoardError::InvalidArgs(
+            "Embedding must be a non-empty JSON array of numbers".to_string(),
+        )),
+    }
+}
+
+pub fn read_file(path: &Path) -> Result<Vec<f32>, AgentBoardError> {
+    let text = std::fs::read_to_string(path).map_err(|e| {
+        AgentBoardError::InvalidArgs(format!("Cannot read {}: {}", path.display(), e))
+    })?;
+    parse(&text)
+}
+
+/// Run the embedding command on `text` and parse what it prints
+pub fn compute(command: &str, text: &str) -> Result<Vec<f32>, AgentBoardError> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|e| {
+            AgentBoardError::General(format!("Embedding command failed to start: {}", e))
+        })?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(AgentBoardError::General(format!(
+            "Embedding command exited with {}",
+            output.status
+        )));
+    }
+    parse(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// The vector as the JSON text `vector32()` takes
+pub fn to_json(vector: &[f32]) -> String {
+    serde_json::to_string(vector).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_bare_and_wrapped_vectors() {
+        assert_eq!(parse("[1, 0.5]\n").unwrap(), vec![1.0, 0.5]);
+        assert_eq!(parse(r#"{"embedding": [2]}"#).unwrap(), vec![2.0]);
+        assert!(parse("[]").is_err());
+        assert!(parse(r#"[1, "two"]"#).is_err());
+    }
+}
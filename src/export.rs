@@ -0,0 +1,116 @@
+//! Renderers for `export` subcommands.
+
+use crate::models::{Board, Card, Status};
+use crate::output::format_minutes;
+use chrono::{DateTime, Duration, Utc};
+use std::fmt::Write;
+
+const GANTT_TIME: &str = "%Y-%m-%d %H:%M";
+
+/// Mermaid gantt chart with one section per status. A card needs a due date
+/// or an estimate to be placed on the timeline:
+/// - due + estimate: ends at the due date, starts `estimate` earlier
+/// - due only: runs from creation to the due date
+/// - estimate only: starts after its dependencies (or at creation)
+///
+/// Done cards are marked `done`, in-progress ones `active`, and open cards
+/// past their due date `crit`. Cards with neither field are listed in a comment.
+pub fn render_gantt(board: &Board, cards: &[Card]) -> String {
+    let now = Utc::now();
+    let mut out = String::from("gantt\n");
+    let _ = writeln!(out, "  title {}", label(&board.name));
+    let _ = writeln!(out, "  dateFormat YYYY-MM-DD HH:mm");
+    let _ = writeln!(out, "  axisFormat %m-%d");
+
+    let scheduled: Vec<&Card> = cards
+        .iter()
+        .filter(|c| c.due_at.is_some() || c.estimate_minutes.is_some())
+        .collect();
+    let on_chart = |id: &String| scheduled.iter().any(|c| &c.id == id);
+
+    for (status, section) in [
+        (Status::Todo, "Todo"),
+        (Status::InProgress, "In Progress"),
+        (Status::PendingReview, "Pending Review"),
+        (Status::Done, "Done"),
+    ] {
+        let in_section: Vec<&&Card> = scheduled.iter().filter(|c| c.status == status).collect();
+        if in_section.is_empty() {
+            continue;
+        }
+        let _ = writeln!(out, "  section {}", section);
+        for card in in_section {
+            let mut tags = Vec::new();
+            match card.status {
+                Status::Done => tags.push("done"),
+                Status::InProgress => tags.push("active"),
+                _ => {}
+            }
+            if card.status != Status::Done && card.due_at.is_some_and(|due| due < now) {
+                tags.push("crit");
+            }
+            tags.push(&card.id);
+
+            let timing = match (card.due_at, card.estimate_minutes) {
+                (Some(due), Some(minutes)) => {
+                    let start = due - Duration::minutes(minutes);
+                    format!("{}, {}", time(start), time(due))
+                }
+                (Some(due), None) => {
+                    // Keep at least a day visible when created after the due date
+                    let start = card.created_at.min(due - Duration::days(1));
+                    format!("{}, {}", time(start), time(due))
+                }
+                (None, Some(minutes)) => {
+                    let after: Vec<&str> = card
+                        .depends_on
+                        .iter()
+                        .filter(|id| on_chart(id))
+                        .map(String::as_str)
+                        .collect();
+                    let start = if after.is_empty() {
+                        time(card.created_at)
+                    } else {
+                        format!("after {}", after.join(" "))
+                    };
+                    format!("{}, {}", start, format_minutes(minutes))
+                }
+                (None, None) => unreachable!("filtered above"),
+            };
+            let _ = writeln!(
+                out,
+                "  {} :{}, {}",
+                label(&card.name),
+                tags.join(", "),
+                timing
+            );
+        }
+    }
+
+    let unscheduled: Vec<&str> = cards
+        .iter()
+        .filter(|c| c.due_at.is_none() && c.estimate_minutes.is_none())
+        .map(|c| c.id.as_str())
+        .collect();
+    if !unscheduled.is_empty() {
+        let _ = writeln!(
+            out,
+            "  %% no due date or estimate: {}",
+            unscheduled.join(", ")
+        );
+    }
+    out
+}
+
+fn time(ts: DateTime<Utc>) -> String {
+    ts.format(GANTT_TIME).to_string()
+}
+
+/// Mermaid gantt uses ':' to separate a task name from its data and '#'/';'
+/// as comment/statement markers, so strip them from labels
+fn label(name: &str) -> String {
+    name.replace([':', '#', ';'], " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,97 @@
+//! Card dependency graph rendering for `graph`.
+//!
+//! Edges point from a card to the cards waiting on it, so the graph reads in
+//! the order work has to happen.
+
+use crate::models::{Board, Card, Status};
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// (blocker, dependent) pairs whose blocker is also in the graph, skipping
+/// edges to deleted cards
+fn edges(cards: &[Card]) -> Vec<(&str, &str)> {
+    let ids: HashSet<&str> = cards.iter().map(|c| c.id.as_str()).collect();
+    cards
+        .iter()
+        .flat_map(|card| {
+            card.depends_on
+                .iter()
+                .filter(|dep| ids.contains(dep.as_str()))
+                .map(|dep| (dep.as_str(), card.id.as_str()))
+        })
+        .collect()
+}
+
+/// Fill color per status, shared by both formats
+fn status_color(status: &Status) -> &'static str {
+    match status {
+        Status::Todo => "#e0e0e0",
+        Status::InProgress => "#90caf9",
+        Status::PendingReview => "#ffcc80",
+        Status::Done => "#a5d6a7",
+    }
+}
+
+fn status_class(status: &Status) -> &'static str {
+    match status {
+        Status::Todo => "todo",
+        Status::InProgress => "in_progress",
+        Status::PendingReview => "pending_review",
+        Status::Done => "done",
+    }
+}
+
+/// Graphviz DOT
+pub fn render_dot(board: &Board, cards: &[Card]) -> String {
+    let escape = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+    let mut out = String::new();
+    let _ = writeln!(out, "digraph \"{}\" {{", escape(&board.name));
+    let _ = writeln!(out, "  rankdir=LR;");
+    let _ = writeln!(out, "  node [shape=box, style=\"rounded,filled\"];");
+    for card in cards {
+        let _ = writeln!(
+            out,
+            "  \"{}\" [label=\"{}\\n{}\", fillcolor=\"{}\"];",
+            card.id,
+            escape(&card.name),
+            card.id,
+            status_color(&card.status)
+        );
+    }
+    for (from, to) in edges(cards) {
+        let _ = writeln!(out, "  \"{}\" -> \"{}\";", from, to);
+    }
+    let _ = writeln!(out, "}}");
+    out
+}
+
+/// Mermaid flowchart
+pub fn render_mermaid(cards: &[Card]) -> String {
+    let mut out = String::from("flowchart LR\n");
+    for card in cards {
+        let _ = writeln!(
+            out,
+            "  {}[\"{}\"]:::{}",
+            card.id,
+            card.name.replace('"', "#quot;"),
+            status_class(&card.status)
+        );
+    }
+    for (from, to) in edges(cards) {
+        let _ = writeln!(out, "  {} --> {}", from, to);
+    }
+    for status in [
+        Status::Todo,
+        Status::InProgress,
+        Status::PendingReview,
+        Status::Done,
+    ] {
+        let _ = writeln!(
+            out,
+            "  classDef {} fill:{},stroke:#555",
+            status_class(&status),
+            status_color(&status)
+        );
+    }
+    out
+}
@@ -0,0 +1,121 @@
+//! Terminal rendering for comment text.
+//!
+//! Fenced code blocks (```` ```lang ````) are indented and syntax highlighted
+//! with syntect; `diff`/`patch` blocks, and untagged blocks that look like a
+//! diff, get `+`/`-` line coloring instead. Everything else passes through
+//! untouched, as does all text when colors are disabled.
+
+use colored::Colorize;
+use std::sync::OnceLock;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{Theme, ThemeSet};
+use syntect::parsing::SyntaxSet;
+use syntect::util::as_24_bit_terminal_escaped;
+
+const CODE_INDENT: &str = "    ";
+const THEME: &str = "base16-ocean.dark";
+
+fn syntaxes() -> &'static SyntaxSet {
+    static SYNTAXES: OnceLock<SyntaxSet> = OnceLock::new();
+    SYNTAXES.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+fn theme() -> &'static Theme {
+    static THEME_SET: OnceLock<ThemeSet> = OnceLock::new();
+    &THEME_SET.get_or_init(ThemeSet::load_defaults).themes[THEME]
+}
+
+/// Render comment text for table/pretty output.
+pub fn comment_text(text: &str) -> String {
+    if !colored::control::SHOULD_COLORIZE.should_colorize() || !text.contains("```") {
+        return text.to_string();
+    }
+
+    let mut out: Vec<String> = Vec::new();
+    let mut lines = text.lines();
+    while let Some(line) = lines.next() {
+        let Some(lang) = line.trim_start().strip_prefix("```") else {
+            out.push(line.to_string());
+            continue;
+        };
+        let lang = lang.trim();
+        let mut block = Vec::new();
+        let mut closing = None;
+        for inner in lines.by_ref() {
+            if inner.trim_start().starts_with("```") {
+                closing = Some(inner);
+                break;
+            }
+            block.push(inner);
+        }
+
+        out.push(line.dimmed().to_string());
+        out.extend(render_block(lang, &block));
+        if let Some(closing) = closing {
+            out.push(closing.dimmed().to_string());
+        }
+    }
+    out.join("\n")
+}
+
+fn render_block(lang: &str, block: &[&str]) -> Vec<String> {
+    if is_diff(lang, block) {
+        return block
+            .iter()
+            .map(|line| format!("{}{}", CODE_INDENT, diff_line(line)))
+            .collect();
+    }
+
+    let syntaxes = syntaxes();
+    let syntax = syntaxes
+        .find_syntax_by_token(lang)
+        .unwrap_or_else(|| syntaxes.find_syntax_plain_text());
+    let mut highlighter = HighlightLines::new(syntax, theme());
+    block
+        .iter()
+        .map(|line| {
+            let line = format!("{}\n", line);
+            match highlighter.highlight_line(&line, syntaxes) {
+                Ok(ranges) => format!(
+                    "{}{}\x1b[0m",
+                    CODE_INDENT,
+                    as_24_bit_terminal_escaped(&ranges, false).trim_end_matches('\n')
+                ),
+                Err(_) => format!("{}{}", CODE_INDENT, line.trim_end_matches('\n')),
+            }
+        })
+        .collect()
+}
+
+/// Tagged `diff`/`patch`, or untagged with every non-empty line a diff line.
+fn is_diff(lang: &str, block: &[&str]) -> bool {
+    match lang {
+        "diff" | "patch" => true,
+        "" => {
+            block
+                .iter()
+                .any(|l| l.starts_with('+') || l.starts_with('-'))
+                && block.iter().all(|l| {
+                    l.is_empty()
+                        || l.starts_with(['+', '-', ' ', '@'])
+                        || l.starts_with("diff ")
+                        || l.starts_with("index ")
+                })
+        }
+        _ => false,
+    }
+}
+
+fn diff_line(line: &str) -> String {
+    if line.starts_with("+++") || line.starts_with("---") {
+        line.bold().to_string()
+    } else if line.starts_with('+') {
+        line.green().to_string()
+    } else if line.starts_with('-') {
+        line.red().to_string()
+    } else if line.starts_with("@@") {
+        line.cyan().to_string()
+    } else {
+        line.to_string()
+    }
+}
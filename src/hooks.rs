@@ -0,0 +1,183 @@
+//! Runs hook commands for events.
+//!
+//! Each hook is started with `sh -c <command>` and receives the event as JSON
+//! on stdin, plus `AGENT_BOARD_EVENT` and `AGENT_BOARD_CARD_ID` in its
+//! environment. Hooks run in the background so a slow command never holds up
+//! the CLI; their output goes to stderr and failures only produce a warning.
+//!
+//! Notifiers are built-in hooks: the message is formatted here and posted to
+//! the Slack or Discord webhook with `curl`, under the same rules.
+
+use crate::models::{Card, Event, Hook, Notifier, NotifierKind};
+use std::fs::{File, OpenOptions};
+use std::io::{Seek, Write};
+use std::process::{Command, Stdio};
+use uuid::Uuid;
+
+/// A hook or notifier matched by an event, held back until its transaction
+/// commits
+pub enum Dispatch {
+    Hook(Hook, Event),
+    Notifier(Notifier, serde_json::Value),
+}
+
+impl Dispatch {
+    pub fn run(self) {
+        match self {
+            Dispatch::Hook(hook, event) => run(&hook, &event),
+            Dispatch::Notifier(notifier, payload) => post(&notifier, &payload),
+        }
+    }
+}
+
+pub fn run(hook: &Hook, event: &Event) {
+    let payload = serde_json::to_string(event).unwrap();
+    let mut command = Command::new("sh");
+    command
+        .arg("-c")
+        .arg(&hook.command)
+        .env("AGENT_BOARD_EVENT", event.event.to_string())
+        .env("AGENT_BOARD_CARD_ID", &event.card_id);
+    spawn(command, &format!("hook {}", hook.id), &payload);
+}
+
+pub fn post(notifier: &Notifier, payload: &serde_json::Value) {
+    let (command, config) = curl(notifier, payload);
+    spawn(command, &format!("notifier {}", notifier.id), &config);
+}
+
+/// The curl command for a notifier and the config it reads on stdin. The
+/// webhook URL is its secret, so it goes in the config with the payload
+/// rather than in argv, where any local user could read it
+fn curl(notifier: &Notifier, payload: &serde_json::Value) -> (Command, String) {
+    let mut command = Command::new("curl");
+    command
+        .args(["--silent", "--show-error", "--fail", "--max-time", "10"])
+        .args(["--header", "Content-Type: application/json"])
+        .args(["--output", "/dev/null", "--config", "-"]);
+    let config = format!(
+        "url = {}\ndata-binary = {}\n",
+        config_string(&notifier.url),
+        config_string(&payload.to_string())
+    );
+    (command, config)
+}
+
+/// `value` as a double-quoted curl config string
+fn config_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' | '\\' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// The chat message for `card` entering its new status: name, the agent
+/// who did the work, and a link to the PR (or the command that shows the card)
+pub fn message(notifier: &Notifier, event: &Event, card: &Card, agent: &str) -> serde_json::Value {
+    let status = event
+        .data
+        .get("to")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .replace('_', " ");
+    let show = format!("agent-board get card {}", card.id);
+    match notifier.kind {
+        NotifierKind::Slack => {
+            let link = match &card.pr_url {
+                Some(url) => format!("<{}|{}>", url, card.id),
+                None => format!("`{}`", show),
+            };
+            serde_json::json!({
+                "text": format!("*{}* moved to {} by {}\n{}", card.name, status, agent, link)
+            })
+        }
+        NotifierKind::Discord => {
+            let link = match &card.pr_url {
+                Some(url) => format!("[{}]({})", card.id, url),
+                None => format!("`{}`", show),
+            };
+            serde_json::json!({
+                "content": format!("**{}** moved to {} by {}\n{}", card.name, status, agent, link)
+            })
+        }
+    }
+}
+
+fn spawn(mut command: Command, what: &str, payload: &str) {
+    // The payload goes through an unlinked temp file rather than a pipe: a
+    // hook that never reads stdin would otherwise block the CLI on a payload
+    // bigger than the pipe buffer
+    let stdin = match payload_file(payload) {
+        Ok(file) => file,
+        Err(e) => {
+            eprintln!("Warning: {} payload could not be written: {}", what, e);
+            return;
+        }
+    };
+    let spawned = command
+        .stdin(Stdio::from(stdin))
+        .stdout(Stdio::from(std::io::stderr()))
+        .spawn();
+    if let Err(e) = spawned {
+        eprintln!("Warning: {} failed to start: {}", what, e);
+    }
+}
+
+/// `payload` in a temp file that is already removed, rewound for reading
+fn payload_file(payload: &str) -> std::io::Result<File> {
+    let path = std::env::temp_dir().join(format!("agent-board-hook-{}.json", Uuid::new_v4()));
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create_new(true)
+        .open(&path)?;
+    let _ = std::fs::remove_file(&path);
+    file.write_all(payload.as_bytes())?;
+    file.rewind()?;
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_webhook_url_stays_out_of_curl_argv() {
+        let notifier = Notifier {
+            id: "notifier_1".into(),
+            board_id: "board_1".into(),
+            kind: NotifierKind::Slack,
+            url: "https://hooks.slack.com/services/T0/B0/secret".into(),
+            statuses: Vec::new(),
+            created_at: chrono::Utc::now(),
+        };
+        let payload = serde_json::json!({ "text": "say \"hi\"" });
+        let (command, config) = curl(&notifier, &payload);
+
+        assert!(
+            command
+                .get_args()
+                .all(|arg| !arg.to_string_lossy().contains("secret"))
+        );
+        let lines: Vec<&str> = config.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                r#"url = "https://hooks.slack.com/services/T0/B0/secret""#,
+                r#"data-binary = "{\"text\":\"say \\\"hi\\\"\"}""#,
+            ]
+        );
+    }
+}
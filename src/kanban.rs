@@ -0,0 +1,686 @@
+//! Kanban rendering for the pretty board view.
+//!
+//! `render` lays a board out as four status columns of card boxes and returns
+//! the text instead of printing it, so it can be golden-tested and reused by
+//! other front ends. Colors follow `colored`'s global setting.
+
+use crate::models::*;
+use crate::output::{agent_name, label_text, truncate};
+use chrono::{DateTime, Utc};
+use colored::{ColoredString, Colorize};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Render a board as kanban columns, one string ready to print; `now` drives
+/// the age and due lines so output is reproducible
+pub fn render(
+    board: &Board,
+    cards: &[Card],
+    comment_counts: &HashMap<String, usize>,
+    options: &KanbanOptions,
+    now: DateTime<Utc>,
+) -> String {
+    const COL_WIDTH: usize = 28;
+    const COLLAPSED_WIDTH: usize = 16; // Fits "PENDING REVIEW"
+    const CARD_INNER: usize = COL_WIDTH - 4; // Account for borders and padding
+    let mut out = String::new();
+
+    // Group cards by status
+    let statuses = [
+        Status::Todo,
+        Status::InProgress,
+        Status::PendingReview,
+        Status::Done,
+    ];
+    let groups: Vec<Vec<&Card>> = statuses
+        .iter()
+        .map(|s| cards.iter().filter(|c| c.status == *s).collect())
+        .collect();
+    let collapsed: Vec<bool> = statuses
+        .iter()
+        .map(|s| options.collapse.contains(s))
+        .collect();
+    let widths: Vec<usize> = collapsed
+        .iter()
+        .map(|&c| if c { COLLAPSED_WIDTH } else { COL_WIDTH })
+        .collect();
+    let inner_width = widths.iter().sum::<usize>() + 3;
+    let rule = |left: &str, mid: &str, right: &str| {
+        let segments: Vec<String> = widths.iter().map(|w| "─".repeat(*w)).collect();
+        format!("{}{}{}", left, segments.join(mid), right)
+    };
+
+    // Board header
+    out.push('\n');
+    writeln!(out, "┌{}┐", "─".repeat(inner_width)).unwrap();
+    let title = format!("{} - {}", board.name, board.id);
+    writeln!(out, "│ {:<width$} │", title, width = inner_width - 2).unwrap();
+    // The pinned note is wrapped rather than cut: it's the one line everyone must read
+    if let Some(note) = &board.pinned_note {
+        for line in wrap(&format!("PINNED: {}", note), inner_width - 2) {
+            let padded = format!("{:<width$}", line, width = inner_width - 2);
+            writeln!(out, "│ {} │", padded.yellow().bold()).unwrap();
+        }
+    }
+    if let Some(desc) = &board.description {
+        writeln!(
+            out,
+            "│ {:<width$} │",
+            truncate(desc, inner_width - 4),
+            width = inner_width - 2
+        )
+        .unwrap();
+    }
+    // --highlight: Some(true) for matching cards, Some(false) for the rest
+    let term = options
+        .highlight
+        .as_deref()
+        .map(str::to_lowercase)
+        .filter(|t| !t.is_empty());
+    let lit = |card: &Card| term.as_deref().map(|t| matches(card, t));
+    if let (Some(term), Some(shown)) = (&term, &options.highlight) {
+        let hits = cards.iter().filter(|c| matches(c, term)).count();
+        let line = truncate(
+            &format!(
+                "Highlighting \"{}\": {} of {} cards match",
+                shown,
+                hits,
+                cards.len()
+            ),
+            inner_width - 2,
+        );
+        let padded = format!("{:<width$}", line, width = inner_width - 2);
+        writeln!(out, "│ {} │", padded.bold()).unwrap();
+    }
+    // Matching cards get heavy top and bottom borders, visible without color too
+    let border = |card: &Card, light: (&str, &str), heavy: (&str, &str)| {
+        let ((left, right), fill) = if lit(card) == Some(true) {
+            (heavy, "━")
+        } else {
+            (light, "─")
+        };
+        format!(" {}{}{} │", left, fill.repeat(CARD_INNER), right)
+    };
+    let fade = |card: &Card, text: ColoredString| match lit(card) {
+        Some(true) => text.bold(),
+        Some(false) => text.dimmed(),
+        None => text,
+    };
+    writeln!(out, "{}", rule("├", "┬", "┤")).unwrap();
+
+    // Column headers with colors
+    let header_colors = [
+        "TODO".white(),
+        "IN PROGRESS".yellow(),
+        "PENDING REVIEW".cyan(),
+        "DONE".green(),
+    ];
+    out.push('│');
+    for (col_idx, header) in header_colors.iter().enumerate() {
+        write!(out, " {:<width$}│", header, width = widths[col_idx] - 1).unwrap();
+    }
+    out.push('\n');
+
+    // Counts
+    out.push('│');
+    for (col_idx, group) in groups.iter().enumerate() {
+        let count_str = format!("({} cards)", group.len());
+        write!(out, " {:<width$}│", count_str, width = widths[col_idx] - 1).unwrap();
+    }
+    out.push('\n');
+
+    writeln!(out, "{}", rule("├", "┼", "┤")).unwrap();
+
+    // Collapsed columns show no cards; the rest stop at --max-cards-per-column
+    let columns: Vec<&[&Card]> = groups
+        .iter()
+        .zip(&collapsed)
+        .map(|(group, &collapsed)| {
+            let shown = if collapsed {
+                0
+            } else {
+                options
+                    .max_cards_per_column
+                    .map_or(group.len(), |max| group.len().min(max))
+            };
+            &group[..shown]
+        })
+        .collect();
+    let hidden: Vec<usize> = groups
+        .iter()
+        .zip(&columns)
+        .zip(&collapsed)
+        .map(|((group, col), &collapsed)| {
+            if collapsed {
+                0
+            } else {
+                group.len() - col.len()
+            }
+        })
+        .collect();
+    let max_cards = columns.iter().map(|col| col.len()).max().unwrap_or(0);
+    let show_labels = columns
+        .iter()
+        .any(|col| col.iter().any(|c| !c.labels.is_empty()));
+    let show_due = columns
+        .iter()
+        .any(|col| col.iter().any(|c| c.due_at.is_some()));
+
+    // Print cards row by row (each card takes 5 lines)
+    for i in 0..max_cards {
+        // Card top border
+        out.push('│');
+        for (col_idx, col) in columns.iter().enumerate() {
+            if i < col.len() {
+                out.push_str(&border(col[i], ("┌", "┐"), ("┍", "┑")));
+            } else {
+                write!(out, "{:width$}│", "", width = widths[col_idx]).unwrap();
+            }
+        }
+        out.push('\n');
+
+        // Card name line 1 (colored by status)
+        out.push('│');
+        for (col_idx, col) in columns.iter().enumerate() {
+            if i < col.len() {
+                let card = col[i];
+                let max_chars = CARD_INNER - 2;
+                let name_line1 = if card.name.len() > max_chars {
+                    &card.name[..max_chars]
+                } else {
+                    &card.name
+                };
+                let colored_name = match col_idx {
+                    1 => format!("{:<width$}", name_line1, width = max_chars).yellow(),
+                    2 => format!("{:<width$}", name_line1, width = max_chars).cyan(),
+                    3 => format!("{:<width$}", name_line1, width = max_chars).green(),
+                    _ => format!("{:<width$}", name_line1, width = max_chars).white(),
+                };
+                write!(out, " │ {} │ │", fade(card, colored_name)).unwrap();
+            } else {
+                write!(out, "{:width$}│", "", width = widths[col_idx]).unwrap();
+            }
+        }
+        out.push('\n');
+
+        // Card name line 2 (continuation, colored by status)
+        out.push('│');
+        for (col_idx, col) in columns.iter().enumerate() {
+            if i < col.len() {
+                let card = col[i];
+                let max_chars = CARD_INNER - 2;
+                let name_line2 = if card.name.len() > max_chars {
+                    truncate(&card.name[max_chars..], max_chars)
+                } else {
+                    String::new()
+                };
+                let colored_name = match col_idx {
+                    1 => format!("{:<width$}", name_line2, width = max_chars).yellow(),
+                    2 => format!("{:<width$}", name_line2, width = max_chars).cyan(),
+                    3 => format!("{:<width$}", name_line2, width = max_chars).green(),
+                    _ => format!("{:<width$}", name_line2, width = max_chars).white(),
+                };
+                write!(out, " │ {} │ │", fade(card, colored_name)).unwrap();
+            } else {
+                write!(out, "{:width$}│", "", width = widths[col_idx]).unwrap();
+            }
+        }
+        out.push('\n');
+
+        // Card ID line (dimmed)
+        out.push('│');
+        for (col_idx, col) in columns.iter().enumerate() {
+            if i < col.len() {
+                let card = col[i];
+                let id_short = if card.id.len() > CARD_INNER - 2 {
+                    format!("{}...", &card.id[..CARD_INNER - 5])
+                } else {
+                    card.id.clone()
+                };
+                let dimmed_id = format!("{:<width$}", id_short, width = CARD_INNER - 2).dimmed();
+                write!(out, " │ {} │ │", dimmed_id).unwrap();
+            } else {
+                write!(out, "{:width$}│", "", width = widths[col_idx]).unwrap();
+            }
+        }
+        out.push('\n');
+
+        // Card assignee line
+        out.push('│');
+        for (col_idx, col) in columns.iter().enumerate() {
+            if i < col.len() {
+                let card = col[i];
+                let assignee = card.assigned_to.as_deref().map(agent_name).unwrap_or("-");
+                let assignee_display = format!(
+                    "{:<width$}",
+                    format!("@{}", truncate(assignee, CARD_INNER - 4)),
+                    width = CARD_INNER - 2
+                );
+                write!(out, " │ {} │ │", fade(card, assignee_display.normal())).unwrap();
+            } else {
+                write!(out, "{:width$}│", "", width = widths[col_idx]).unwrap();
+            }
+        }
+        out.push('\n');
+
+        // Card age line
+        out.push('│');
+        for (col_idx, col) in columns.iter().enumerate() {
+            if i < col.len() {
+                let age = format!("created {} ago", coarse_span(now - col[i].created_at));
+                let padded = format!("{:<width$}", age, width = CARD_INNER - 2);
+                write!(out, " │ {} │ │", padded.dimmed()).unwrap();
+            } else {
+                write!(out, "{:width$}│", "", width = widths[col_idx]).unwrap();
+            }
+        }
+        out.push('\n');
+
+        // Card due line (only on boards that use due dates); done cards can't be overdue
+        if show_due {
+            out.push('│');
+            for (col_idx, col) in columns.iter().enumerate() {
+                if i < col.len() {
+                    let card = col[i];
+                    let due = match card.due_at {
+                        Some(_) if card.status == Status::Done => String::new(),
+                        Some(due) if due <= now => {
+                            format!("{:<width$}", "OVERDUE", width = CARD_INNER - 2)
+                                .red()
+                                .bold()
+                                .to_string()
+                        }
+                        Some(due) => {
+                            let text = format!("due in {}", coarse_span(due - now));
+                            format!("{:<width$}", text, width = CARD_INNER - 2)
+                                .yellow()
+                                .to_string()
+                        }
+                        None => String::new(),
+                    };
+                    if due.is_empty() {
+                        write!(out, " │ {:<width$} │ │", "", width = CARD_INNER - 2).unwrap();
+                    } else {
+                        write!(out, " │ {} │ │", due).unwrap();
+                    }
+                } else {
+                    write!(out, "{:width$}│", "", width = widths[col_idx]).unwrap();
+                }
+            }
+            out.push('\n');
+        }
+
+        // Card tags line 1
+        out.push('│');
+        for (col_idx, col) in columns.iter().enumerate() {
+            if i < col.len() {
+                let card = col[i];
+                if card.tags.is_empty() {
+                    write!(out, " │ {:<width$} │ │", "", width = CARD_INNER - 2).unwrap();
+                } else {
+                    let tags_str = card
+                        .tags
+                        .iter()
+                        .map(|t| format!("#{}", t))
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    let line1 = if tags_str.len() > CARD_INNER - 2 {
+                        &tags_str[..CARD_INNER - 2]
+                    } else {
+                        &tags_str
+                    };
+                    let tags_part = format!("{:<width$}", line1, width = CARD_INNER - 2);
+                    write!(out, " │ {} │ │", fade(card, tags_part.blue())).unwrap();
+                }
+            } else {
+                write!(out, "{:width$}│", "", width = widths[col_idx]).unwrap();
+            }
+        }
+        out.push('\n');
+
+        // Card tags line 2
+        out.push('│');
+        for (col_idx, col) in columns.iter().enumerate() {
+            if i < col.len() {
+                let card = col[i];
+                if card.tags.is_empty() {
+                    write!(out, " │ {:<width$} │ │", "", width = CARD_INNER - 2).unwrap();
+                } else {
+                    let tags_str = card
+                        .tags
+                        .iter()
+                        .map(|t| format!("#{}", t))
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    let line2 = if tags_str.len() > CARD_INNER - 2 {
+                        let remaining = &tags_str[CARD_INNER - 2..];
+                        truncate(remaining, CARD_INNER - 2)
+                    } else {
+                        String::new()
+                    };
+                    let tags_part = format!("{:<width$}", line2, width = CARD_INNER - 2);
+                    write!(out, " │ {} │ │", fade(card, tags_part.blue())).unwrap();
+                }
+            } else {
+                write!(out, "{:width$}│", "", width = widths[col_idx]).unwrap();
+            }
+        }
+        out.push('\n');
+
+        // Card labels line (only on boards that use labels)
+        if show_labels {
+            out.push('│');
+            for (col_idx, col) in columns.iter().enumerate() {
+                if i < col.len() {
+                    let card = col[i];
+                    // Pad by visible width; color codes would throw off format! padding
+                    let mut used = 0;
+                    let mut parts = Vec::new();
+                    for label in &card.labels {
+                        let sep = usize::from(!parts.is_empty());
+                        let remaining = (CARD_INNER - 2).saturating_sub(used + sep);
+                        if label.name.chars().count() > remaining && remaining < 4 {
+                            break;
+                        }
+                        let name = truncate(&label.name, remaining);
+                        used += sep + name.chars().count();
+                        parts.push(label_text(&Label {
+                            name,
+                            ..label.clone()
+                        }));
+                    }
+                    write!(
+                        out,
+                        " │ {}{} │ │",
+                        parts.join(" "),
+                        " ".repeat((CARD_INNER - 2).saturating_sub(used))
+                    )
+                    .unwrap();
+                } else {
+                    write!(out, "{:width$}│", "", width = widths[col_idx]).unwrap();
+                }
+            }
+            out.push('\n');
+        }
+
+        // Card comments line
+        out.push('│');
+        for (col_idx, col) in columns.iter().enumerate() {
+            if i < col.len() {
+                let card = col[i];
+                let comment_count = comment_counts.get(&card.id).copied().unwrap_or(0);
+                let comment_text = if comment_count == 1 {
+                    "[1 comment]".to_string()
+                } else {
+                    format!("[{} comments]", comment_count)
+                };
+                let padded = format!("{:<width$}", comment_text, width = CARD_INNER - 2);
+                write!(out, " │ {} │ │", padded.dimmed()).unwrap();
+            } else {
+                write!(out, "{:width$}│", "", width = widths[col_idx]).unwrap();
+            }
+        }
+        out.push('\n');
+
+        // Card bottom border
+        out.push('│');
+        for (col_idx, col) in columns.iter().enumerate() {
+            if i < col.len() {
+                out.push_str(&border(col[i], ("└", "┘"), ("┕", "┙")));
+            } else {
+                write!(out, "{:width$}│", "", width = widths[col_idx]).unwrap();
+            }
+        }
+        out.push('\n');
+    }
+
+    // Footer for columns cut off by --max-cards-per-column
+    if hidden.iter().any(|&n| n > 0) {
+        out.push('│');
+        for (col_idx, &n) in hidden.iter().enumerate() {
+            if n > 0 {
+                let more = format!(
+                    "{:<width$}",
+                    format!("…and {} more", n),
+                    width = widths[col_idx] - 1
+                );
+                write!(out, " {}│", more.dimmed()).unwrap();
+            } else {
+                write!(out, "{:width$}│", "", width = widths[col_idx]).unwrap();
+            }
+        }
+        out.push('\n');
+    }
+
+    // If no cards at all
+    if max_cards == 0 && hidden.iter().all(|&n| n == 0) {
+        out.push('│');
+        for (col_idx, group) in groups.iter().enumerate() {
+            let placeholder = if group.is_empty() { "(empty)" } else { "" };
+            write!(
+                out,
+                " {:<width$}│",
+                placeholder,
+                width = widths[col_idx] - 1
+            )
+            .unwrap();
+        }
+        out.push('\n');
+    }
+
+    // Bottom border
+    writeln!(out, "{}", rule("└", "┴", "┘")).unwrap();
+    out.push('\n');
+    out
+}
+
+/// Case-insensitive substring match for --highlight; `term` is lowercase
+fn matches(card: &Card, term: &str) -> bool {
+    let hit = |text: &str| text.to_lowercase().contains(term);
+    hit(&card.name)
+        || hit(&card.id)
+        || card.description.as_deref().is_some_and(hit)
+        || card.tags.iter().any(|t| hit(t))
+        || card.labels.iter().any(|l| hit(&l.name))
+}
+
+/// Round a span to the nearest whole unit for kanban cards: "4d", "3h", or "12m"
+fn coarse_span(span: chrono::Duration) -> String {
+    let minutes = span.num_minutes().max(0);
+    if minutes >= 24 * 60 {
+        format!("{}d", (minutes + 12 * 60) / (24 * 60))
+    } else if minutes >= 60 {
+        format!("{}h", (minutes + 30) / 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+/// Split `text` into lines of at most `width` characters at spaces
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let word = truncate(word, width);
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(&word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn at(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339).unwrap().into()
+    }
+
+    fn now() -> DateTime<Utc> {
+        at("2026-03-10T12:00:00Z")
+    }
+
+    fn board() -> Board {
+        Board {
+            id: "board_0123456789ab".into(),
+            name: "Website".into(),
+            description: Some("Marketing site rebuild".into()),
+            created_at: at("2026-03-01T09:00:00Z"),
+            updated_at: at("2026-03-01T09:00:00Z"),
+            deleted_at: None,
+            namespace: "default".into(),
+            starred: false,
+            last_used_at: None,
+            group: None,
+            pinned_note: None,
+            card_counts: None,
+            etag: None,
+        }
+    }
+
+    fn card(n: u32, name: &str, status: Status, created_at: &str) -> Card {
+        Card {
+            id: format!("card_{:012x}", n),
+            board_id: board().id,
+            name: name.into(),
+            description: None,
+            status,
+            assigned_to: None,
+            tags: Vec::new(),
+            labels: Vec::new(),
+            checklist: Vec::new(),
+            depends_on: Vec::new(),
+            created_at: at(created_at),
+            updated_at: at(created_at),
+            deleted_at: None,
+            due_at: None,
+            estimate_minutes: None,
+            pr_url: None,
+            plan_key: None,
+            comment_count: None,
+            etag: None,
+        }
+    }
+
+    fn cards() -> Vec<Card> {
+        let mut nav = card(1, "Fix navigation", Status::Todo, "2026-03-06T12:00:00Z");
+        nav.tags = vec!["frontend".into(), "needs-design".into()];
+        nav.due_at = Some(at("2026-03-12T12:00:00Z"));
+        let mut footer = card(2, "Footer links", Status::Todo, "2026-03-10T09:00:00Z");
+        footer.due_at = Some(at("2026-03-09T12:00:00Z"));
+        let mut hero = card(
+            3,
+            "Rewrite the hero section copy for the spring launch",
+            Status::InProgress,
+            "2026-03-08T12:00:00Z",
+        );
+        hero.assigned_to = Some("agent_writer".into());
+        hero.labels = vec![Label {
+            id: "label_1".into(),
+            name: "copy".into(),
+            color: "magenta".into(),
+        }];
+        let review = card(
+            4,
+            "SEO audit",
+            Status::PendingReview,
+            "2026-03-10T11:30:00Z",
+        );
+        let mut shipped = card(5, "Set up CI", Status::Done, "2026-02-20T12:00:00Z");
+        shipped.due_at = Some(at("2026-03-01T12:00:00Z"));
+        vec![nav, footer, hero, review, shipped]
+    }
+
+    /// Compare against tests/golden/<name>; UPDATE_GOLDEN=1 rewrites the file
+    fn assert_golden(name: &str, actual: &str) {
+        let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "golden", name]
+            .iter()
+            .collect();
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, actual).unwrap();
+            return;
+        }
+        let expected = std::fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("{}: {} (run with UPDATE_GOLDEN=1)", path.display(), e));
+        assert_eq!(actual, expected, "output differs from {}", path.display());
+    }
+
+    fn render_plain(cards: &[Card], options: &KanbanOptions) -> String {
+        colored::control::set_override(false);
+        let counts = HashMap::from([("card_000000000001".to_string(), 3)]);
+        render(&board(), cards, &counts, options, now())
+    }
+
+    #[test]
+    fn full_board() {
+        let out = render_plain(&cards(), &KanbanOptions::default());
+        assert_golden("kanban_full.txt", &out);
+    }
+
+    #[test]
+    fn collapsed_and_capped() {
+        let options = KanbanOptions {
+            collapse: vec![Status::Done],
+            max_cards_per_column: Some(1),
+            highlight: None,
+        };
+        let out = render_plain(&cards(), &options);
+        assert_golden("kanban_collapsed.txt", &out);
+    }
+
+    #[test]
+    fn highlighted() {
+        let options = KanbanOptions {
+            highlight: Some("FRONTEND".into()),
+            ..KanbanOptions::default()
+        };
+        let out = render_plain(&cards(), &options);
+        assert_golden("kanban_highlight.txt", &out);
+    }
+
+    #[test]
+    fn pinned_note_wraps_at_the_top() {
+        colored::control::set_override(false);
+        let board = Board {
+            pinned_note: Some(
+                "Freeze: only bugfixes until Friday. Anything else goes to the next sprint board, \
+                 and ask in #releases before merging."
+                    .into(),
+            ),
+            ..board()
+        };
+        let out = render(
+            &board,
+            &[],
+            &HashMap::new(),
+            &KanbanOptions::default(),
+            now(),
+        );
+        assert_golden("kanban_pinned.txt", &out);
+    }
+
+    #[test]
+    fn empty_board() {
+        let out = render_plain(&[], &KanbanOptions::default());
+        assert_golden("kanban_empty.txt", &out);
+    }
+
+    #[test]
+    fn coarse_span_rounds_to_nearest_unit() {
+        assert_eq!(coarse_span(chrono::Duration::minutes(-5)), "0m");
+        assert_eq!(coarse_span(chrono::Duration::minutes(59)), "59m");
+        assert_eq!(coarse_span(chrono::Duration::minutes(89)), "1h");
+        assert_eq!(coarse_span(chrono::Duration::minutes(90)), "2h");
+        assert_eq!(coarse_span(chrono::Duration::hours(47)), "2d");
+    }
+}
@@ -1,10 +1,34 @@
+mod bench;
+mod chart;
 mod cli;
+mod dates;
 mod db;
+mod digest;
+#[cfg(feature = "semantic")]
+mod embedding;
+mod export;
+mod graph;
+mod highlight;
+mod hooks;
+mod kanban;
 mod models;
+mod ops;
 mod output;
+mod plan;
+mod schedule;
+mod similarity;
+mod storage;
+mod taskwarrior;
 
 use clap::Parser;
-use cli::{Cli, Commands, CreateCommands, DeleteCommands, ListCommands, UpdateCommands};
+use cli::{
+    BoardConfigCommands, ChartCommands, Cli, Commands, ConfigCommands, CreateCommands, DbCommands,
+    DeleteCommands, ExportCommands, GateCommands, ImportCommands, LabelCommands, ListCommands,
+    PlanCommands, PurgeCommands, ReportCommands, SnapshotCommands, StatsCommands, SyncCommands,
+    TagsCommands, UpdateCommands,
+};
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 
 fn main() -> ExitCode {
@@ -17,6 +41,10 @@ fn main() -> ExitCode {
 
     match rt.block_on(run(cli)) {
         Ok(()) => ExitCode::from(0),
+        // The reader went away (e.g. piped into `head`); nothing left to report
+        Err(AgentBoardError::Io(e)) if e.kind() == std::io::ErrorKind::BrokenPipe => {
+            ExitCode::from(0)
+        }
         Err(e) => {
             eprintln!("Error: {}", e);
             e.exit_code()
@@ -25,70 +53,1104 @@ fn main() -> ExitCode {
 }
 
 async fn run(cli: Cli) -> Result<(), AgentBoardError> {
+    // With --output, results are buffered and written to the file once the command succeeds
+    let output_path = cli.output.clone();
+    if let Some(path) = &output_path {
+        check_output_path(path)?;
+    }
+    let mut stdout = std::io::stdout();
+    let mut buffer = Vec::new();
+    let mut out: &mut dyn Write = if output_path.is_some() {
+        &mut buffer
+    } else {
+        &mut stdout
+    };
+
     // Handle version command before loading DB
     if matches!(cli.command, Commands::Version) {
-        println!("agent-board {}", env!("CARGO_PKG_VERSION"));
+        writeln!(out, "agent-board {}", env!("CARGO_PKG_VERSION"))?;
+        if let Some(path) = &output_path {
+            write_atomic(path, &buffer)?;
+        }
+        return Ok(());
+    }
+
+    // Restoring replaces the database file, so it consumes its own connection
+    if let Commands::Snapshot {
+        command: SnapshotCommands::Restore { name },
+    } = &cli.command
+    {
+        let (restored, backup) = db::Database::load(&cli)
+            .await?
+            .restore_snapshot(name)
+            .await?;
+        if !cli.quiet {
+            writeln!(out, "Restored snapshot: {}", restored.name)?;
+            writeln!(out, "Previous state saved as: {}", backup.name)?;
+        }
+        if let Some(path) = &output_path {
+            write_atomic(path, &buffer)?;
+        }
         return Ok(());
     }
 
     let db = db::Database::load(&cli).await?;
+    if let Some(path) = db.read_only_path()
+        && !cli.quiet
+    {
+        eprintln!(
+            "Warning: {} is read-only; reading from a temporary copy, changes will be refused",
+            path.display()
+        );
+    }
+    dates::set_default_zone(db.db_setting(db::DB_SETTING_TIMEZONE).await?.as_deref());
+    output::set_agent_names(db.agent_names().await?);
+    output::set_table_layout(output::TableLayout {
+        no_truncate: cli.no_truncate,
+        max_width: cli.max_width,
+        columns: db.column_widths().await?,
+    });
     let default_format = cli.format.clone();
     let quiet = cli.quiet;
     let agent_id_result = cli.get_agent_id();
+    // Resolve the --board context once; commands that take a board fall back to it
+    let board_context = match &cli.board {
+        Some(board) => Some(db.resolve_board(board).await?.id),
+        None => None,
+    };
+    if let Some(board_id) = &board_context {
+        db.touch_board(board_id).await?;
+    }
+    // Set by commands that report partial results before failing (apply)
+    let mut failure = None;
 
     match cli.command {
         Commands::Version => unreachable!(), // Handled above
 
-        Commands::Get { id, format } => {
-            let fmt = format.unwrap_or(default_format);
-            if id.starts_with("agent_") {
-                let agent = db.get_agent(&id).await?;
-                output::print_agent(&agent, fmt);
-            } else if id.starts_with("board_") {
-                let board = db.get_board(&id).await?;
-                if fmt == models::OutputFormat::Pretty {
-                    let cards = db.list_cards(&id, None, None, &[], false).await?;
+        Commands::Get {
+            ids,
+            history,
+            timings,
+            collapse,
+            max_cards_per_column,
+            highlight,
+            etag_only,
+            format,
+        } => {
+            let fmt = command_format(format, default_format, &[])?;
+            if etag_only {
+                for id in &ids {
+                    writeln!(out, "{}", entity_etag(&db, id).await?)?;
+                }
+            } else if let [id] = ids.as_slice() {
+                if id.starts_with("board_") && fmt == models::OutputFormat::Pretty {
+                    let board = db.get_board(id).await?;
+                    // Kanban shows tags and labels but not checklists, so skip full hydration
+                    let mut cards = db
+                        .list_cards_brief(id, &models::CardFilter::default())
+                        .await?;
+                    db.attach_tags(&mut cards).await?;
+                    db.attach_labels(&mut cards).await?;
                     let card_ids: Vec<String> = cards.iter().map(|c| c.id.clone()).collect();
                     let comment_counts = db.get_comment_counts(&card_ids).await?;
-                    output::print_kanban(&board, &cards, &comment_counts);
+                    let options = models::KanbanOptions {
+                        collapse,
+                        max_cards_per_column,
+                        highlight,
+                    };
+                    output::print_kanban(&mut out, &board, &cards, &comment_counts, &options)?;
                 } else {
-                    let summary = db.get_board_summary(&id).await?;
-                    output::print_board(&board, &summary, fmt);
+                    let entity = resolve_entity(&db, id, history, timings).await?;
+                    output::print_entity(&mut out, &entity, fmt)?;
+                }
+                if id.starts_with("board_") {
+                    db.touch_board(id).await?;
                 }
-            } else if id.starts_with("card_") {
-                let card = db.get_card(&id).await?;
-                let comments = db.list_comments(&id).await?;
-                output::print_card(&card, &comments, fmt);
             } else {
-                return Err(AgentBoardError::InvalidArgs(format!(
-                    "Unknown ID prefix: {}. Expected agent_, board_, or card_",
-                    id
-                )));
+                let mut entities = Vec::with_capacity(ids.len());
+                for id in &ids {
+                    entities.push(resolve_entity(&db, id, history, timings).await?);
+                }
+                output::print_entities(&mut out, &entities, fmt)?;
             }
         }
 
+        Commands::History {
+            card_id,
+            field,
+            format,
+        } => match field {
+            models::HistoryField::Assignee => {
+                let assignments = db.list_assignments(&card_id).await?;
+                output::print_assignments(
+                    &mut out,
+                    &assignments,
+                    command_format(format, default_format, &[])?,
+                )?;
+            }
+            models::HistoryField::Description => {
+                let card = db.get_card(&card_id).await?;
+                let revisions = db.list_revisions(&card_id, field).await?;
+                output::print_revisions(
+                    &mut out,
+                    &card,
+                    &revisions,
+                    command_format(format, default_format, &[])?,
+                )?;
+            }
+        },
+
         Commands::Mine {
+            agent,
+            all,
             board,
             status,
             format,
         } => {
-            let agent_id = agent_id_result?;
-            let cards = db
-                .get_cards_by_assignee(&agent_id, board.as_deref(), status)
-                .await?;
-            output::print_cards(&cards, format.unwrap_or(default_format));
+            let board = board.or_else(|| board_context.clone());
+            if all {
+                let cards = db
+                    .get_cards_by_assignee(None, board.as_deref(), status)
+                    .await?;
+                output::print_cards_by_agent(
+                    &mut out,
+                    &cards,
+                    command_format(format, default_format, &[])?,
+                )?;
+            } else {
+                let agent_id = match agent {
+                    Some(id) => id,
+                    None => agent_id_result?,
+                };
+                let cards = db
+                    .get_cards_by_assignee(Some(&agent_id), board.as_deref(), status)
+                    .await?;
+                output::print_cards(
+                    &mut out,
+                    &cards,
+                    command_format(format, default_format, &[])?,
+                )?;
+            }
         }
 
         Commands::Whoami => {
             let agent_id = agent_id_result?;
             let agent = db.get_agent(&agent_id).await?;
-            let cwd = std::env::current_dir()
-                .map_err(|e| {
-                    AgentBoardError::General(format!("Failed to get current directory: {}", e))
-                })?
-                .to_string_lossy()
-                .to_string();
-            output::print_agent_whoami(&agent, &cwd);
+            let cwd = current_dir()?;
+            output::print_agent_whoami(&mut out, &agent, &cwd)?;
+        }
+
+        Commands::Graph { board_id, format } => {
+            let board_id = board_or_context(board_id, &board_context)?;
+            let board = db.get_board(&board_id).await?;
+            let cards = db.dependency_graph(&board_id).await?;
+            match command_format(
+                format,
+                default_format,
+                &[models::OutputFormat::Dot, models::OutputFormat::Mermaid],
+            )? {
+                models::OutputFormat::Dot => write!(out, "{}", graph::render_dot(&board, &cards))?,
+                models::OutputFormat::Json => {
+                    output::print_cards(&mut out, &cards, models::OutputFormat::Json)?
+                }
+                _ => write!(out, "{}", graph::render_mermaid(&cards))?,
+            }
+        }
+
+        Commands::Check {
+            board,
+            fail_on,
+            format,
+        } => {
+            let board = board_or_context(board, &board_context)?;
+            let rules = if fail_on.is_empty() {
+                vec![
+                    models::CheckRule::Overdue,
+                    models::CheckRule::UnassignedInProgress,
+                    models::CheckRule::WipExceeded,
+                ]
+            } else {
+                fail_on
+            };
+            let report = db.check_board(&board, &rules).await?;
+            output::print_check_report(
+                &mut out,
+                &report,
+                command_format(format, default_format, &[models::OutputFormat::Gha])?,
+            )?;
+            if !report.passed {
+                return Err(AgentBoardError::PolicyViolation(format!(
+                    "{} check(s) failed on {}",
+                    report.violations.len(),
+                    board
+                )));
+            }
+        }
+
+        Commands::Audit {
+            board_id,
+            stale,
+            idle,
+            format,
+        } => {
+            let board_id = board_or_context(board_id, &board_context)?;
+            let report = db
+                .audit_board(&board_id, parse_time(&stale)?, parse_time(&idle)?)
+                .await?;
+            output::print_audit_report(
+                &mut out,
+                &report,
+                command_format(format, default_format, &[models::OutputFormat::Gha])?,
+            )?;
+        }
+
+        Commands::Done {
+            card_id,
+            comment,
+            review,
+        } => {
+            let status = if review {
+                models::Status::PendingReview
+            } else {
+                models::Status::Done
+            };
+            let comment = db.close_card(&card_id, comment, status.clone()).await?;
+            if !quiet {
+                writeln!(
+                    out,
+                    "Moved {} to {} (comment: {})",
+                    card_id, status, comment.id
+                )?;
+            }
+        }
+
+        Commands::Reopen {
+            card_id,
+            reason,
+            to,
+        } => {
+            let comment = db.reopen_card(&card_id, reason, to.clone()).await?;
+            if !quiet {
+                writeln!(
+                    out,
+                    "Reopened {} to {} (comment: {})",
+                    card_id, to, comment.id
+                )?;
+            }
+        }
+
+        Commands::Apply { keep_going } => {
+            let input = std::io::read_to_string(std::io::stdin())?;
+            let ops = ops::parse(&input)?;
+            let (results, error) = ops::run(&db, ops, board_context.as_deref(), keep_going).await;
+            writeln!(out, "{}", serde_json::to_string_pretty(&results)?)?;
+            failure = error;
+        }
+
+        Commands::React {
+            comment_id,
+            emoji,
+            remove,
+        } => {
+            let comment = db.react(&comment_id, &emoji, remove).await?;
+            if !quiet {
+                let summary = output::reactions_summary(&comment.reactions);
+                writeln!(
+                    out,
+                    "{} {} {} {}",
+                    if remove { "Removed" } else { "Reacted" },
+                    emoji.trim(),
+                    if remove { "from" } else { "to" },
+                    comment_id
+                )?;
+                if !summary.is_empty() {
+                    writeln!(out, "Reactions: {}", summary)?;
+                }
+            }
+        }
+
+        Commands::Sweep {
+            board,
+            only,
+            skip,
+            dry_run,
+            format,
+        } => {
+            let board = board.or_else(|| board_context.clone());
+            let board = board.as_deref();
+            let runs = |step| (only.is_empty() || only.contains(&step)) && !skip.contains(&step);
+            let mut summary = models::SweepSummary {
+                dry_run,
+                ..Default::default()
+            };
+            if runs(models::SweepStep::Stale) {
+                summary.stale = Some(db.sweep_stale(board, dry_run).await?);
+            }
+            if runs(models::SweepStep::DueSoon) {
+                summary.due_soon = Some(db.remind_due_soon(board, dry_run).await?);
+            }
+            if runs(models::SweepStep::Retention) {
+                summary.retention = Some(db.prune_events(board, dry_run).await?);
+            }
+            if runs(models::SweepStep::Stats) && !dry_run {
+                summary.stats = Some(db.record_board_stats(board).await?);
+            }
+            output::print_sweep(
+                &mut out,
+                &summary,
+                command_format(format, default_format, &[])?,
+            )?;
+        }
+
+        Commands::Brief {
+            since,
+            peek,
+            format,
+        } => {
+            let agent_id = agent_id_result?;
+            let since = since.as_deref().map(parse_time).transpose()?;
+            let brief = db.brief(&agent_id, since, peek).await?;
+            output::print_brief(
+                &mut out,
+                &brief,
+                command_format(format, default_format, &[])?,
+            )?;
+        }
+
+        Commands::Describe {
+            card_id,
+            depth,
+            comments,
+            format,
+        } => {
+            let ctx = db.describe_card(&card_id, depth, comments).await?;
+            output::print_card_context(
+                &mut out,
+                &ctx,
+                command_format(
+                    format,
+                    default_format,
+                    &[models::OutputFormat::Prompt, models::OutputFormat::Markdown],
+                )?,
+            )?;
+        }
+
+        Commands::Context {
+            card_id,
+            out: path,
+            depth,
+            comments,
+            watch,
+            interval,
+        } => {
+            check_output_path(&path)?;
+            write_card_context(&db, &card_id, depth, comments, &path).await?;
+            if !quiet {
+                writeln!(out, "Wrote {} for {}", path.display(), card_id)?;
+            }
+            // Polls rather than holding a lock; ends when the card is deleted
+            if watch {
+                loop {
+                    std::thread::sleep(std::time::Duration::from_secs(interval.max(1)));
+                    match write_card_context(&db, &card_id, depth, comments, &path).await {
+                        Ok(false) => {}
+                        Ok(true) if !quiet => writeln!(out, "Updated {}", path.display())?,
+                        Ok(true) => {}
+                        Err(AgentBoardError::NotFound(_)) => {
+                            if !quiet {
+                                writeln!(out, "Card {} was deleted; stopped watching", card_id)?;
+                            }
+                            break;
+                        }
+                        Err(e) => return Err(e),
+                    }
+                }
+            }
+        }
+
+        Commands::Star { board, remove } => {
+            let board = db.resolve_board(&board).await?;
+            db.star_board(&board.id, remove).await?;
+            if !quiet {
+                let verb = if remove { "Unstarred" } else { "Starred" };
+                writeln!(out, "{} board: {} ({})", verb, board.id, board.name)?;
+            }
+        }
+
+        Commands::Diff {
+            board_id,
+            since,
+            until,
+            format,
+        } => {
+            let board = board_or_context(board_id, &board_context)?;
+            let since = parse_time(&since)?;
+            let until = match until {
+                Some(until) => parse_time(&until)?,
+                None => chrono::Utc::now(),
+            };
+            if until < since {
+                return Err(AgentBoardError::InvalidArgs(
+                    "--until must not be before --since".into(),
+                ));
+            }
+            let diff = db.board_diff(&board, since, until).await?;
+            output::print_board_diff(
+                &mut out,
+                &diff,
+                command_format(format, default_format, &[models::OutputFormat::Markdown])?,
+            )?;
+        }
+
+        Commands::Digest {
+            board,
+            since,
+            due_within,
+            send,
+            to,
+            format,
+        } => {
+            let board = board_or_context(board, &board_context)?;
+            let since = parse_time(&since)?;
+            let due_within = parse_duration(&due_within).ok_or_else(|| {
+                AgentBoardError::InvalidArgs(format!(
+                    "Invalid --due-within: {}. Use e.g. 3d, 1w, 12h",
+                    due_within
+                ))
+            })?;
+            let until = chrono::Utc::now();
+            let digest = db.digest(&board, since, until, until + due_within).await?;
+            if send {
+                let command = db
+                    .db_setting(db::DB_SETTING_DIGEST_COMMAND)
+                    .await?
+                    .ok_or_else(|| {
+                        AgentBoardError::InvalidArgs(format!(
+                            "No mail command: set one with `config db set {} 'sendmail -t'` (message on stdin)",
+                            db::DB_SETTING_DIGEST_COMMAND
+                        ))
+                    })?;
+                digest::send(&command, &digest::email(&digest, &to))?;
+                if !quiet {
+                    writeln!(out, "Sent digest: {}", digest::subject(&digest))?;
+                }
+            } else {
+                output::print_digest(
+                    &mut out,
+                    &digest,
+                    command_format(
+                        format,
+                        default_format,
+                        &[models::OutputFormat::Email, models::OutputFormat::Markdown],
+                    )?,
+                )?;
+            }
+        }
+
+        Commands::Recent {
+            board,
+            limit,
+            format,
+        } => {
+            let board = board.or_else(|| board_context.clone());
+            let recent = db.recent_cards(board.as_deref(), limit).await?;
+            output::print_recent_cards(
+                &mut out,
+                &recent,
+                command_format(format, default_format, &[])?,
+            )?;
+        }
+
+        Commands::Similar {
+            text,
+            board,
+            open,
+            min_score,
+            limit,
+            format,
+        } => {
+            if !(0.0..=1.0).contains(&min_score) {
+                return Err(AgentBoardError::InvalidArgs(format!(
+                    "--min-score must be between 0 and 1, got {}",
+                    min_score
+                )));
+            }
+            let board = match board {
+                Some(board) => Some(db.resolve_board(&board).await?.id),
+                None => board_context.clone(),
+            };
+            let similar = db
+                .similar_cards(&text, board.as_deref(), open, min_score, limit)
+                .await?;
+            output::print_similar_cards(
+                &mut out,
+                &similar,
+                command_format(format, default_format, &[models::OutputFormat::Markdown])?,
+            )?;
+        }
+
+        Commands::Search {
+            query,
+            semantic,
+            embedding_file,
+            board,
+            open,
+            limit,
+            format,
+        } => {
+            let board = match board {
+                Some(board) => Some(db.resolve_board(&board).await?.id),
+                None => board_context.clone(),
+            };
+            let found = if semantic {
+                semantic_search(&db, &query, embedding_file, board.as_deref(), open, limit).await?
+            } else {
+                db.similar_cards(&query, board.as_deref(), open, 0.0, limit)
+                    .await?
+            };
+            output::print_similar_cards(
+                &mut out,
+                &found,
+                command_format(format, default_format, &[models::OutputFormat::Markdown])?,
+            )?;
+        }
+
+        #[cfg(feature = "semantic")]
+        Commands::Embed {
+            card_id,
+            board,
+            embedding_file,
+        } => {
+            let cards = match (card_id, board) {
+                (Some(card_id), _) => vec![db.get_card(&card_id).await?],
+                (None, board) => {
+                    let board = match board {
+                        Some(board) => db.resolve_board(&board).await?.id,
+                        None => board_or_context(None, &board_context)?,
+                    };
+                    db.cards_needing_embedding(&board).await?
+                }
+            };
+            let command = match embedding_file {
+                Some(_) => None,
+                None => Some(embedding_command(&db).await?),
+            };
+            for card in &cards {
+                let vector = match (&embedding_file, &command) {
+                    (Some(path), _) => embedding::read_file(path)?,
+                    (None, Some(command)) => {
+                        embedding::compute(command, &embedding::card_text(card))?
+                    }
+                    (None, None) => unreachable!(),
+                };
+                db.set_card_embedding(&card.id, &vector).await?;
+            }
+            if !quiet {
+                writeln!(
+                    out,
+                    "Embedded {} card{}",
+                    cards.len(),
+                    if cards.len() == 1 { "" } else { "s" }
+                )?;
+            }
+        }
+
+        Commands::Report { command } => match command {
+            ReportCommands::Agents { since, format } => {
+                let since = parse_time(&since)?;
+                let stats = db.agent_stats(since).await?;
+                output::print_agent_stats(
+                    &mut out,
+                    &stats,
+                    since,
+                    command_format(format, default_format, &[models::OutputFormat::Markdown])?,
+                )?;
+            }
+            ReportCommands::Aging {
+                board,
+                threshold,
+                format,
+            } => {
+                let board = board_or_context(board, &board_context)?;
+                let threshold_before = parse_time(&threshold)?;
+                let aging = db.aging_cards(&board, threshold_before).await?;
+                output::print_aging_cards(
+                    &mut out,
+                    &aging,
+                    command_format(format, default_format, &[])?,
+                )?;
+            }
+            ReportCommands::Reopened {
+                board,
+                since,
+                format,
+            } => {
+                let board = board_or_context(board, &board_context)?;
+                let since = parse_time(&since)?;
+                let reopens = db.reopened_cards(&board, since).await?;
+                output::print_reopens(
+                    &mut out,
+                    &reopens,
+                    command_format(format, default_format, &[])?,
+                )?;
+            }
+            ReportCommands::Blocked {
+                board,
+                stale,
+                format,
+            } => {
+                let board = board_or_context(board, &board_context)?;
+                let stale_before = stale.as_deref().map(parse_time).transpose()?;
+                let blocked = db.blocked_cards(&board, stale_before).await?;
+                output::print_blocked_cards(
+                    &mut out,
+                    &blocked,
+                    command_format(format, default_format, &[])?,
+                )?;
+            }
+            ReportCommands::Schedule {
+                board,
+                capacity,
+                all,
+                format,
+            } => {
+                let board = board_or_context(board, &board_context)?;
+                let capacity = parse_estimate(&capacity)?;
+                if capacity > 24 * 60 {
+                    return Err(AgentBoardError::InvalidArgs(
+                        "--capacity is work per day; it can't exceed 24h".into(),
+                    ));
+                }
+                let mut entries = db.schedule_report(&board, capacity).await?;
+                entries.retain(|e| all || e.risk != models::ScheduleRisk::OnTrack);
+                output::print_schedule(
+                    &mut out,
+                    &entries,
+                    command_format(format, default_format, &[])?,
+                )?;
+            }
+            ReportCommands::CriticalPath { board_id, format } => {
+                let board = board_or_context(board_id, &board_context)?;
+                let path = db.critical_path(&board).await?;
+                output::print_critical_path(
+                    &mut out,
+                    &path,
+                    command_format(format, default_format, &[])?,
+                )?;
+            }
+            ReportCommands::ReviewQueue { board, format } => {
+                let board = match board {
+                    Some(board) => Some(db.resolve_board(&board).await?.id),
+                    None => board_context.clone(),
+                };
+                let queue = db.review_queue(board.as_deref()).await?;
+                output::print_review_queue(
+                    &mut out,
+                    &queue,
+                    command_format(format, default_format, &[models::OutputFormat::Markdown])?,
+                )?;
+            }
+        },
+
+        Commands::Import { command } => match command {
+            ImportCommands::Taskwarrior {
+                file,
+                board,
+                dry_run,
+                format,
+            } => {
+                let (cards, skipped) = taskwarrior::load(Path::new(&file))?;
+                let default_board = match board {
+                    Some(board) => db.resolve_board(&board).await?.id,
+                    None => board_context.clone().unwrap_or_else(|| "Inbox".into()),
+                };
+                let mut report = db.import_cards(&cards, &default_board, dry_run).await?;
+                report.skipped = skipped;
+                output::print_import_report(
+                    &mut out,
+                    &report,
+                    command_format(format, default_format, &[])?,
+                )?;
+            }
+        },
+
+        Commands::Stats { command } => match command {
+            StatsCommands::Snapshot { board, format } => {
+                let board = match board {
+                    Some(board) => Some(db.resolve_board(&board).await?.id),
+                    None => board_context.clone(),
+                };
+                let stats = db.record_board_stats(board.as_deref()).await?;
+                output::print_board_stats(
+                    &mut out,
+                    &stats,
+                    command_format(format, default_format, &[])?,
+                )?;
+            }
+        },
+
+        Commands::Chart { command } => match command {
+            ChartCommands::Cfd {
+                board_id,
+                days,
+                width,
+                format,
+            } => {
+                let board_id = board_or_context(board_id, &board_context)?;
+                let board = db.get_board(&board_id).await?;
+                let since = chrono::Utc::now().date_naive()
+                    - chrono::Duration::days(i64::from(days.max(1)) - 1);
+                let stats = db.board_stats(&board_id, since).await?;
+                match command_format(format, default_format, &[])? {
+                    fmt @ (models::OutputFormat::Json | models::OutputFormat::Simple) => {
+                        output::print_board_stats(&mut out, &stats, fmt)?;
+                    }
+                    _ => write!(out, "{}", chart::render_cfd(&board, &stats, width.max(1)))?,
+                }
+            }
+        },
+
+        Commands::Export { command } => match command {
+            ExportCommands::Gantt { board_id } => {
+                let board_id = board_or_context(board_id, &board_context)?;
+                let board = db.get_board(&board_id).await?;
+                let cards = db
+                    .list_cards(&board_id, &models::CardFilter::default())
+                    .await?;
+                write!(out, "{}", export::render_gantt(&board, &cards))?;
+            }
+        },
+
+        Commands::Plan { command } => match command {
+            PlanCommands::Diff {
+                file,
+                board,
+                format,
+            } => {
+                let board = board_or_context(board, &board_context)?;
+                let plan = plan::load(std::path::Path::new(&file))?;
+                let diff = db.plan_diff(&board, &plan).await?;
+                output::print_plan_diff(
+                    &mut out,
+                    &diff,
+                    false,
+                    command_format(format, default_format, &[])?,
+                )?;
+            }
+            PlanCommands::Apply {
+                file,
+                board,
+                format,
+            } => {
+                let board = board_or_context(board, &board_context)?;
+                let plan = plan::load(std::path::Path::new(&file))?;
+                let applied = db.apply_plan(&board, &plan).await?;
+                output::print_plan_diff(
+                    &mut out,
+                    &applied,
+                    true,
+                    command_format(format, default_format, &[])?,
+                )?;
+            }
+        },
+
+        Commands::Gate { command } => match command {
+            GateCommands::Create { card_id, require } => {
+                let gate = db.create_gate(&card_id, require).await?;
+                if !quiet {
+                    writeln!(
+                        out,
+                        "Created gate: {} ({} approval required)",
+                        gate.id, require
+                    )?;
+                }
+            }
+            GateCommands::Approve { card_id, by } => {
+                let approver = by
+                    .or_else(|| std::env::var("USER").ok())
+                    .unwrap_or_else(|| "human".to_string());
+                let gates = db.approve_gates(&card_id, &approver).await?;
+                if !quiet {
+                    writeln!(
+                        out,
+                        "Approved {} gate(s) on {} as {}",
+                        gates.len(),
+                        card_id,
+                        approver
+                    )?;
+                }
+            }
+            GateCommands::List { card_id, format } => {
+                let gates = db.list_gates(&card_id).await?;
+                output::print_gates(
+                    &mut out,
+                    &gates,
+                    command_format(format, default_format, &[])?,
+                )?;
+            }
+        },
+
+        Commands::Tags { command } => match command {
+            TagsCommands::Suggest {
+                board,
+                prefix,
+                format,
+            } => {
+                let board = board_or_context(board, &board_context)?;
+                let tags = db.board_tags(&board, prefix.as_deref()).await?;
+                output::print_tag_counts(
+                    &mut out,
+                    &tags,
+                    command_format(format, default_format, &[])?,
+                )?;
+            }
+            TagsCommands::Apply {
+                board,
+                status,
+                assigned_to,
+                tag,
+                add_tag,
+                remove_tag,
+                create_tag,
+                dry_run,
+            } => {
+                let board = board_or_context(board, &board_context)?;
+                if !add_tag.is_empty() && !create_tag && !dry_run {
+                    warn_new_tags(&db, &board, &add_tag).await?;
+                }
+                let filter = models::CardFilter {
+                    status,
+                    assigned_to,
+                    tags: tag,
+                    ..Default::default()
+                };
+                let cards = db
+                    .bulk_tag(&board, &filter, &add_tag, &remove_tag, dry_run)
+                    .await?;
+                if dry_run {
+                    writeln!(
+                        out,
+                        "{} card(s) match (dry run, nothing changed):",
+                        cards.len()
+                    )?;
+                    for card in &cards {
+                        writeln!(out, "  {}  {}", card.id, card.name)?;
+                    }
+                } else if !quiet {
+                    writeln!(out, "Updated tags on {} card(s)", cards.len())?;
+                }
+            }
+        },
+
+        Commands::Label { command } => match command {
+            LabelCommands::Create { name, board, color } => {
+                let board = board_or_context(board, &board_context)?;
+                let label = db.create_label(&board, &name, &color).await?;
+                if !quiet {
+                    writeln!(out, "Created label: {} ({})", label.name, label.id)?;
+                }
+            }
+            LabelCommands::Rename {
+                name,
+                new_name,
+                board,
+            } => {
+                let board = board_or_context(board, &board_context)?;
+                let label = db.rename_label(&board, &name, &new_name).await?;
+                if !quiet {
+                    writeln!(out, "Renamed label: {} -> {}", name, label.name)?;
+                }
+            }
+            LabelCommands::Delete { name, board } => {
+                let board = board_or_context(board, &board_context)?;
+                db.delete_label(&board, &name).await?;
+                if !quiet {
+                    writeln!(out, "Deleted label: {}", name)?;
+                }
+            }
+            LabelCommands::List { board, format } => {
+                let board = board_or_context(board, &board_context)?;
+                let labels = db.list_labels(&board).await?;
+                output::print_labels(
+                    &mut out,
+                    &labels,
+                    command_format(format, default_format, &[])?,
+                )?;
+            }
+        },
+
+        Commands::Purge { command } => match command {
+            PurgeCommands::Board {
+                board_id,
+                dry_run,
+                request,
+                confirm,
+                yes,
+                format,
+            } => {
+                if request {
+                    let token = db.request_removal(&board_id, "purge").await?;
+                    output::print_removal_token(
+                        &mut out,
+                        &token,
+                        command_format(format, default_format, &[])?,
+                    )?;
+                } else {
+                    let impact = db.board_impact(&board_id, true).await?;
+                    if !dry_run {
+                        db.purge_board(&board_id, yes, confirm.as_deref()).await?;
+                    }
+                    if dry_run || !quiet {
+                        output::print_board_impact(
+                            &mut out,
+                            &impact,
+                            "purge",
+                            dry_run,
+                            command_format(format, default_format, &[])?,
+                        )?;
+                    }
+                }
+            }
+        },
+
+        Commands::Snapshot { command } => match command {
+            SnapshotCommands::Create { name } => {
+                let snapshot = db.create_snapshot(&name).await?;
+                if !quiet {
+                    writeln!(out, "Created snapshot: {}", snapshot.name)?;
+                }
+            }
+            SnapshotCommands::List { format } => {
+                let snapshots = db::Database::list_snapshots()?;
+                output::print_snapshots(
+                    &mut out,
+                    &snapshots,
+                    command_format(format, default_format, &[])?,
+                )?;
+            }
+            SnapshotCommands::Restore { .. } => unreachable!(), // Handled above
+        },
+
+        Commands::Config { command } => match command {
+            ConfigCommands::Board { board_id, command } => match command {
+                BoardConfigCommands::Get { key } => {
+                    match db.board_setting(&board_id, &key).await? {
+                        Some(value) => writeln!(out, "{}", value)?,
+                        None => {
+                            return Err(AgentBoardError::NotFound(format!(
+                                "Setting not set on {}: {}",
+                                board_id, key
+                            )));
+                        }
+                    }
+                }
+                BoardConfigCommands::Set { key, value } => {
+                    db.set_board_setting(&board_id, &key, Some(&value)).await?;
+                    if !quiet {
+                        writeln!(out, "Set {} = {} on {}", key, value, board_id)?;
+                    }
+                }
+                BoardConfigCommands::Unset { key } => {
+                    db.set_board_setting(&board_id, &key, None).await?;
+                    if !quiet {
+                        writeln!(out, "Unset {} on {}", key, board_id)?;
+                    }
+                }
+                BoardConfigCommands::List { format } => {
+                    let settings = db.board_settings(&board_id).await?;
+                    output::print_board_settings(
+                        &mut out,
+                        &settings,
+                        command_format(format, default_format, &[])?,
+                    )?;
+                }
+            },
+            ConfigCommands::Db { command } => match command {
+                BoardConfigCommands::Get { key } => match db.db_setting(&key).await? {
+                    Some(value) => writeln!(out, "{}", value)?,
+                    None => {
+                        return Err(AgentBoardError::NotFound(format!(
+                            "Database setting not set: {}",
+                            key
+                        )));
+                    }
+                },
+                BoardConfigCommands::Set { key, value } => {
+                    db.set_db_setting(&key, Some(&value)).await?;
+                    if !quiet {
+                        // Some settings are normalized, others (commands, zones) kept as typed
+                        let stored = db.db_setting(&key).await?.unwrap_or(value);
+                        writeln!(
+                            out,
+                            "Set {} = {} (applies from the next command)",
+                            key, stored
+                        )?;
+                    }
+                }
+                BoardConfigCommands::Unset { key } => {
+                    db.set_db_setting(&key, None).await?;
+                    if !quiet {
+                        writeln!(out, "Unset {}", key)?;
+                    }
+                }
+                BoardConfigCommands::List { format } => {
+                    let settings = db.db_settings().await?;
+                    output::print_board_settings(
+                        &mut out,
+                        &settings,
+                        command_format(format, default_format, &[])?,
+                    )?;
+                }
+            },
+        },
+
+        Commands::Sync { command } => match command {
+            SyncCommands::Merge { path, format } => {
+                let report = db.merge_from(&path).await?;
+                output::print_merge_report(
+                    &mut out,
+                    &report,
+                    command_format(format, default_format, &[])?,
+                )?;
+            }
+        },
+
+        Commands::Db { command } => match command {
+            DbCommands::Status { format } => {
+                let status = db.db_status().await?;
+                output::print_db_status(
+                    &mut out,
+                    &status,
+                    command_format(format, default_format, &[])?,
+                )?;
+            }
+            DbCommands::Maintain { gc_blobs, format } => {
+                if !gc_blobs {
+                    return Err(AgentBoardError::InvalidArgs(
+                        "Choose a maintenance step (e.g., --gc-blobs)".into(),
+                    ));
+                }
+                let report = db.gc_blobs().await?;
+                output::print_blob_gc(
+                    &mut out,
+                    &report,
+                    command_format(format, default_format, &[])?,
+                )?;
+            }
+        },
+
+        Commands::Bench { iterations, format } => {
+            if iterations == 0 {
+                return Err(AgentBoardError::InvalidArgs(
+                    "--iterations must be at least 1".into(),
+                ));
+            }
+            let results = bench::run(&db, iterations).await?;
+            output::print_bench(
+                &mut out,
+                &results,
+                command_format(format, default_format, &[])?,
+            )?;
         }
 
         // ====================================================================
@@ -97,10 +1159,26 @@ async fn run(cli: Cli) -> Result<(), AgentBoardError> {
         Commands::List { command } => match command {
             ListCommands::Boards {
                 include_deleted,
+                created_after,
+                created_before,
+                updated_since,
+                sort,
+                group,
+                no_summary,
                 format,
             } => {
-                let boards = db.list_boards(include_deleted).await?;
-                output::print_boards(&boards, format.unwrap_or(default_format));
+                let filter = models::BoardFilter {
+                    include_deleted,
+                    dates: date_range(created_after, created_before, updated_since)?,
+                    sort,
+                    group,
+                };
+                let boards = db.list_boards(&filter).await?;
+                let fmt = command_format(format, default_format, &[])?;
+                output::print_boards(&mut out, &boards, fmt.clone())?;
+                if !no_summary {
+                    output::print_boards_summary(&mut out, &boards, fmt)?;
+                }
             }
             ListCommands::Cards {
                 board_id,
@@ -108,29 +1186,114 @@ async fn run(cli: Cli) -> Result<(), AgentBoardError> {
                 assigned_to,
                 tag,
                 include_deleted,
+                created_after,
+                created_before,
+                updated_since,
+                brief,
+                no_summary,
                 format,
             } => {
-                let cards = db
-                    .list_cards(
-                        &board_id,
-                        status,
-                        assigned_to.as_deref(),
-                        &tag,
-                        include_deleted,
-                    )
-                    .await?;
-                output::print_cards(&cards, format.unwrap_or(default_format));
+                let board_id = board_or_context(board_id, &board_context)?;
+                let filter = models::CardFilter {
+                    status,
+                    assigned_to,
+                    tags: tag,
+                    include_deleted,
+                    dates: date_range(created_after, created_before, updated_since)?,
+                };
+                let cards = if brief {
+                    db.list_cards_brief(&board_id, &filter).await?
+                } else {
+                    db.list_cards(&board_id, &filter).await?
+                };
+                db.touch_board(&board_id).await?;
+                let fmt = command_format(format, default_format, &[])?;
+                output::print_cards(&mut out, &cards, fmt.clone())?;
+                if !no_summary {
+                    output::print_cards_summary(&mut out, &cards, fmt)?;
+                }
             }
             ListCommands::Agents {
                 include_inactive,
+                capability,
+                format,
+            } => {
+                let mut agents = db.list_agents(include_inactive).await?;
+                agents.retain(|a| {
+                    capability
+                        .iter()
+                        .all(|c| a.capabilities.contains(&c.trim().to_lowercase()))
+                });
+                output::print_agents(
+                    &mut out,
+                    &agents,
+                    command_format(format, default_format, &[])?,
+                )?;
+            }
+            ListCommands::Comments {
+                card_id,
+                author,
+                since,
+                limit,
+                reverse,
+                kind,
+                full,
                 format,
             } => {
-                let agents = db.list_agents(include_inactive).await?;
-                output::print_agents(&agents, format.unwrap_or(default_format));
+                let filter = models::CommentFilter {
+                    author,
+                    since: since.as_deref().map(parse_time).transpose()?,
+                    limit,
+                    reverse,
+                    kind,
+                    full,
+                };
+                let comments = db.list_comments_filtered(&card_id, &filter).await?;
+                output::print_comments(
+                    &mut out,
+                    &comments,
+                    command_format(format, default_format, &[])?,
+                )?;
             }
-            ListCommands::Comments { card_id, format } => {
-                let comments = db.list_comments(&card_id).await?;
-                output::print_comments(&comments, format.unwrap_or(default_format));
+            ListCommands::Hooks { format } => {
+                let hooks = db.list_hooks().await?;
+                output::print_hooks(
+                    &mut out,
+                    &hooks,
+                    command_format(format, default_format, &[])?,
+                )?;
+            }
+            ListCommands::Notifiers { board, format } => {
+                let board = board.or_else(|| board_context.clone());
+                let notifiers = db.list_notifiers(board.as_deref()).await?;
+                output::print_notifiers(
+                    &mut out,
+                    &notifiers,
+                    command_format(format, default_format, &[])?,
+                )?;
+            }
+            ListCommands::Events {
+                board,
+                card,
+                event,
+                since,
+                limit,
+                format,
+            } => {
+                let board = board.or_else(|| board_context.clone());
+                let filter = models::EventFilter {
+                    board_id: board,
+                    card_id: card,
+                    event,
+                    since: since.as_deref().map(parse_time).transpose()?,
+                    limit: Some(limit),
+                };
+                let events = db.list_events(&filter).await?;
+                output::print_events(
+                    &mut out,
+                    &events,
+                    command_format(format, default_format, &[])?,
+                )?;
             }
         },
 
@@ -138,10 +1301,16 @@ async fn run(cli: Cli) -> Result<(), AgentBoardError> {
         // CREATE commands
         // ====================================================================
         Commands::Create { command } => match command {
-            CreateCommands::Board { name, description } => {
+            CreateCommands::Board {
+                name,
+                description,
+                id_only,
+            } => {
                 let board = db.create_board(name, description).await?;
-                if !quiet {
-                    println!("Created board: {}", board.id);
+                if id_only {
+                    writeln!(out, "{}", board.id)?;
+                } else if !quiet {
+                    writeln!(out, "Created board: {}", board.id)?;
                 }
             }
             CreateCommands::Card {
@@ -149,44 +1318,158 @@ async fn run(cli: Cli) -> Result<(), AgentBoardError> {
                 name,
                 description,
                 status,
+                due,
+                estimate,
+                label,
+                dedupe,
+                id_only,
             } => {
-                let card = db.create_card(&board_id, name, description, status).await?;
-                if !quiet {
-                    println!("Created card: {}", card.id);
+                // With a --board context, the single positional argument is the card name
+                let (board_id, name) = match (name, &board_context) {
+                    (Some(name), _) => (board_id, name),
+                    (None, Some(context)) => (context.clone(), board_id),
+                    (None, None) => {
+                        return Err(AgentBoardError::InvalidArgs(
+                            "Card name required: create card <board_id> <name>, or pass --board before the command".into(),
+                        ));
+                    }
+                };
+                let due_at = due.as_deref().map(parse_due).transpose()?;
+                let estimate_minutes = estimate.as_deref().map(parse_estimate).transpose()?;
+                let dedupe_mode = if dedupe {
+                    Some("return".to_string())
+                } else {
+                    db.board_setting(&board_id, db::SETTING_CARDS_DEDUPE)
+                        .await?
+                };
+                let duplicate = match dedupe_mode {
+                    Some(_) => db.find_duplicate_card(&board_id, &name).await?,
+                    None => None,
+                };
+                if let Some((existing, score)) = duplicate {
+                    let similar = format!(
+                        "{} \"{}\" ({:.0}% similar)",
+                        existing.id,
+                        existing.name,
+                        score * 100.0
+                    );
+                    if dedupe_mode.as_deref() == Some("error") {
+                        return Err(AgentBoardError::PolicyViolation(format!(
+                            "Card \"{}\" duplicates open card {}; {} is error on this board, so use that card (--dedupe prints its ID)",
+                            name,
+                            similar,
+                            db::SETTING_CARDS_DEDUPE
+                        )));
+                    }
+                    if id_only {
+                        writeln!(out, "{}", existing.id)?;
+                    } else if !quiet {
+                        writeln!(
+                            out,
+                            "Found existing card: {}; not creating a duplicate",
+                            similar
+                        )?;
+                    }
+                } else {
+                    let card = db
+                        .create_card(
+                            &board_id,
+                            name,
+                            description,
+                            status,
+                            due_at,
+                            estimate_minutes,
+                            &label,
+                        )
+                        .await?;
+                    if id_only {
+                        writeln!(out, "{}", card.id)?;
+                    } else if !quiet {
+                        writeln!(out, "Created card: {}", card.id)?;
+                    }
                 }
+                db.touch_board(&board_id).await?;
             }
             CreateCommands::Agent {
                 command,
                 name,
                 description,
+                id_only,
             } => {
-                let cwd = std::env::current_dir()
-                    .map_err(|e| {
-                        AgentBoardError::General(format!("Failed to get current directory: {}", e))
-                    })?
-                    .to_string_lossy()
-                    .to_string();
+                let cwd = current_dir()?;
                 let agent = db
                     .register_agent(name, command, cwd.clone(), description)
                     .await?;
+                if id_only {
+                    writeln!(out, "{}", agent.id)?;
+                } else if !quiet {
+                    writeln!(out, "Created agent: {} (Name: {})", agent.id, agent.name)?;
+                    writeln!(out, "Working directory: {}", cwd)?;
+                    writeln!(out)?;
+                    writeln!(out, "To use this agent, run:")?;
+                    writeln!(out, "  export AGENT_BOARD_AGENT_ID={}", agent.id)?;
+                }
+            }
+            CreateCommands::Dependency {
+                card_id,
+                depends_on,
+            } => {
+                db.add_dependency(&card_id, &depends_on).await?;
                 if !quiet {
-                    println!("Created agent: {} (Name: {})", agent.id, agent.name);
-                    println!("Working directory: {}", cwd);
-                    println!();
-                    println!("To use this agent, run:");
-                    println!("  export AGENT_BOARD_AGENT_ID={}", agent.id);
+                    writeln!(
+                        out,
+                        "Added dependency: {} depends on {}",
+                        card_id, depends_on
+                    )?;
+                }
+            }
+            CreateCommands::Hook {
+                event,
+                command,
+                board,
+                status,
+                id_only,
+            } => {
+                let board = board.or_else(|| board_context.clone());
+                let hook = db.create_hook(event, command, board, status).await?;
+                if id_only {
+                    writeln!(out, "{}", hook.id)?;
+                } else if !quiet {
+                    writeln!(out, "Created hook: {}", hook.id)?;
+                }
+            }
+            CreateCommands::Notifier {
+                kind,
+                url,
+                board,
+                on,
+                id_only,
+            } => {
+                let board = board.or_else(|| board_context.clone()).ok_or_else(|| {
+                    AgentBoardError::InvalidArgs(
+                        "Board required: create notifier <kind> <url> --board <board_id>".into(),
+                    )
+                })?;
+                let notifier = db.create_notifier(&board, kind, url, on).await?;
+                if id_only {
+                    writeln!(out, "{}", notifier.id)?;
+                } else if !quiet {
+                    writeln!(out, "Created notifier: {}", notifier.id)?;
                 }
             }
             CreateCommands::Checklist { card_id, item } => {
                 let items = db.add_checklist_items(&card_id, item).await?;
                 if !quiet {
-                    println!("Added {} checklist item(s)", items.len());
+                    writeln!(out, "Added {} checklist item(s)", items.len())?;
                 }
             }
             CreateCommands::Comment {
                 card_id,
                 text,
                 file,
+                kind,
+                meta,
+                id_only,
             } => {
                 let content = if let Some(path) = file {
                     std::fs::read_to_string(&path).map_err(|e| {
@@ -197,10 +1480,21 @@ async fn run(cli: Cli) -> Result<(), AgentBoardError> {
                         "Either text or --file required".into(),
                     ))?
                 };
-                let agent_id = std::env::var("AGENT_BOARD_AGENT_ID").ok();
-                let comment = db.add_comment(&card_id, content, agent_id).await?;
-                if !quiet {
-                    println!("Added comment: {}", comment.id);
+                let agent_id = db.actor().map(str::to_string);
+                let meta = meta
+                    .map(|m| {
+                        serde_json::value::RawValue::from_string(m).map_err(|e| {
+                            AgentBoardError::InvalidArgs(format!("--meta is not valid JSON: {}", e))
+                        })
+                    })
+                    .transpose()?;
+                let comment = db
+                    .add_comment(&card_id, content, agent_id, kind, meta)
+                    .await?;
+                if id_only {
+                    writeln!(out, "{}", comment.id)?;
+                } else if !quiet {
+                    writeln!(out, "Added comment: {}", comment.id)?;
                 }
             }
         },
@@ -213,18 +1507,41 @@ async fn run(cli: Cli) -> Result<(), AgentBoardError> {
                 card_id,
                 name,
                 description,
+                append_description,
                 status,
                 assign,
                 assign_to_me,
+                force,
+                due,
+                estimate,
+                pr_url,
                 add_tag,
                 remove_tag,
+                create_tag,
+                label,
+                remove_label,
             } => {
+                if !add_tag.is_empty() && !create_tag {
+                    let board_id = db.get_card(&card_id).await?.board_id;
+                    warn_new_tags(&db, &board_id, &add_tag).await?;
+                }
                 let agent_id = match (&assign, assign_to_me) {
                     (Some(s), _) if s == "null" => Some(None), // explicit unassign
-                    (Some(s), _) => Some(Some(s.clone())),     // explicit assign
+                    (Some(s), _) => {
+                        // explicit assign: catch typos unless the identity is external
+                        if !force {
+                            db.get_agent(s).await.map_err(|_| {
+                                AgentBoardError::NotFound(format!(
+                                    "No active agent with ID {} (use --force to assign anyway)",
+                                    s
+                                ))
+                            })?;
+                        }
+                        Some(Some(s.clone()))
+                    }
                     (None, true) => {
                         // --assign-to-me flag: require existing agent identity
-                        let id = std::env::var("AGENT_BOARD_AGENT_ID").map_err(|_| {
+                        let id = db.actor().map(str::to_string).ok_or_else(|| {
                             AgentBoardError::InvalidArgs(
                                 "No agent identity configured.\n\n\
                                 To use --assign-to-me, first set up your agent identity:\n  \
@@ -238,7 +1555,7 @@ async fn run(cli: Cli) -> Result<(), AgentBoardError> {
                     (None, false) => {
                         // Use env var agent ID if status is being changed to in_progress
                         if status == Some(models::Status::InProgress) {
-                            let id = std::env::var("AGENT_BOARD_AGENT_ID").map_err(|_| {
+                            let id = db.actor().map(str::to_string).ok_or_else(|| {
                                 AgentBoardError::InvalidArgs(
                                     "No agent identity configured.\n\n\
                                     Setting status to in-progress requires an agent identity:\n  \
@@ -256,24 +1573,64 @@ async fn run(cli: Cli) -> Result<(), AgentBoardError> {
                 let update = models::CardUpdate {
                     name,
                     description,
+                    append_description,
                     status,
                     session_id: agent_id,
                     add_tags: add_tag,
                     remove_tags: remove_tag,
+                    add_labels: label,
+                    remove_labels: remove_label,
+                    due_at: match due.as_deref() {
+                        None => None,
+                        Some("null") => Some(None),
+                        Some(value) => Some(Some(parse_due(value)?)),
+                    },
+                    estimate_minutes: match estimate.as_deref() {
+                        None => None,
+                        Some("null") => Some(None),
+                        Some(value) => Some(Some(parse_estimate(value)?)),
+                    },
+                    pr_url: match pr_url.as_deref() {
+                        None => None,
+                        Some("null") => Some(None),
+                        Some(value) => Some(Some(value.to_string())),
+                    },
                 };
                 db.update_card(&card_id, update).await?;
                 if !quiet {
-                    println!("Updated card: {}", card_id);
+                    writeln!(out, "Updated card: {}", card_id)?;
                 }
             }
             UpdateCommands::Board {
                 board_id,
                 name,
                 description,
+                group,
+                pin,
+                auto_unblock,
             } => {
-                db.update_board(&board_id, name, description).await?;
+                let group = match group.as_deref() {
+                    None => None,
+                    Some("null") => Some(None),
+                    Some(value) => Some(Some(value.to_string())),
+                };
+                let pin = match pin.as_deref() {
+                    None => None,
+                    Some("null") => Some(None),
+                    Some(value) => Some(Some(value.to_string())),
+                };
+                db.update_board(&board_id, name, description, group, pin)
+                    .await?;
+                if let Some(enabled) = auto_unblock {
+                    db.set_board_setting(
+                        &board_id,
+                        db::SETTING_AUTO_UNBLOCK,
+                        Some(&enabled.to_string()),
+                    )
+                    .await?;
+                }
                 if !quiet {
-                    println!("Updated board: {}", board_id);
+                    writeln!(out, "Updated board: {}", board_id)?;
                 }
             }
             UpdateCommands::Agent {
@@ -282,31 +1639,39 @@ async fn run(cli: Cli) -> Result<(), AgentBoardError> {
                 command,
                 description,
                 workdir,
+                add_workdir,
+                remove_workdir,
+                add_capability,
+                remove_capability,
+                access,
+                only_board,
+                any_board,
             } => {
-                let working_directory = match workdir {
-                    Some(w) if w == "." => Some(
-                        std::env::current_dir()
-                            .map_err(|e| {
-                                AgentBoardError::General(format!(
-                                    "Failed to get current directory: {}",
-                                    e
-                                ))
-                            })?
-                            .to_string_lossy()
-                            .to_string(),
-                    ),
-                    Some(w) => Some(w),
-                    None => None,
-                };
                 let update = models::AgentUpdate {
                     name,
                     command,
                     description,
-                    working_directory,
+                    working_directory: workdir.map(resolve_workdir).transpose()?,
+                    add_workdirs: add_workdir
+                        .into_iter()
+                        .map(resolve_workdir)
+                        .collect::<Result<_, _>>()?,
+                    remove_workdirs: remove_workdir
+                        .into_iter()
+                        .map(resolve_workdir)
+                        .collect::<Result<_, _>>()?,
+                    add_capabilities: add_capability,
+                    remove_capabilities: remove_capability,
+                    access,
+                    scope_board: match only_board {
+                        Some(board) => Some(Some(db.resolve_board(&board).await?.id)),
+                        None if any_board => Some(None),
+                        None => None,
+                    },
                 };
                 db.update_agent(&agent_id, update).await?;
                 if !quiet {
-                    println!("Updated agent: {}", agent_id);
+                    writeln!(out, "Updated agent: {}", agent_id)?;
                 }
             }
             UpdateCommands::ChecklistItem {
@@ -322,11 +1687,12 @@ async fn run(cli: Cli) -> Result<(), AgentBoardError> {
                 }
                 db.check_item(&item_id, check).await?;
                 if !quiet {
-                    println!(
+                    writeln!(
+                        out,
                         "{} item: {}",
                         if check { "Checked" } else { "Unchecked" },
                         item_id
-                    );
+                    )?;
                 }
             }
         },
@@ -335,43 +1701,453 @@ async fn run(cli: Cli) -> Result<(), AgentBoardError> {
         // DELETE commands
         // ====================================================================
         Commands::Delete { command } => match command {
-            DeleteCommands::Board { board_id } => {
-                db.delete_board(&board_id).await?;
-                if !quiet {
-                    println!("Deleted board: {}", board_id);
+            DeleteCommands::Board {
+                board_id,
+                dry_run,
+                request,
+                confirm,
+                yes,
+                format,
+            } => {
+                if request {
+                    let token = db.request_removal(&board_id, "delete").await?;
+                    output::print_removal_token(
+                        &mut out,
+                        &token,
+                        command_format(format, default_format, &[])?,
+                    )?;
+                } else {
+                    let impact = db.board_impact(&board_id, false).await?;
+                    if !dry_run {
+                        db.delete_board(&board_id, yes, confirm.as_deref()).await?;
+                    }
+                    if dry_run || !quiet {
+                        output::print_board_impact(
+                            &mut out,
+                            &impact,
+                            "delete",
+                            dry_run,
+                            command_format(format, default_format, &[])?,
+                        )?;
+                    }
                 }
             }
             DeleteCommands::Card { card_id } => {
                 db.delete_card(&card_id).await?;
                 if !quiet {
-                    println!("Deleted card: {}", card_id);
+                    writeln!(out, "Deleted card: {}", card_id)?;
                 }
             }
-            DeleteCommands::Agent { agent_id } => {
-                db.unregister_agent(&agent_id).await?;
+            DeleteCommands::Agent {
+                agent_id,
+                reassign_to,
+                unassign: _, // default behavior; the flag only makes it explicit
+            } => {
+                let cards = db
+                    .unregister_agent(&agent_id, reassign_to.as_deref())
+                    .await?;
                 if !quiet {
-                    println!("Deleted agent: {}", agent_id);
+                    writeln!(out, "Deleted agent: {}", agent_id)?;
+                    if !cards.is_empty() {
+                        match &reassign_to {
+                            Some(target) => writeln!(
+                                out,
+                                "Reassigned {} open card(s) to {}:",
+                                cards.len(),
+                                target
+                            )?,
+                            None => writeln!(out, "Unassigned {} open card(s):", cards.len())?,
+                        }
+                        for card in &cards {
+                            writeln!(out, "  {}  {}", card.id, card.name)?;
+                        }
+                    }
                 }
             }
             DeleteCommands::Comment { comment_id } => {
                 db.delete_comment(&comment_id).await?;
                 if !quiet {
-                    println!("Deleted comment: {}", comment_id);
+                    writeln!(out, "Deleted comment: {}", comment_id)?;
                 }
             }
             DeleteCommands::ChecklistItem { item_id } => {
                 db.delete_checklist_item(&item_id).await?;
                 if !quiet {
-                    println!("Deleted checklist item: {}", item_id);
+                    writeln!(out, "Deleted checklist item: {}", item_id)?;
+                }
+            }
+            DeleteCommands::Hook { hook_id } => {
+                db.delete_hook(&hook_id).await?;
+                if !quiet {
+                    writeln!(out, "Deleted hook: {}", hook_id)?;
+                }
+            }
+            DeleteCommands::Notifier { notifier_id } => {
+                db.delete_notifier(&notifier_id).await?;
+                if !quiet {
+                    writeln!(out, "Deleted notifier: {}", notifier_id)?;
+                }
+            }
+            DeleteCommands::Dependency {
+                card_id,
+                depends_on,
+            } => {
+                db.remove_dependency(&card_id, &depends_on).await?;
+                if !quiet {
+                    writeln!(out, "Removed dependency: {} -> {}", card_id, depends_on)?;
                 }
             }
         },
     }
 
     db.save().await?;
+    if let Some(path) = output_path {
+        write_atomic(&path, &buffer)?;
+    }
+    match failure {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+/// Cards closest in meaning to `query`, whose embedding comes from
+/// `embedding_file` or the `embedding.command` setting
+#[cfg(feature = "semantic")]
+async fn semantic_search(
+    db: &db::Database,
+    query: &str,
+    embedding_file: Option<PathBuf>,
+    board: Option<&str>,
+    open: bool,
+    limit: usize,
+) -> Result<Vec<models::SimilarCard>, AgentBoardError> {
+    let vector = match embedding_file {
+        Some(path) => embedding::read_file(&path)?,
+        None => embedding::compute(&embedding_command(db).await?, query)?,
+    };
+    db.semantic_search(&vector, board, open, limit).await
+}
+
+#[cfg(not(feature = "semantic"))]
+async fn semantic_search(
+    _db: &db::Database,
+    _query: &str,
+    _embedding_file: Option<PathBuf>,
+    _board: Option<&str>,
+    _open: bool,
+    _limit: usize,
+) -> Result<Vec<models::SimilarCard>, AgentBoardError> {
+    Err(AgentBoardError::InvalidArgs(
+        "search --semantic needs a build with the semantic feature (cargo install --features semantic)"
+            .to_string(),
+    ))
+}
+
+#[cfg(feature = "semantic")]
+async fn embedding_command(db: &db::Database) -> Result<String, AgentBoardError> {
+    db.db_setting(db::DB_SETTING_EMBEDDING_COMMAND)
+        .await?
+        .ok_or_else(|| {
+            AgentBoardError::InvalidArgs(format!(
+                "No embedding command: pass --embedding-file, or set one with `config db set {} '<command>'` (text on stdin, JSON array on stdout)",
+                db::DB_SETTING_EMBEDDING_COMMAND
+            ))
+        })
+}
+
+/// The ETag `get` would show for a card or board
+async fn entity_etag(db: &db::Database, id: &str) -> Result<String, AgentBoardError> {
+    if id.starts_with("card_") {
+        let card = db.get_card(id).await?;
+        let comments = db.list_comments(id).await?;
+        Ok(db::Database::card_etag(&card, &comments))
+    } else if id.starts_with("board_") {
+        db.board_etag(&db.get_board(id).await?).await
+    } else {
+        Err(AgentBoardError::InvalidArgs(format!(
+            "{}: only cards (card_) and boards (board_) have ETags",
+            id
+        )))
+    }
+}
+
+fn current_dir() -> Result<String, AgentBoardError> {
+    Ok(std::env::current_dir()
+        .map_err(|e| AgentBoardError::General(format!("Failed to get current directory: {}", e)))?
+        .to_string_lossy()
+        .to_string())
+}
+
+/// A `--workdir`-style argument, where "." means the current directory
+fn resolve_workdir(path: String) -> Result<String, AgentBoardError> {
+    if path == "." { current_dir() } else { Ok(path) }
+}
+
+/// Fail before running the command if `--output` can't be a file
+fn check_output_path(path: &Path) -> Result<(), AgentBoardError> {
+    let parent = path.parent().filter(|p| !p.as_os_str().is_empty());
+    if path.file_name().is_none() || path.is_dir() {
+        return Err(AgentBoardError::InvalidArgs(format!(
+            "--output must be a file path: {}",
+            path.display()
+        )));
+    }
+    if let Some(dir) = parent
+        && !dir.is_dir()
+    {
+        return Err(AgentBoardError::InvalidArgs(format!(
+            "--output directory does not exist: {}",
+            dir.display()
+        )));
+    }
     Ok(())
 }
 
+/// Write through a temp file in the same directory and rename it into place,
+/// so readers never see a half-written result
+fn write_atomic(path: &Path, contents: &[u8]) -> Result<(), AgentBoardError> {
+    let dir = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let tmp = dir.join(format!(".{}.{}.tmp", name, std::process::id()));
+    std::fs::write(&tmp, contents)?;
+    if let Err(e) = std::fs::rename(&tmp, path) {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+/// Render a card's context as markdown into `path`; false when the file
+/// already held exactly that
+async fn write_card_context(
+    db: &db::Database,
+    card_id: &str,
+    depth: usize,
+    comments: usize,
+    path: &Path,
+) -> Result<bool, AgentBoardError> {
+    let ctx = db.describe_card(card_id, depth, comments).await?;
+    let mut doc = Vec::new();
+    output::print_card_context(&mut doc, &ctx, models::OutputFormat::Markdown)?;
+    if std::fs::read(path).is_ok_and(|old| old == doc) {
+        return Ok(false);
+    }
+    write_atomic(path, &doc)?;
+    Ok(true)
+}
+
+/// Look up any entity by ID, detecting its type from the prefix
+async fn resolve_entity(
+    db: &db::Database,
+    id: &str,
+    with_history: bool,
+    with_timings: bool,
+) -> Result<models::Entity, AgentBoardError> {
+    if id.starts_with("agent_") {
+        Ok(models::Entity::Agent(db.get_agent(id).await?))
+    } else if id.starts_with("board_") {
+        let mut board = db.get_board(id).await?;
+        let summary = db.get_board_summary(id).await?;
+        board.etag = Some(db.board_etag(&board).await?);
+        Ok(models::Entity::Board { board, summary })
+    } else if id.starts_with("card_") {
+        let mut card = db.get_card(id).await?;
+        let comments = db.list_comments(id).await?;
+        card.etag = Some(db::Database::card_etag(&card, &comments));
+        let history = if with_history {
+            Some(db.list_assignments(id).await?)
+        } else {
+            None
+        };
+        let timings = if with_timings {
+            db.status_spans(std::slice::from_ref(&card))
+                .await?
+                .remove(&card.id)
+        } else {
+            None
+        };
+        Ok(models::Entity::Card {
+            card,
+            comments,
+            history,
+            timings,
+        })
+    } else if id.starts_with("comment_") {
+        let comment = db.get_comment(id).await?;
+        let card = db.get_card(&comment.card_id).await?;
+        Ok(models::Entity::Comment { comment, card })
+    } else if id.starts_with("item_") {
+        let (item, card_id) = db.get_checklist_item(id).await?;
+        let card = db.get_card(&card_id).await?;
+        Ok(models::Entity::ChecklistItem { item, card })
+    } else if id.starts_with("checklist_") {
+        Err(AgentBoardError::InvalidArgs(format!(
+            "{}: checklists no longer have their own IDs (each card has one checklist). \
+            Use the card ID (card_xxx) or a checklist item ID (item_xxx)",
+            id
+        )))
+    } else {
+        Err(AgentBoardError::InvalidArgs(format!(
+            "Unknown ID prefix: {}. Expected agent_, board_, card_, comment_, or item_",
+            id
+        )))
+    }
+}
+
+/// Use the command's own board argument, else the --board context
+fn board_or_context(
+    board: Option<String>,
+    context: &Option<String>,
+) -> Result<String, AgentBoardError> {
+    board.or_else(|| context.clone()).ok_or_else(|| {
+        AgentBoardError::InvalidArgs(
+            "Board required: pass a board ID, or --board <name-or-id> before the command".into(),
+        )
+    })
+}
+
+/// Resolve a command's output format. The formats only some commands render
+/// are refused anywhere else rather than quietly printed as a table;
+/// `supported` lists the ones this command renders.
+fn command_format(
+    format: Option<models::OutputFormat>,
+    default: models::OutputFormat,
+    supported: &[models::OutputFormat],
+) -> Result<models::OutputFormat, AgentBoardError> {
+    use models::OutputFormat;
+    let format = format.unwrap_or(default);
+    let (name, commands) = match format {
+        OutputFormat::Dot => ("dot", "graph"),
+        OutputFormat::Mermaid => ("mermaid", "graph"),
+        OutputFormat::Markdown => (
+            "markdown",
+            "describe, diff, digest, similar, search, report agents, and report review-queue",
+        ),
+        OutputFormat::Prompt => ("prompt", "describe"),
+        OutputFormat::Gha => ("gha", "check and audit"),
+        OutputFormat::Email => ("email", "digest"),
+        _ => return Ok(format),
+    };
+    if supported.contains(&format) {
+        return Ok(format);
+    }
+    Err(AgentBoardError::InvalidArgs(format!(
+        "--format {} only works with {}",
+        name, commands
+    )))
+}
+
+/// Warn on stderr about tags no live card on the board uses yet (tag sprawl);
+/// built-in tags like `blocked` are always known
+async fn warn_new_tags(
+    db: &db::Database,
+    board_id: &str,
+    tags: &[String],
+) -> Result<(), AgentBoardError> {
+    let existing = db.board_tags(board_id, None).await?;
+    for tag in tags {
+        if db::BUILT_IN_TAGS.contains(&tag.as_str()) || existing.iter().any(|t| &t.tag == tag) {
+            continue;
+        }
+        let stem: String = tag.chars().take(2).collect();
+        let similar: Vec<&str> = existing
+            .iter()
+            .map(|t| t.tag.as_str())
+            .filter(|t| t.starts_with(&stem))
+            .take(5)
+            .collect();
+        eprintln!(
+            "Warning: tag '{}' is new on board {}{} (pass --create-tag to add it without this warning)",
+            tag,
+            board_id,
+            if similar.is_empty() {
+                String::new()
+            } else {
+                format!("; existing: {}", similar.join(", "))
+            }
+        );
+    }
+    Ok(())
+}
+
+/// Parse a duration like "30m", "2h", "1d", or "1w"; None if it would overflow
+pub(crate) fn parse_duration(value: &str) -> Option<chrono::Duration> {
+    let split = value.find(|c: char| !c.is_ascii_digit())?;
+    let (amount, unit) = value.split_at(split);
+    let amount: i64 = amount.parse().ok()?;
+    let duration = match unit {
+        "s" => chrono::Duration::try_seconds(amount),
+        "m" => chrono::Duration::try_minutes(amount),
+        "h" => chrono::Duration::try_hours(amount),
+        "d" => chrono::Duration::try_days(amount),
+        "w" => chrono::Duration::try_weeks(amount),
+        _ => None,
+    }?;
+    // Callers add it to or subtract it from now, which must stay representable
+    let now = chrono::Utc::now();
+    now.checked_sub_signed(duration)?;
+    now.checked_add_signed(duration)?;
+    Some(duration)
+}
+
+/// Parse a point in time given as a relative age ("30m", "2h", "1d", "1w"),
+/// a date ("2026-01-31", its start), a natural form ("yesterday 5pm",
+/// "last monday"), or an RFC 3339 timestamp; see `dates`
+fn parse_time(value: &str) -> Result<chrono::DateTime<chrono::Utc>, AgentBoardError> {
+    let now = chrono::Utc::now();
+    dates::parse(value, now, dates::default_zone(), dates::Bias::Past)
+        .or_else(|| parse_duration(value).and_then(|age| now.checked_sub_signed(age)))
+        .ok_or_else(|| {
+            AgentBoardError::InvalidArgs(format!(
+                "Invalid time: {}. Use e.g. 30m, 2h, 1d, 1w, 2026-01-31, yesterday 5pm, last monday, or an RFC 3339 timestamp",
+                value
+            ))
+        })
+}
+
+/// Parse a due date: a duration from now ("3d"), a date (end of that day),
+/// a natural form ("tomorrow 5pm", "next friday"), or an RFC 3339 timestamp;
+/// see `dates`
+fn parse_due(value: &str) -> Result<chrono::DateTime<chrono::Utc>, AgentBoardError> {
+    let now = chrono::Utc::now();
+    dates::parse(value, now, dates::default_zone(), dates::Bias::Future)
+        .or_else(|| parse_duration(value).and_then(|d| now.checked_add_signed(d)))
+        .ok_or_else(|| {
+            AgentBoardError::InvalidArgs(format!(
+                "Invalid due date: {}. Use e.g. 3d, 1w, 2026-01-31, tomorrow 5pm, next friday, or an RFC 3339 timestamp",
+                value
+            ))
+        })
+}
+
+/// Parse an effort estimate ("30m", "4h", "2d") into minutes
+fn parse_estimate(value: &str) -> Result<i64, AgentBoardError> {
+    parse_duration(value)
+        .map(|d| d.num_minutes())
+        .filter(|minutes| *minutes > 0)
+        .ok_or_else(|| {
+            AgentBoardError::InvalidArgs(format!(
+                "Invalid estimate: {}. Use e.g. 30m, 4h, or 2d",
+                value
+            ))
+        })
+}
+
+fn date_range(
+    created_after: Option<String>,
+    created_before: Option<String>,
+    updated_since: Option<String>,
+) -> Result<models::DateRange, AgentBoardError> {
+    Ok(models::DateRange {
+        created_after: created_after.as_deref().map(parse_time).transpose()?,
+        created_before: created_before.as_deref().map(parse_time).transpose()?,
+        updated_since: updated_since.as_deref().map(parse_time).transpose()?,
+    })
+}
+
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -386,6 +2162,10 @@ pub enum AgentBoardError {
     PermissionDenied(String),
     #[error("Session conflict: {0}")]
     SessionConflict(String),
+    #[error("Policy violation: {0}")]
+    PolicyViolation(String),
+    #[error("Rate limited: {0}")]
+    RateLimited(String),
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
     #[error("JSON error: {0}")]
@@ -394,14 +2174,20 @@ pub enum AgentBoardError {
 
 impl AgentBoardError {
     pub fn exit_code(&self) -> ExitCode {
+        ExitCode::from(self.code())
+    }
+
+    pub fn code(&self) -> u8 {
         match self {
-            AgentBoardError::General(_) => ExitCode::from(1),
-            AgentBoardError::InvalidArgs(_) => ExitCode::from(2),
-            AgentBoardError::NotFound(_) => ExitCode::from(4),
-            AgentBoardError::PermissionDenied(_) => ExitCode::from(5),
-            AgentBoardError::SessionConflict(_) => ExitCode::from(6),
-            AgentBoardError::Io(_) => ExitCode::from(1),
-            AgentBoardError::Json(_) => ExitCode::from(1),
+            AgentBoardError::General(_) => 1,
+            AgentBoardError::InvalidArgs(_) => 2,
+            AgentBoardError::NotFound(_) => 4,
+            AgentBoardError::PermissionDenied(_) => 5,
+            AgentBoardError::SessionConflict(_) => 6,
+            AgentBoardError::PolicyViolation(_) => 7,
+            AgentBoardError::RateLimited(_) => 8,
+            AgentBoardError::Io(_) => 1,
+            AgentBoardError::Json(_) => 1,
         }
     }
 }
@@ -0,0 +1,26 @@
+-- Namespaces: boards and agents are scoped to a namespace so several teams
+-- can share one database. Agent names become unique per namespace.
+
+ALTER TABLE boards ADD COLUMN namespace TEXT NOT NULL DEFAULT 'default';
+
+CREATE TABLE agents_new (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    command TEXT NOT NULL,
+    working_directory TEXT NOT NULL,
+    description TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    deactivated_at TEXT,
+    namespace TEXT NOT NULL DEFAULT 'default',
+    UNIQUE (namespace, name)
+);
+
+INSERT INTO agents_new (id, name, command, working_directory, description, created_at, updated_at, deactivated_at)
+SELECT id, name, command, working_directory, description, created_at, updated_at, deactivated_at FROM agents;
+
+DROP TABLE agents;
+ALTER TABLE agents_new RENAME TO agents;
+
+CREATE INDEX IF NOT EXISTS idx_boards_namespace ON boards(namespace);
+CREATE INDEX IF NOT EXISTS idx_agents_namespace ON agents(namespace);
@@ -0,0 +1,9 @@
+-- Composite indexes for the hot list/filter paths. Single-column indexes on
+-- cards(assigned_to), card_tags(card_id), comments(card_id) and
+-- checklist_items(card_id) already exist in schema.sql.
+
+-- list cards <board> [--status] (deleted rows filtered by default)
+CREATE INDEX IF NOT EXISTS idx_cards_board_status_deleted ON cards(board_id, status, deleted_at);
+
+-- Comments are always read per card in creation order
+CREATE INDEX IF NOT EXISTS idx_comments_card_created ON comments(card_id, created_at);
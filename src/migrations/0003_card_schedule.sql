@@ -0,0 +1,5 @@
+-- Scheduling fields: when a card is due and how long it is expected to take.
+ALTER TABLE cards ADD COLUMN due_at TEXT;
+ALTER TABLE cards ADD COLUMN estimate_minutes INTEGER;
+
+CREATE INDEX IF NOT EXISTS idx_cards_due_at ON cards(due_at);
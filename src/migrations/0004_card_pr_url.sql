@@ -0,0 +1,2 @@
+-- Link to the pull request that implements a card (checked by done policies).
+ALTER TABLE cards ADD COLUMN pr_url TEXT;
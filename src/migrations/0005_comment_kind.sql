@@ -0,0 +1,4 @@
+-- Optional comment kind (status_update, question, review, blocker).
+ALTER TABLE comments ADD COLUMN kind TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_comments_kind ON comments(kind);
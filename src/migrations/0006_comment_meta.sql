@@ -0,0 +1,2 @@
+-- Machine-readable JSON attached to a comment, returned verbatim.
+ALTER TABLE comments ADD COLUMN meta TEXT;
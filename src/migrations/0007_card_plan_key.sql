@@ -0,0 +1,3 @@
+-- Stable key a plan file uses to find the card it created on a later apply.
+ALTER TABLE cards ADD COLUMN plan_key TEXT;
+CREATE INDEX IF NOT EXISTS idx_cards_board_plan_key ON cards(board_id, plan_key);
@@ -0,0 +1,3 @@
+-- Deleted comments and checklist items are hidden, not removed.
+ALTER TABLE comments ADD COLUMN deleted_at TEXT;
+ALTER TABLE checklist_items ADD COLUMN deleted_at TEXT;
@@ -0,0 +1,2 @@
+-- Large comment bodies live in `blobs`; the comment keeps a preview.
+ALTER TABLE comments ADD COLUMN blob_hash TEXT;
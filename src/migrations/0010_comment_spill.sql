@@ -0,0 +1,2 @@
+-- Comments over the board's size limit show a preview; the full body stays in `blobs`.
+ALTER TABLE comments ADD COLUMN spilled INTEGER NOT NULL DEFAULT 0;
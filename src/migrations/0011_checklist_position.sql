@@ -0,0 +1,4 @@
+-- Explicit checklist order; rowid order is not guaranteed to survive VACUUM.
+ALTER TABLE checklist_items ADD COLUMN position INTEGER NOT NULL DEFAULT 0;
+UPDATE checklist_items SET position = rowid;
+CREATE INDEX IF NOT EXISTS idx_checklist_items_position ON checklist_items(card_id, position);
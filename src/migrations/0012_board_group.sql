@@ -0,0 +1,2 @@
+-- Optional group (folder) a board is listed under; NULL means ungrouped.
+ALTER TABLE boards ADD COLUMN group_name TEXT;
@@ -0,0 +1,2 @@
+-- Announcement shown at the top of the board's views; NULL means none.
+ALTER TABLE boards ADD COLUMN pinned_note TEXT;
@@ -0,0 +1,2 @@
+-- Who ran the command when it was run `--as` the event's actor; NULL otherwise.
+ALTER TABLE events ADD COLUMN via TEXT;
@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,6 +12,63 @@ pub struct Agent {
     pub updated_at: DateTime<Utc>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deactivated_at: Option<DateTime<Utc>>,
+    pub namespace: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub capabilities: Vec<String>,
+    /// More directories the agent works in (worktrees, monorepo subdirs)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_workdirs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<AgentScope>, // None: unrestricted
+    /// Workload and recent activity; filled by `list agents`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub activity: Option<AgentActivity>,
+}
+
+/// What an agent is doing, for the `list agents` fleet overview
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AgentActivity {
+    /// Assigned cards that aren't done
+    pub open_cards: usize,
+    /// Latest event the agent caused (move, comment, assignment, ...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_active_at: Option<DateTime<Utc>>,
+    /// Most recently updated in-progress card assigned to the agent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_card_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_card_name: Option<String>,
+}
+
+/// What an agent may change while running as AGENT_BOARD_AGENT_ID
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum AgentAccess {
+    /// Any change
+    #[default]
+    Full,
+    /// Comments and reactions only
+    CommentOnly,
+    /// No changes at all
+    ReadOnly,
+}
+
+impl std::fmt::Display for AgentAccess {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AgentAccess::Full => write!(f, "full"),
+            AgentAccess::CommentOnly => write!(f, "comment-only"),
+            AgentAccess::ReadOnly => write!(f, "read-only"),
+        }
+    }
+}
+
+/// Restrictions on an agent's writes; reads are never restricted
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AgentScope {
+    pub access: AgentAccess,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub board_id: Option<String>, // changes limited to this board
 }
 
 /// Parameters for updating an agent
@@ -21,6 +78,12 @@ pub struct AgentUpdate {
     pub command: Option<String>,
     pub description: Option<String>,
     pub working_directory: Option<String>,
+    pub add_workdirs: Vec<String>,
+    pub remove_workdirs: Vec<String>,
+    pub add_capabilities: Vec<String>,
+    pub remove_capabilities: Vec<String>,
+    pub access: Option<AgentAccess>,
+    pub scope_board: Option<Option<String>>, // Some(None) lifts the board restriction
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, clap::ValueEnum)]
@@ -43,15 +106,437 @@ impl std::fmt::Display for Status {
     }
 }
 
+/// One per-board key/value setting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoardSetting {
+    pub key: String,
+    pub value: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Storage settings in effect, for `db status`
+#[derive(Debug, Clone, Serialize)]
+pub struct DbStatus {
+    pub path: String,
+    pub size_bytes: u64,
+    pub journal_mode: String,
+    pub synchronous: String,
+    pub schema_version: usize, // migrations applied
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filesystem: Option<String>, // Linux only
+    pub local_cache: bool,     // working on a temporary copy
+    pub read_only: bool,
+    pub single_writer: bool,
+}
+
+/// Things that happen to cards, recorded in `events` and matched by hooks
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    CardCreated,
+    CardMoved,
+    CardAssigned,
+    CommentAdded,
+    CardStale,    // flagged by `sweep`
+    CardReopened, // moved back out of done by `reopen`
+    CardDueSoon,  // reminded by `sweep`
+}
+
+impl std::fmt::Display for EventKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EventKind::CardCreated => write!(f, "card_created"),
+            EventKind::CardMoved => write!(f, "card_moved"),
+            EventKind::CardAssigned => write!(f, "card_assigned"),
+            EventKind::CommentAdded => write!(f, "comment_added"),
+            EventKind::CardStale => write!(f, "card_stale"),
+            EventKind::CardReopened => write!(f, "card_reopened"),
+            EventKind::CardDueSoon => write!(f, "card_due_soon"),
+        }
+    }
+}
+
+/// Most mutations one agent may make in a sliding window, from
+/// `AGENT_BOARD_MUTATION_BUDGET` (e.g. `100/1h`)
+#[derive(Debug, Clone, Copy)]
+pub struct MutationBudget {
+    pub limit: usize,
+    pub window: chrono::Duration,
+}
+
 /// Parameters for updating a card
 #[derive(Debug, Default)]
 pub struct CardUpdate {
     pub name: Option<String>,
     pub description: Option<String>,
+    pub append_description: Option<String>, // added to the end of the current description
     pub status: Option<Status>,
     pub session_id: Option<Option<String>>, // None = no change, Some(None) = unassign, Some(Some(x)) = assign
     pub add_tags: Vec<String>,
     pub remove_tags: Vec<String>,
+    pub add_labels: Vec<String>, // label names on the card's board
+    pub remove_labels: Vec<String>,
+    pub due_at: Option<Option<DateTime<Utc>>>, // None = no change, Some(None) = clear
+    pub estimate_minutes: Option<Option<i64>>,
+    pub pr_url: Option<Option<String>>,
+}
+
+/// One change of a card's assignee; `agent_id` is None when the card was unassigned
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Assignment {
+    pub id: String,
+    pub card_id: String,
+    pub agent_id: Option<String>,
+    pub previous_agent_id: Option<String>,
+    pub assigned_by: Option<String>,
+    pub assigned_at: DateTime<Utc>,
+}
+
+/// A value a card field had before it was replaced
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardRevision {
+    pub id: String,
+    pub card_id: String,
+    pub field: HistoryField,
+    pub value: Option<String>,
+    pub replaced_by: Option<String>,
+    pub replaced_at: DateTime<Utc>,
+}
+
+/// Which change history `history` shows
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryField {
+    /// Assignee changes
+    Assignee,
+    /// Earlier versions of the description
+    Description,
+}
+
+impl std::fmt::Display for HistoryField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HistoryField::Assignee => write!(f, "assignee"),
+            HistoryField::Description => write!(f, "description"),
+        }
+    }
+}
+
+/// Who must sign off on a gated card before it can move past pending_review
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum GateRequirement {
+    /// A person, i.e. `gate approve` run without AGENT_BOARD_AGENT_ID
+    Human,
+}
+
+impl std::fmt::Display for GateRequirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GateRequirement::Human => write!(f, "human"),
+        }
+    }
+}
+
+/// An approval a card needs before it can be done; open while `approved_at` is None
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Gate {
+    pub id: String,
+    pub card_id: String,
+    pub requirement: GateRequirement,
+    pub created_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub approved_by: Option<String>,
+    pub approved_at: Option<DateTime<Utc>>,
+}
+
+/// Creation/update time bounds shared by board and card listings
+#[derive(Debug, Default)]
+pub struct DateRange {
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+    pub updated_since: Option<DateTime<Utc>>,
+}
+
+/// Order for `list boards`; starred boards always come first
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum BoardSort {
+    /// Alphabetical by name
+    Name,
+    /// Newest first
+    Created,
+    /// Most recently used first, then newest
+    #[default]
+    Recent,
+}
+
+/// Filters for listing boards
+#[derive(Debug, Default)]
+pub struct BoardFilter {
+    pub include_deleted: bool,
+    pub dates: DateRange,
+    pub sort: BoardSort,
+    /// Only boards in this group (case-insensitive)
+    pub group: Option<String>,
+}
+
+/// Layout options for the pretty (kanban) board view
+#[derive(Debug, Default)]
+pub struct KanbanOptions {
+    /// Columns shown as just their card count
+    pub collapse: Vec<Status>,
+    /// Cards shown per column before a "…and N more" footer
+    pub max_cards_per_column: Option<usize>,
+    /// Cards matching this term get heavy borders; the rest are dimmed
+    pub highlight: Option<String>,
+}
+
+/// Filters for listing cards on a board
+#[derive(Debug, Default)]
+pub struct CardFilter {
+    pub status: Option<Status>,
+    pub assigned_to: Option<String>,
+    pub tags: Vec<String>, // card must have ALL of these
+    pub include_deleted: bool,
+    pub dates: DateRange,
+}
+
+/// An open card that can't make progress, with the reasons it was flagged
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockedCard {
+    pub card: Card,
+    pub waiting_on: Vec<String>, // dependencies that aren't done yet
+    pub tagged_blocked: bool,
+    pub stale_since: Option<DateTime<Utc>>, // last update, when older than --stale
+}
+
+/// How a dated card stands against its due date in `report schedule`
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduleRisk {
+    OnTrack,
+    /// The assignee's queue finishes it after the due date
+    Late,
+    /// Already past the due date
+    Overdue,
+    Unassigned,
+    /// Can't be projected without an estimate
+    NoEstimate,
+}
+
+impl std::fmt::Display for ScheduleRisk {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            ScheduleRisk::OnTrack => "on track",
+            ScheduleRisk::Late => "late",
+            ScheduleRisk::Overdue => "overdue",
+            ScheduleRisk::Unassigned => "unassigned",
+            ScheduleRisk::NoEstimate => "no estimate",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// An open card with a due date, projected against its assignee's queue
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleEntry {
+    pub card: Card,
+    /// None when unassigned or without an estimate
+    pub projected_finish: Option<DateTime<Utc>>,
+    pub risk: ScheduleRisk,
+    /// An agent who could finish it by the due date, for late and unassigned cards
+    pub suggested_agent: Option<String>,
+}
+
+/// The dependency chain of open cards with the most estimated work
+#[derive(Debug, Clone, Serialize)]
+pub struct CriticalPath {
+    pub board_id: String,
+    /// First card to do first; may include cards on other boards it waits for
+    pub cards: Vec<Card>,
+    /// Sum of the estimates on the path (unestimated cards count as none)
+    pub total_minutes: i64,
+}
+
+/// A done card moved back by `reopen`, from its `card_reopened` event
+#[derive(Debug, Clone, Serialize)]
+pub struct Reopen {
+    pub card_id: String,
+    pub card_name: String,
+    pub reopened_at: DateTime<Utc>,
+    pub reopened_by: Option<String>,
+    /// Who held the card when it was reopened, i.e. whose work didn't stick
+    pub assigned_to: Option<String>,
+    pub to: Status,
+    pub reason: String,
+}
+
+/// Net effect on one card in a `diff` window
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Completed,
+    Reopened,
+    Moved,
+    Reassigned,
+}
+
+impl std::fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChangeKind::Added => write!(f, "added"),
+            ChangeKind::Completed => write!(f, "completed"),
+            ChangeKind::Reopened => write!(f, "reopened"),
+            ChangeKind::Moved => write!(f, "moved"),
+            ChangeKind::Reassigned => write!(f, "reassigned"),
+        }
+    }
+}
+
+/// One changelog line: `from`/`to` are statuses, or agent IDs for reassignments
+#[derive(Debug, Clone, Serialize)]
+pub struct BoardChange {
+    pub card_id: String,
+    pub card_name: String,
+    pub kind: ChangeKind,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub at: DateTime<Utc>,  // last event behind this change
+    pub by: Option<String>, // actor of that event
+}
+
+/// What happened on a board between two points in time
+#[derive(Debug, Clone, Serialize)]
+pub struct BoardDiff {
+    pub board_id: String,
+    pub board_name: String,
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    pub changes: Vec<BoardChange>,
+}
+
+/// A stakeholder summary of a board over a window, for `digest`
+#[derive(Debug, Clone, Serialize)]
+pub struct Digest {
+    pub board_id: String,
+    pub board_name: String,
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    pub completed: Vec<BoardChange>,
+    pub started: Vec<BoardChange>, // entered in_progress
+    pub blocked: Vec<BlockedCard>,
+    pub due_soon: Vec<Card>, // open, due before `due_before` (overdue included)
+    pub due_before: DateTime<Utc>,
+}
+
+/// A stretch of time a card spent in one status; `left_at` is None for the
+/// current status
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusSpan {
+    pub status: Status,
+    pub entered_at: DateTime<Utc>,
+    pub left_at: Option<DateTime<Utc>>,
+    pub minutes: i64,
+}
+
+/// An open card and how long it has been in its current status, for `report aging`
+#[derive(Debug, Clone, Serialize)]
+pub struct AgingCard {
+    pub card: Card,
+    pub in_status_since: DateTime<Utc>,
+    pub minutes_in_status: i64,
+    pub over_threshold: bool,
+}
+
+/// A card waiting in pending_review, for `report review-queue`
+#[derive(Debug, Clone, Serialize)]
+pub struct ReviewQueueEntry {
+    pub card: Card,
+    pub board_name: String,
+    pub in_review_since: DateTime<Utc>,
+    pub minutes_in_review: i64,
+    /// Who approved the card's gate, else the author of the latest review
+    /// comment since it entered review, else `human` for an open gate
+    pub reviewer: Option<String>,
+}
+
+/// A card ranked by how closely its name matches the text given to `similar`
+#[derive(Debug, Clone, Serialize)]
+pub struct SimilarCard {
+    pub card: Card,
+    pub board_name: String,
+    /// Trigram similarity of the names, 0.0 to 1.0
+    pub score: f64,
+}
+
+/// A card `sweep` found idle for longer than its board's staleness policy
+#[derive(Debug, Clone, Serialize)]
+pub struct StaleCard {
+    pub card: Card,
+    pub active_at: DateTime<Utc>, // last update, event, or comment
+    pub policy: String,           // the setting that matched, e.g. "stale.in_progress=24h"
+}
+
+/// An open card coming due within its board's `due.remind_before` policy
+#[derive(Debug, Clone, Serialize)]
+pub struct DueReminder {
+    pub card: Card,
+    pub policy: String, // e.g. "due.remind_before=24h"
+}
+
+/// Events `sweep` pruned from one board under its `retention.events` policy
+#[derive(Debug, Clone, Serialize)]
+pub struct PrunedEvents {
+    pub board_id: String,
+    pub board_name: String,
+    pub policy: String, // e.g. "retention.events=90d"
+    pub events: usize,
+}
+
+/// A step of `sweep`, in the order they run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SweepStep {
+    /// Tag cards idle past `stale.<status>`
+    Stale,
+    /// Remind about cards due within `due.remind_before`
+    DueSoon,
+    /// Delete events older than `retention.events`
+    Retention,
+    /// Record today's board stats
+    Stats,
+}
+
+/// What `sweep` did; a step that didn't run is None
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SweepSummary {
+    pub dry_run: bool,
+    pub stale: Option<Vec<StaleCard>>,
+    pub due_soon: Option<Vec<DueReminder>>,
+    pub retention: Option<Vec<PrunedEvents>>,
+    /// Never recorded in a dry run
+    pub stats: Option<Vec<BoardStats>>,
+}
+
+/// Filters for listing events
+#[derive(Debug, Default)]
+pub struct EventFilter {
+    pub board_id: Option<String>,
+    pub card_id: Option<String>,
+    pub event: Option<EventKind>,
+    pub since: Option<DateTime<Utc>>,
+    pub limit: Option<usize>, // keeps the most recent N
+}
+
+/// Filters for listing comments on a card
+#[derive(Debug, Default)]
+pub struct CommentFilter {
+    pub author: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub limit: Option<usize>, // keeps the most recent N
+    pub reverse: bool,        // newest first
+    pub kind: Option<CommentKind>,
+    pub full: bool, // full text of oversized comments instead of their preview
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, clap::ValueEnum, Default)]
@@ -63,6 +548,18 @@ pub enum OutputFormat {
     Simple,
     /// Visual kanban board display
     Pretty,
+    /// Graphviz DOT (graph command only)
+    Dot,
+    /// Mermaid diagram (graph command only)
+    Mermaid,
+    /// Markdown table (reports only)
+    Markdown,
+    /// Plain-text document for an LLM prompt (describe only)
+    Prompt,
+    /// GitHub Actions workflow annotations (check and audit only)
+    Gha,
+    /// HTML email with Subject and MIME headers (digest only)
+    Email,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -74,6 +571,24 @@ pub struct Board {
     pub updated_at: DateTime<Utc>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deleted_at: Option<DateTime<Utc>>,
+    pub namespace: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub starred: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_used_at: Option<DateTime<Utc>>,
+    /// Group (folder) `list boards` shows the board under
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// Announcement shown at the top of the kanban view, listings and briefs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinned_note: Option<String>,
+    /// Live cards by status; filled by `list boards`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub card_counts: Option<StatusCounts>,
+    /// Content hash set by `get`: changes whenever the board or any of its
+    /// cards do
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -85,12 +600,48 @@ pub struct Card {
     pub status: Status,
     pub assigned_to: Option<String>,
     pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<Label>,
     /// Single checklist per card (items stored directly)
     pub checklist: Vec<ChecklistItem>,
+    /// IDs of cards that must be done before this one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deleted_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate_minutes: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pr_url: Option<String>,
+    /// Key from the plan file that created this card (see `plan apply`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plan_key: Option<String>,
+    /// Live comments on the card; None when the listing skipped hydration (`--brief`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment_count: Option<usize>,
+    /// Content hash set by `get`: changes whenever the card, its checklist,
+    /// labels, dependencies, or comments do
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+}
+
+/// A tag in use on a board and how many of its cards carry it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagCount {
+    pub tag: String,
+    pub cards: usize,
+}
+
+/// A board-scoped label; `color` is a terminal color name (e.g. "red", "bright blue")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Label {
+    pub id: String,
+    pub name: String,
+    pub color: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -107,6 +658,274 @@ pub struct Comment {
     pub author: Option<String>,
     pub text: String,
     pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<CommentKind>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Box<serde_json::value::RawValue>>, // caller-defined JSON, kept verbatim
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full_size: Option<usize>, // set when the body was over the size limit: `text` is a preview unless expanded
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reactions: Vec<Reaction>,
+}
+
+/// One emoji on a comment and who reacted with it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Reaction {
+    pub emoji: String,
+    pub count: usize,
+    pub authors: Vec<String>, // agent IDs; anonymous reactions are counted but not listed
+}
+
+/// What a comment is for, so structured updates can be filtered
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum CommentKind {
+    StatusUpdate,
+    Question,
+    Review,
+    Blocker,
+}
+
+impl std::fmt::Display for CommentKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CommentKind::StatusUpdate => write!(f, "status_update"),
+            CommentKind::Question => write!(f, "question"),
+            CommentKind::Review => write!(f, "review"),
+            CommentKind::Blocker => write!(f, "blocker"),
+        }
+    }
+}
+
+/// A recorded event; also the JSON payload hooks receive on stdin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Event {
+    pub id: String,
+    pub event: EventKind,
+    pub board_id: String,
+    pub card_id: String,
+    pub actor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub via: Option<String>, // who ran the command as `actor` (`--as`)
+    pub data: serde_json::Value, // card_moved: from/to, card_assigned: from/to, comment_added: comment
+    pub created_at: DateTime<Utc>,
+}
+
+/// Board hygiene rules for `check`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum CheckRule {
+    /// Open card past its due date
+    Overdue,
+    /// In-progress card with no assignee
+    UnassignedInProgress,
+    /// More cards in a status than the board's `wip.<status>` limit
+    WipExceeded,
+}
+
+impl std::fmt::Display for CheckRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CheckRule::Overdue => write!(f, "overdue"),
+            CheckRule::UnassignedInProgress => write!(f, "unassigned-in-progress"),
+            CheckRule::WipExceeded => write!(f, "wip-exceeded"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckViolation {
+    pub rule: CheckRule,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub card_id: Option<String>,
+    pub message: String,
+}
+
+/// Result of `check`: passed is false when any violation was found
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckReport {
+    pub board_id: String,
+    pub rules: Vec<CheckRule>,
+    pub passed: bool,
+    pub violations: Vec<CheckViolation>,
+}
+
+/// One finding in an audit: a card, tag, or agent ID with a short detail
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditItem {
+    pub id: String,
+    pub detail: String,
+}
+
+/// Board hygiene findings from `audit`
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditReport {
+    pub board_id: String,
+    pub missing_description: Vec<AuditItem>,
+    pub unassigned_in_progress: Vec<AuditItem>,
+    pub stale_review: Vec<AuditItem>, // in pending_review since before --stale
+    pub single_use_tags: Vec<AuditItem>, // tag used on one card only (likely a typo)
+    pub idle_agents: Vec<AuditItem>,  // active agents with no events or comments since --idle
+}
+
+/// Per-agent activity over a reporting window, for `report agents`
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentStats {
+    pub agent_id: String,
+    pub name: String,
+    pub completed: usize,               // cards moved to done in the window
+    pub avg_cycle_minutes: Option<i64>, // first in_progress (or creation) to done
+    pub comments: usize,                // comments posted in the window
+    pub holding: usize,                 // open cards currently assigned
+}
+
+/// A card with its latest activity, for `recent`
+#[derive(Debug, Clone, Serialize)]
+pub struct RecentCard {
+    pub card: Card,
+    pub active_at: DateTime<Utc>,
+    pub last_event: Option<Event>, // None when only untracked fields (e.g., tags) changed
+}
+
+/// What an agent needs to resume work, for `brief`
+#[derive(Debug, Clone, Serialize)]
+pub struct Brief {
+    pub agent_id: String,
+    pub since: Option<DateTime<Utc>>, // None on the first brief
+    pub generated_at: DateTime<Utc>,
+    /// Open cards assigned to the agent
+    pub cards: Vec<Card>,
+    /// New comments by others on those cards
+    pub comments: Vec<Comment>,
+    /// New comments on other cards that mention `@name` or `@agent_id`
+    pub mentions: Vec<Comment>,
+    /// Assigned cards whose last unfinished dependency was done since the last brief
+    pub unblocked: Vec<String>,
+    /// Pinned notes of the boards holding the assigned cards
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pinned_notes: Vec<PinnedNote>,
+}
+
+/// A board's pinned note, as listed in a brief
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinnedNote {
+    pub board_id: String,
+    pub board_name: String,
+    pub note: String,
+}
+
+/// A shell command run when a matching event fires
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Hook {
+    pub id: String,
+    pub event: EventKind,
+    pub command: String,
+    pub board_id: Option<String>, // None matches every board
+    pub status: Option<Status>,   // card_moved only: new status to match
+    pub created_at: DateTime<Utc>,
+}
+
+/// Chat service a notifier posts to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum NotifierKind {
+    Slack,
+    Discord,
+}
+
+impl std::fmt::Display for NotifierKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NotifierKind::Slack => write!(f, "slack"),
+            NotifierKind::Discord => write!(f, "discord"),
+        }
+    }
+}
+
+/// A chat webhook posted to when a board's cards move into `statuses`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Notifier {
+    pub id: String,
+    pub board_id: String,
+    pub kind: NotifierKind,
+    pub url: String,
+    pub statuses: Vec<Status>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Any entity resolvable by `get`, tagged with its type in JSON
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Entity {
+    Agent(Agent),
+    Board {
+        board: Board,
+        summary: BoardSummary,
+    },
+    Card {
+        card: Card,
+        comments: Vec<Comment>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        history: Option<Vec<Assignment>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        timings: Option<Vec<StatusSpan>>,
+    },
+    Comment {
+        comment: Comment,
+        card: Card,
+    },
+    ChecklistItem {
+        item: ChecklistItem,
+        card: Card,
+    },
+}
+
+/// A card reached from the described one through dependencies; depth 1 is a direct link
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelatedCard {
+    pub id: String,
+    pub name: String,
+    pub status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assigned_to: Option<String>,
+    pub depth: usize,
+}
+
+/// Everything about a card an agent needs in one document (`describe`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardContext {
+    pub card: Card,
+    pub board: Board,
+    pub board_summary: BoardSummary,
+    /// Registered agent holding the card; None when unassigned or free-form
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<Agent>,
+    /// Cards this one waits for, transitively up to the requested depth
+    pub depends_on: Vec<RelatedCard>,
+    /// Cards waiting for this one, transitively up to the requested depth
+    pub dependents: Vec<RelatedCard>,
+    /// Most recent comments, oldest first
+    pub recent_comments: Vec<Comment>,
+}
+
+/// What deleting or purging a board would remove
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoardImpact {
+    pub board_id: String,
+    pub board_name: String,
+    pub cards: usize,
+    pub comments: usize,
+    pub checklist_items: usize,
+}
+
+/// Token that confirms one `delete board` or `purge board` on a board that
+/// requires confirmation
+#[derive(Debug, Clone, Serialize)]
+pub struct RemovalToken {
+    pub token: String,
+    pub action: String, // "delete" or "purge"
+    pub expires_at: DateTime<Utc>,
+    pub impact: BoardImpact,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -116,11 +935,300 @@ pub struct BoardSummary {
     pub pending_review_count: usize,
     pub done_count: usize,
     pub total_cards: usize,
+    pub agents: Vec<AgentLoad>,
+}
+
+/// Live card counts by status; `open` is everything not done
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct StatusCounts {
+    pub todo: usize,
+    pub in_progress: usize,
+    pub pending_review: usize,
+    pub done: usize,
+    pub open: usize,
+    pub total: usize,
+}
+
+impl StatusCounts {
+    pub fn add(&mut self, status: &Status, n: usize) {
+        match status {
+            Status::Todo => self.todo += n,
+            Status::InProgress => self.in_progress += n,
+            Status::PendingReview => self.pending_review += n,
+            Status::Done => self.done += n,
+        }
+        if *status != Status::Done {
+            self.open += n;
+        }
+        self.total += n;
+    }
+
+    pub fn merge(&mut self, other: &StatusCounts) {
+        self.todo += other.todo;
+        self.in_progress += other.in_progress;
+        self.pending_review += other.pending_review;
+        self.done += other.done;
+        self.open += other.open;
+        self.total += other.total;
+    }
+}
+
+/// One board's card counts on one day (UTC); a later snapshot the same day
+/// replaces the earlier one
+#[derive(Debug, Clone, Serialize)]
+pub struct BoardStats {
+    pub board_id: String,
+    pub day: NaiveDate,
+    #[serde(flatten)]
+    pub counts: StatusCounts,
+}
+
+/// Per-assignee card counts on a board
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentLoad {
+    pub agent_id: String,
+    pub open: usize, // any status except done
+    pub in_progress: usize,
+    pub done_this_week: usize, // moved to done in the last 7 days
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub name: String,
+    pub path: String,
+    pub size_bytes: u64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// What `db maintain --gc-blobs` removed
+#[derive(Debug, Clone, Serialize)]
+pub struct BlobGcReport {
+    pub blobs_removed: usize,
+    pub bytes_freed: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct AgentBoardData {
-    pub boards: Vec<Board>,
-    pub cards: Vec<Card>,
-    pub comments: Vec<Comment>,
+pub struct MergeReport {
+    pub agents_added: usize,
+    pub agents_updated: usize,
+    pub boards_added: usize,
+    pub boards_updated: usize,
+    pub cards_added: usize,
+    pub cards_updated: usize,
+    pub comments_added: usize,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeConflict {
+    pub entity_id: String,
+    pub reason: String,
+}
+
+/// Latency summary for one benchmarked operation (milliseconds)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchResult {
+    pub operation: String,
+    pub iterations: usize,
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+    pub ops_per_sec: f64,
+}
+
+/// Work breakdown read from a plan file by `plan apply`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Plan {
+    pub cards: Vec<PlanCard>,
+}
+
+/// One declared card; `key` identifies it across applies
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PlanCard {
+    pub key: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub status: Option<Status>, // only used when the card is created
+    #[serde(default)]
+    pub assignee: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub checklist: Vec<String>,
+    #[serde(default)]
+    pub depends_on: Vec<String>, // keys of other cards in the plan
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanAction {
+    Create,
+    Update,
+    Unchanged,
+    Orphaned, // created by an earlier apply, no longer in the plan
+}
+
+/// What applying a plan does (or did) to one card
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanCardChange {
+    pub key: String,
+    pub card_id: Option<String>, // None for a card not created yet
+    pub name: String,
+    pub action: PlanAction,
+    pub changes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanDiff {
+    pub board_id: String,
+    pub cards: Vec<PlanCardChange>,
+}
+
+/// A card to create from another tool's export; `board` is a board name,
+/// None for the import's default board
+#[derive(Debug, Clone)]
+pub struct ImportedCard {
+    pub board: Option<String>,
+    pub name: String,
+    pub description: Option<String>,
+    pub status: Status,
+    pub due_at: Option<DateTime<Utc>>,
+    pub tags: Vec<String>,
+    pub comments: Vec<String>,
+}
+
+/// What an import created (or, with `dry_run`, would create)
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub boards_created: Vec<String>, // names
+    pub cards_created: usize,
+    pub comments_added: usize,
+    pub skipped: usize,
+}
+
+/// One operation in an `apply` document; `op` selects the variant
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case", deny_unknown_fields)]
+pub enum Operation {
+    CreateBoard {
+        name: String,
+        #[serde(default)]
+        description: Option<String>,
+    },
+    CreateCard {
+        #[serde(default)]
+        board: Option<String>, // name or ID; falls back to --board
+        name: String,
+        #[serde(default)]
+        description: Option<String>,
+        #[serde(default)]
+        status: Option<Status>,
+        #[serde(default)]
+        due: Option<String>,
+        #[serde(default)]
+        estimate: Option<String>,
+        #[serde(default)]
+        tags: Vec<String>,
+        #[serde(default)]
+        labels: Vec<String>,
+    },
+    UpdateCard {
+        card: String,
+        #[serde(default)]
+        name: Option<String>,
+        #[serde(default)]
+        description: Option<String>,
+        #[serde(default)]
+        append_description: Option<String>,
+        #[serde(default)]
+        status: Option<Status>,
+        // For these, JSON null clears the field and a missing key leaves it alone
+        #[serde(default, deserialize_with = "nullable")]
+        assign: Option<Option<String>>,
+        #[serde(default)]
+        force: bool, // assign an identity that isn't a registered agent
+        #[serde(default, deserialize_with = "nullable")]
+        due: Option<Option<String>>,
+        #[serde(default, deserialize_with = "nullable")]
+        estimate: Option<Option<String>>,
+        #[serde(default, deserialize_with = "nullable")]
+        pr_url: Option<Option<String>>,
+        #[serde(default)]
+        add_tags: Vec<String>,
+        #[serde(default)]
+        remove_tags: Vec<String>,
+        #[serde(default)]
+        add_labels: Vec<String>,
+        #[serde(default)]
+        remove_labels: Vec<String>,
+    },
+    Comment {
+        card: String,
+        text: String,
+        #[serde(default)]
+        kind: Option<CommentKind>,
+    },
+    Done {
+        card: String,
+        comment: String,
+        #[serde(default)]
+        review: bool,
+    },
+    Reopen {
+        card: String,
+        reason: String,
+        #[serde(default)]
+        to: Option<Status>,
+    },
+    AddDependency {
+        card: String,
+        depends_on: String,
+    },
+    DeleteCard {
+        card: String,
+    },
+}
+
+impl Operation {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Operation::CreateBoard { .. } => "create_board",
+            Operation::CreateCard { .. } => "create_card",
+            Operation::UpdateCard { .. } => "update_card",
+            Operation::Comment { .. } => "comment",
+            Operation::Done { .. } => "done",
+            Operation::Reopen { .. } => "reopen",
+            Operation::AddDependency { .. } => "add_dependency",
+            Operation::DeleteCard { .. } => "delete_card",
+        }
+    }
+}
+
+/// Keep an explicit JSON null as `Some(None)` so it can mean "clear"
+fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// Outcome of one `apply` operation, in document order
+#[derive(Debug, Clone, Serialize)]
+pub struct OperationResult {
+    pub index: usize,
+    pub op: String,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>, // the created or affected entity
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<u8>, // what the equivalent CLI command would exit with
 }
@@ -0,0 +1,254 @@
+//! Operation documents for `apply`: board changes as JSON on stdin, results
+//! as JSON on stdout.
+//!
+//! ```json
+//! [
+//!   {"op": "create_card", "board": "backend", "name": "Add rate limits", "tags": ["api"]},
+//!   {"op": "update_card", "card": "card_abc123", "status": "in_progress"},
+//!   {"op": "comment", "card": "card_abc123", "text": "Started on the middleware"}
+//! ]
+//! ```
+
+use crate::AgentBoardError;
+use crate::db::Database;
+use crate::models::{CardUpdate, Operation, OperationResult, Status};
+
+/// Parse a document holding one operation object or an array of them. Every
+/// operation is parsed before any runs, so malformed JSON changes nothing.
+pub fn parse(input: &str) -> Result<Vec<Operation>, AgentBoardError> {
+    let value: serde_json::Value = serde_json::from_str(input)
+        .map_err(|e| AgentBoardError::InvalidArgs(format!("Invalid operations JSON: {}", e)))?;
+    let values = match value {
+        serde_json::Value::Array(values) => values,
+        value => vec![value],
+    };
+    if values.is_empty() {
+        return Err(AgentBoardError::InvalidArgs(
+            "No operations to apply".into(),
+        ));
+    }
+    values
+        .into_iter()
+        .enumerate()
+        .map(|(index, value)| {
+            serde_json::from_value(value)
+                .map_err(|e| AgentBoardError::InvalidArgs(format!("Operation {}: {}", index, e)))
+        })
+        .collect()
+}
+
+/// Run operations in order. Unless `keep_going` is set, the batch is one
+/// transaction: the first failure stops it and rolls back the operations
+/// before it, which are then reported as not applied. The failure is returned
+/// alongside the results so the caller can exit with its code.
+pub async fn run(
+    db: &Database,
+    ops: Vec<Operation>,
+    board_context: Option<&str>,
+    keep_going: bool,
+) -> (Vec<OperationResult>, Option<AgentBoardError>) {
+    if keep_going {
+        return run_each(db, ops, board_context, true).await;
+    }
+    if let Err(e) = db.begin().await {
+        return (Vec::new(), Some(e));
+    }
+    let (mut results, error) = run_each(db, ops, board_context, false).await;
+    let Some(error) = error else {
+        return match db.commit().await {
+            Ok(()) => (results, None),
+            Err(e) => (results, Some(e)),
+        };
+    };
+    db.rollback().await;
+    for result in results.iter_mut().filter(|r| r.ok) {
+        result.ok = false;
+        result.id = None;
+        result.error = Some("Rolled back: a later operation in the batch failed".into());
+    }
+    (results, Some(error))
+}
+
+async fn run_each(
+    db: &Database,
+    ops: Vec<Operation>,
+    board_context: Option<&str>,
+    keep_going: bool,
+) -> (Vec<OperationResult>, Option<AgentBoardError>) {
+    let mut results = Vec::with_capacity(ops.len());
+    let mut first_error = None;
+    for (index, op) in ops.into_iter().enumerate() {
+        let name = op.name().to_string();
+        match run_one(db, op, board_context).await {
+            Ok(id) => results.push(OperationResult {
+                index,
+                op: name,
+                ok: true,
+                id,
+                error: None,
+                exit_code: None,
+            }),
+            Err(e) => {
+                results.push(OperationResult {
+                    index,
+                    op: name,
+                    ok: false,
+                    id: None,
+                    error: Some(e.to_string()),
+                    exit_code: Some(e.code()),
+                });
+                first_error.get_or_insert(e);
+                if !keep_going {
+                    break;
+                }
+            }
+        }
+    }
+    (results, first_error)
+}
+
+async fn run_one(
+    db: &Database,
+    op: Operation,
+    board_context: Option<&str>,
+) -> Result<Option<String>, AgentBoardError> {
+    let agent_id = db.actor().map(str::to_string);
+    match op {
+        Operation::CreateBoard { name, description } => {
+            let board = db.create_board(name, description).await?;
+            Ok(Some(board.id))
+        }
+        Operation::CreateCard {
+            board,
+            name,
+            description,
+            status,
+            due,
+            estimate,
+            tags,
+            labels,
+        } => {
+            let board_id = match (board, board_context) {
+                (Some(board), _) => db.resolve_board(&board).await?.id,
+                (None, Some(context)) => context.to_string(),
+                (None, None) => {
+                    return Err(AgentBoardError::InvalidArgs(
+                        "create_card needs a \"board\", or pass --board before apply".into(),
+                    ));
+                }
+            };
+            let due_at = due.as_deref().map(crate::parse_due).transpose()?;
+            let estimate_minutes = estimate.as_deref().map(crate::parse_estimate).transpose()?;
+            let card = db
+                .create_card(
+                    &board_id,
+                    name,
+                    description,
+                    status.unwrap_or(Status::Todo),
+                    due_at,
+                    estimate_minutes,
+                    &labels,
+                )
+                .await?;
+            if !tags.is_empty() {
+                let update = CardUpdate {
+                    add_tags: tags,
+                    ..Default::default()
+                };
+                db.update_card(&card.id, update).await?;
+            }
+            db.touch_board(&board_id).await?;
+            Ok(Some(card.id))
+        }
+        Operation::UpdateCard {
+            card,
+            name,
+            description,
+            append_description,
+            status,
+            assign,
+            force,
+            due,
+            estimate,
+            pr_url,
+            add_tags,
+            remove_tags,
+            add_labels,
+            remove_labels,
+        } => {
+            if let Some(Some(id)) = &assign
+                && !force
+            {
+                db.get_agent(id).await.map_err(|_| {
+                    AgentBoardError::NotFound(format!(
+                        "No active agent with ID {} (set \"force\": true to assign anyway)",
+                        id
+                    ))
+                })?;
+            }
+            // Same rule as `update card`: starting work claims the card for this agent
+            let session_id = match assign {
+                Some(assign) => Some(assign),
+                None if status == Some(Status::InProgress) => Some(Some(agent_id.ok_or_else(
+                    || {
+                        AgentBoardError::InvalidArgs(
+                            "Setting status to in_progress requires AGENT_BOARD_AGENT_ID or an \"assign\" field".into(),
+                        )
+                    },
+                )?)),
+                None => None,
+            };
+            let update = CardUpdate {
+                name,
+                description,
+                append_description,
+                status,
+                session_id,
+                add_tags,
+                remove_tags,
+                add_labels,
+                remove_labels,
+                due_at: due
+                    .map(|due| due.as_deref().map(crate::parse_due).transpose())
+                    .transpose()?,
+                estimate_minutes: estimate
+                    .map(|e| e.as_deref().map(crate::parse_estimate).transpose())
+                    .transpose()?,
+                pr_url,
+            };
+            db.update_card(&card, update).await?;
+            Ok(Some(card))
+        }
+        Operation::Comment { card, text, kind } => {
+            let comment = db.add_comment(&card, text, agent_id, kind, None).await?;
+            Ok(Some(comment.id))
+        }
+        Operation::Done {
+            card,
+            comment,
+            review,
+        } => {
+            let status = if review {
+                Status::PendingReview
+            } else {
+                Status::Done
+            };
+            let comment = db.close_card(&card, comment, status).await?;
+            Ok(Some(comment.id))
+        }
+        Operation::Reopen { card, reason, to } => {
+            let comment = db
+                .reopen_card(&card, reason, to.unwrap_or(Status::Todo))
+                .await?;
+            Ok(Some(comment.id))
+        }
+        Operation::AddDependency { card, depends_on } => {
+            db.add_dependency(&card, &depends_on).await?;
+            Ok(Some(card))
+        }
+        Operation::DeleteCard { card } => {
+            db.delete_card(&card).await?;
+            Ok(Some(card))
+        }
+    }
+}
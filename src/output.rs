@@ -1,6 +1,56 @@
+use crate::highlight;
+use crate::kanban;
 use crate::models::*;
+use chrono::{DateTime, Utc};
 use colored::Colorize;
-use tabled::{Table, Tabled, settings::Style};
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::OnceLock;
+use tabled::settings::{Style, Width, peaker::PriorityMax};
+use tabled::{Table, Tabled};
+
+/// Agent names by ID, loaded once per run so tables can show who is who
+static AGENT_NAMES: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+pub fn set_agent_names(names: HashMap<String, String>) {
+    let _ = AGENT_NAMES.set(names);
+}
+
+/// How tables fit long values: `--no-truncate`, `--max-width`, and the
+/// `width.<column>` database settings
+#[derive(Debug, Default)]
+pub struct TableLayout {
+    pub no_truncate: bool,
+    pub max_width: Option<usize>,
+    pub columns: HashMap<String, usize>,
+}
+
+static TABLE_LAYOUT: OnceLock<TableLayout> = OnceLock::new();
+
+pub fn set_table_layout(layout: TableLayout) {
+    let _ = TABLE_LAYOUT.set(layout);
+}
+
+/// Name of a known agent, or the raw value (e.g., a free-form author)
+pub(crate) fn agent_name(id: &str) -> &str {
+    AGENT_NAMES
+        .get()
+        .and_then(|names| names.get(id))
+        .map(String::as_str)
+        .unwrap_or(id)
+}
+
+/// Display an agent ID as `name (agent_abc123…)`; JSON output keeps raw IDs
+fn agent_label(id: &str) -> String {
+    match AGENT_NAMES.get().and_then(|names| names.get(id)) {
+        Some(name) => {
+            let short: String = id.chars().take(12).collect();
+            format!("{} ({}…)", name, short)
+        }
+        None => id.to_string(),
+    }
+}
 
 #[derive(Tabled)]
 struct AgentRow {
@@ -10,12 +60,302 @@ struct AgentRow {
     name: String,
     #[tabled(rename = "Command")]
     command: String,
+    #[tabled(rename = "Capabilities")]
+    capabilities: String,
+    #[tabled(rename = "Open")]
+    open_cards: String,
+    #[tabled(rename = "Working On")]
+    working_on: String,
+    #[tabled(rename = "Last Active")]
+    last_active: String,
     #[tabled(rename = "Working Directory")]
     working_directory: String,
     #[tabled(rename = "Created")]
     created_at: String,
 }
 
+#[derive(Tabled)]
+struct AgentLoadRow {
+    #[tabled(rename = "Agent")]
+    agent: String,
+    #[tabled(rename = "Open")]
+    open: usize,
+    #[tabled(rename = "In Progress")]
+    in_progress: usize,
+    #[tabled(rename = "Done (7d)")]
+    done_this_week: usize,
+}
+
+#[derive(Tabled)]
+struct BoardSettingRow {
+    #[tabled(rename = "Key")]
+    key: String,
+    #[tabled(rename = "Value")]
+    value: String,
+    #[tabled(rename = "Updated")]
+    updated_at: String,
+}
+
+#[derive(Tabled)]
+struct CheckViolationRow {
+    #[tabled(rename = "Rule")]
+    rule: String,
+    #[tabled(rename = "Card")]
+    card: String,
+    #[tabled(rename = "Problem")]
+    message: String,
+}
+
+#[derive(Tabled)]
+struct AgentStatsRow {
+    #[tabled(rename = "Agent")]
+    agent: String,
+    #[tabled(rename = "Completed")]
+    completed: usize,
+    #[tabled(rename = "Avg Cycle")]
+    avg_cycle: String,
+    #[tabled(rename = "Comments")]
+    comments: usize,
+    #[tabled(rename = "Holding")]
+    holding: usize,
+}
+
+#[derive(Tabled)]
+struct RecentCardRow {
+    #[tabled(rename = "Active")]
+    active_at: String,
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Status")]
+    status: String,
+    #[tabled(rename = "Last Change")]
+    last_change: String,
+}
+
+#[derive(Tabled)]
+struct HookRow {
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "Event")]
+    event: String,
+    #[tabled(rename = "Board")]
+    board: String,
+    #[tabled(rename = "Command")]
+    command: String,
+}
+
+#[derive(Tabled)]
+struct NotifierRow {
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "Board")]
+    board: String,
+    #[tabled(rename = "Kind")]
+    kind: String,
+    #[tabled(rename = "On")]
+    on: String,
+    #[tabled(rename = "URL")]
+    url: String,
+}
+
+#[derive(Tabled)]
+struct EventRow {
+    #[tabled(rename = "Time")]
+    time: String,
+    #[tabled(rename = "Event")]
+    event: String,
+    #[tabled(rename = "Card")]
+    card: String,
+    #[tabled(rename = "By")]
+    actor: String,
+    #[tabled(rename = "Details")]
+    details: String,
+}
+
+#[derive(Tabled)]
+struct AgingCardRow {
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Status")]
+    status: String,
+    #[tabled(rename = "Assigned To")]
+    assigned_to: String,
+    #[tabled(rename = "In Status")]
+    in_status: String,
+    #[tabled(rename = "Since")]
+    since: String,
+}
+
+#[derive(Tabled)]
+struct BoardStatsRow {
+    #[tabled(rename = "Board")]
+    board_id: String,
+    #[tabled(rename = "Day")]
+    day: String,
+    #[tabled(rename = "Todo")]
+    todo: usize,
+    #[tabled(rename = "In Progress")]
+    in_progress: usize,
+    #[tabled(rename = "Pending Review")]
+    pending_review: usize,
+    #[tabled(rename = "Done")]
+    done: usize,
+}
+
+#[derive(Tabled)]
+struct SimilarRow {
+    #[tabled(rename = "Match")]
+    score: String,
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Status")]
+    status: String,
+    #[tabled(rename = "Board")]
+    board: String,
+}
+
+#[derive(Tabled)]
+struct ReviewQueueRow {
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Board")]
+    board: String,
+    #[tabled(rename = "Assignee")]
+    assigned_to: String,
+    #[tabled(rename = "Reviewer")]
+    reviewer: String,
+    #[tabled(rename = "PR")]
+    pr: String,
+    #[tabled(rename = "In Review")]
+    in_review: String,
+}
+
+#[derive(Tabled)]
+struct StaleCardRow {
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Status")]
+    status: String,
+    #[tabled(rename = "Assigned To")]
+    assigned_to: String,
+    #[tabled(rename = "Last Activity")]
+    active_at: String,
+    #[tabled(rename = "Policy")]
+    policy: String,
+}
+
+#[derive(Tabled)]
+struct DueReminderRow {
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Status")]
+    status: String,
+    #[tabled(rename = "Assigned To")]
+    assigned_to: String,
+    #[tabled(rename = "Due")]
+    due_at: String,
+    #[tabled(rename = "Policy")]
+    policy: String,
+}
+
+#[derive(Tabled)]
+struct BlockedCardRow {
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Status")]
+    status: String,
+    #[tabled(rename = "Assigned To")]
+    assigned_to: String,
+    #[tabled(rename = "Reasons")]
+    reasons: String,
+}
+
+#[derive(Tabled)]
+struct ScheduleRow {
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Assigned To")]
+    assigned_to: String,
+    #[tabled(rename = "Estimate")]
+    estimate: String,
+    #[tabled(rename = "Due")]
+    due: String,
+    #[tabled(rename = "Projected")]
+    projected: String,
+    #[tabled(rename = "Risk")]
+    risk: String,
+    #[tabled(rename = "Suggest")]
+    suggest: String,
+}
+
+#[derive(Tabled)]
+struct CriticalPathRow {
+    #[tabled(rename = "#")]
+    step: usize,
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Status")]
+    status: String,
+    #[tabled(rename = "Owner")]
+    owner: String,
+    #[tabled(rename = "Estimate")]
+    estimate: String,
+    #[tabled(rename = "Cumulative")]
+    cumulative: String,
+}
+
+#[derive(Tabled)]
+struct ReopenRow {
+    #[tabled(rename = "Reopened")]
+    reopened_at: String,
+    #[tabled(rename = "Card")]
+    card_id: String,
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Worked By")]
+    assigned_to: String,
+    #[tabled(rename = "Reopened By")]
+    reopened_by: String,
+    #[tabled(rename = "To")]
+    to: String,
+    #[tabled(rename = "Reason")]
+    reason: String,
+}
+
+#[derive(Tabled)]
+struct ChangeRow {
+    #[tabled(rename = "Change")]
+    kind: String,
+    #[tabled(rename = "Card")]
+    card_id: String,
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Detail")]
+    detail: String,
+    #[tabled(rename = "When")]
+    at: String,
+    #[tabled(rename = "By")]
+    by: String,
+}
+
 #[derive(Tabled)]
 struct CardRow {
     #[tabled(rename = "ID")]
@@ -26,12 +366,78 @@ struct CardRow {
     status: String,
     #[tabled(rename = "Assigned To")]
     assigned_to: String,
+    #[tabled(rename = "Labels")]
+    labels: String,
+    #[tabled(rename = "Comments")]
+    comments: String,
     #[tabled(rename = "Board")]
     board_id: String,
     #[tabled(rename = "Created")]
     created_at: String,
 }
 
+#[derive(Tabled)]
+struct GateRow {
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "Requires")]
+    requirement: String,
+    #[tabled(rename = "Status")]
+    status: String,
+    #[tabled(rename = "Created")]
+    created_at: String,
+    #[tabled(rename = "Approved By")]
+    approved_by: String,
+    #[tabled(rename = "Approved At")]
+    approved_at: String,
+}
+
+#[derive(Tabled)]
+struct TagCountRow {
+    #[tabled(rename = "Tag")]
+    tag: String,
+    #[tabled(rename = "Cards")]
+    cards: usize,
+}
+
+#[derive(Tabled)]
+struct LabelRow {
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Color")]
+    color: String,
+    #[tabled(rename = "ID")]
+    id: String,
+}
+
+#[derive(Tabled)]
+struct SnapshotRow {
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Size")]
+    size: String,
+    #[tabled(rename = "Created")]
+    created_at: String,
+}
+
+#[derive(Tabled)]
+struct BenchRow {
+    #[tabled(rename = "Operation")]
+    operation: String,
+    #[tabled(rename = "N")]
+    iterations: usize,
+    #[tabled(rename = "p50 (ms)")]
+    p50: String,
+    #[tabled(rename = "p90 (ms)")]
+    p90: String,
+    #[tabled(rename = "p99 (ms)")]
+    p99: String,
+    #[tabled(rename = "max (ms)")]
+    max: String,
+    #[tabled(rename = "ops/s")]
+    ops_per_sec: String,
+}
+
 #[derive(Tabled)]
 struct BoardRow {
     #[tabled(rename = "ID")]
@@ -40,19 +446,156 @@ struct BoardRow {
     name: String,
     #[tabled(rename = "Description")]
     description: String,
+    #[tabled(rename = "Open")]
+    open_cards: String,
+    #[tabled(rename = "Total")]
+    total_cards: String,
     #[tabled(rename = "Created")]
     created_at: String,
+    #[tabled(rename = "Last Used")]
+    last_used_at: String,
+}
+
+/// Render one label in its color; unknown colors fall back to plain text
+pub(crate) fn label_text(label: &Label) -> String {
+    match label.color.parse::<colored::Color>() {
+        Ok(color) => label.name.color(color).to_string(),
+        Err(_) => label.name.clone(),
+    }
+}
+
+fn labels_text(labels: &[Label]) -> String {
+    labels.iter().map(label_text).collect::<Vec<_>>().join(", ")
+}
+
+pub fn print_gates(out: &mut impl Write, gates: &[Gate], format: OutputFormat) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&gates).unwrap())?;
+        }
+        OutputFormat::Table => {
+            if gates.is_empty() {
+                writeln!(out, "No gates found.")?;
+                return Ok(());
+            }
+            let rows: Vec<GateRow> = gates
+                .iter()
+                .map(|g| GateRow {
+                    id: g.id.clone(),
+                    requirement: g.requirement.to_string(),
+                    status: if g.approved_at.is_some() {
+                        "approved".to_string()
+                    } else {
+                        "pending".to_string()
+                    },
+                    created_at: g.created_at.format("%Y-%m-%d %H:%M").to_string(),
+                    approved_by: g.approved_by.clone().unwrap_or_else(|| "-".to_string()),
+                    approved_at: g
+                        .approved_at
+                        .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                })
+                .collect();
+            let table = rounded_table(rows);
+            writeln!(out, "{}", table)?;
+        }
+        OutputFormat::Simple => {
+            for gate in gates {
+                writeln!(out, "{}", gate.id)?;
+            }
+        }
+        _ => {
+            // Pretty, graph, and markdown formats don't apply to gates, fall back to table
+            print_gates(out, gates, OutputFormat::Table)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn print_tag_counts(
+    out: &mut impl Write,
+    tags: &[TagCount],
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&tags).unwrap())?;
+        }
+        OutputFormat::Table => {
+            if tags.is_empty() {
+                writeln!(out, "No tags found.")?;
+                return Ok(());
+            }
+            let rows: Vec<TagCountRow> = tags
+                .iter()
+                .map(|t| TagCountRow {
+                    tag: t.tag.clone(),
+                    cards: t.cards,
+                })
+                .collect();
+            let table = rounded_table(rows);
+            writeln!(out, "{}", table)?;
+        }
+        OutputFormat::Simple => {
+            for tag in tags {
+                writeln!(out, "{}", tag.tag)?;
+            }
+        }
+        _ => {
+            // Pretty, graph, and markdown formats don't apply to tag lists, fall back to table
+            print_tag_counts(out, tags, OutputFormat::Table)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn print_labels(
+    out: &mut impl Write,
+    labels: &[Label],
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&labels).unwrap())?;
+        }
+        OutputFormat::Table => {
+            if labels.is_empty() {
+                writeln!(out, "No labels found.")?;
+                return Ok(());
+            }
+            let rows: Vec<LabelRow> = labels
+                .iter()
+                .map(|l| LabelRow {
+                    name: label_text(l),
+                    color: l.color.clone(),
+                    id: l.id.clone(),
+                })
+                .collect();
+            let table = rounded_table(rows);
+            writeln!(out, "{}", table)?;
+        }
+        OutputFormat::Simple => {
+            for label in labels {
+                writeln!(out, "{}", label.name)?;
+            }
+        }
+        _ => {
+            // Pretty, graph, and markdown formats don't apply to label lists, fall back to table
+            print_labels(out, labels, OutputFormat::Table)?;
+        }
+    }
+    Ok(())
 }
 
-pub fn print_cards(cards: &[Card], format: OutputFormat) {
+pub fn print_cards(out: &mut impl Write, cards: &[Card], format: OutputFormat) -> io::Result<()> {
     match format {
         OutputFormat::Json => {
-            println!("{}", serde_json::to_string_pretty(&cards).unwrap());
+            writeln!(out, "{}", serde_json::to_string_pretty(&cards).unwrap())?;
         }
         OutputFormat::Table => {
             if cards.is_empty() {
-                println!("No cards found.");
-                return;
+                writeln!(out, "No cards found.")?;
+                return Ok(());
             }
             let rows: Vec<CardRow> = cards
                 .iter()
@@ -64,466 +607,2295 @@ pub fn print_cards(cards: &[Card], format: OutputFormat) {
                     };
                     CardRow {
                         id: c.id.clone(),
-                        name: format!("{}{}", truncate(&c.name, 35), deleted_marker),
+                        name: format!("{}{}", cell("name", &c.name, 35), deleted_marker),
                         status: c.status.to_string(),
-                        assigned_to: c.assigned_to.clone().unwrap_or_else(|| "-".to_string()),
+                        assigned_to: c
+                            .assigned_to
+                            .as_deref()
+                            .map(agent_label)
+                            .unwrap_or_else(|| "-".to_string()),
+                        labels: if c.labels.is_empty() {
+                            "-".to_string()
+                        } else {
+                            labels_text(&c.labels)
+                        },
+                        comments: c
+                            .comment_count
+                            .map(|n| n.to_string())
+                            .unwrap_or_else(|| "-".to_string()),
                         board_id: c.board_id.clone(),
                         created_at: c.created_at.format("%Y-%m-%d %H:%M").to_string(),
                     }
                 })
                 .collect();
-            let table = Table::new(rows).with(Style::rounded()).to_string();
-            println!("{}", table);
+            let table = rounded_table(rows);
+            writeln!(out, "{}", table)?;
         }
         OutputFormat::Simple => {
             for card in cards {
-                println!("{}", card.id);
+                writeln!(out, "{}", card.id)?;
+            }
+        }
+        _ => {
+            // Pretty, graph, and markdown formats don't apply to card lists, fall back to table
+            print_cards(out, cards, OutputFormat::Table)?;
+        }
+    }
+    Ok(())
+}
+
+/// Cards grouped by assignee, for supervisors looking across agents
+pub fn print_cards_by_agent(
+    out: &mut impl Write,
+    cards: &[Card],
+    format: OutputFormat,
+) -> io::Result<()> {
+    let mut groups: std::collections::BTreeMap<&str, Vec<Card>> = std::collections::BTreeMap::new();
+    for card in cards {
+        if let Some(agent_id) = card.assigned_to.as_deref() {
+            groups.entry(agent_id).or_default().push(card.clone());
+        }
+    }
+
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&groups).unwrap())?;
+        }
+        OutputFormat::Simple => {
+            for (agent_id, cards) in &groups {
+                for card in cards {
+                    writeln!(out, "{}\t{}", agent_id, card.id)?;
+                }
+            }
+        }
+        _ => {
+            if groups.is_empty() {
+                writeln!(out, "No assigned cards.")?;
+                return Ok(());
+            }
+            for (agent_id, cards) in &groups {
+                writeln!(out, "{} - {} card(s)", agent_label(agent_id), cards.len())?;
+                print_cards(out, cards, OutputFormat::Table)?;
+                writeln!(out)?;
             }
         }
-        OutputFormat::Pretty => {
-            // Pretty format doesn't apply to card lists, fall back to table
-            print_cards(cards, OutputFormat::Table);
+    }
+    Ok(())
+}
+
+pub fn print_card(
+    out: &mut impl Write,
+    card: &Card,
+    comments: &[Comment],
+    history: Option<&[Assignment]>,
+    timings: Option<&[StatusSpan]>,
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            let mut output = serde_json::json!({
+                "card": card,
+                "comments": comments
+            });
+            if let Some(history) = history {
+                output["history"] = serde_json::json!(history);
+            }
+            if let Some(timings) = timings {
+                output["timings"] = serde_json::json!(timings);
+            }
+            writeln!(out, "{}", serde_json::to_string_pretty(&output).unwrap())?;
+        }
+        OutputFormat::Table => {
+            writeln!(out, "Card: {}", card.id)?;
+            writeln!(out, "Name: {}", card.name)?;
+            writeln!(out, "Board: {}", card.board_id)?;
+            writeln!(out, "Status: {}", card.status)?;
+            writeln!(
+                out,
+                "Assigned To: {}",
+                card.assigned_to
+                    .as_deref()
+                    .map(agent_label)
+                    .unwrap_or_else(|| "-".to_string())
+            )?;
+            if let Some(desc) = &card.description {
+                writeln!(out, "Description: {}", desc)?;
+            }
+            if !card.tags.is_empty() {
+                writeln!(out, "Tags: {}", card.tags.join(", "))?;
+            }
+            if !card.labels.is_empty() {
+                writeln!(out, "Labels: {}", labels_text(&card.labels))?;
+            }
+            if !card.depends_on.is_empty() {
+                writeln!(out, "Depends On: {}", card.depends_on.join(", "))?;
+            }
+            if let Some(due) = card.due_at {
+                writeln!(out, "Due: {}", due.format("%Y-%m-%d %H:%M"))?;
+            }
+            if let Some(minutes) = card.estimate_minutes {
+                writeln!(out, "Estimate: {}", format_minutes(minutes))?;
+            }
+            if let Some(url) = &card.pr_url {
+                writeln!(out, "PR: {}", url)?;
+            }
+            if let Some(key) = &card.plan_key {
+                writeln!(out, "Plan Key: {}", key)?;
+            }
+            if !card.checklist.is_empty() {
+                writeln!(out, "\nChecklist:")?;
+                for item in &card.checklist {
+                    let check = if item.checked { "x" } else { " " };
+                    writeln!(out, "  [{}] {} ({})", check, item.text, item.id)?;
+                }
+            }
+            if !comments.is_empty() {
+                writeln!(out, "\nComments:")?;
+                for comment in comments {
+                    let author = comment
+                        .author
+                        .as_deref()
+                        .map(agent_label)
+                        .unwrap_or_else(|| "anonymous".to_string());
+                    let time = comment.created_at.format("%Y-%m-%d %H:%M");
+                    writeln!(
+                        out,
+                        "  [{}] {} ({}){}",
+                        author,
+                        time,
+                        comment.id,
+                        comment_kind_badge(comment.kind)
+                    )?;
+                    for line in highlight::comment_text(&comment.text).lines() {
+                        writeln!(out, "    {}", line)?;
+                    }
+                    if !comment.reactions.is_empty() {
+                        writeln!(
+                            out,
+                            "    {}",
+                            reactions_summary(&comment.reactions).dimmed()
+                        )?;
+                    }
+                }
+            }
+            if let Some(history) = history {
+                writeln!(out, "\nAssignment History:")?;
+                if history.is_empty() {
+                    writeln!(out, "  (no assignment changes recorded)")?;
+                }
+                for entry in history {
+                    writeln!(out, "  {}", assignment_line(entry))?;
+                }
+            }
+            if let Some(timings) = timings {
+                writeln!(out, "\nTime in Status:")?;
+                for span in timings {
+                    let left = span
+                        .left_at
+                        .map(|at| at.format("%Y-%m-%d %H:%M").to_string())
+                        .unwrap_or_else(|| "now".to_string());
+                    writeln!(
+                        out,
+                        "  {:<15} {} -> {:<16}  {}",
+                        span.status.to_string(),
+                        span.entered_at.format("%Y-%m-%d %H:%M"),
+                        left,
+                        format_duration_minutes(span.minutes)
+                    )?;
+                }
+            }
+        }
+        OutputFormat::Simple => {
+            writeln!(out, "{}", card.id)?;
+        }
+        _ => {
+            // Pretty, graph, and markdown formats don't apply to single card, fall back to table
+            print_card(out, card, comments, history, timings, OutputFormat::Table)?;
+        }
+    }
+    Ok(())
+}
+
+/// One line of assignment history: "2026-01-02 15:04  alice -> bob (by carol)"
+fn assignment_line(entry: &Assignment) -> String {
+    let from = entry
+        .previous_agent_id
+        .as_deref()
+        .map(agent_label)
+        .unwrap_or_else(|| "-".to_string());
+    let to = entry
+        .agent_id
+        .as_deref()
+        .map(agent_label)
+        .unwrap_or_else(|| "-".to_string());
+    let mut line = format!(
+        "{}  {} -> {}",
+        entry.assigned_at.format("%Y-%m-%d %H:%M"),
+        from,
+        to
+    );
+    if let Some(by) = &entry.assigned_by {
+        line.push_str(&format!(" (by {})", agent_label(by)));
+    }
+    line
+}
+
+pub fn print_assignments(
+    out: &mut impl Write,
+    assignments: &[Assignment],
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(
+                out,
+                "{}",
+                serde_json::to_string_pretty(&assignments).unwrap()
+            )?;
+        }
+        OutputFormat::Simple => {
+            for entry in assignments {
+                writeln!(out, "{}", entry.agent_id.as_deref().unwrap_or("-"))?;
+            }
+        }
+        _ => {
+            if assignments.is_empty() {
+                writeln!(out, "No assignment changes recorded.")?;
+                return Ok(());
+            }
+            for entry in assignments {
+                writeln!(out, "{}", assignment_line(entry))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn print_board_settings(
+    out: &mut impl Write,
+    settings: &[BoardSetting],
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            let map: serde_json::Map<String, serde_json::Value> = settings
+                .iter()
+                .map(|s| (s.key.clone(), s.value.clone().into()))
+                .collect();
+            writeln!(out, "{}", serde_json::to_string_pretty(&map).unwrap())?;
+        }
+        OutputFormat::Simple => {
+            for setting in settings {
+                writeln!(out, "{}={}", setting.key, setting.value)?;
+            }
+        }
+        _ => {
+            if settings.is_empty() {
+                writeln!(out, "No settings found.")?;
+                return Ok(());
+            }
+            let rows: Vec<BoardSettingRow> = settings
+                .iter()
+                .map(|s| BoardSettingRow {
+                    key: s.key.clone(),
+                    value: s.value.clone(),
+                    updated_at: s.updated_at.format("%Y-%m-%d %H:%M").to_string(),
+                })
+                .collect();
+            let table = rounded_table(rows);
+            writeln!(out, "{}", table)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn print_hooks(out: &mut impl Write, hooks: &[Hook], format: OutputFormat) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&hooks).unwrap())?;
+        }
+        OutputFormat::Simple => {
+            for hook in hooks {
+                writeln!(out, "{}", hook.id)?;
+            }
+        }
+        _ => {
+            if hooks.is_empty() {
+                writeln!(out, "No hooks found.")?;
+                return Ok(());
+            }
+            let rows: Vec<HookRow> = hooks
+                .iter()
+                .map(|h| HookRow {
+                    id: h.id.clone(),
+                    event: match &h.status {
+                        Some(s) => format!("{} -> {}", h.event, s),
+                        None => h.event.to_string(),
+                    },
+                    board: h.board_id.clone().unwrap_or_else(|| "(all)".to_string()),
+                    command: cell("command", &h.command, 50),
+                })
+                .collect();
+            let table = rounded_table(rows);
+            writeln!(out, "{}", table)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn print_notifiers(
+    out: &mut impl Write,
+    notifiers: &[Notifier],
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&notifiers).unwrap())?;
+        }
+        OutputFormat::Simple => {
+            for notifier in notifiers {
+                writeln!(out, "{}", notifier.id)?;
+            }
+        }
+        _ => {
+            if notifiers.is_empty() {
+                writeln!(out, "No notifiers found.")?;
+                return Ok(());
+            }
+            let rows: Vec<NotifierRow> = notifiers
+                .iter()
+                .map(|n| NotifierRow {
+                    id: n.id.clone(),
+                    board: n.board_id.clone(),
+                    kind: n.kind.to_string(),
+                    on: n
+                        .statuses
+                        .iter()
+                        .map(|s| s.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    url: cell("url", &n.url, 50),
+                })
+                .collect();
+            let table = rounded_table(rows);
+            writeln!(out, "{}", table)?;
+        }
+    }
+    Ok(())
+}
+
+/// One-line summary of an event's data for tables
+fn event_details(event: &Event) -> String {
+    let field = |key: &str| {
+        event
+            .data
+            .get(key)
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+    };
+    match event.event {
+        EventKind::CardCreated => field("name").unwrap_or_default(),
+        EventKind::CardMoved => format!(
+            "{} -> {}",
+            field("from").unwrap_or_default(),
+            field("to").unwrap_or_default()
+        ),
+        EventKind::CardAssigned => format!(
+            "{} -> {}",
+            field("from")
+                .as_deref()
+                .map(agent_label)
+                .unwrap_or_else(|| "-".to_string()),
+            field("to")
+                .as_deref()
+                .map(agent_label)
+                .unwrap_or_else(|| "-".to_string())
+        ),
+        EventKind::CardStale => field("policy").unwrap_or_default(),
+        EventKind::CardDueSoon => format!(
+            "due {}",
+            field("due_at")
+                .and_then(|at| DateTime::parse_from_rfc3339(&at).ok())
+                .map(|at| at.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_default()
+        ),
+        EventKind::CardReopened => format!(
+            "{} -> {}: {}",
+            field("from").unwrap_or_default(),
+            field("to").unwrap_or_default(),
+            cell("detail", &field("reason").unwrap_or_default(), 40)
+        ),
+        EventKind::CommentAdded => event
+            .data
+            .get("comment")
+            .and_then(|c| c.get("text"))
+            .and_then(|t| t.as_str())
+            .map(|t| cell("detail", t, 50))
+            .unwrap_or_default(),
+    }
+}
+
+pub fn print_check_report(
+    out: &mut impl Write,
+    report: &CheckReport,
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&report).unwrap())?;
+        }
+        OutputFormat::Simple => {
+            for v in &report.violations {
+                writeln!(out, "{}\t{}", v.rule, v.card_id.as_deref().unwrap_or("-"))?;
+            }
+        }
+        OutputFormat::Gha => {
+            for v in &report.violations {
+                let message = match &v.card_id {
+                    Some(card_id) => format!("{}: {}", card_id, v.message),
+                    None => v.message.clone(),
+                };
+                writeln!(
+                    out,
+                    "::error title={}::{}",
+                    gha_property(&format!("agent-board check: {}", v.rule)),
+                    gha_message(&message)
+                )?;
+            }
+        }
+        _ => {
+            if report.passed {
+                writeln!(out, "All checks passed on {}", report.board_id)?;
+                return Ok(());
+            }
+            let rows: Vec<CheckViolationRow> = report
+                .violations
+                .iter()
+                .map(|v| CheckViolationRow {
+                    rule: v.rule.to_string(),
+                    card: v.card_id.clone().unwrap_or_else(|| "-".to_string()),
+                    message: v.message.clone(),
+                })
+                .collect();
+            let table = rounded_table(rows);
+            writeln!(out, "{}", table)?;
+        }
+    }
+    Ok(())
+}
+
+/// Result of `plan apply` (or the preview from `plan diff`): one line per
+/// declared card with what changes, then orphaned cards
+pub fn print_plan_diff(
+    out: &mut impl Write,
+    diff: &PlanDiff,
+    applied: bool,
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&diff).unwrap())?;
+        }
+        OutputFormat::Simple => {
+            for change in &diff.cards {
+                writeln!(out, "{}", change.card_id.as_deref().unwrap_or("-"))?;
+            }
+        }
+        _ => {
+            let key_width = diff.cards.iter().map(|c| c.key.len()).max().unwrap_or(0);
+            let (mut created, mut updated, mut unchanged, mut orphaned) = (0, 0, 0, 0);
+            for change in &diff.cards {
+                let (marker, verb) = match change.action {
+                    PlanAction::Create => {
+                        created += 1;
+                        let verb = if applied { "created" } else { "to create" };
+                        ("+".green().bold(), verb)
+                    }
+                    PlanAction::Update => {
+                        updated += 1;
+                        let verb = if applied { "updated" } else { "to update" };
+                        ("~".yellow().bold(), verb)
+                    }
+                    PlanAction::Unchanged => {
+                        unchanged += 1;
+                        (" ".normal(), "unchanged")
+                    }
+                    PlanAction::Orphaned => {
+                        orphaned += 1;
+                        ("-".red().bold(), "orphaned")
+                    }
+                };
+                writeln!(
+                    out,
+                    "{} {:<width$}  {} ({}{})",
+                    marker,
+                    change.key,
+                    change.name,
+                    verb,
+                    change
+                        .card_id
+                        .as_deref()
+                        .map(|id| format!(" {}", id))
+                        .unwrap_or_default(),
+                    width = key_width
+                )?;
+                for note in &change.changes {
+                    writeln!(out, "      {}", note.dimmed())?;
+                }
+            }
+            if applied {
+                writeln!(
+                    out,
+                    "\nPlan applied to {}: {} created, {} updated, {} unchanged, {} orphaned.",
+                    diff.board_id, created, updated, unchanged, orphaned
+                )?;
+            } else {
+                writeln!(
+                    out,
+                    "\nPlan for {}: {} to create, {} to update, {} unchanged, {} orphaned. Nothing was changed.",
+                    diff.board_id, created, updated, unchanged, orphaned
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn print_audit_report(
+    out: &mut impl Write,
+    report: &AuditReport,
+    format: OutputFormat,
+) -> io::Result<()> {
+    let sections = [
+        ("Cards without a description", &report.missing_description),
+        (
+            "In progress with no assignee",
+            &report.unassigned_in_progress,
+        ),
+        ("Stale in pending review", &report.stale_review),
+        ("Tags used on a single card", &report.single_use_tags),
+        ("Idle agents", &report.idle_agents),
+    ];
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&report).unwrap())?;
+        }
+        OutputFormat::Simple => {
+            for (_, items) in sections {
+                for item in items {
+                    writeln!(out, "{}", item.id)?;
+                }
+            }
+        }
+        OutputFormat::Gha => {
+            for (title, items) in sections {
+                for item in items {
+                    writeln!(
+                        out,
+                        "::warning title={}::{}",
+                        gha_property(&format!("agent-board audit: {}", title)),
+                        gha_message(&format!("{}: {}", item.id, item.detail))
+                    )?;
+                }
+            }
+        }
+        _ => {
+            writeln!(out, "Audit of {}", report.board_id.bold())?;
+            for (title, items) in sections {
+                writeln!(out)?;
+                writeln!(out, "{} ({}):", title, items.len())?;
+                if items.is_empty() {
+                    writeln!(out, "  none")?;
+                }
+                for item in items {
+                    writeln!(out, "  {}  {}", item.id, item.detail)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Escape a workflow command message (`::error::<message>`)
+fn gha_message(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a workflow command property (`title=<value>`), which also can't
+/// hold the `:` and `,` separators
+fn gha_property(value: &str) -> String {
+    gha_message(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+pub fn print_agent_stats(
+    out: &mut impl Write,
+    stats: &[AgentStats],
+    since: DateTime<Utc>,
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            let output = serde_json::json!({ "since": since, "agents": stats });
+            writeln!(out, "{}", serde_json::to_string_pretty(&output).unwrap())?;
+        }
+        OutputFormat::Simple => {
+            for s in stats {
+                writeln!(out, "{}", s.agent_id)?;
+            }
+        }
+        _ => {
+            if stats.is_empty() {
+                writeln!(out, "No agents found.")?;
+                return Ok(());
+            }
+            let rows: Vec<AgentStatsRow> = stats
+                .iter()
+                .map(|s| AgentStatsRow {
+                    agent: format!("{} ({})", s.name, s.agent_id),
+                    completed: s.completed,
+                    avg_cycle: s
+                        .avg_cycle_minutes
+                        .map(format_duration_minutes)
+                        .unwrap_or_else(|| "-".to_string()),
+                    comments: s.comments,
+                    holding: s.holding,
+                })
+                .collect();
+            let mut table = Table::new(rows);
+            if format == OutputFormat::Markdown {
+                table.with(Style::markdown());
+            } else {
+                table.with(Style::rounded());
+                fit_width(&mut table);
+                writeln!(out, "Since {}", since.format("%Y-%m-%d %H:%M"))?;
+            }
+            writeln!(out, "{}", table)?;
+        }
+    }
+    Ok(())
+}
+
+/// Render a span of minutes as "2d 3h", "5h 10m", or "45m"
+fn format_duration_minutes(minutes: i64) -> String {
+    let (days, hours, mins) = (minutes / 1440, minutes % 1440 / 60, minutes % 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, mins)
+    } else {
+        format!("{}m", mins)
+    }
+}
+
+pub fn print_recent_cards(
+    out: &mut impl Write,
+    recent: &[RecentCard],
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&recent).unwrap())?;
+        }
+        OutputFormat::Simple => {
+            for entry in recent {
+                writeln!(out, "{}", entry.card.id)?;
+            }
+        }
+        _ => {
+            if recent.is_empty() {
+                writeln!(out, "No recent activity.")?;
+                return Ok(());
+            }
+            let rows: Vec<RecentCardRow> = recent
+                .iter()
+                .map(|entry| {
+                    // Events older than the card's own update don't describe the last change
+                    let last_change = match &entry.last_event {
+                        Some(e) if e.created_at >= entry.card.updated_at => {
+                            let by = e.actor.as_deref().map(agent_name).unwrap_or_default();
+                            let mut change = format!("{}: {}", e.event, event_details(e));
+                            if !by.is_empty() {
+                                change.push_str(&format!(" (by {})", by));
+                            }
+                            change
+                        }
+                        _ => "updated".to_string(),
+                    };
+                    RecentCardRow {
+                        active_at: entry.active_at.format("%Y-%m-%d %H:%M").to_string(),
+                        id: entry.card.id.clone(),
+                        name: cell("name", &entry.card.name, 30),
+                        status: entry.card.status.to_string(),
+                        last_change: cell("last_change", &last_change, 60),
+                    }
+                })
+                .collect();
+            let table = rounded_table(rows);
+            writeln!(out, "{}", table)?;
+        }
+    }
+    Ok(())
+}
+
+/// Compact, plain-text digest for `brief` (meant to be pasted into a prompt)
+/// `describe`: JSON, the IDs involved (simple), or a markdown-style document
+/// meant to be pasted into an LLM prompt (every other format)
+pub fn print_card_context(
+    out: &mut impl Write,
+    ctx: &CardContext,
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&ctx).unwrap())?;
+        }
+        OutputFormat::Simple => {
+            writeln!(out, "{}", ctx.card.id)?;
+            for related in ctx.depends_on.iter().chain(&ctx.dependents) {
+                writeln!(out, "{}", related.id)?;
+            }
+        }
+        _ => {
+            let card = &ctx.card;
+            writeln!(out, "# Card: {} ({})", card.name, card.id)?;
+            writeln!(out, "Status: {}", card.status)?;
+            writeln!(
+                out,
+                "Assigned To: {}",
+                card.assigned_to
+                    .as_deref()
+                    .map(agent_label)
+                    .unwrap_or_else(|| "-".to_string())
+            )?;
+            if !card.tags.is_empty() {
+                writeln!(out, "Tags: {}", card.tags.join(", "))?;
+            }
+            if !card.labels.is_empty() {
+                let names: Vec<&str> = card.labels.iter().map(|l| l.name.as_str()).collect();
+                writeln!(out, "Labels: {}", names.join(", "))?;
+            }
+            if let Some(due) = card.due_at {
+                writeln!(out, "Due: {}", due.format("%Y-%m-%d %H:%M"))?;
+            }
+            if let Some(minutes) = card.estimate_minutes {
+                writeln!(out, "Estimate: {}", format_minutes(minutes))?;
+            }
+            if let Some(url) = &card.pr_url {
+                writeln!(out, "PR: {}", url)?;
+            }
+            if let Some(desc) = card.description.as_deref().filter(|d| !d.is_empty()) {
+                writeln!(out, "\n## Description\n{}", desc)?;
+            }
+            if !card.checklist.is_empty() {
+                writeln!(out, "\n## Checklist")?;
+                for item in &card.checklist {
+                    let check = if item.checked { "x" } else { " " };
+                    writeln!(out, "- [{}] {}", check, item.text)?;
+                }
+            }
+
+            let summary = &ctx.board_summary;
+            writeln!(out, "\n## Board: {} ({})", ctx.board.name, ctx.board.id)?;
+            if let Some(desc) = ctx.board.description.as_deref().filter(|d| !d.is_empty()) {
+                writeln!(out, "{}", desc)?;
+            }
+            writeln!(
+                out,
+                "{} cards: {} todo, {} in progress, {} pending review, {} done",
+                summary.total_cards,
+                summary.todo_count,
+                summary.in_progress_count,
+                summary.pending_review_count,
+                summary.done_count
+            )?;
+
+            if let Some(agent) = &ctx.assignee {
+                writeln!(out, "\n## Assignee: {} ({})", agent.name, agent.id)?;
+                if let Some(desc) = &agent.description {
+                    writeln!(out, "{}", desc)?;
+                }
+                writeln!(out, "Command: {}", agent.command)?;
+                writeln!(out, "Working Directory: {}", agent.working_directory)?;
+                if !agent.capabilities.is_empty() {
+                    writeln!(out, "Capabilities: {}", agent.capabilities.join(", "))?;
+                }
+            }
+
+            for (title, related) in [
+                ("Depends on", &ctx.depends_on),
+                ("Dependents (waiting on this card)", &ctx.dependents),
+            ] {
+                if related.is_empty() {
+                    continue;
+                }
+                writeln!(out, "\n## {}", title)?;
+                for r in related {
+                    let via = if r.depth > 1 {
+                        format!(" (via {} links)", r.depth)
+                    } else {
+                        String::new()
+                    };
+                    let assignee = r
+                        .assigned_to
+                        .as_deref()
+                        .map(|a| format!(" @{}", agent_name(a)))
+                        .unwrap_or_default();
+                    writeln!(
+                        out,
+                        "- [{}] {} ({}){}{}",
+                        r.status, r.name, r.id, assignee, via
+                    )?;
+                }
+            }
+
+            if !ctx.recent_comments.is_empty() {
+                writeln!(out, "\n## Recent comments")?;
+                for comment in &ctx.recent_comments {
+                    let author = comment
+                        .author
+                        .as_deref()
+                        .filter(|a| !a.is_empty())
+                        .map(agent_name)
+                        .unwrap_or("anonymous");
+                    let kind = comment
+                        .kind
+                        .map(|k| format!(" [{}]", k))
+                        .unwrap_or_default();
+                    writeln!(
+                        out,
+                        "- {} {}{}: {}",
+                        comment.created_at.format("%Y-%m-%d %H:%M"),
+                        author,
+                        kind,
+                        comment.text
+                    )?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn print_brief(out: &mut impl Write, brief: &Brief, format: OutputFormat) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&brief).unwrap())?;
+        }
+        OutputFormat::Simple => {
+            // Cards that need a look: new comments, mentions, or newly unblocked
+            let mut ids: Vec<&str> = Vec::new();
+            let touched = brief
+                .comments
+                .iter()
+                .chain(&brief.mentions)
+                .map(|c| c.card_id.as_str())
+                .chain(brief.unblocked.iter().map(String::as_str));
+            for id in touched {
+                if !ids.contains(&id) {
+                    ids.push(id);
+                }
+            }
+            for id in ids {
+                writeln!(out, "{}", id)?;
+            }
+        }
+        _ => {
+            let since = match brief.since {
+                Some(since) => since.format("%Y-%m-%d %H:%M").to_string(),
+                None => "the beginning (first brief)".to_string(),
+            };
+            writeln!(
+                out,
+                "Brief for {} since {}",
+                agent_label(&brief.agent_id),
+                since
+            )?;
+            for pin in &brief.pinned_notes {
+                writeln!(
+                    out,
+                    "{} {}: {}",
+                    "PINNED".yellow().bold(),
+                    pin.board_name,
+                    pin.note
+                )?;
+            }
+
+            writeln!(out)?;
+            writeln!(out, "Assigned cards ({}):", brief.cards.len())?;
+            for card in &brief.cards {
+                let new = brief
+                    .comments
+                    .iter()
+                    .filter(|c| c.card_id == card.id)
+                    .count();
+                let mut notes = Vec::new();
+                if new > 0 {
+                    notes.push(format!("{} new comment(s)", new));
+                }
+                if brief.unblocked.contains(&card.id) {
+                    notes.push("unblocked".to_string());
+                }
+                writeln!(
+                    out,
+                    "- {} [{}] {}{}",
+                    card.id,
+                    card.status,
+                    card.name,
+                    if notes.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" ({})", notes.join(", "))
+                    }
+                )?;
+            }
+
+            for (title, comments) in [
+                ("New comments", &brief.comments),
+                ("Mentions", &brief.mentions),
+            ] {
+                if comments.is_empty() {
+                    continue;
+                }
+                writeln!(out)?;
+                writeln!(out, "{} ({}):", title, comments.len())?;
+                for comment in comments {
+                    let author = comment
+                        .author
+                        .as_deref()
+                        .filter(|a| !a.is_empty())
+                        .map(agent_name)
+                        .unwrap_or("anonymous");
+                    let text = comment
+                        .text
+                        .split_whitespace()
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    writeln!(
+                        out,
+                        "- {} {} {}{}: {}",
+                        comment.card_id,
+                        comment.created_at.format("%Y-%m-%d %H:%M"),
+                        author,
+                        comment_kind_badge(comment.kind),
+                        truncate(&text, 160)
+                    )?;
+                }
+            }
+
+            if brief.comments.is_empty() && brief.mentions.is_empty() && brief.unblocked.is_empty()
+            {
+                writeln!(out)?;
+                writeln!(out, "Nothing new since {}.", since)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn print_events(
+    out: &mut impl Write,
+    events: &[Event],
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&events).unwrap())?;
+        }
+        OutputFormat::Simple => {
+            for event in events {
+                writeln!(
+                    out,
+                    "{}\t{}\t{}",
+                    event.created_at.to_rfc3339(),
+                    event.event,
+                    event.card_id
+                )?;
+            }
+        }
+        _ => {
+            if events.is_empty() {
+                writeln!(out, "No events found.")?;
+                return Ok(());
+            }
+            let rows: Vec<EventRow> = events
+                .iter()
+                .map(|e| EventRow {
+                    time: e.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    event: e.event.to_string(),
+                    card: e.card_id.clone(),
+                    actor: match (&e.via, e.actor.as_deref().map(agent_label)) {
+                        (Some(via), Some(actor)) => {
+                            format!("{} acting as {}", agent_label(via), actor)
+                        }
+                        (_, actor) => actor.unwrap_or_else(|| "-".to_string()),
+                    },
+                    details: event_details(e),
+                })
+                .collect();
+            let table = rounded_table(rows);
+            writeln!(out, "{}", table)?;
+        }
+    }
+    Ok(())
+}
+
+/// Simple format lists only the cards over the threshold
+pub fn print_aging_cards(
+    out: &mut impl Write,
+    aging: &[AgingCard],
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&aging).unwrap())?;
+        }
+        OutputFormat::Simple => {
+            for entry in aging.iter().filter(|a| a.over_threshold) {
+                writeln!(out, "{}", entry.card.id)?;
+            }
+        }
+        _ => {
+            if aging.is_empty() {
+                writeln!(out, "No open cards found.")?;
+                return Ok(());
+            }
+            let rows: Vec<AgingCardRow> = aging
+                .iter()
+                .map(|entry| {
+                    let in_status = format_duration_minutes(entry.minutes_in_status);
+                    AgingCardRow {
+                        id: entry.card.id.clone(),
+                        name: cell("name", &entry.card.name, 35),
+                        status: entry.card.status.to_string(),
+                        assigned_to: entry
+                            .card
+                            .assigned_to
+                            .as_deref()
+                            .map(agent_label)
+                            .unwrap_or_else(|| "-".to_string()),
+                        in_status: if entry.over_threshold {
+                            format!("{} (over)", in_status)
+                        } else {
+                            in_status
+                        },
+                        since: entry.in_status_since.format("%Y-%m-%d %H:%M").to_string(),
+                    }
+                })
+                .collect();
+            let table = rounded_table(rows);
+            writeln!(out, "{}", table)?;
+            let over = aging.iter().filter(|a| a.over_threshold).count();
+            if over > 0 {
+                writeln!(out, "{} card(s) over the threshold.", over)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `sweep`: one section per step that ran
+pub fn print_sweep(
+    out: &mut impl Write,
+    summary: &SweepSummary,
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&summary).unwrap())?;
+        }
+        OutputFormat::Simple => {
+            let stale = summary.stale.iter().flatten().map(|s| &s.card);
+            let due = summary.due_soon.iter().flatten().map(|d| &d.card);
+            for card in stale.chain(due) {
+                writeln!(out, "{}", card.id)?;
+            }
+        }
+        _ => {
+            let verb = |done: &'static str, planned: &'static str| {
+                if summary.dry_run { planned } else { done }
+            };
+            let mut sections = Vec::new();
+            if let Some(stale) = &summary.stale {
+                let mut section = Vec::new();
+                if stale.is_empty() {
+                    writeln!(section, "No stale cards found.")?;
+                } else {
+                    let rows = stale.iter().map(|entry| StaleCardRow {
+                        id: entry.card.id.clone(),
+                        name: cell("name", &entry.card.name, 35),
+                        status: entry.card.status.to_string(),
+                        assigned_to: entry
+                            .card
+                            .assigned_to
+                            .as_deref()
+                            .map(agent_label)
+                            .unwrap_or_else(|| "-".to_string()),
+                        active_at: entry.active_at.format("%Y-%m-%d %H:%M").to_string(),
+                        policy: entry.policy.clone(),
+                    });
+                    writeln!(section, "{}", rounded_table(rows))?;
+                    writeln!(
+                        section,
+                        "{} {} card(s) stale.",
+                        verb("Tagged", "Dry run: would tag"),
+                        stale.len()
+                    )?;
+                }
+                sections.push(section);
+            }
+            if let Some(due) = &summary.due_soon {
+                let mut section = Vec::new();
+                if due.is_empty() {
+                    writeln!(section, "No cards coming due.")?;
+                } else {
+                    let rows = due.iter().map(|entry| DueReminderRow {
+                        id: entry.card.id.clone(),
+                        name: cell("name", &entry.card.name, 35),
+                        status: entry.card.status.to_string(),
+                        assigned_to: entry
+                            .card
+                            .assigned_to
+                            .as_deref()
+                            .map(agent_label)
+                            .unwrap_or_else(|| "-".to_string()),
+                        due_at: entry
+                            .card
+                            .due_at
+                            .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                            .unwrap_or_default(),
+                        policy: entry.policy.clone(),
+                    });
+                    writeln!(section, "{}", rounded_table(rows))?;
+                    writeln!(
+                        section,
+                        "{} {} card(s) coming due.",
+                        verb("Reminded about", "Dry run: would remind about"),
+                        due.len()
+                    )?;
+                }
+                sections.push(section);
+            }
+            if let Some(pruned) = &summary.retention {
+                let mut section = Vec::new();
+                if pruned.is_empty() {
+                    writeln!(section, "No boards with a retention.events policy.")?;
+                }
+                for entry in pruned {
+                    writeln!(
+                        section,
+                        "{} {} event(s) from {} ({}).",
+                        verb("Pruned", "Dry run: would prune"),
+                        entry.events,
+                        entry.board_name,
+                        entry.policy
+                    )?;
+                }
+                sections.push(section);
+            }
+            if let Some(stats) = &summary.stats {
+                let mut section = Vec::new();
+                writeln!(section, "Recorded stats for {} board(s).", stats.len())?;
+                sections.push(section);
+            }
+            for (i, section) in sections.iter().enumerate() {
+                if i > 0 {
+                    writeln!(out)?;
+                }
+                out.write_all(section)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn print_reopens(
+    out: &mut impl Write,
+    reopens: &[Reopen],
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&reopens).unwrap())?;
+        }
+        OutputFormat::Simple => {
+            for reopen in reopens {
+                writeln!(out, "{}", reopen.card_id)?;
+            }
+        }
+        _ => {
+            if reopens.is_empty() {
+                writeln!(out, "No reopened cards found.")?;
+                return Ok(());
+            }
+            let rows: Vec<ReopenRow> = reopens
+                .iter()
+                .map(|r| ReopenRow {
+                    reopened_at: r.reopened_at.format("%Y-%m-%d %H:%M").to_string(),
+                    card_id: r.card_id.clone(),
+                    name: cell("name", &r.card_name, 30),
+                    assigned_to: r
+                        .assigned_to
+                        .as_deref()
+                        .map(agent_label)
+                        .unwrap_or_else(|| "-".to_string()),
+                    reopened_by: r
+                        .reopened_by
+                        .as_deref()
+                        .map(agent_label)
+                        .unwrap_or_else(|| "human".to_string()),
+                    to: r.to.to_string(),
+                    reason: cell("reason", &r.reason, 40),
+                })
+                .collect();
+            let table = rounded_table(rows);
+            writeln!(out, "{}", table)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn print_digest(out: &mut impl Write, digest: &Digest, format: OutputFormat) -> io::Result<()> {
+    let window = format!(
+        "{} to {}",
+        digest.since.format("%Y-%m-%d %H:%M"),
+        digest.until.format("%Y-%m-%d %H:%M")
+    );
+    let changes = |items: &[BoardChange]| -> Vec<(String, String)> {
+        items
+            .iter()
+            .map(|c| {
+                let by = c.by.as_deref().map(agent_label);
+                let line = match by {
+                    Some(by) => format!("{} · {}", c.card_name, by),
+                    None => c.card_name.clone(),
+                };
+                (c.card_id.clone(), line)
+            })
+            .collect()
+    };
+    let owner = |card: &Card| {
+        card.assigned_to
+            .as_deref()
+            .map(|a| format!(" ({})", agent_label(a)))
+            .unwrap_or_default()
+    };
+    let blocked: Vec<(String, String)> = digest
+        .blocked
+        .iter()
+        .map(|b| {
+            let mut reasons = Vec::new();
+            if !b.waiting_on.is_empty() {
+                reasons.push(format!("waiting on {}", b.waiting_on.join(", ")));
+            }
+            if b.tagged_blocked {
+                reasons.push("tagged blocked".to_string());
+            }
+            let line = format!("{}{} · {}", b.card.name, owner(&b.card), reasons.join("; "));
+            (b.card.id.clone(), line)
+        })
+        .collect();
+    let due: Vec<(String, String)> = digest
+        .due_soon
+        .iter()
+        .map(|c| {
+            let due_at = c.due_at.unwrap_or(digest.until);
+            let overdue = if due_at <= digest.until {
+                " (overdue)"
+            } else {
+                ""
+            };
+            let line = format!(
+                "{}{} · due {}{}",
+                c.name,
+                owner(c),
+                due_at.format("%Y-%m-%d %H:%M"),
+                overdue
+            );
+            (c.id.clone(), line)
+        })
+        .collect();
+    let sections = [
+        ("Completed".to_string(), changes(&digest.completed)),
+        ("Started".to_string(), changes(&digest.started)),
+        ("Blocked".to_string(), blocked),
+        (
+            format!("Due by {}", digest.due_before.format("%Y-%m-%d")),
+            due,
+        ),
+    ];
+
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&digest).unwrap())?;
+        }
+        OutputFormat::Simple => {
+            for (_, items) in &sections {
+                for (id, _) in items {
+                    writeln!(out, "{}", id)?;
+                }
+            }
+        }
+        OutputFormat::Email => {
+            write!(out, "{}", crate::digest::email(digest, &[]))?;
+        }
+        OutputFormat::Markdown => {
+            writeln!(out, "### {} ({})", digest.board_name, window)?;
+            for (title, items) in &sections {
+                writeln!(out, "\n**{} ({})**\n", title, items.len())?;
+                if items.is_empty() {
+                    writeln!(out, "None.")?;
+                }
+                for (id, line) in items {
+                    writeln!(out, "- {} (`{}`)", line, id)?;
+                }
+            }
+        }
+        _ => {
+            writeln!(out, "{} ({})", digest.board_name.bold(), window)?;
+            for (title, items) in &sections {
+                writeln!(out, "\n{}", format!("{} ({})", title, items.len()).bold())?;
+                if items.is_empty() {
+                    writeln!(out, "  {}", "None".dimmed())?;
+                }
+                for (id, line) in items {
+                    writeln!(out, "  {}  {}", id.dimmed(), line)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn print_board_diff(
+    out: &mut impl Write,
+    diff: &BoardDiff,
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&diff).unwrap())?;
+        }
+        OutputFormat::Simple => {
+            for change in &diff.changes {
+                writeln!(out, "{}", change.card_id)?;
+            }
+        }
+        _ => {
+            let window = format!(
+                "{} to {}",
+                diff.since.format("%Y-%m-%d %H:%M"),
+                diff.until.format("%Y-%m-%d %H:%M")
+            );
+            if diff.changes.is_empty() {
+                writeln!(out, "No changes on {} from {}.", diff.board_name, window)?;
+                return Ok(());
+            }
+            let rows: Vec<ChangeRow> = diff
+                .changes
+                .iter()
+                .map(|c| {
+                    let detail = match c.kind {
+                        ChangeKind::Added => format!("in {}", c.to.as_deref().unwrap_or("-")),
+                        ChangeKind::Reassigned => format!(
+                            "{} → {}",
+                            c.from
+                                .as_deref()
+                                .map(agent_label)
+                                .unwrap_or_else(|| "unassigned".into()),
+                            c.to.as_deref()
+                                .map(agent_label)
+                                .unwrap_or_else(|| "unassigned".into())
+                        ),
+                        _ => format!(
+                            "{} → {}",
+                            c.from.as_deref().unwrap_or("-"),
+                            c.to.as_deref().unwrap_or("-")
+                        ),
+                    };
+                    ChangeRow {
+                        kind: c.kind.to_string(),
+                        card_id: c.card_id.clone(),
+                        name: cell("name", &c.card_name, 30),
+                        detail,
+                        at: c.at.format("%m-%d %H:%M").to_string(),
+                        by: c
+                            .by
+                            .as_deref()
+                            .map(agent_label)
+                            .unwrap_or_else(|| "human".to_string()),
+                    }
+                })
+                .collect();
+            let mut table = Table::new(rows);
+            if format == OutputFormat::Markdown {
+                table.with(Style::markdown());
+                writeln!(out, "### {} ({})\n", diff.board_name, window)?;
+            } else {
+                table.with(Style::rounded());
+                fit_width(&mut table);
+                writeln!(out, "{} ({})", diff.board_name, window)?;
+            }
+            writeln!(out, "{}", table)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn print_review_queue(
+    out: &mut impl Write,
+    queue: &[ReviewQueueEntry],
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&queue).unwrap())?;
+        }
+        OutputFormat::Simple => {
+            for entry in queue {
+                writeln!(out, "{}", entry.card.id)?;
+            }
+        }
+        _ => {
+            if queue.is_empty() {
+                writeln!(out, "No cards waiting for review.")?;
+                return Ok(());
+            }
+            let rows: Vec<ReviewQueueRow> = queue
+                .iter()
+                .map(|entry| ReviewQueueRow {
+                    id: entry.card.id.clone(),
+                    name: cell("name", &entry.card.name, 35),
+                    board: cell("board", &entry.board_name, 20),
+                    assigned_to: entry
+                        .card
+                        .assigned_to
+                        .as_deref()
+                        .map(agent_label)
+                        .unwrap_or_else(|| "-".to_string()),
+                    reviewer: entry
+                        .reviewer
+                        .as_deref()
+                        .map(agent_label)
+                        .unwrap_or_else(|| "-".to_string()),
+                    pr: entry.card.pr_url.clone().unwrap_or_else(|| "-".to_string()),
+                    in_review: format_duration_minutes(entry.minutes_in_review),
+                })
+                .collect();
+            let mut table = Table::new(rows);
+            if format == OutputFormat::Markdown {
+                table.with(Style::markdown());
+            } else {
+                table.with(Style::rounded());
+                fit_width(&mut table);
+            }
+            writeln!(out, "{}", table)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn print_similar_cards(
+    out: &mut impl Write,
+    similar: &[SimilarCard],
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&similar).unwrap())?;
+        }
+        OutputFormat::Simple => {
+            for entry in similar {
+                writeln!(out, "{}", entry.card.id)?;
+            }
+        }
+        _ => {
+            if similar.is_empty() {
+                writeln!(out, "No similar cards found.")?;
+                return Ok(());
+            }
+            let rows: Vec<SimilarRow> = similar
+                .iter()
+                .map(|entry| SimilarRow {
+                    score: format!("{:.0}%", entry.score * 100.0),
+                    id: entry.card.id.clone(),
+                    name: cell("name", &entry.card.name, 35),
+                    status: entry.card.status.to_string(),
+                    board: cell("board", &entry.board_name, 20),
+                })
+                .collect();
+            let mut table = Table::new(rows);
+            if format == OutputFormat::Markdown {
+                table.with(Style::markdown());
+            } else {
+                table.with(Style::rounded());
+                fit_width(&mut table);
+            }
+            writeln!(out, "{}", table)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn print_blocked_cards(
+    out: &mut impl Write,
+    blocked: &[BlockedCard],
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&blocked).unwrap())?;
+        }
+        OutputFormat::Simple => {
+            for entry in blocked {
+                writeln!(out, "{}", entry.card.id)?;
+            }
+        }
+        _ => {
+            if blocked.is_empty() {
+                writeln!(out, "No blocked cards found.")?;
+                return Ok(());
+            }
+            let rows: Vec<BlockedCardRow> = blocked
+                .iter()
+                .map(|entry| {
+                    let mut reasons = Vec::new();
+                    if !entry.waiting_on.is_empty() {
+                        reasons.push(format!("waits on {}", entry.waiting_on.join(", ")));
+                    }
+                    if entry.tagged_blocked {
+                        reasons.push("tagged blocked".to_string());
+                    }
+                    if let Some(since) = entry.stale_since {
+                        reasons.push(format!(
+                            "no update since {}",
+                            since.format("%Y-%m-%d %H:%M")
+                        ));
+                    }
+                    BlockedCardRow {
+                        id: entry.card.id.clone(),
+                        name: cell("name", &entry.card.name, 35),
+                        status: entry.card.status.to_string(),
+                        assigned_to: entry
+                            .card
+                            .assigned_to
+                            .as_deref()
+                            .map(agent_label)
+                            .unwrap_or_else(|| "-".to_string()),
+                        reasons: reasons.join("; "),
+                    }
+                })
+                .collect();
+            let table = rounded_table(rows);
+            writeln!(out, "{}", table)?;
+        }
+    }
+    Ok(())
+}
+
+/// `report schedule`: dated cards with their projected finish and risk
+pub fn print_schedule(
+    out: &mut impl Write,
+    entries: &[ScheduleEntry],
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&entries).unwrap())?;
+        }
+        OutputFormat::Simple => {
+            for entry in entries {
+                writeln!(out, "{}", entry.card.id)?;
+            }
+        }
+        _ => {
+            if entries.is_empty() {
+                writeln!(out, "No cards at risk of missing their due date.")?;
+                return Ok(());
+            }
+            let when = |t: Option<DateTime<Utc>>| {
+                t.map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_else(|| "-".to_string())
+            };
+            let rows = entries.iter().map(|entry| ScheduleRow {
+                id: entry.card.id.clone(),
+                name: cell("name", &entry.card.name, 35),
+                assigned_to: entry
+                    .card
+                    .assigned_to
+                    .as_deref()
+                    .map(agent_label)
+                    .unwrap_or_else(|| "-".to_string()),
+                estimate: entry
+                    .card
+                    .estimate_minutes
+                    .map(format_minutes)
+                    .unwrap_or_else(|| "-".to_string()),
+                due: when(entry.card.due_at),
+                projected: when(entry.projected_finish),
+                risk: match entry.risk {
+                    ScheduleRisk::OnTrack => entry.risk.to_string().green().to_string(),
+                    ScheduleRisk::Late | ScheduleRisk::Overdue => {
+                        entry.risk.to_string().red().to_string()
+                    }
+                    _ => entry.risk.to_string().yellow().to_string(),
+                },
+                suggest: entry
+                    .suggested_agent
+                    .as_deref()
+                    .map(agent_label)
+                    .unwrap_or_else(|| "-".to_string()),
+            });
+            writeln!(out, "{}", rounded_table(rows))?;
+        }
+    }
+    Ok(())
+}
+
+/// `report critical-path`: the chain in order, with a running total of work
+pub fn print_critical_path(
+    out: &mut impl Write,
+    path: &CriticalPath,
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&path).unwrap())?;
+        }
+        OutputFormat::Simple => {
+            for card in &path.cards {
+                writeln!(out, "{}", card.id)?;
+            }
+        }
+        _ => {
+            if path.cards.is_empty() {
+                writeln!(out, "No open cards on this board.")?;
+                return Ok(());
+            }
+            let mut cumulative = 0;
+            let rows = path.cards.iter().enumerate().map(|(i, card)| {
+                cumulative += card.estimate_minutes.unwrap_or_default();
+                let external = if card.board_id == path.board_id {
+                    ""
+                } else {
+                    " (other board)"
+                };
+                CriticalPathRow {
+                    step: i + 1,
+                    id: card.id.clone(),
+                    name: format!("{}{}", cell("name", &card.name, 35), external),
+                    status: card.status.to_string(),
+                    owner: card
+                        .assigned_to
+                        .as_deref()
+                        .map(agent_label)
+                        .unwrap_or_else(|| "-".to_string()),
+                    estimate: card
+                        .estimate_minutes
+                        .map(format_minutes)
+                        .unwrap_or_else(|| "-".to_string()),
+                    cumulative: format_minutes(cumulative),
+                }
+            });
+            writeln!(out, "{}", rounded_table(rows))?;
+            let unestimated = path
+                .cards
+                .iter()
+                .filter(|c| c.estimate_minutes.is_none())
+                .count();
+            write!(
+                out,
+                "{} card{}, {} of estimated work",
+                path.cards.len(),
+                if path.cards.len() == 1 { "" } else { "s" },
+                format_minutes(path.total_minutes)
+            )?;
+            if unestimated > 0 {
+                write!(out, " ({} without an estimate)", unestimated)?;
+            }
+            writeln!(out)?;
+        }
+    }
+    Ok(())
+}
+
+/// Earlier versions of a card field, oldest first, followed by the current value
+pub fn print_revisions(
+    out: &mut impl Write,
+    card: &Card,
+    revisions: &[CardRevision],
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&revisions).unwrap())?;
+        }
+        OutputFormat::Simple => {
+            for revision in revisions {
+                writeln!(out, "{}", revision.id)?;
+            }
+        }
+        _ => {
+            if revisions.is_empty() {
+                writeln!(out, "No earlier versions recorded.")?;
+                return Ok(());
+            }
+            for (i, revision) in revisions.iter().enumerate() {
+                let by = revision
+                    .replaced_by
+                    .as_deref()
+                    .map(agent_label)
+                    .unwrap_or_else(|| "-".to_string());
+                writeln!(
+                    out,
+                    "{}",
+                    format!(
+                        "── v{} (replaced {} by {})",
+                        i + 1,
+                        revision.replaced_at.format("%Y-%m-%d %H:%M"),
+                        by
+                    )
+                    .dimmed()
+                )?;
+                writeln!(out, "{}", revision.value.as_deref().unwrap_or("(empty)"))?;
+                writeln!(out)?;
+            }
+            writeln!(out, "{}", "── current".dimmed())?;
+            writeln!(out, "{}", card.description.as_deref().unwrap_or("(empty)"))?;
+        }
+    }
+    Ok(())
+}
+
+/// Report what a board delete/purge removes (or would remove, on a dry run)
+pub fn print_board_impact(
+    out: &mut impl Write,
+    impact: &BoardImpact,
+    action: &str,
+    dry_run: bool,
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            let output = serde_json::json!({
+                "action": action,
+                "dry_run": dry_run,
+                "impact": impact
+            });
+            writeln!(out, "{}", serde_json::to_string_pretty(&output).unwrap())?;
+        }
+        OutputFormat::Simple => {
+            writeln!(out, "{}", impact.board_id)?;
+        }
+        _ => {
+            let verb = match (action, dry_run) {
+                ("purge", true) => "Would permanently remove",
+                ("purge", false) => "Permanently removed",
+                (_, true) => "Would delete",
+                (_, false) => "Deleted",
+            };
+            writeln!(
+                out,
+                "{} board: {} ({})",
+                verb, impact.board_id, impact.board_name
+            )?;
+            writeln!(out, "  Cards:           {}", impact.cards)?;
+            writeln!(out, "  Comments:        {}", impact.comments)?;
+            writeln!(out, "  Checklist items: {}", impact.checklist_items)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn print_removal_token(
+    out: &mut impl Write,
+    token: &RemovalToken,
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(token).unwrap())?;
+        }
+        OutputFormat::Simple => {
+            writeln!(out, "{}", token.token)?;
+        }
+        _ => {
+            print_board_impact(out, &token.impact, &token.action, true, format)?;
+            writeln!(
+                out,
+                "Confirmation token: {} (expires {})",
+                token.token,
+                token.expires_at.format("%Y-%m-%d %H:%M UTC")
+            )?;
+            writeln!(
+                out,
+                "To proceed: agent-board {} board {} --confirm {}",
+                token.action, token.impact.board_id, token.token
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// "4 cards: 2 todo, 1 in progress, 0 pending review, 1 done (25% done)"
+fn status_summary(counts: &StatusCounts) -> String {
+    let total = counts.total;
+    let mut line = format!(
+        "{} card{}: {} todo, {} in progress, {} pending review, {} done",
+        total,
+        if total == 1 { "" } else { "s" },
+        counts.todo,
+        counts.in_progress,
+        counts.pending_review,
+        counts.done
+    );
+    if let Some(percent) = (counts.done * 100).checked_div(total) {
+        line.push_str(&format!(" ({}% done)", percent));
+    }
+    line
+}
+
+/// Totals footer under a card table; JSON and simple output stay untouched
+pub fn print_cards_summary(
+    out: &mut impl Write,
+    cards: &[Card],
+    format: OutputFormat,
+) -> io::Result<()> {
+    if cards.is_empty() || matches!(format, OutputFormat::Json | OutputFormat::Simple) {
+        return Ok(());
+    }
+    let mut counts = StatusCounts::default();
+    for card in cards {
+        counts.add(&card.status, 1);
+    }
+    writeln!(out, "{}", status_summary(&counts))
+}
+
+/// Totals footer under a board table, summed from each board's `card_counts`
+pub fn print_boards_summary(
+    out: &mut impl Write,
+    boards: &[Board],
+    format: OutputFormat,
+) -> io::Result<()> {
+    if boards.is_empty() || matches!(format, OutputFormat::Json | OutputFormat::Simple) {
+        return Ok(());
+    }
+    let mut total = StatusCounts::default();
+    for counts in boards.iter().filter_map(|b| b.card_counts.as_ref()) {
+        total.merge(counts);
+    }
+    writeln!(
+        out,
+        "{} board{}, {}",
+        boards.len(),
+        if boards.len() == 1 { "" } else { "s" },
+        status_summary(&total)
+    )
+}
+
+/// Groups match case-insensitively, as `list boards --group` does
+fn same_group(a: &Board, b: &Board) -> bool {
+    match (&a.group, &b.group) {
+        (Some(x), Some(y)) => x.eq_ignore_ascii_case(y),
+        (x, y) => x.is_none() && y.is_none(),
+    }
+}
+
+fn board_row(b: &Board) -> BoardRow {
+    let deleted_marker = if b.deleted_at.is_some() {
+        " [DELETED]"
+    } else {
+        ""
+    };
+    let star = if b.starred { "★ " } else { "" };
+    BoardRow {
+        id: b.id.clone(),
+        name: format!("{}{}{}", star, b.name, deleted_marker),
+        description: b.description.clone().unwrap_or_else(|| "-".to_string()),
+        open_cards: b
+            .card_counts
+            .map_or_else(|| "-".to_string(), |c| c.open.to_string()),
+        total_cards: b
+            .card_counts
+            .map_or_else(|| "-".to_string(), |c| c.total.to_string()),
+        created_at: b.created_at.format("%Y-%m-%d %H:%M").to_string(),
+        last_used_at: b
+            .last_used_at
+            .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| "-".to_string()),
+    }
+}
+
+pub fn print_boards(
+    out: &mut impl Write,
+    boards: &[Board],
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&boards).unwrap())?;
+        }
+        OutputFormat::Table => {
+            if boards.is_empty() {
+                writeln!(out, "No boards found.")?;
+                return Ok(());
+            }
+            let pinned: Vec<&Board> = boards.iter().filter(|b| b.pinned_note.is_some()).collect();
+            for b in &pinned {
+                writeln!(
+                    out,
+                    "{} {}: {}",
+                    "PINNED".yellow().bold(),
+                    b.name,
+                    b.pinned_note.as_deref().unwrap_or_default()
+                )?;
+            }
+            if !pinned.is_empty() {
+                writeln!(out)?;
+            }
+            if boards.iter().all(|b| b.group.is_none()) {
+                writeln!(out, "{}", rounded_table(boards.iter().map(board_row)))?;
+                return Ok(());
+            }
+            // `list_boards()` orders by group, so each group is one run
+            for (i, chunk) in boards.chunk_by(same_group).enumerate() {
+                if i > 0 {
+                    writeln!(out)?;
+                }
+                let title = chunk[0].group.as_deref().unwrap_or("Ungrouped");
+                let count = format!(
+                    "({} board{})",
+                    chunk.len(),
+                    if chunk.len() == 1 { "" } else { "s" }
+                );
+                writeln!(out, "{} {}", title.bold(), count.dimmed())?;
+                writeln!(out, "{}", rounded_table(chunk.iter().map(board_row)))?;
+            }
+        }
+        OutputFormat::Simple => {
+            for board in boards {
+                writeln!(out, "{}", board.id)?;
+            }
+        }
+        _ => {
+            // Pretty, graph, and markdown formats don't apply to board list, fall back to table
+            print_boards(out, boards, OutputFormat::Table)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn print_board(
+    out: &mut impl Write,
+    board: &Board,
+    summary: &BoardSummary,
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            let output = serde_json::json!({
+                "board": board,
+                "summary": summary
+            });
+            writeln!(out, "{}", serde_json::to_string_pretty(&output).unwrap())?;
+        }
+        OutputFormat::Table => {
+            writeln!(out, "Board: {}", board.id)?;
+            writeln!(out, "Name: {}", board.name)?;
+            if let Some(note) = &board.pinned_note {
+                writeln!(out, "{} {}", "Pinned:".yellow().bold(), note)?;
+            }
+            if let Some(desc) = &board.description {
+                writeln!(out, "Description: {}", desc)?;
+            }
+            if let Some(group) = &board.group {
+                writeln!(out, "Group: {}", group)?;
+            }
+            writeln!(out, "\nSummary:")?;
+            writeln!(out, "  Todo: {}", summary.todo_count)?;
+            writeln!(out, "  In Progress: {}", summary.in_progress_count)?;
+            writeln!(out, "  Pending Review: {}", summary.pending_review_count)?;
+            writeln!(out, "  Done: {}", summary.done_count)?;
+            writeln!(out, "  Total: {}", summary.total_cards)?;
+            if !summary.agents.is_empty() {
+                writeln!(out, "\nBy Agent:")?;
+                let rows: Vec<AgentLoadRow> = summary
+                    .agents
+                    .iter()
+                    .map(|a| AgentLoadRow {
+                        agent: agent_label(&a.agent_id),
+                        open: a.open,
+                        in_progress: a.in_progress,
+                        done_this_week: a.done_this_week,
+                    })
+                    .collect();
+                let table = rounded_table(rows);
+                writeln!(out, "{}", table)?;
+            }
+        }
+        OutputFormat::Simple => {
+            writeln!(out, "{}", board.id)?;
+        }
+        _ => {
+            // Pretty is handled separately in main.rs with print_kanban
+            // This shouldn't be reached, but fall back to table
+            print_board(out, board, summary, OutputFormat::Table)?;
         }
     }
+    Ok(())
 }
 
-pub fn print_card(card: &Card, comments: &[Comment], format: OutputFormat) {
+pub fn print_entity(out: &mut impl Write, entity: &Entity, format: OutputFormat) -> io::Result<()> {
+    match entity {
+        Entity::Agent(agent) => print_agent(out, agent, format)?,
+        Entity::Board { board, summary } => print_board(out, board, summary, format)?,
+        Entity::Card {
+            card,
+            comments,
+            history,
+            timings,
+        } => print_card(
+            out,
+            card,
+            comments,
+            history.as_deref(),
+            timings.as_deref(),
+            format,
+        )?,
+        Entity::Comment { comment, card } => print_comment(out, comment, card, format)?,
+        Entity::ChecklistItem { item, card } => print_checklist_item(out, item, card, format)?,
+    }
+    Ok(())
+}
+
+pub fn print_comment(
+    out: &mut impl Write,
+    comment: &Comment,
+    card: &Card,
+    format: OutputFormat,
+) -> io::Result<()> {
     match format {
         OutputFormat::Json => {
             let output = serde_json::json!({
-                "card": card,
-                "comments": comments
+                "comment": comment,
+                "card": card
             });
-            println!("{}", serde_json::to_string_pretty(&output).unwrap());
+            writeln!(out, "{}", serde_json::to_string_pretty(&output).unwrap())?;
         }
         OutputFormat::Table => {
-            println!("Card: {}", card.id);
-            println!("Name: {}", card.name);
-            println!("Board: {}", card.board_id);
-            println!("Status: {}", card.status);
-            println!(
-                "Assigned To: {}",
-                card.assigned_to.as_deref().unwrap_or("-")
-            );
-            if let Some(desc) = &card.description {
-                println!("Description: {}", desc);
+            writeln!(out, "Comment: {}", comment.id)?;
+            writeln!(out, "Card: {} ({}) [{}]", card.id, card.name, card.status)?;
+            writeln!(
+                out,
+                "Author: {}",
+                comment
+                    .author
+                    .as_deref()
+                    .map(agent_label)
+                    .unwrap_or_else(|| "anonymous".to_string())
+            )?;
+            writeln!(
+                out,
+                "Created: {}",
+                comment.created_at.format("%Y-%m-%d %H:%M")
+            )?;
+            if let Some(kind) = comment.kind {
+                writeln!(out, "Kind: {}", kind)?;
             }
-            if !card.tags.is_empty() {
-                println!("Tags: {}", card.tags.join(", "));
-            }
-            if !card.checklist.is_empty() {
-                println!("\nChecklist:");
-                for item in &card.checklist {
-                    let check = if item.checked { "x" } else { " " };
-                    println!("  [{}] {} ({})", check, item.text, item.id);
-                }
+            if let Some(meta) = &comment.meta {
+                writeln!(out, "Meta: {}", meta.get())?;
             }
-            if !comments.is_empty() {
-                println!("\nComments:");
-                for comment in comments {
-                    let author = comment.author.as_deref().unwrap_or("anonymous");
-                    let time = comment.created_at.format("%Y-%m-%d %H:%M");
-                    println!("  [{}] {} ({})", author, time, comment.id);
-                    for line in comment.text.lines() {
-                        println!("    {}", line);
-                    }
-                }
+            writeln!(out)?;
+            writeln!(out, "{}", highlight::comment_text(&comment.text))?;
+            if !comment.reactions.is_empty() {
+                writeln!(out)?;
+                writeln!(out, "Reactions: {}", reactions_detail(&comment.reactions))?;
             }
         }
         OutputFormat::Simple => {
-            println!("{}", card.id);
+            writeln!(out, "{}", comment.id)?;
         }
-        OutputFormat::Pretty => {
-            // Pretty format doesn't apply to single card, fall back to table
-            print_card(card, comments, OutputFormat::Table);
+        _ => {
+            // Pretty, graph, and markdown formats don't apply to single comment, fall back to table
+            print_comment(out, comment, card, OutputFormat::Table)?;
         }
     }
+    Ok(())
 }
 
-pub fn print_boards(boards: &[Board], format: OutputFormat) {
+pub fn print_checklist_item(
+    out: &mut impl Write,
+    item: &ChecklistItem,
+    card: &Card,
+    format: OutputFormat,
+) -> io::Result<()> {
     match format {
         OutputFormat::Json => {
-            println!("{}", serde_json::to_string_pretty(&boards).unwrap());
+            let output = serde_json::json!({
+                "item": item,
+                "card": card
+            });
+            writeln!(out, "{}", serde_json::to_string_pretty(&output).unwrap())?;
         }
         OutputFormat::Table => {
-            if boards.is_empty() {
-                println!("No boards found.");
-                return;
-            }
-            let rows: Vec<BoardRow> = boards
-                .iter()
-                .map(|b| {
-                    let deleted_marker = if b.deleted_at.is_some() {
-                        " [DELETED]"
-                    } else {
-                        ""
-                    };
-                    BoardRow {
-                        id: b.id.clone(),
-                        name: format!("{}{}", b.name, deleted_marker),
-                        description: b.description.clone().unwrap_or_else(|| "-".to_string()),
-                        created_at: b.created_at.format("%Y-%m-%d %H:%M").to_string(),
-                    }
-                })
-                .collect();
-            let table = Table::new(rows).with(Style::rounded()).to_string();
-            println!("{}", table);
+            let check = if item.checked { "x" } else { " " };
+            writeln!(out, "Checklist Item: {}", item.id)?;
+            writeln!(out, "Card: {} ({}) [{}]", card.id, card.name, card.status)?;
+            writeln!(out, "[{}] {}", check, item.text)?;
         }
         OutputFormat::Simple => {
-            for board in boards {
-                println!("{}", board.id);
-            }
+            writeln!(out, "{}", item.id)?;
         }
-        OutputFormat::Pretty => {
-            // Pretty format doesn't apply to board list, fall back to table
-            print_boards(boards, OutputFormat::Table);
+        _ => {
+            // Pretty, graph, and markdown formats don't apply to single item, fall back to table
+            print_checklist_item(out, item, card, OutputFormat::Table)?;
         }
     }
+    Ok(())
 }
 
-pub fn print_board(board: &Board, summary: &BoardSummary, format: OutputFormat) {
+pub fn print_entities(
+    out: &mut impl Write,
+    entities: &[Entity],
+    format: OutputFormat,
+) -> io::Result<()> {
     match format {
         OutputFormat::Json => {
-            let output = serde_json::json!({
-                "board": board,
-                "summary": summary
-            });
-            println!("{}", serde_json::to_string_pretty(&output).unwrap());
-        }
-        OutputFormat::Table => {
-            println!("Board: {}", board.id);
-            println!("Name: {}", board.name);
-            if let Some(desc) = &board.description {
-                println!("Description: {}", desc);
-            }
-            println!("\nSummary:");
-            println!("  Todo: {}", summary.todo_count);
-            println!("  In Progress: {}", summary.in_progress_count);
-            println!("  Pending Review: {}", summary.pending_review_count);
-            println!("  Done: {}", summary.done_count);
-            println!("  Total: {}", summary.total_cards);
+            writeln!(out, "{}", serde_json::to_string_pretty(&entities).unwrap())?;
         }
         OutputFormat::Simple => {
-            println!("{}", board.id);
+            for entity in entities {
+                print_entity(out, entity, OutputFormat::Simple)?;
+            }
         }
-        OutputFormat::Pretty => {
-            // Pretty is handled separately in main.rs with print_kanban
-            // This shouldn't be reached, but fall back to table
-            print_board(board, summary, OutputFormat::Table);
+        _ => {
+            for (i, entity) in entities.iter().enumerate() {
+                if i > 0 {
+                    writeln!(out)?;
+                }
+                print_entity(out, entity, format.clone())?;
+            }
         }
     }
+    Ok(())
 }
 
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
+/// Render a minute count as the largest whole unit: "2d", "4h", or "90m"
+pub fn format_minutes(minutes: i64) -> String {
+    if minutes % (24 * 60) == 0 {
+        format!("{}d", minutes / (24 * 60))
+    } else if minutes % 60 == 0 {
+        format!("{}h", minutes / 60)
     } else {
-        format!("{}...", &s[..max_len - 3])
+        format!("{}m", minutes)
     }
 }
 
-/// Print a visual kanban board with cards organized by status columns
-pub fn print_kanban(
-    board: &Board,
-    cards: &[Card],
-    comment_counts: &std::collections::HashMap<String, usize>,
-) {
-    const COL_WIDTH: usize = 28;
-    const CARD_INNER: usize = COL_WIDTH - 4; // Account for borders and padding
-
-    // Group cards by status
-    let todo: Vec<_> = cards.iter().filter(|c| c.status == Status::Todo).collect();
-    let in_progress: Vec<_> = cards
-        .iter()
-        .filter(|c| c.status == Status::InProgress)
-        .collect();
-    let pending_review: Vec<_> = cards
-        .iter()
-        .filter(|c| c.status == Status::PendingReview)
-        .collect();
-    let done: Vec<_> = cards.iter().filter(|c| c.status == Status::Done).collect();
-
-    // Board header
-    println!();
-    println!("┌{}┐", "─".repeat(COL_WIDTH * 4 + 3));
-    let title = format!("{} - {}", board.name, board.id);
-    println!("│ {:<width$} │", title, width = COL_WIDTH * 4 + 1);
-    if let Some(desc) = &board.description {
-        println!(
-            "│ {:<width$} │",
-            truncate(desc, COL_WIDTH * 4 - 1),
-            width = COL_WIDTH * 4 + 1
-        );
+/// Colored tag for a comment's kind, with a leading space; empty when unset
+fn comment_kind_badge(kind: Option<CommentKind>) -> String {
+    match kind {
+        Some(CommentKind::StatusUpdate) => format!(" {}", "[status update]".cyan()),
+        Some(CommentKind::Question) => format!(" {}", "[? question]".yellow()),
+        Some(CommentKind::Review) => format!(" {}", "[review]".magenta()),
+        Some(CommentKind::Blocker) => format!(" {}", "[! blocker]".red().bold()),
+        None => String::new(),
     }
-    println!(
-        "├{}┬{}┬{}┬{}┤",
-        "─".repeat(COL_WIDTH),
-        "─".repeat(COL_WIDTH),
-        "─".repeat(COL_WIDTH),
-        "─".repeat(COL_WIDTH)
-    );
-
-    // Column headers with colors
-    let header_colors = [
-        "TODO".white(),
-        "IN PROGRESS".yellow(),
-        "PENDING REVIEW".cyan(),
-        "DONE".green(),
-    ];
-    print!("│");
-    for header in &header_colors {
-        print!(" {:<width$}│", header, width = COL_WIDTH - 1);
-    }
-    println!();
-
-    // Counts
-    print!("│");
-    for count in [
-        todo.len(),
-        in_progress.len(),
-        pending_review.len(),
-        done.len(),
-    ] {
-        let count_str = format!("({} cards)", count);
-        print!(" {:<width$}│", count_str, width = COL_WIDTH - 1);
-    }
-    println!();
-
-    println!(
-        "├{}┼{}┼{}┼{}┤",
-        "─".repeat(COL_WIDTH),
-        "─".repeat(COL_WIDTH),
-        "─".repeat(COL_WIDTH),
-        "─".repeat(COL_WIDTH)
-    );
-
-    // Find max cards in any column
-    let max_cards = [
-        todo.len(),
-        in_progress.len(),
-        pending_review.len(),
-        done.len(),
-    ]
-    .into_iter()
-    .max()
-    .unwrap_or(0);
-
-    let columns = [&todo, &in_progress, &pending_review, &done];
-
-    // Print cards row by row (each card takes 5 lines)
-    for i in 0..max_cards {
-        // Card top border
-        print!("│");
-        for col in &columns {
-            if i < col.len() {
-                print!(" ┌{}┐ │", "─".repeat(CARD_INNER));
-            } else {
-                print!("{:width$}│", "", width = COL_WIDTH);
-            }
-        }
-        println!();
-
-        // Card name line 1 (colored by status)
-        print!("│");
-        for (col_idx, col) in columns.iter().enumerate() {
-            if i < col.len() {
-                let card = col[i];
-                let max_chars = CARD_INNER - 2;
-                let name_line1 = if card.name.len() > max_chars {
-                    &card.name[..max_chars]
-                } else {
-                    &card.name
-                };
-                let colored_name = match col_idx {
-                    1 => format!("{:<width$}", name_line1, width = max_chars).yellow(),
-                    2 => format!("{:<width$}", name_line1, width = max_chars).cyan(),
-                    3 => format!("{:<width$}", name_line1, width = max_chars).green(),
-                    _ => format!("{:<width$}", name_line1, width = max_chars).white(),
-                };
-                print!(" │ {} │ │", colored_name);
-            } else {
-                print!("{:width$}│", "", width = COL_WIDTH);
-            }
-        }
-        println!();
-
-        // Card name line 2 (continuation, colored by status)
-        print!("│");
-        for (col_idx, col) in columns.iter().enumerate() {
-            if i < col.len() {
-                let card = col[i];
-                let max_chars = CARD_INNER - 2;
-                let name_line2 = if card.name.len() > max_chars {
-                    truncate(&card.name[max_chars..], max_chars)
-                } else {
-                    String::new()
-                };
-                let colored_name = match col_idx {
-                    1 => format!("{:<width$}", name_line2, width = max_chars).yellow(),
-                    2 => format!("{:<width$}", name_line2, width = max_chars).cyan(),
-                    3 => format!("{:<width$}", name_line2, width = max_chars).green(),
-                    _ => format!("{:<width$}", name_line2, width = max_chars).white(),
-                };
-                print!(" │ {} │ │", colored_name);
-            } else {
-                print!("{:width$}│", "", width = COL_WIDTH);
-            }
-        }
-        println!();
+}
 
-        // Card ID line (dimmed)
-        print!("│");
-        for col in &columns {
-            if i < col.len() {
-                let card = col[i];
-                let id_short = if card.id.len() > CARD_INNER - 2 {
-                    format!("{}...", &card.id[..CARD_INNER - 5])
-                } else {
-                    card.id.clone()
-                };
-                let dimmed_id = format!("{:<width$}", id_short, width = CARD_INNER - 2).dimmed();
-                print!(" │ {} │ │", dimmed_id);
-            } else {
-                print!("{:width$}│", "", width = COL_WIDTH);
-            }
-        }
-        println!();
-
-        // Card assignee line
-        print!("│");
-        for col in &columns {
-            if i < col.len() {
-                let card = col[i];
-                let assignee = card.assigned_to.as_deref().unwrap_or("-");
-                let assignee_display = format!("@{}", truncate(assignee, CARD_INNER - 4));
-                print!(
-                    " │ {:<width$} │ │",
-                    assignee_display,
-                    width = CARD_INNER - 2
-                );
-            } else {
-                print!("{:width$}│", "", width = COL_WIDTH);
-            }
-        }
-        println!();
+/// Compact reaction line, e.g. "+1 ×2  👀 ×1"
+pub fn reactions_summary(reactions: &[Reaction]) -> String {
+    reactions
+        .iter()
+        .map(|r| format!("{} ×{}", r.emoji, r.count))
+        .collect::<Vec<_>>()
+        .join("  ")
+}
 
-        // Card tags line 1
-        print!("│");
-        for col in &columns {
-            if i < col.len() {
-                let card = col[i];
-                if card.tags.is_empty() {
-                    print!(" │ {:<width$} │ │", "", width = CARD_INNER - 2);
-                } else {
-                    let tags_str = card
-                        .tags
-                        .iter()
-                        .map(|t| format!("#{}", t))
-                        .collect::<Vec<_>>()
-                        .join(" ");
-                    let line1 = if tags_str.len() > CARD_INNER - 2 {
-                        &tags_str[..CARD_INNER - 2]
-                    } else {
-                        &tags_str
-                    };
-                    let tags_part = format!("{:<width$}", line1, width = CARD_INNER - 2);
-                    print!(" │ {} │ │", tags_part.blue());
-                }
-            } else {
-                print!("{:width$}│", "", width = COL_WIDTH);
+/// Reactions with who left them, for the single-comment view
+fn reactions_detail(reactions: &[Reaction]) -> String {
+    reactions
+        .iter()
+        .map(|r| {
+            let mut names: Vec<String> = r.authors.iter().map(|a| agent_label(a)).collect();
+            let anonymous = r.count - r.authors.len();
+            if anonymous > 0 {
+                names.push(format!("{} anonymous", anonymous));
             }
-        }
-        println!();
+            format!("{} ({})", r.emoji, names.join(", "))
+        })
+        .collect::<Vec<_>>()
+        .join("  ")
+}
 
-        // Card tags line 2
-        print!("│");
-        for col in &columns {
-            if i < col.len() {
-                let card = col[i];
-                if card.tags.is_empty() {
-                    print!(" │ {:<width$} │ │", "", width = CARD_INNER - 2);
-                } else {
-                    let tags_str = card
-                        .tags
-                        .iter()
-                        .map(|t| format!("#{}", t))
-                        .collect::<Vec<_>>()
-                        .join(" ");
-                    let line2 = if tags_str.len() > CARD_INNER - 2 {
-                        let remaining = &tags_str[CARD_INNER - 2..];
-                        truncate(remaining, CARD_INNER - 2)
-                    } else {
-                        String::new()
-                    };
-                    let tags_part = format!("{:<width$}", line2, width = CARD_INNER - 2);
-                    print!(" │ {} │ │", tags_part.blue());
-                }
-            } else {
-                print!("{:width$}│", "", width = COL_WIDTH);
-            }
-        }
-        println!();
+/// A table cell cut to its column's width: the `width.<column>` database
+/// setting, else `default`; kept whole with --no-truncate
+fn cell(column: &str, s: &str, default: usize) -> String {
+    let layout = TABLE_LAYOUT.get();
+    if layout.is_some_and(|l| l.no_truncate) {
+        return s.to_string();
+    }
+    let width = layout
+        .and_then(|l| l.columns.get(column))
+        .copied()
+        .unwrap_or(default);
+    truncate(s, width)
+}
 
-        // Card comments line
-        print!("│");
-        for col in &columns {
-            if i < col.len() {
-                let card = col[i];
-                let comment_count = comment_counts.get(&card.id).copied().unwrap_or(0);
-                let comment_text = if comment_count == 1 {
-                    "[1 comment]".to_string()
-                } else {
-                    format!("[{} comments]", comment_count)
-                };
-                let padded = format!("{:<width$}", comment_text, width = CARD_INNER - 2);
-                print!(" │ {} │ │", padded.dimmed());
-            } else {
-                print!("{:width$}│", "", width = COL_WIDTH);
-            }
-        }
-        println!();
+/// A rounded table, fitted to --max-width
+fn rounded_table<T: Tabled>(rows: impl IntoIterator<Item = T>) -> String {
+    let mut table = Table::new(rows);
+    table.with(Style::rounded());
+    fit_width(&mut table);
+    table.to_string()
+}
 
-        // Card bottom border
-        print!("│");
-        for col in &columns {
-            if i < col.len() {
-                print!(" └{}┘ │", "─".repeat(CARD_INNER));
-            } else {
-                print!("{:width$}│", "", width = COL_WIDTH);
-            }
-        }
-        println!();
+/// Shrink a table to --max-width, trimming the widest column first
+fn fit_width(table: &mut Table) {
+    if let Some(max) = TABLE_LAYOUT.get().and_then(|l| l.max_width) {
+        table.with(Width::truncate(max).suffix("...").priority::<PriorityMax>());
     }
+}
 
-    // If no cards at all
-    if max_cards == 0 {
-        print!("│");
-        for _ in 0..4 {
-            print!(" {:<width$}│", "(empty)", width = COL_WIDTH - 1);
-        }
-        println!();
+pub(crate) fn truncate(s: &str, max_len: usize) -> String {
+    if s.chars().count() <= max_len {
+        s.to_string()
+    } else {
+        let kept: String = s.chars().take(max_len - 3).collect();
+        format!("{}...", kept)
     }
+}
 
-    // Bottom border
-    println!(
-        "└{}┴{}┴{}┴{}┘",
-        "─".repeat(COL_WIDTH),
-        "─".repeat(COL_WIDTH),
-        "─".repeat(COL_WIDTH),
-        "─".repeat(COL_WIDTH)
-    );
-    println!();
+/// Print a visual kanban board with cards organized by status columns
+pub fn print_kanban(
+    out: &mut impl Write,
+    board: &Board,
+    cards: &[Card],
+    comment_counts: &HashMap<String, usize>,
+    options: &KanbanOptions,
+) -> io::Result<()> {
+    write!(
+        out,
+        "{}",
+        kanban::render(board, cards, comment_counts, options, Utc::now())
+    )?;
+    Ok(())
 }
 
-pub fn print_agents(agents: &[Agent], format: OutputFormat) {
+pub fn print_agents(
+    out: &mut impl Write,
+    agents: &[Agent],
+    format: OutputFormat,
+) -> io::Result<()> {
     match format {
         OutputFormat::Json => {
-            println!("{}", serde_json::to_string_pretty(&agents).unwrap());
+            writeln!(out, "{}", serde_json::to_string_pretty(&agents).unwrap())?;
         }
         OutputFormat::Table => {
             if agents.is_empty() {
-                println!("No agents found.");
-                return;
+                writeln!(out, "No agents found.")?;
+                return Ok(());
             }
             let rows: Vec<AgentRow> = agents
                 .iter()
@@ -533,106 +2905,569 @@ pub fn print_agents(agents: &[Agent], format: OutputFormat) {
                     } else {
                         ""
                     };
+                    let activity = a.activity.clone().unwrap_or_default();
                     AgentRow {
                         id: a.id.clone(),
                         name: format!("{}{}", a.name, inactive_marker),
                         command: a.command.clone(),
-                        working_directory: truncate(&a.working_directory, 40),
+                        capabilities: if a.capabilities.is_empty() {
+                            "-".to_string()
+                        } else {
+                            a.capabilities.join(", ")
+                        },
+                        open_cards: activity.open_cards.to_string(),
+                        working_on: match (&activity.current_card_id, &activity.current_card_name) {
+                            (Some(id), Some(name)) => format!("{} {}", id, cell("name", name, 30)),
+                            _ => "-".to_string(),
+                        },
+                        last_active: activity
+                            .last_active_at
+                            .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                            .unwrap_or_else(|| "-".to_string()),
+                        working_directory: match a.extra_workdirs.len() {
+                            0 => cell("working_directory", &a.working_directory, 40),
+                            n => format!(
+                                "{} (+{})",
+                                cell("working_directory", &a.working_directory, 40),
+                                n
+                            ),
+                        },
                         created_at: a.created_at.format("%Y-%m-%d %H:%M").to_string(),
                     }
                 })
                 .collect();
-            let table = Table::new(rows).with(Style::rounded()).to_string();
-            println!("{}", table);
+            let table = rounded_table(rows);
+            writeln!(out, "{}", table)?;
         }
         OutputFormat::Simple => {
             for agent in agents {
-                println!("{}", agent.id);
+                writeln!(out, "{}", agent.id)?;
             }
         }
-        OutputFormat::Pretty => {
-            // Pretty format doesn't apply to agent list, fall back to table
-            print_agents(agents, OutputFormat::Table);
+        _ => {
+            // Pretty, graph, and markdown formats don't apply to agent list, fall back to table
+            print_agents(out, agents, OutputFormat::Table)?;
         }
     }
+    Ok(())
 }
 
-pub fn print_agent(agent: &Agent, format: OutputFormat) {
+pub fn print_agent(out: &mut impl Write, agent: &Agent, format: OutputFormat) -> io::Result<()> {
     match format {
         OutputFormat::Json => {
-            println!("{}", serde_json::to_string_pretty(&agent).unwrap());
+            writeln!(out, "{}", serde_json::to_string_pretty(&agent).unwrap())?;
         }
         OutputFormat::Table => {
-            println!("Agent: {}", agent.id);
-            println!("Name: {}", agent.name);
-            println!("Command: {}", agent.command);
-            println!("Working Directory: {}", agent.working_directory);
+            writeln!(out, "Agent: {}", agent.id)?;
+            writeln!(out, "Name: {}", agent.name)?;
+            writeln!(out, "Command: {}", agent.command)?;
+            writeln!(out, "Working Directory: {}", agent.working_directory)?;
+            if !agent.extra_workdirs.is_empty() {
+                writeln!(
+                    out,
+                    "Other Working Directories: {}",
+                    agent.extra_workdirs.join(", ")
+                )?;
+            }
             if let Some(desc) = &agent.description {
-                println!("Description: {}", desc);
+                writeln!(out, "Description: {}", desc)?;
+            }
+            if !agent.capabilities.is_empty() {
+                writeln!(out, "Capabilities: {}", agent.capabilities.join(", "))?;
+            }
+            if let Some(scope) = &agent.scope {
+                match &scope.board_id {
+                    Some(board_id) => {
+                        writeln!(out, "Scope: {} (board {})", scope.access, board_id)?
+                    }
+                    None => writeln!(out, "Scope: {}", scope.access)?,
+                }
             }
-            println!("Created: {}", agent.created_at.format("%Y-%m-%d %H:%M"));
+            writeln!(
+                out,
+                "Created: {}",
+                agent.created_at.format("%Y-%m-%d %H:%M")
+            )?;
             if let Some(deactivated) = agent.deactivated_at {
-                println!("Deactivated: {}", deactivated.format("%Y-%m-%d %H:%M"));
+                writeln!(out, "Deactivated: {}", deactivated.format("%Y-%m-%d %H:%M"))?;
             }
         }
         OutputFormat::Simple => {
-            println!("{}", agent.id);
+            writeln!(out, "{}", agent.id)?;
         }
-        OutputFormat::Pretty => {
-            // Pretty format doesn't apply to single agent, fall back to table
-            print_agent(agent, OutputFormat::Table);
+        _ => {
+            // Pretty, graph, and markdown formats don't apply to single agent, fall back to table
+            print_agent(out, agent, OutputFormat::Table)?;
         }
     }
+    Ok(())
 }
 
-pub fn print_agent_whoami(agent: &Agent, current_dir: &str) {
-    println!("Agent: {}", agent.id);
-    println!("Name: {}", agent.name);
-    println!("Command: {}", agent.command);
-    println!("Working Directory: {}", agent.working_directory);
+pub fn print_agent_whoami(
+    out: &mut impl Write,
+    agent: &Agent,
+    current_dir: &str,
+) -> io::Result<()> {
+    writeln!(out, "Agent: {}", agent.id)?;
+    writeln!(out, "Name: {}", agent.name)?;
+    writeln!(out, "Command: {}", agent.command)?;
+    writeln!(out, "Working Directory: {}", agent.working_directory)?;
+    if !agent.extra_workdirs.is_empty() {
+        writeln!(
+            out,
+            "Other Working Directories: {}",
+            agent.extra_workdirs.join(", ")
+        )?;
+    }
     if let Some(desc) = &agent.description {
-        println!("Description: {}", desc);
+        writeln!(out, "Description: {}", desc)?;
     }
 
-    // Check if current directory matches
-    if current_dir != agent.working_directory {
-        eprintln!(
-            "WARNING: Current directory ({}) does not match registered working directory",
-            current_dir
-        );
+    // Check if current directory is one of the registered ones
+    let current = Path::new(current_dir);
+    let registered = std::iter::once(&agent.working_directory).chain(&agent.extra_workdirs);
+    if !registered.clone().any(|dir| Path::new(dir) == current) {
+        if agent.extra_workdirs.is_empty() {
+            eprintln!(
+                "WARNING: Current directory ({}) does not match registered working directory",
+                current_dir
+            );
+        } else {
+            eprintln!(
+                "WARNING: Current directory ({}) is not one of the registered working directories: {}",
+                current_dir,
+                registered
+                    .map(String::as_str)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
     }
+    Ok(())
 }
 
-pub fn print_comments(comments: &[Comment], format: OutputFormat) {
+pub fn print_comments(
+    out: &mut impl Write,
+    comments: &[Comment],
+    format: OutputFormat,
+) -> io::Result<()> {
     match format {
         OutputFormat::Json => {
-            println!("{}", serde_json::to_string_pretty(&comments).unwrap());
+            writeln!(out, "{}", serde_json::to_string_pretty(&comments).unwrap())?;
         }
         OutputFormat::Table => {
             if comments.is_empty() {
-                println!("No comments found.");
-                return;
+                writeln!(out, "No comments found.")?;
+                return Ok(());
             }
             for comment in comments {
-                let author = comment.author.as_deref().unwrap_or("anonymous");
+                let author = comment
+                    .author
+                    .as_deref()
+                    .map(agent_label)
+                    .unwrap_or_else(|| "anonymous".to_string());
                 let time = comment.created_at.format("%Y-%m-%d %H:%M");
-                println!("─────────────────────────────────────────────────────────────");
-                println!("[{}] {} ({})", author, time, comment.id);
-                println!();
-                println!("{}", comment.text);
-                println!();
+                writeln!(
+                    out,
+                    "─────────────────────────────────────────────────────────────"
+                )?;
+                writeln!(
+                    out,
+                    "[{}] {} ({}){}",
+                    author,
+                    time,
+                    comment.id,
+                    comment_kind_badge(comment.kind)
+                )?;
+                writeln!(out)?;
+                writeln!(out, "{}", highlight::comment_text(&comment.text))?;
+                if !comment.reactions.is_empty() {
+                    writeln!(out, "{}", reactions_summary(&comment.reactions).dimmed())?;
+                }
+                if let Some(meta) = &comment.meta {
+                    writeln!(out, "{} {}", "meta:".dimmed(), truncate(meta.get(), 200))?;
+                }
+                writeln!(out)?;
             }
         }
         OutputFormat::Simple => {
             for comment in comments {
-                println!("{}", comment.id);
+                writeln!(out, "{}", comment.id)?;
+            }
+        }
+        _ => {
+            // Pretty, graph, and markdown formats don't apply to comments, fall back to table
+            print_comments(out, comments, OutputFormat::Table)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn print_snapshots(
+    out: &mut impl Write,
+    snapshots: &[Snapshot],
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&snapshots).unwrap())?;
+        }
+        OutputFormat::Table => {
+            if snapshots.is_empty() {
+                writeln!(out, "No snapshots found.")?;
+                return Ok(());
+            }
+            let rows: Vec<SnapshotRow> = snapshots
+                .iter()
+                .map(|s| SnapshotRow {
+                    name: s.name.clone(),
+                    size: format_size(s.size_bytes),
+                    created_at: s.created_at.format("%Y-%m-%d %H:%M").to_string(),
+                })
+                .collect();
+            let table = rounded_table(rows);
+            writeln!(out, "{}", table)?;
+        }
+        OutputFormat::Simple => {
+            for snapshot in snapshots {
+                writeln!(out, "{}", snapshot.name)?;
+            }
+        }
+        _ => {
+            // Pretty, graph, and markdown formats don't apply to snapshots, fall back to table
+            print_snapshots(out, snapshots, OutputFormat::Table)?;
+        }
+    }
+    Ok(())
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+pub fn print_merge_report(
+    out: &mut impl Write,
+    report: &MergeReport,
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&report).unwrap())?;
+        }
+        OutputFormat::Table => {
+            writeln!(
+                out,
+                "Agents: {} added, {} updated",
+                report.agents_added, report.agents_updated
+            )?;
+            writeln!(
+                out,
+                "Boards: {} added, {} updated",
+                report.boards_added, report.boards_updated
+            )?;
+            writeln!(
+                out,
+                "Cards: {} added, {} updated",
+                report.cards_added, report.cards_updated
+            )?;
+            writeln!(out, "Comments: {} added", report.comments_added)?;
+            if !report.conflicts.is_empty() {
+                writeln!(out, "\nUnresolved conflicts ({}):", report.conflicts.len())?;
+                for conflict in &report.conflicts {
+                    writeln!(out, "  {}: {}", conflict.entity_id, conflict.reason)?;
+                }
+            }
+        }
+        OutputFormat::Simple => {
+            for conflict in &report.conflicts {
+                writeln!(out, "{}", conflict.entity_id)?;
+            }
+        }
+        _ => {
+            // Pretty, graph, and markdown formats don't apply to merge reports, fall back to table
+            print_merge_report(out, report, OutputFormat::Table)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn print_db_status(
+    out: &mut impl Write,
+    status: &DbStatus,
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&status).unwrap())?;
+        }
+        OutputFormat::Simple => {
+            writeln!(out, "{}", status.path)?;
+        }
+        _ => {
+            writeln!(out, "Path: {}", status.path)?;
+            if let Some(filesystem) = &status.filesystem {
+                writeln!(out, "Filesystem: {}", filesystem)?;
+            }
+            if status.read_only {
+                writeln!(out, "Access: read-only (reading a temporary copy)")?;
+            } else if status.local_cache {
+                writeln!(out, "Access: local cache (written back on success)")?;
+            }
+            writeln!(out, "Size: {} bytes", status.size_bytes)?;
+            writeln!(out, "Schema version: {}", status.schema_version)?;
+            writeln!(out, "Journal mode: {}", status.journal_mode)?;
+            writeln!(out, "Synchronous: {}", status.synchronous)?;
+            writeln!(
+                out,
+                "Single writer: {}",
+                if status.single_writer { "on" } else { "off" }
+            )?;
+        }
+    }
+    Ok(())
+}
+
+pub fn print_board_stats(
+    out: &mut impl Write,
+    stats: &[BoardStats],
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&stats).unwrap())?;
+        }
+        OutputFormat::Simple => {
+            for entry in stats {
+                let c = &entry.counts;
+                writeln!(
+                    out,
+                    "{}\t{}\t{}\t{}\t{}\t{}",
+                    entry.board_id, entry.day, c.todo, c.in_progress, c.pending_review, c.done
+                )?;
+            }
+        }
+        _ => {
+            if stats.is_empty() {
+                writeln!(out, "No boards found.")?;
+                return Ok(());
+            }
+            let rows: Vec<BoardStatsRow> = stats
+                .iter()
+                .map(|entry| BoardStatsRow {
+                    board_id: entry.board_id.clone(),
+                    day: entry.day.to_string(),
+                    todo: entry.counts.todo,
+                    in_progress: entry.counts.in_progress,
+                    pending_review: entry.counts.pending_review,
+                    done: entry.counts.done,
+                })
+                .collect();
+            let table = rounded_table(rows);
+            writeln!(out, "{}", table)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn print_blob_gc(
+    out: &mut impl Write,
+    report: &BlobGcReport,
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&report).unwrap())?;
+        }
+        OutputFormat::Simple => {
+            writeln!(out, "{}", report.blobs_removed)?;
+        }
+        _ => {
+            writeln!(
+                out,
+                "Removed {} unreferenced blob(s), freed {} bytes",
+                report.blobs_removed, report.bytes_freed
+            )?;
+        }
+    }
+    Ok(())
+}
+
+pub fn print_import_report(
+    out: &mut impl Write,
+    report: &ImportReport,
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&report).unwrap())?;
+        }
+        OutputFormat::Simple => {
+            writeln!(out, "{}", report.cards_created)?;
+        }
+        _ => {
+            let verb = if report.dry_run {
+                "Would import"
+            } else {
+                "Imported"
+            };
+            writeln!(
+                out,
+                "{} {} card(s) and {} comment(s), skipped {} task(s)",
+                verb, report.cards_created, report.comments_added, report.skipped
+            )?;
+            for name in &report.boards_created {
+                let verb = if report.dry_run {
+                    "Would create"
+                } else {
+                    "Created"
+                };
+                writeln!(out, "  {} board: {}", verb, name)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn print_bench(
+    out: &mut impl Write,
+    results: &[BenchResult],
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&results).unwrap())?;
+        }
+        OutputFormat::Table => {
+            let rows: Vec<BenchRow> = results
+                .iter()
+                .map(|r| BenchRow {
+                    operation: r.operation.clone(),
+                    iterations: r.iterations,
+                    p50: format!("{:.2}", r.p50_ms),
+                    p90: format!("{:.2}", r.p90_ms),
+                    p99: format!("{:.2}", r.p99_ms),
+                    max: format!("{:.2}", r.max_ms),
+                    ops_per_sec: format!("{:.0}", r.ops_per_sec),
+                })
+                .collect();
+            let table = rounded_table(rows);
+            writeln!(out, "{}", table)?;
+        }
+        OutputFormat::Simple => {
+            for r in results {
+                writeln!(out, "{}\t{:.2}", r.operation, r.p50_ms)?;
             }
         }
-        OutputFormat::Pretty => {
-            // Pretty format doesn't apply to comments, fall back to table
-            print_comments(comments, OutputFormat::Table);
+        _ => {
+            // Pretty, graph, and markdown formats don't apply to benchmarks, fall back to table
+            print_bench(out, results, OutputFormat::Table)?;
         }
     }
+    Ok(())
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
 
+    fn label(name: &str) -> Label {
+        Label {
+            id: format!("label_{}", name),
+            name: name.into(),
+            color: "red".into(),
+        }
+    }
+
+    fn capture(print: impl FnOnce(&mut Vec<u8>) -> io::Result<()>) -> String {
+        let mut buf = Vec::new();
+        print(&mut buf).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn simple_format_prints_one_name_per_line() {
+        let labels = [label("bug"), label("ux")];
+        let out = capture(|w| print_labels(w, &labels, OutputFormat::Simple));
+        assert_eq!(out, "bug\nux\n");
+    }
+
+    #[test]
+    fn empty_table_prints_placeholder() {
+        let out = capture(|w| print_cards(w, &[], OutputFormat::Table));
+        assert_eq!(out, "No cards found.\n");
+    }
+
+    #[test]
+    fn gha_format_prints_escaped_annotations() {
+        let report = CheckReport {
+            board_id: "board_1".into(),
+            rules: vec![CheckRule::Overdue, CheckRule::WipExceeded],
+            passed: false,
+            violations: vec![
+                CheckViolation {
+                    rule: CheckRule::Overdue,
+                    card_id: Some("card_1".into()),
+                    message: "Due 2026-01-31, 100% late\nsee board".into(),
+                },
+                CheckViolation {
+                    rule: CheckRule::WipExceeded,
+                    card_id: None,
+                    message: "in_progress has 4 cards (limit 3)".into(),
+                },
+            ],
+        };
+        let out = capture(|w| print_check_report(w, &report, OutputFormat::Gha));
+        assert_eq!(
+            out,
+            "::error title=agent-board check%3A overdue::card_1: Due 2026-01-31, 100%25 late%0Asee board\n\
+             ::error title=agent-board check%3A wip-exceeded::in_progress has 4 cards (limit 3)\n"
+        );
+    }
+
+    #[test]
+    fn unsupported_format_falls_back_to_table() {
+        let out = capture(|w| print_labels(w, &[], OutputFormat::Markdown));
+        assert_eq!(out, "No labels found.\n");
+    }
+
+    #[test]
+    fn grouped_boards_print_a_table_per_group() {
+        let board = |id: &str, group: Option<&str>| Board {
+            id: id.into(),
+            name: id.into(),
+            description: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+            namespace: "default".into(),
+            starred: false,
+            last_used_at: None,
+            group: group.map(Into::into),
+            pinned_note: None,
+            card_counts: None,
+            etag: None,
+        };
+        let boards = [
+            board("board_a", Some("infra")),
+            board("board_b", Some("Infra")),
+            board("board_c", None),
+        ];
+        let out = capture(|w| print_boards(w, &boards, OutputFormat::Table));
+        assert_eq!(out.matches('╭').count(), 2);
+        let infra = out.find("infra").unwrap();
+        let ungrouped = out.find("Ungrouped").unwrap();
+        assert!(infra < out.find("board_b").unwrap() && out.find("board_b").unwrap() < ungrouped);
+        assert!(out.contains("(2 boards)") && out.contains("(1 board)"));
+    }
+}
@@ -0,0 +1,77 @@
+//! Plan files for `plan apply`: a declared work breakdown in YAML or JSON.
+//!
+//! ```yaml
+//! cards:
+//!   - key: schema
+//!     name: Design the schema
+//!     tags: [backend]
+//!     checklist: [Draft tables, Review indexes]
+//!   - key: api
+//!     name: Build the API
+//!     depends_on: [schema]
+//!     assignee: agent_abc123
+//! ```
+
+use crate::AgentBoardError;
+use crate::models::Plan;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Read and validate a plan file; `.json` files are parsed as JSON, anything
+/// else as YAML.
+pub fn load(path: &Path) -> Result<Plan, AgentBoardError> {
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        AgentBoardError::InvalidArgs(format!("Cannot read plan {}: {}", path.display(), e))
+    })?;
+    let is_json = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    let plan: Plan = if is_json {
+        serde_json::from_str(&content).map_err(|e| {
+            AgentBoardError::InvalidArgs(format!("Invalid plan {}: {}", path.display(), e))
+        })?
+    } else {
+        serde_yaml::from_str(&content).map_err(|e| {
+            AgentBoardError::InvalidArgs(format!("Invalid plan {}: {}", path.display(), e))
+        })?
+    };
+    validate(&plan)?;
+    Ok(plan)
+}
+
+/// Keys must be unique and non-empty, and dependencies must name keys in the
+/// same plan (checked before anything is written)
+fn validate(plan: &Plan) -> Result<(), AgentBoardError> {
+    let mut keys = HashSet::new();
+    for card in &plan.cards {
+        if card.key.trim().is_empty() {
+            return Err(AgentBoardError::InvalidArgs(format!(
+                "Plan card '{}' has an empty key",
+                card.name
+            )));
+        }
+        if !keys.insert(card.key.as_str()) {
+            return Err(AgentBoardError::InvalidArgs(format!(
+                "Duplicate plan key: {}",
+                card.key
+            )));
+        }
+    }
+    for card in &plan.cards {
+        for dep in &card.depends_on {
+            if !keys.contains(dep.as_str()) {
+                return Err(AgentBoardError::InvalidArgs(format!(
+                    "Plan card '{}' depends on unknown key '{}'",
+                    card.key, dep
+                )));
+            }
+            if dep == &card.key {
+                return Err(AgentBoardError::InvalidArgs(format!(
+                    "Plan card '{}' depends on itself",
+                    card.key
+                )));
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,265 @@
+//! Delivery planning: deadline projection for `report schedule` and the
+//! critical path for `report critical-path`.
+//!
+//! Each assignee works through their open, estimated cards one at a time,
+//! earliest due date first, at `capacity` minutes of work per calendar day.
+//! A card's projected finish is when the assignee's queue reaches its end;
+//! past the due date, the card is flagged and another agent who could finish
+//! it in time (with their own queue ahead of it) is suggested.
+//!
+//! The critical path is the dependency chain of open cards with the most
+//! estimated work: nothing on it can overlap, so it bounds delivery.
+
+use crate::models::{Card, ScheduleEntry, ScheduleRisk, Status};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{HashMap, HashSet};
+
+/// Cards without a due date go after every dated one
+fn queue_order(a: &&Card, b: &&Card) -> std::cmp::Ordering {
+    (a.due_at.is_none(), a.due_at, a.created_at, &a.id).cmp(&(
+        b.due_at.is_none(),
+        b.due_at,
+        b.created_at,
+        &b.id,
+    ))
+}
+
+fn finish_after(now: DateTime<Utc>, work_minutes: i64, capacity: i64) -> DateTime<Utc> {
+    now + Duration::minutes(work_minutes * 24 * 60 / capacity.max(1))
+}
+
+/// Project the open, dated cards of `board_cards` against everyone's
+/// queues; `assigned` is every open assigned card (any board), `agents` the
+/// candidates for reassignment. Sorted by due date.
+pub fn project(
+    board_cards: &[Card],
+    assigned: &[Card],
+    agents: &[String],
+    capacity: i64,
+    now: DateTime<Utc>,
+) -> Vec<ScheduleEntry> {
+    let mut queues: HashMap<&str, Vec<&Card>> = HashMap::new();
+    for card in assigned
+        .iter()
+        .filter(|c| c.status != Status::Done && c.estimate_minutes.is_some())
+    {
+        if let Some(agent) = card.assigned_to.as_deref() {
+            queues.entry(agent).or_default().push(card);
+        }
+    }
+    for queue in queues.values_mut() {
+        queue.sort_by(queue_order);
+    }
+
+    // Minutes of work `agent` has queued up to and including `card`
+    let load_through = |agent: &str, card: &Card| -> i64 {
+        let queue = queues.get(agent).map(Vec::as_slice).unwrap_or_default();
+        let ahead: i64 = queue
+            .iter()
+            .filter(|c| c.id != card.id && queue_order(c, &card).is_lt())
+            .filter_map(|c| c.estimate_minutes)
+            .sum();
+        ahead + card.estimate_minutes.unwrap_or_default()
+    };
+
+    let mut entries: Vec<ScheduleEntry> = board_cards
+        .iter()
+        .filter(|c| c.status != Status::Done)
+        .filter_map(|card| {
+            let due = card.due_at?;
+            if card.estimate_minutes.is_none() {
+                return Some(ScheduleEntry {
+                    card: card.clone(),
+                    projected_finish: None,
+                    risk: ScheduleRisk::NoEstimate,
+                    suggested_agent: None,
+                });
+            }
+            let projected_finish = card
+                .assigned_to
+                .as_deref()
+                .map(|agent| finish_after(now, load_through(agent, card), capacity));
+            let risk = if due <= now {
+                ScheduleRisk::Overdue
+            } else if card.assigned_to.is_none() {
+                ScheduleRisk::Unassigned
+            } else if projected_finish.is_some_and(|finish| finish > due) {
+                ScheduleRisk::Late
+            } else {
+                ScheduleRisk::OnTrack
+            };
+            let suggested_agent = match risk {
+                ScheduleRisk::Late | ScheduleRisk::Unassigned => agents
+                    .iter()
+                    .filter(|a| card.assigned_to.as_deref() != Some(a.as_str()))
+                    .map(|a| (finish_after(now, load_through(a, card), capacity), a))
+                    .filter(|(finish, _)| *finish <= due)
+                    .min()
+                    .map(|(_, a)| a.clone()),
+                _ => None,
+            };
+            Some(ScheduleEntry {
+                card: card.clone(),
+                projected_finish,
+                risk,
+                suggested_agent,
+            })
+        })
+        .collect();
+    entries.sort_by(|a, b| queue_order(&&a.card, &&b.card));
+    entries
+}
+
+/// Longest chain ending at each card: (estimated minutes, cards, the
+/// dependency it continues from)
+type Chains<'a> = HashMap<&'a str, (i64, usize, Option<&'a str>)>;
+
+fn longest_chain<'a>(
+    id: &'a str,
+    open: &HashMap<&'a str, &'a Card>,
+    chains: &mut Chains<'a>,
+    visiting: &mut HashSet<&'a str>,
+) -> (i64, usize) {
+    if let Some((minutes, len, _)) = chains.get(id) {
+        return (*minutes, *len);
+    }
+    visiting.insert(id);
+    let card = open[id];
+    let mut best = (0, 0);
+    let mut from = None;
+    for dep in card
+        .depends_on
+        .iter()
+        .filter(|d| open.contains_key(d.as_str()))
+    {
+        // A dependency cycle (possible after `merge`) ends the chain
+        if visiting.contains(dep.as_str()) {
+            continue;
+        }
+        let chain = longest_chain(dep, open, chains, visiting);
+        if from.is_none() || chain > best {
+            best = chain;
+            from = Some(dep.as_str());
+        }
+    }
+    visiting.remove(id);
+    let chain = (
+        best.0 + card.estimate_minutes.unwrap_or_default(),
+        best.1 + 1,
+    );
+    chains.insert(id, (chain.0, chain.1, from));
+    chain
+}
+
+/// The chain of open cards with the most estimated work (then the most
+/// cards), first to do first; unestimated cards count as no work
+pub fn critical_path(cards: &[Card]) -> Vec<&Card> {
+    let open: HashMap<&str, &Card> = cards
+        .iter()
+        .filter(|c| c.status != Status::Done)
+        .map(|c| (c.id.as_str(), c))
+        .collect();
+    let mut chains = Chains::new();
+    let mut end: Option<(&str, (i64, usize))> = None;
+    for card in cards.iter().filter(|c| open.contains_key(c.id.as_str())) {
+        let chain = longest_chain(&card.id, &open, &mut chains, &mut HashSet::new());
+        if end.is_none_or(|(_, best)| chain > best) {
+            end = Some((&card.id, chain));
+        }
+    }
+
+    let mut path = Vec::new();
+    let mut next = end.map(|(id, _)| id);
+    while let Some(id) = next {
+        path.push(open[id]);
+        next = chains[id].2;
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-03-10T09:00:00Z")
+            .unwrap()
+            .into()
+    }
+
+    fn card(id: &str, agent: Option<&str>, estimate_hours: i64, due_days: i64) -> Card {
+        Card {
+            id: id.into(),
+            board_id: "board_x".into(),
+            name: id.into(),
+            description: None,
+            status: Status::Todo,
+            assigned_to: agent.map(Into::into),
+            tags: Vec::new(),
+            labels: Vec::new(),
+            checklist: Vec::new(),
+            depends_on: Vec::new(),
+            created_at: now(),
+            updated_at: now(),
+            deleted_at: None,
+            due_at: Some(now() + Duration::days(due_days)),
+            estimate_minutes: Some(estimate_hours * 60),
+            pr_url: None,
+            plan_key: None,
+            comment_count: None,
+            etag: None,
+        }
+    }
+
+    #[test]
+    fn queued_work_pushes_later_cards_past_their_due_date() {
+        // 6h a day: alice's 12h card takes two days, so the 6h card due in
+        // two days can't start until it's done
+        let cards = vec![
+            card("card_a", Some("alice"), 12, 2),
+            card("card_b", Some("alice"), 6, 2),
+            card("card_c", None, 6, 3),
+        ];
+        let agents = vec!["alice".to_string(), "bob".to_string()];
+        let entries = project(&cards, &cards, &agents, 6 * 60, now());
+        let risk: Vec<_> = entries
+            .iter()
+            .map(|e| (e.card.id.as_str(), e.risk))
+            .collect();
+        assert_eq!(
+            risk,
+            [
+                ("card_a", ScheduleRisk::OnTrack),
+                ("card_b", ScheduleRisk::Late),
+                ("card_c", ScheduleRisk::Unassigned),
+            ]
+        );
+        assert_eq!(entries[1].suggested_agent.as_deref(), Some("bob"));
+        assert_eq!(entries[2].suggested_agent.as_deref(), Some("bob"));
+    }
+
+    #[test]
+    fn critical_path_follows_the_most_estimated_work() {
+        // design (4h) -> api (8h) -> launch (1h) outweighs design -> copy (2h) -> launch
+        let mut cards = vec![
+            card("design", None, 4, 9),
+            card("api", None, 8, 9),
+            card("copy", None, 2, 9),
+            card("launch", None, 1, 9),
+            card("shipped", None, 40, 9),
+        ];
+        cards[1].depends_on = vec!["design".into()];
+        cards[2].depends_on = vec!["design".into()];
+        cards[3].depends_on = vec!["copy".into(), "api".into(), "shipped".into()];
+        cards[4].status = Status::Done;
+        let path: Vec<&str> = critical_path(&cards)
+            .iter()
+            .map(|c| c.id.as_str())
+            .collect();
+        assert_eq!(path, ["design", "api", "launch"]);
+
+        cards[0].depends_on = vec!["launch".into()];
+        assert_eq!(critical_path(&cards).len(), 3);
+    }
+}
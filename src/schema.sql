@@ -64,6 +64,239 @@ CREATE TABLE IF NOT EXISTS comments (
     FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE
 );
 
+-- Assignment history (one row per change of a card's assignee)
+CREATE TABLE IF NOT EXISTS assignments (
+    id TEXT PRIMARY KEY NOT NULL,
+    card_id TEXT NOT NULL,
+    agent_id TEXT,
+    previous_agent_id TEXT,
+    assigned_by TEXT,
+    assigned_at TEXT NOT NULL,
+    FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE
+);
+
+-- Earlier values of card text fields, written by update_card() before a change
+CREATE TABLE IF NOT EXISTS card_revisions (
+    id TEXT PRIMARY KEY NOT NULL,
+    card_id TEXT NOT NULL,
+    field TEXT NOT NULL,
+    value TEXT,
+    replaced_by TEXT,
+    replaced_at TEXT NOT NULL,
+    FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE
+);
+
+-- Card dependencies (card_id cannot be finished before depends_on is done)
+CREATE TABLE IF NOT EXISTS card_dependencies (
+    card_id TEXT NOT NULL,
+    depends_on TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (card_id, depends_on),
+    FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE,
+    FOREIGN KEY (depends_on) REFERENCES cards(id) ON DELETE CASCADE
+);
+
+-- Per-board settings (key/value, e.g. auto_unblock)
+CREATE TABLE IF NOT EXISTS board_settings (
+    board_id TEXT NOT NULL,
+    key TEXT NOT NULL,
+    value TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (board_id, key),
+    FOREIGN KEY (board_id) REFERENCES boards(id) ON DELETE CASCADE
+);
+
+-- Event log (card created/moved/assigned, comment added); data is JSON
+CREATE TABLE IF NOT EXISTS events (
+    id TEXT PRIMARY KEY NOT NULL,
+    event TEXT NOT NULL,
+    board_id TEXT NOT NULL,
+    card_id TEXT NOT NULL,
+    actor TEXT,
+    data TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+-- Due dates `sweep` has already sent a card_due_soon reminder for; moving
+-- the due date makes the card eligible again
+CREATE TABLE IF NOT EXISTS due_reminders (
+    card_id TEXT PRIMARY KEY NOT NULL,
+    due_at TEXT NOT NULL,
+    reminded_at TEXT NOT NULL,
+    FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE
+);
+
+-- Shell commands run when matching events fire
+CREATE TABLE IF NOT EXISTS hooks (
+    id TEXT PRIMARY KEY NOT NULL,
+    event TEXT NOT NULL,
+    command TEXT NOT NULL,
+    board_id TEXT,
+    status TEXT,
+    created_at TEXT NOT NULL
+);
+
+-- Chat webhooks posted to when a board's cards reach one of `statuses`
+CREATE TABLE IF NOT EXISTS notifiers (
+    id TEXT PRIMARY KEY NOT NULL,
+    board_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    url TEXT NOT NULL,
+    statuses TEXT NOT NULL, -- comma-separated
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (board_id) REFERENCES boards(id) ON DELETE CASCADE
+);
+
+-- Board-scoped labels with a display color (managed, unlike free-form tags)
+CREATE TABLE IF NOT EXISTS labels (
+    id TEXT PRIMARY KEY NOT NULL,
+    board_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    color TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    UNIQUE (board_id, name),
+    FOREIGN KEY (board_id) REFERENCES boards(id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS card_labels (
+    card_id TEXT NOT NULL,
+    label_id TEXT NOT NULL,
+    PRIMARY KEY (card_id, label_id),
+    FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE,
+    FOREIGN KEY (label_id) REFERENCES labels(id) ON DELETE CASCADE
+);
+
+-- Sign-offs a card needs before it can be done (approved_at is NULL while open)
+CREATE TABLE IF NOT EXISTS card_gates (
+    id TEXT PRIMARY KEY NOT NULL,
+    card_id TEXT NOT NULL,
+    requirement TEXT NOT NULL,
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+    approved_by TEXT,
+    approved_at TEXT,
+    FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE
+);
+
+-- Skills an agent declares (e.g. rust, terraform), for filtering agents
+CREATE TABLE IF NOT EXISTS agent_capabilities (
+    agent_id TEXT NOT NULL,
+    capability TEXT NOT NULL,
+    PRIMARY KEY (agent_id, capability),
+    FOREIGN KEY (agent_id) REFERENCES agents(id) ON DELETE CASCADE
+);
+
+-- Working directories an agent uses besides agents.working_directory
+-- (worktrees, monorepo subdirectories); `whoami` accepts any of them
+CREATE TABLE IF NOT EXISTS agent_workdirs (
+    agent_id TEXT NOT NULL,
+    path TEXT NOT NULL,
+    PRIMARY KEY (agent_id, path),
+    FOREIGN KEY (agent_id) REFERENCES agents(id) ON DELETE CASCADE
+);
+
+-- Database-wide key/value settings (`config db`), e.g. journal_mode
+CREATE TABLE IF NOT EXISTS db_settings (
+    key TEXT PRIMARY KEY NOT NULL,
+    value TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+-- Review card opened by the `review.board` policy for a card (latest one)
+CREATE TABLE IF NOT EXISTS review_cards (
+    card_id TEXT PRIMARY KEY NOT NULL,
+    review_card_id TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE,
+    FOREIGN KEY (review_card_id) REFERENCES cards(id) ON DELETE CASCADE
+);
+
+-- Card embeddings (`embed`, `search --semantic`; semantic feature): a
+-- vector32() blob per card, compared only with vectors of the same length
+CREATE TABLE IF NOT EXISTS card_embeddings (
+    card_id TEXT PRIMARY KEY NOT NULL,
+    embedding F32_BLOB NOT NULL,
+    dims INTEGER NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE
+);
+
+-- Daily per-status card counts (`stats snapshot`, `sweep`), for `chart cfd`
+CREATE TABLE IF NOT EXISTS board_stats (
+    board_id TEXT NOT NULL,
+    day TEXT NOT NULL,
+    todo INTEGER NOT NULL,
+    in_progress INTEGER NOT NULL,
+    pending_review INTEGER NOT NULL,
+    done INTEGER NOT NULL,
+    recorded_at TEXT NOT NULL,
+    PRIMARY KEY (board_id, day),
+    FOREIGN KEY (board_id) REFERENCES boards(id) ON DELETE CASCADE
+);
+
+-- Restrictions on what an agent may change (no row: unrestricted)
+CREATE TABLE IF NOT EXISTS agent_scopes (
+    agent_id TEXT PRIMARY KEY NOT NULL,
+    access TEXT NOT NULL DEFAULT 'full',
+    board_id TEXT,
+    FOREIGN KEY (agent_id) REFERENCES agents(id) ON DELETE CASCADE
+);
+
+-- When each agent last ran `brief`; its next brief starts here
+CREATE TABLE IF NOT EXISTS agent_briefs (
+    agent_id TEXT PRIMARY KEY NOT NULL,
+    briefed_at TEXT NOT NULL
+);
+
+-- Boards pinned to the top of `list boards`
+CREATE TABLE IF NOT EXISTS board_stars (
+    board_id TEXT PRIMARY KEY NOT NULL,
+    starred_at TEXT NOT NULL,
+    FOREIGN KEY (board_id) REFERENCES boards(id) ON DELETE CASCADE
+);
+
+-- When each board was last used by a command, for most-recently-used ordering
+CREATE TABLE IF NOT EXISTS board_usage (
+    board_id TEXT PRIMARY KEY NOT NULL,
+    used_at TEXT NOT NULL,
+    FOREIGN KEY (board_id) REFERENCES boards(id) ON DELETE CASCADE
+);
+
+-- Content-addressed payloads (large comment bodies), shared by identical posts
+CREATE TABLE IF NOT EXISTS blobs (
+    hash TEXT PRIMARY KEY NOT NULL, -- SHA-256 of content, hex
+    content TEXT NOT NULL,
+    size INTEGER NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+-- One row per mutation an agent's budget admitted; `check_budget()` counts
+-- these and prunes rows older than the window
+CREATE TABLE IF NOT EXISTS mutation_log (
+    actor TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+-- Single-use tokens from `delete/purge board --request`; actor is '' for humans
+CREATE TABLE IF NOT EXISTS removal_tokens (
+    token TEXT PRIMARY KEY NOT NULL,
+    board_id TEXT NOT NULL,
+    action TEXT NOT NULL,
+    actor TEXT NOT NULL DEFAULT '',
+    expires_at TEXT NOT NULL,
+    FOREIGN KEY (board_id) REFERENCES boards(id) ON DELETE CASCADE
+);
+
+-- Emoji reactions on comments; author is '' when no agent is configured
+CREATE TABLE IF NOT EXISTS comment_reactions (
+    comment_id TEXT NOT NULL,
+    emoji TEXT NOT NULL,
+    author TEXT NOT NULL DEFAULT '',
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (comment_id, emoji, author),
+    FOREIGN KEY (comment_id) REFERENCES comments(id) ON DELETE CASCADE
+);
+
 -- Indexes for common queries
 CREATE INDEX IF NOT EXISTS idx_cards_board_id ON cards(board_id);
 CREATE INDEX IF NOT EXISTS idx_cards_status ON cards(status);
@@ -71,3 +304,10 @@ CREATE INDEX IF NOT EXISTS idx_cards_assigned_to ON cards(assigned_to);
 CREATE INDEX IF NOT EXISTS idx_checklist_items_card_id ON checklist_items(card_id);
 CREATE INDEX IF NOT EXISTS idx_comments_card_id ON comments(card_id);
 CREATE INDEX IF NOT EXISTS idx_card_tags_card_id ON card_tags(card_id);
+CREATE INDEX IF NOT EXISTS idx_card_gates_card_id ON card_gates(card_id);
+CREATE INDEX IF NOT EXISTS idx_card_revisions_card_id ON card_revisions(card_id, field);
+CREATE INDEX IF NOT EXISTS idx_assignments_card_id ON assignments(card_id, assigned_at);
+CREATE INDEX IF NOT EXISTS idx_card_dependencies_depends_on ON card_dependencies(depends_on);
+CREATE INDEX IF NOT EXISTS idx_events_card_id ON events(card_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_events_board_id ON events(board_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_mutation_log_actor ON mutation_log(actor, created_at);
@@ -0,0 +1,53 @@
+//! Fuzzy text matching for card names.
+//!
+//! Text is lowercased and split into words, and each word (padded the way
+//! PostgreSQL's pg_trgm does) is cut into three-character grams. Two texts
+//! score the share of grams they have in common, from 0.0 to 1.0, so word
+//! order, punctuation, and small typos barely move the score.
+
+use std::collections::HashSet;
+
+/// Score at or above which two card names count as the same task
+pub const DUPLICATE_THRESHOLD: f64 = 0.8;
+
+fn trigrams(text: &str) -> HashSet<String> {
+    let mut grams = HashSet::new();
+    for word in text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+    {
+        let padded: Vec<char> = format!("  {} ", word.to_lowercase()).chars().collect();
+        for gram in padded.windows(3) {
+            grams.insert(gram.iter().collect());
+        }
+    }
+    grams
+}
+
+/// Shared trigrams over all trigrams of both texts (1.0 = same words)
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (trigrams(a), trigrams(b));
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f64 / union as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ignores_case_punctuation_and_order() {
+        assert_eq!(similarity("Fix login timeout", "timeout: fix LOGIN"), 1.0);
+    }
+
+    #[test]
+    fn near_duplicates_pass_the_threshold_and_unrelated_names_do_not() {
+        assert!(similarity("Fix login timeout", "Fix the login timeout") >= DUPLICATE_THRESHOLD);
+        assert!(similarity("Fix login timeout", "Fix logout timeout") < DUPLICATE_THRESHOLD);
+        assert!(similarity("Fix login timeout", "Write release notes") < 0.1);
+        assert_eq!(similarity("", "anything"), 0.0);
+    }
+}
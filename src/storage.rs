@@ -0,0 +1,144 @@
+//! Where the database file lives: read-only and network filesystem
+//! detection, and the local cache that works on a temporary copy.
+
+use crate::AgentBoardError;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Filesystems where SQLite's locking (and WAL's shared memory) can't be
+/// trusted across hosts or the VM boundary
+const NETWORK_FILESYSTEMS: &[&str] = &[
+    "nfs",
+    "nfs4",
+    "cifs",
+    "smb3",
+    "smbfs",
+    "9p",
+    "virtiofs",
+    "fuse.sshfs",
+    "afs",
+    "ceph",
+    "glusterfs",
+    "lustre",
+];
+
+/// The mount a path is on
+pub struct Mount {
+    pub fs_type: String,
+    pub read_only: bool,
+}
+
+impl Mount {
+    pub fn is_network(&self) -> bool {
+        NETWORK_FILESYSTEMS.contains(&self.fs_type.as_str())
+    }
+}
+
+/// Find the mount holding `path` from /proc/mounts (longest matching mount
+/// point). None where /proc/mounts doesn't exist, i.e. outside Linux.
+pub fn mount_of(path: &Path) -> Option<Mount> {
+    let mounts = std::fs::read_to_string("/proc/mounts").ok()?;
+    // The database may not exist yet; its nearest existing ancestor decides
+    let path = path
+        .ancestors()
+        .find_map(|p| p.canonicalize().ok())
+        .unwrap_or_else(|| path.to_path_buf());
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let (_, point, fs_type, options) = (
+                fields.next()?,
+                fields.next()?,
+                fields.next()?,
+                fields.next()?,
+            );
+            // Spaces in mount points are written as \040
+            let point = PathBuf::from(point.replace("\\040", " "));
+            path.starts_with(&point).then(|| {
+                let read_only = options.split(',').any(|o| o == "ro");
+                (point, fs_type.to_string(), read_only)
+            })
+        })
+        .max_by_key(|(point, _, _)| point.as_os_str().len())
+        .map(|(_, fs_type, read_only)| Mount { fs_type, read_only })
+}
+
+/// Whether the database at `path` can't be written: a read-only mount, or
+/// a file without write permission
+pub fn is_read_only(path: &Path, mount: Option<&Mount>) -> bool {
+    mount.is_some_and(|m| m.read_only)
+        || std::fs::metadata(path).is_ok_and(|m| m.permissions().readonly())
+}
+
+/// A temporary copy of the database that commands run against. Changes are
+/// written back to `target` in one rename by `sync_back`; the copy is removed
+/// when dropped.
+pub struct LocalCache {
+    pub target: PathBuf,
+    pub copy: PathBuf,
+    pub read_only: bool, // target can't be written; changes are refused
+}
+
+impl LocalCache {
+    pub fn create(target: &Path, read_only: bool) -> Result<Self, AgentBoardError> {
+        let copy = std::env::temp_dir().join(format!("agent-board-cache-{}.db", Uuid::new_v4()));
+        if target.exists() {
+            std::fs::copy(target, &copy).map_err(|e| {
+                AgentBoardError::General(format!(
+                    "Failed to copy {} to a local cache: {}",
+                    target.display(),
+                    e
+                ))
+            })?;
+            // Commits not yet checkpointed out of a WAL database live in its -wal file
+            let (source_wal, copy_wal) = (sidecar(target, "-wal"), sidecar(&copy, "-wal"));
+            if source_wal.exists() {
+                std::fs::copy(&source_wal, &copy_wal)?;
+            }
+        }
+        Ok(Self {
+            target: target.to_path_buf(),
+            copy,
+            read_only,
+        })
+    }
+
+    /// Replace the target with the cached copy. The copy is written next to
+    /// the target first, so readers see the old file or the new one, never
+    /// half of it. Concurrent writers through other caches are not merged:
+    /// the last sync wins.
+    pub fn sync_back(&self) -> Result<(), AgentBoardError> {
+        let staged = sidecar(&self.target, &format!(".sync-{}", Uuid::new_v4()));
+        let result =
+            std::fs::copy(&self.copy, &staged).and_then(|_| std::fs::rename(&staged, &self.target));
+        if let Err(e) = result {
+            let _ = std::fs::remove_file(&staged);
+            return Err(AgentBoardError::General(format!(
+                "Failed to write the local cache back to {}: {}",
+                self.target.display(),
+                e
+            )));
+        }
+        // The target's old -wal/-shm belong to the replaced file
+        for suffix in ["-wal", "-shm"] {
+            let _ = std::fs::remove_file(sidecar(&self.target, suffix));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for LocalCache {
+    fn drop(&mut self) {
+        for suffix in ["", "-wal", "-shm", "-journal"] {
+            let _ = std::fs::remove_file(sidecar(&self.copy, suffix));
+        }
+    }
+}
+
+/// `path` with `suffix` appended to its file name (e.g. data.db-wal)
+pub fn sidecar(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
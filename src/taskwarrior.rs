@@ -0,0 +1,114 @@
+//! `import taskwarrior`: map a `task export` JSON file onto boards and cards.
+//!
+//! - project → board (matched by name, created when missing)
+//! - pending/waiting → todo (in_progress when started), completed → done;
+//!   deleted tasks and recurring templates are skipped
+//! - tags → tags, annotations → comments, due → due date
+//! - priority (H/M/L), or urgency when unset → `priority:high|medium|low` tag
+
+use crate::AgentBoardError;
+use crate::models::{ImportedCard, Status};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::Deserialize;
+use std::path::Path;
+
+/// The fields of an exported task that have a place on the board; other
+/// attributes (and UDAs) are ignored
+#[derive(Debug, Deserialize)]
+struct Task {
+    uuid: String,
+    description: String,
+    status: String,
+    #[serde(default)]
+    project: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    annotations: Vec<Annotation>,
+    #[serde(default)]
+    priority: Option<String>,
+    #[serde(default)]
+    urgency: Option<f64>,
+    #[serde(default)]
+    due: Option<String>,
+    #[serde(default)]
+    start: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Annotation {
+    entry: String,
+    description: String,
+}
+
+/// Read an export file. Returns the cards to create and how many tasks were
+/// skipped.
+pub fn load(path: &Path) -> Result<(Vec<ImportedCard>, usize), AgentBoardError> {
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        AgentBoardError::InvalidArgs(format!("Cannot read {}: {}", path.display(), e))
+    })?;
+    let tasks: Vec<Task> = serde_json::from_str(&content).map_err(|e| {
+        AgentBoardError::InvalidArgs(format!(
+            "Invalid Taskwarrior export {}: {} (expected the output of `task export`)",
+            path.display(),
+            e
+        ))
+    })?;
+
+    let mut cards = Vec::new();
+    let mut skipped = 0;
+    for task in tasks {
+        let status = match task.status.as_str() {
+            "pending" | "waiting" if task.start.is_some() => Status::InProgress,
+            "pending" | "waiting" => Status::Todo,
+            "completed" => Status::Done,
+            _ => {
+                skipped += 1;
+                continue;
+            }
+        };
+        let mut tags = task.tags;
+        tags.push(format!(
+            "priority:{}",
+            priority(task.priority.as_deref(), task.urgency)
+        ));
+        let comments = task
+            .annotations
+            .into_iter()
+            .map(|a| match parse_date(&a.entry) {
+                Some(at) => format!("[{}] {}", at.format("%Y-%m-%d"), a.description),
+                None => a.description,
+            })
+            .collect();
+        cards.push(ImportedCard {
+            board: task.project,
+            name: task.description,
+            description: Some(format!("Imported from Taskwarrior ({})", task.uuid)),
+            status,
+            due_at: task.due.as_deref().and_then(parse_date),
+            tags,
+            comments,
+        });
+    }
+    Ok((cards, skipped))
+}
+
+/// Taskwarrior's own priority wins; otherwise bucket urgency (default
+/// coefficients put most tasks between 0 and 15)
+fn priority(priority: Option<&str>, urgency: Option<f64>) -> &'static str {
+    match (priority, urgency) {
+        (Some("H"), _) => "high",
+        (Some("M"), _) => "medium",
+        (Some("L"), _) => "low",
+        (_, Some(u)) if u >= 10.0 => "high",
+        (_, Some(u)) if u >= 5.0 => "medium",
+        _ => "low",
+    }
+}
+
+/// Taskwarrior dates look like 20260131T170000Z
+fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%SZ")
+        .ok()
+        .map(|naive| naive.and_utc())
+}
@@ -0,0 +1,92 @@
+-- A database as the first release left it (PRAGMA user_version 0), before
+-- any migration ran, with a little data for the upgrade to carry over.
+
+-- Agents table
+CREATE TABLE IF NOT EXISTS agents (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL UNIQUE,
+    command TEXT NOT NULL,
+    working_directory TEXT NOT NULL,
+    description TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    deactivated_at TEXT
+);
+
+-- Boards table
+CREATE TABLE IF NOT EXISTS boards (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    deleted_at TEXT
+);
+
+-- Cards table
+CREATE TABLE IF NOT EXISTS cards (
+    id TEXT PRIMARY KEY NOT NULL,
+    board_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    status TEXT NOT NULL DEFAULT 'todo',
+    assigned_to TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    deleted_at TEXT,
+    FOREIGN KEY (board_id) REFERENCES boards(id) ON DELETE CASCADE
+);
+
+-- Card tags (many-to-many via separate table)
+CREATE TABLE IF NOT EXISTS card_tags (
+    card_id TEXT NOT NULL,
+    tag TEXT NOT NULL,
+    PRIMARY KEY (card_id, tag),
+    FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE
+);
+
+-- Checklist items table (directly on cards - each card has one implicit checklist)
+CREATE TABLE IF NOT EXISTS checklist_items (
+    id TEXT PRIMARY KEY NOT NULL,
+    card_id TEXT NOT NULL,
+    text TEXT NOT NULL,
+    checked INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE
+);
+
+-- Comments table
+CREATE TABLE IF NOT EXISTS comments (
+    id TEXT PRIMARY KEY NOT NULL,
+    card_id TEXT NOT NULL,
+    author TEXT,
+    text TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE
+);
+
+-- Indexes for common queries
+CREATE INDEX IF NOT EXISTS idx_cards_board_id ON cards(board_id);
+CREATE INDEX IF NOT EXISTS idx_cards_status ON cards(status);
+CREATE INDEX IF NOT EXISTS idx_cards_assigned_to ON cards(assigned_to);
+CREATE INDEX IF NOT EXISTS idx_checklist_items_card_id ON checklist_items(card_id);
+CREATE INDEX IF NOT EXISTS idx_comments_card_id ON comments(card_id);
+CREATE INDEX IF NOT EXISTS idx_card_tags_card_id ON card_tags(card_id);
+
+INSERT INTO agents (id, name, command, working_directory, description, created_at, updated_at)
+VALUES ('agent_000000000001', 'loop', 'claude', '/work', NULL, '2025-01-01T00:00:00+00:00', '2025-01-01T00:00:00+00:00');
+
+INSERT INTO boards (id, name, description, created_at, updated_at)
+VALUES ('board_00000000001', 'Legacy', 'Made before namespaces', '2025-01-01T00:00:00+00:00', '2025-01-01T00:00:00+00:00');
+
+INSERT INTO cards (id, board_id, name, description, status, assigned_to, created_at, updated_at)
+VALUES ('card_000000000001', 'board_00000000001', 'Old card', NULL, 'in_progress', 'agent_000000000001', '2025-01-02T00:00:00+00:00', '2025-01-02T00:00:00+00:00');
+
+INSERT INTO card_tags (card_id, tag) VALUES ('card_000000000001', 'backend');
+
+-- Inserted out of id order: the migration has to keep insertion order
+INSERT INTO checklist_items (id, card_id, text, checked) VALUES ('item_000000000003', 'card_000000000001', 'first', 1);
+INSERT INTO checklist_items (id, card_id, text, checked) VALUES ('item_000000000001', 'card_000000000001', 'second', 0);
+INSERT INTO checklist_items (id, card_id, text, checked) VALUES ('item_000000000002', 'card_000000000001', 'third', 0);
+
+INSERT INTO comments (id, card_id, author, text, created_at)
+VALUES ('comment_00000000001', 'card_000000000001', 'loop', 'Started', '2025-01-02T01:00:00+00:00');